@@ -0,0 +1,333 @@
+# English text; the fallback for every other catalog.
+# Lines are `id = text`; `{name}` is filled in by the app.
+
+## Status bar
+state-playing = Playing
+state-paused = Paused
+state-stopped = Stopped
+status-volume = Vol:
+status-rec = REC
+status-behind-live = behind live by {time}
+status-surf = Surf
+status-queued = {count} queued
+hint-play = play
+hint-fav = fav
+hint-viz = viz
+hint-help = help
+
+## Header
+header-now-playing = Now Playing: {station}
+header-listening = listening for {time}
+header-reconnecting = reconnecting
+header-buffering = buffering
+header-api-offline = API offline
+
+## Station footer
+footer-week = {time} this week
+footer-session = {time} this session
+
+## Help overlay
+help-title = Keyboard Shortcuts
+help-footer = j/k scroll · any other key closes
+help-section-playback = Playback
+help-section-navigation = Navigation
+help-section-volume = Volume
+help-section-stations = Stations
+help-section-display = Display
+help-section-audio = Audio
+help-play-pause = Play / Pause
+help-jump-to-live = Jump back to live after a pause
+help-play-selected = Play selected station
+help-random = Play a random station
+help-surf = Toggle surf mode
+help-next-in-queue = Play the next station in the queue
+help-sleep = Sleep timer (15, 30, 60, 90 min, off)
+help-compare = Compare selected with playing (A/B)
+help-swap-compare = Switch A/B comparison
+help-record = Record / stop recording
+help-recordings = Browse recordings
+help-quit = Quit
+help-back = Clear the search, or quit
+help-down = Move down
+help-up = Move up
+help-top = Go to top
+help-bottom = Go to bottom
+help-left = Move left (grid)
+help-right = Move right (grid)
+help-grid = Toggle grid browser
+help-volume-up = Volume up
+help-volume-down = Volume down
+help-mute = Mute / Unmute
+help-volume-exact = Type an exact volume
+help-jump = Go to a station by its number
+help-search = Search title, genre, DJ, description
+help-favorite = Toggle favorite
+help-sort = Cycle sort mode
+help-source = List one station source at a time
+help-detailed = Toggle detailed list
+help-timeline = Timeline of everything played
+help-links = Station links and donating to SomaFM
+help-enqueue = Add the selected station to the queue
+help-queue = Show the play queue
+help-similar = More stations like the playing one
+help-recent = Stations you listened to last
+help-watch-artist = Watch the playing artist on favorites
+help-watch-track = Watch the playing track on favorites
+help-play-watched = Tune in to a watched artist's station
+help-favorite-up = Move the selected favorite up
+help-favorite-down = Move the selected favorite down
+help-refresh = Refresh stations
+help-next-viz = Next visualization style
+help-prev-viz = Previous visualization style
+help-pick-viz = Pick visualization by name
+help-gain-up = Visualizer gain up
+help-gain-down = Visualizer gain down
+help-floor-up = Visualizer floor up
+help-floor-down = Visualizer floor down
+help-smoothing = Cycle visualizer smoothing
+help-show-viz = Show/hide visualizer
+help-meters = Show/hide level meters
+help-artwork = Toggle artwork
+help-history = Toggle recently played
+help-theme = Pick a color theme
+help-grow-station-list = Grow station list
+help-shrink-station-list = Shrink station list
+help-grow-visualizer = Grow visualizer
+help-shrink-visualizer = Shrink visualizer
+help-quality-down = Lower audio quality
+help-quality-up = Higher audio quality
+help-dnd = Do not disturb (no notifications)
+help-outputs = Play here or on an AirPlay speaker
+help-settings = Settings
+help-jobs = Pending timers and jobs
+help-dismiss-tips = Hide new-user tips
+help-help = Toggle this help
+
+## Messages
+msg-no-other-stations = No other stations to pick from
+msg-compare-needs-playing = Play a station, then select another to compare
+msg-compare-needs-other = Select a different station to compare
+msg-compare-failed = Couldn't start comparison: {error}
+msg-comparing = Comparing: Tab switches between A and B, c ends
+msg-no-comparison = No comparison running (press c to start one)
+msg-compare-side = {side}: {station}
+msg-unmute-failed = Couldn't unmute {side}: {error}
+msg-comparison-ended = Comparison ended
+msg-volume-range = Volume must be 0-100
+msg-no-station-number = No station {number}
+msg-rename-failed = Couldn't rename: {error}
+msg-delete-failed = Couldn't delete: {error}
+msg-recording-saved = Recording saved: {file}
+msg-stop-recording-failed = Couldn't stop recording: {error}
+msg-record-needs-playing = Play a station to record it
+msg-record-failed = Couldn't record: {error}
+msg-rotate-failed = Couldn't delete old recordings: {error}
+msg-quota-stopped = Recording stopped: recordings are over the {quota} limit
+msg-quota-deleted-one = Deleted the oldest recording to stay under {quota}
+msg-quota-deleted = Deleted {count} oldest recordings to stay under {quota}
+msg-quota-warning = Recordings are using {usage} of {quota}; the oldest will be deleted
+msg-schedule-saved = Scheduled recording of {station} saved
+msg-schedule-failed = Scheduled recording failed: {error}
+msg-schedule-started = Recording {station} until {time}
+msg-jump-to-live-failed = Couldn't jump to live: {error}
+msg-dnd-on = Do not disturb on: track notifications paused
+msg-dnd-off = Do not disturb off
+msg-surf-off = Surf mode off
+msg-queued = Queued {station} (#{position})
+msg-already-queued = {station} is already queued
+msg-queue-empty = The queue is empty (press A to add stations)
+msg-no-similar = No other stations share a genre with {station}
+msg-not-favorite = {station} isn't a favorite (press f to add it)
+msg-favorite-moved = {station} is now favorite #{position}
+msg-sync-failed = Couldn't sync favorites: {error}
+msg-source-only = Listing {source} stations only
+msg-source-all = Listing stations from every source
+msg-one-source = Every station is from {source} (add custom_stations to the config for more)
+msg-watching = Watching for {name} on your favorites
+msg-unwatched = No longer watching for {name}
+msg-nothing-to-watch = Nothing playing to watch
+msg-watched-playing = {track} is on {station} (y to tune in)
+msg-no-watched = No watched artist is playing right now
+msg-surf-on = Surf mode on: new station every {minutes} min
+msg-gain = Visualizer gain {db} dB
+msg-floor = Visualizer floor {db} dB
+msg-smoothing = Visualizer smoothing: {name}
+msg-device-removed = Paused: {devices} disconnected
+msg-tips-hidden = Tips hidden
+msg-no-favorites = No favorite stations yet
+msg-server-failover = Server stopped responding, switched to {server}
+msg-server-failover-failed = Couldn't switch to {server}: {error}
+msg-compare-snapcast = Comparing isn't available while playing to Snapcast
+msg-compare-airplay = Comparing isn't available while playing to AirPlay
+msg-airplay-snapcast = AirPlay isn't available while playing to Snapcast
+msg-airplay-on = Playing on {speaker}
+msg-airplay-off = Playing on this computer
+msg-airplay-failed = Couldn't play on the speaker: {error}
+msg-airplay-discovery-failed = Couldn't look for AirPlay speakers: {error}
+msg-control-unavailable = vibecast ctl won't reach this instance: {error}
+msg-link-copied = Copied {url}
+msg-link-open-failed = Couldn't open the link: {error}
+msg-sleep-set = Sleep timer: pausing in {minutes} min
+msg-sleep-off = Sleep timer off
+msg-sleep-done = Sleep timer: paused
+msg-idle-paused = Paused: nobody seemed to be listening
+msg-alarm-set = Alarm set for {time}
+msg-alarm-failed = Alarm couldn't start playback: {error}
+msg-alarm-no-station = Alarm: no station "{station}"
+msg-job-cancelled = Cancelled {job}
+msg-session-resumed = Picked up where the last session left off
+msg-session-recovered = vibecast didn't quit cleanly last time; your session was restored
+msg-resume-failed = Couldn't resume {station}: {error}
+
+## Now playing
+now-playing-title = Now Playing
+now-playing-none = No station selected
+now-playing-loading = Loading song info...
+now-playing-listeners = {count} listeners
+now-playing-by = by
+now-playing-from = from
+now-playing-on = on
+now-playing-estimate = {time} est.
+
+## Panels
+history-title = Previously Played
+history-empty = No history available
+stations-title = Stations
+stations-loading = Loading stations…
+stations-retrying = Retrying: {error}
+stations-only = {source} only
+grid-title = Browse
+viz-title = Visualizer
+viz-title-bpm = Visualizer · ~{bpm} BPM
+viz-paused = PAUSED
+viz-idle = Select a station to play
+viz-no-data = no level data
+levels-title = Levels
+
+## Popups
+recordings-title = Recordings
+recordings-empty = No recordings yet (w records the playing station)
+recordings-new-name = New name:
+recordings-confirm-delete = Press d again to delete, Esc to keep it
+volume-title = Volume
+volume-muted = Muted ({volume}%)
+viz-picker-title = Visualizations
+viz-picker-off = (off)
+queue-title = Queue
+queue-title-next = Queue · next in {time}
+queue-empty = Nothing queued (A adds the selected station)
+jobs-title = Jobs
+jobs-empty = Nothing scheduled
+jobs-due-in = in {time}
+timeline-empty = Nothing recorded yet
+themes-title = Themes
+settings-title = Settings
+recent-title = Recent stations
+recent-empty = Nothing listened to yet
+links-title = Links
+links-title-station = {station} Links
+idle-title = Still listening?
+idle-pausing = Pausing in {seconds}s to save bandwidth
+idle-keep-playing = Press any key to keep playing
+similar-title = More like {station}
+schedule-title = Scheduled Recordings
+schedule-empty = Nothing scheduled
+
+## Popup keys
+keys-recordings = Enter play · r rename · d delete · Esc close
+keys-volume = ←/→ fine · +/- step · Esc hide
+keys-viz-picker = Enter show · Space on/off · Esc close
+keys-queue = Enter play now · J/K move · d remove · Esc close
+keys-jobs = d cancel timer · Esc close
+keys-timeline = j/k scroll · / search · Tab today/session · Esc close
+keys-themes = Enter apply · Esc keep current
+keys-settings = ←/→ change · saved as you go · Esc close
+keys-recent = 1-9 or Enter play · A add to queue · Esc close
+keys-links = Enter open in browser · y copy · Esc close
+keys-similar = Enter play · A add to queue · Esc close
+
+## Settings
+setting-theme = Theme
+setting-visualization = Visualization
+setting-quality = Audio quality
+setting-artwork = Artwork
+setting-notifications = Track notifications
+setting-notify-via = Notify via
+setting-on = on
+setting-off = off
+
+## Times
+ago-now = just now
+ago-minutes = {count}m ago
+ago-hours = {count}h ago
+ago-days = {count}d ago
+timeline-today = today
+timeline-since = since {time}
+
+## Jobs
+job-sleep = sleep timer
+job-alarm = alarm
+job-alarm-station = alarm ({station})
+job-surf = surf to a random station
+job-advance-queue = play next in queue
+job-recording-schedules = check scheduled recordings
+job-recording-quota = check recordings quota
+job-evict-artwork = clear out old artwork
+job-save-session = save session
+job-sync-favorites = sync favorites
+job-scan-watched = look for watched artists
+job-every = every {time}
+
+## Labels
+source-custom = Custom
+sort-favorites = Favorites
+sort-favorites-az = Favorites, A-Z
+sort-az = A-Z
+sort-listeners = Listeners
+sort-genre = Genre
+sort-recent = Recent
+notify-desktop = desktop
+notify-osc9 = terminal (OSC 9)
+notify-osc777 = terminal (OSC 777)
+smoothing-snappy = Snappy
+smoothing-normal = Normal
+smoothing-smooth = Smooth
+link-station-page = Station page
+link-playlist = Playlist ({format}, {quality})
+link-donate = Donate to SomaFM
+
+## Prompts
+prompt-volume = Volume: {input} (0-100, Enter to set)
+prompt-jump = Go to station: {input} (Enter to go)
+
+## New-user tips
+tip-label = Tip:
+tip-dismiss = i hides tips
+tip-play = Press Enter to play the highlighted station
+tip-favorite = Press f to add the highlighted station to your favorites
+tip-help = Press ? to see every key
+
+## Screen reader
+sr-now-playing = Now playing: {track}
+sr-stopped = stopped
+sr-paused = paused
+sr-playing = playing
+sr-muted = {state}, muted
+sr-volume = {state}, volume {volume} percent
+sr-status = Vibecast: {status}
+sr-station = Station: {station}
+sr-station-none = Station: none
+sr-track = Track: {track}
+sr-track-unknown = unknown
+sr-selected = Selected {index} of {total}: {station}, {genre}, {listeners} listeners
+sr-selected-favorite = Selected {index} of {total}: {station}, {genre}, {listeners} listeners, favorite
+sr-no-stations = No stations loaded
+sr-keys = Keys: j and k move, Enter plays, p pauses, plus and minus change volume, f toggles favorite, s changes sort, question mark closes keys, q quits
+sr-keys-hint = Press question mark for keys
+
+## Output picker
+outputs-title = Play on
+outputs-local = This computer
+outputs-searching = Looking for AirPlay speakers…
+outputs-none = No AirPlay speakers found
+keys-outputs = Enter play here · Esc close
@@ -0,0 +1,332 @@
+# Spanish text. Ids left out fall back to English.
+
+## Status bar
+state-playing = Sonando
+state-paused = En pausa
+state-stopped = Detenido
+status-volume = Vol:
+status-rec = GRAB
+status-behind-live = {time} por detrás del directo
+status-surf = Zapeo
+status-queued = {count} en cola
+hint-play = reproducir
+hint-fav = favorito
+hint-viz = visual
+hint-help = ayuda
+
+## Header
+header-now-playing = Sonando: {station}
+header-listening = escuchando {time}
+header-reconnecting = reconectando
+header-buffering = cargando
+header-api-offline = API sin conexión
+
+## Station footer
+footer-week = {time} esta semana
+footer-session = {time} en esta sesión
+
+## Help overlay
+help-title = Atajos de teclado
+help-footer = j/k desplazar · cualquier otra tecla cierra
+help-section-playback = Reproducción
+help-section-navigation = Navegación
+help-section-volume = Volumen
+help-section-stations = Emisoras
+help-section-display = Pantalla
+help-section-audio = Audio
+help-play-pause = Reproducir / Pausa
+help-jump-to-live = Volver al directo tras una pausa
+help-play-selected = Reproducir la emisora seleccionada
+help-random = Reproducir una emisora al azar
+help-surf = Activar/desactivar el zapeo
+help-next-in-queue = Reproducir la siguiente emisora de la cola
+help-sleep = Temporizador (15, 30, 60, 90 min, apagado)
+help-compare = Comparar la seleccionada con la actual (A/B)
+help-swap-compare = Cambiar entre A y B
+help-record = Grabar / detener la grabación
+help-recordings = Ver grabaciones
+help-quit = Salir
+help-back = Borrar la búsqueda, o salir
+help-down = Bajar
+help-up = Subir
+help-top = Ir al principio
+help-bottom = Ir al final
+help-left = Izquierda (cuadrícula)
+help-right = Derecha (cuadrícula)
+help-grid = Mostrar/ocultar la cuadrícula
+help-volume-up = Subir volumen
+help-volume-down = Bajar volumen
+help-mute = Silenciar / Activar sonido
+help-volume-exact = Escribir un volumen exacto
+help-jump = Ir a una emisora por su número
+help-search = Buscar por título, género, DJ o descripción
+help-favorite = Marcar/desmarcar favorita
+help-sort = Cambiar el orden
+help-source = Mostrar una sola fuente de emisoras
+help-detailed = Lista detallada
+help-timeline = Todo lo que ha sonado
+help-links = Enlaces de la emisora y donaciones a SomaFM
+help-enqueue = Añadir la emisora seleccionada a la cola
+help-queue = Ver la cola de reproducción
+help-similar = Más emisoras como la que suena
+help-recent = Emisoras escuchadas hace poco
+help-watch-artist = Seguir al artista actual en las favoritas
+help-watch-track = Seguir la canción actual en las favoritas
+help-play-watched = Sintonizar la emisora de un artista seguido
+help-favorite-up = Subir la favorita seleccionada
+help-favorite-down = Bajar la favorita seleccionada
+help-refresh = Actualizar emisoras
+help-next-viz = Siguiente visualización
+help-prev-viz = Visualización anterior
+help-pick-viz = Elegir visualización por nombre
+help-gain-up = Subir la ganancia del visualizador
+help-gain-down = Bajar la ganancia del visualizador
+help-floor-up = Subir el umbral del visualizador
+help-floor-down = Bajar el umbral del visualizador
+help-smoothing = Cambiar el suavizado
+help-show-viz = Mostrar/ocultar el visualizador
+help-meters = Mostrar/ocultar los vúmetros
+help-artwork = Mostrar/ocultar la carátula
+help-history = Mostrar/ocultar lo último que sonó
+help-theme = Elegir el tema de color
+help-grow-station-list = Ensanchar la lista
+help-shrink-station-list = Estrechar la lista
+help-grow-visualizer = Agrandar el visualizador
+help-shrink-visualizer = Encoger el visualizador
+help-quality-down = Bajar la calidad de audio
+help-quality-up = Subir la calidad de audio
+help-dnd = No molestar (sin notificaciones)
+help-outputs = Reproducir aquí o en un altavoz AirPlay
+help-settings = Ajustes
+help-jobs = Temporizadores y tareas pendientes
+help-dismiss-tips = Ocultar los consejos de inicio
+help-help = Mostrar/ocultar esta ayuda
+
+## Messages
+msg-no-other-stations = No hay otras emisoras entre las que elegir
+msg-compare-needs-playing = Reproduce una emisora y selecciona otra para comparar
+msg-compare-needs-other = Selecciona otra emisora para comparar
+msg-compare-failed = No se pudo iniciar la comparación: {error}
+msg-comparing = Comparando: Tab cambia entre A y B, c termina
+msg-no-comparison = No hay ninguna comparación (pulsa c para empezar)
+msg-compare-side = {side}: {station}
+msg-unmute-failed = No se pudo activar el sonido de {side}: {error}
+msg-comparison-ended = Comparación terminada
+msg-volume-range = El volumen debe estar entre 0 y 100
+msg-no-station-number = No hay emisora {number}
+msg-rename-failed = No se pudo renombrar: {error}
+msg-delete-failed = No se pudo borrar: {error}
+msg-recording-saved = Grabación guardada: {file}
+msg-stop-recording-failed = No se pudo detener la grabación: {error}
+msg-record-needs-playing = Reproduce una emisora para grabarla
+msg-record-failed = No se pudo grabar: {error}
+msg-rotate-failed = No se pudieron borrar las grabaciones antiguas: {error}
+msg-quota-stopped = Grabación detenida: las grabaciones superan el límite de {quota}
+msg-quota-deleted-one = Borrada la grabación más antigua para no pasar de {quota}
+msg-quota-deleted = Borradas {count} grabaciones antiguas para no pasar de {quota}
+msg-quota-warning = Las grabaciones ocupan {usage} de {quota}; se borrarán las más antiguas
+msg-schedule-saved = Grabación programada de {station} guardada
+msg-schedule-failed = Falló la grabación programada: {error}
+msg-schedule-started = Grabando {station} hasta las {time}
+msg-jump-to-live-failed = No se pudo volver al directo: {error}
+msg-dnd-on = No molestar activado: notificaciones en pausa
+msg-dnd-off = No molestar desactivado
+msg-surf-off = Zapeo desactivado
+msg-queued = {station} en cola (n.º {position})
+msg-already-queued = {station} ya está en la cola
+msg-queue-empty = La cola está vacía (pulsa A para añadir emisoras)
+msg-no-similar = Ninguna otra emisora comparte género con {station}
+msg-not-favorite = {station} no es favorita (pulsa f para añadirla)
+msg-favorite-moved = {station} es ahora la favorita n.º {position}
+msg-sync-failed = No se pudieron sincronizar las favoritas: {error}
+msg-source-only = Solo emisoras de {source}
+msg-source-all = Emisoras de todas las fuentes
+msg-one-source = Todas las emisoras son de {source} (añade custom_stations a la configuración para más)
+msg-watching = Siguiendo a {name} en tus favoritas
+msg-unwatched = Ya no sigues a {name}
+msg-nothing-to-watch = No suena nada que seguir
+msg-watched-playing = {track} suena en {station} (y para sintonizarla)
+msg-no-watched = Ahora no suena ningún artista seguido
+msg-surf-on = Zapeo activado: nueva emisora cada {minutes} min
+msg-gain = Ganancia del visualizador {db} dB
+msg-floor = Umbral del visualizador {db} dB
+msg-smoothing = Suavizado del visualizador: {name}
+msg-device-removed = En pausa: se desconectó {devices}
+msg-tips-hidden = Consejos ocultos
+msg-no-favorites = Aún no hay emisoras favoritas
+msg-server-failover = El servidor dejó de responder, cambiado a {server}
+msg-server-failover-failed = No se pudo cambiar a {server}: {error}
+msg-compare-snapcast = No se puede comparar mientras se reproduce en Snapcast
+msg-compare-airplay = No se puede comparar mientras se reproduce en AirPlay
+msg-airplay-snapcast = AirPlay no está disponible mientras se reproduce en Snapcast
+msg-airplay-on = Reproduciendo en {speaker}
+msg-airplay-off = Reproduciendo en este ordenador
+msg-airplay-failed = No se pudo reproducir en el altavoz: {error}
+msg-airplay-discovery-failed = No se pudieron buscar altavoces AirPlay: {error}
+msg-control-unavailable = vibecast ctl no llegará a esta instancia: {error}
+msg-link-copied = Copiado {url}
+msg-link-open-failed = No se pudo abrir el enlace: {error}
+msg-sleep-set = Temporizador: pausa en {minutes} min
+msg-sleep-off = Temporizador apagado
+msg-sleep-done = Temporizador: en pausa
+msg-idle-paused = En pausa: parecía que nadie escuchaba
+msg-alarm-set = Alarma programada para {time}
+msg-alarm-failed = La alarma no pudo iniciar la reproducción: {error}
+msg-alarm-no-station = Alarma: no existe la emisora "{station}"
+msg-session-resumed = Se retomó la última sesión donde se dejó
+msg-session-recovered = vibecast no se cerró bien la última vez; se restauró tu sesión
+msg-resume-failed = No se pudo reanudar {station}: {error}
+msg-job-cancelled = Cancelado: {job}
+
+## Now playing
+now-playing-title = Reproduciendo ahora
+now-playing-none = Ninguna emisora elegida
+now-playing-loading = Cargando la canción...
+now-playing-listeners = {count} oyentes
+now-playing-by = de
+now-playing-from = del álbum
+now-playing-on = sello
+now-playing-estimate = {time} aprox.
+
+## Panels
+history-title = Sonaron antes
+history-empty = Aún no hay historial
+stations-title = Emisoras
+stations-loading = Cargando emisoras…
+stations-retrying = Reintentando: {error}
+stations-only = solo {source}
+grid-title = Explorar
+viz-title = Visualizador
+viz-title-bpm = Visualizador · ~{bpm} BPM
+viz-paused = EN PAUSA
+viz-idle = Elige una emisora para escuchar
+viz-no-data = sin datos de nivel
+levels-title = Niveles
+
+## Popups
+recordings-title = Grabaciones
+recordings-empty = Aún no hay grabaciones (w graba la emisora que suena)
+recordings-new-name = Nuevo nombre:
+recordings-confirm-delete = Pulsa d otra vez para borrar, Esc para conservarla
+volume-title = Volumen
+volume-muted = Silenciado ({volume}%)
+viz-picker-title = Visualizaciones
+viz-picker-off = (apagada)
+queue-title = Cola
+queue-title-next = Cola · siguiente en {time}
+queue-empty = La cola está vacía (A añade la emisora elegida)
+jobs-title = Tareas
+jobs-empty = Nada programado
+jobs-due-in = en {time}
+timeline-empty = Aún no hay nada registrado
+themes-title = Temas
+settings-title = Ajustes
+recent-title = Emisoras recientes
+recent-empty = Aún no has escuchado nada
+links-title = Enlaces
+links-title-station = Enlaces de {station}
+idle-title = ¿Sigues escuchando?
+idle-pausing = Pausa en {seconds} s para ahorrar datos
+idle-keep-playing = Pulsa cualquier tecla para seguir escuchando
+similar-title = Más como {station}
+schedule-title = Grabaciones programadas
+schedule-empty = Nada programado
+
+## Popup keys
+keys-recordings = Enter reproducir · r renombrar · d borrar · Esc cerrar
+keys-volume = ←/→ fino · +/- paso · Esc ocultar
+keys-viz-picker = Enter mostrar · Espacio sí/no · Esc cerrar
+keys-queue = Enter reproducir ya · J/K mover · d quitar · Esc cerrar
+keys-jobs = d cancelar temporizador · Esc cerrar
+keys-timeline = j/k desplazar · / buscar · Tab hoy/sesión · Esc cerrar
+keys-themes = Enter aplicar · Esc mantener el actual
+keys-settings = ←/→ cambiar · se guarda al momento · Esc cerrar
+keys-recent = 1-9 o Enter reproducir · A añadir a la cola · Esc cerrar
+keys-links = Enter abrir en el navegador · y copiar · Esc cerrar
+keys-similar = Enter reproducir · A añadir a la cola · Esc cerrar
+
+## Settings
+setting-theme = Tema
+setting-visualization = Visualización
+setting-quality = Calidad de audio
+setting-artwork = Carátula
+setting-notifications = Avisos de canción
+setting-notify-via = Avisar por
+setting-on = sí
+setting-off = no
+
+## Times
+ago-now = ahora mismo
+ago-minutes = hace {count} min
+ago-hours = hace {count} h
+ago-days = hace {count} d
+timeline-today = hoy
+timeline-since = desde las {time}
+
+## Jobs
+job-sleep = temporizador de apagado
+job-alarm = alarma
+job-alarm-station = alarma ({station})
+job-surf = saltar a una emisora al azar
+job-advance-queue = reproducir la siguiente de la cola
+job-recording-schedules = revisar las grabaciones programadas
+job-recording-quota = revisar el espacio de grabaciones
+job-evict-artwork = borrar carátulas antiguas
+job-save-session = guardar la sesión
+job-sync-favorites = sincronizar favoritas
+job-scan-watched = buscar artistas seguidos
+job-every = cada {time}
+
+## Labels
+source-custom = Personalizadas
+sort-favorites = Favoritas
+sort-favorites-az = Favoritas, A-Z
+sort-az = A-Z
+sort-listeners = Oyentes
+sort-genre = Género
+sort-recent = Recientes
+notify-desktop = escritorio
+notify-osc9 = terminal (OSC 9)
+notify-osc777 = terminal (OSC 777)
+smoothing-snappy = Rápida
+smoothing-normal = Normal
+smoothing-smooth = Suave
+link-station-page = Página de la emisora
+link-playlist = Lista de reproducción ({format}, {quality})
+link-donate = Donar a SomaFM
+
+## Prompts
+prompt-volume = Volumen: {input} (0-100, Enter para fijarlo)
+prompt-jump = Ir a la emisora: {input} (Enter para ir)
+
+## New-user tips
+tip-label = Consejo:
+tip-dismiss = i oculta los consejos
+tip-play = Pulsa Enter para escuchar la emisora resaltada
+tip-favorite = Pulsa f para añadir la emisora resaltada a tus favoritas
+tip-help = Pulsa ? para ver todas las teclas
+
+## Screen reader
+sr-now-playing = Sonando: {track}
+sr-stopped = detenido
+sr-paused = en pausa
+sr-playing = sonando
+sr-muted = {state}, silenciado
+sr-volume = {state}, volumen {volume} por ciento
+sr-status = Vibecast: {status}
+sr-station = Emisora: {station}
+sr-station-none = Emisora: ninguna
+sr-track = Canción: {track}
+sr-track-unknown = desconocida
+sr-selected = Elegida {index} de {total}: {station}, {genre}, {listeners} oyentes
+sr-selected-favorite = Elegida {index} de {total}: {station}, {genre}, {listeners} oyentes, favorita
+sr-no-stations = No hay emisoras cargadas
+sr-keys = Teclas: j y k mueven, Enter reproduce, p pausa, más y menos cambian el volumen, f marca favorita, s cambia el orden, signo de interrogación cierra las teclas, q sale
+sr-keys-hint = Pulsa el signo de interrogación para ver las teclas
+
+## Output picker
+outputs-title = Reproducir en
+outputs-local = Este ordenador
+outputs-searching = Buscando altavoces AirPlay…
+outputs-none = No se encontraron altavoces AirPlay
+keys-outputs = Enter reproducir aquí · Esc cerrar
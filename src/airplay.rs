@@ -0,0 +1,271 @@
+//! AirPlay speakers (Apple TV, HomePod, AirPort Express): finding them on
+//! the local network with an mDNS query, and sending them audio through
+//! `raop_play` from libraop, which handles the RAOP session, ALAC encoding
+//! and timing. mpv decodes the stream to raw PCM and `raop_play` reads it
+//! from a pipe, the same way audio reaches a Snapcast server.
+
+use anyhow::{anyhow, Result};
+use std::io::ErrorKind;
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4, UdpSocket};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command};
+
+/// Service AirPlay speakers advertise for audio
+const SERVICE: &str = "_raop._tcp.local";
+
+const MDNS_GROUP: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::new(224, 0, 0, 251), 5353);
+
+/// How long to collect answers; speakers reply within a second or so
+pub const DISCOVERY_TIME: Duration = Duration::from_secs(2);
+
+/// Sample format `raop_play` expects on stdin
+pub const PCM_ARGS: [&str; 3] = [
+    "--audio-samplerate=44100",
+    "--audio-format=s16",
+    "--audio-channels=stereo",
+];
+
+const TYPE_A: u16 = 1;
+const TYPE_PTR: u16 = 12;
+const TYPE_SRV: u16 = 33;
+
+/// A speaker found on the network
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AirPlayDevice {
+    pub name: String,
+    pub addr: SocketAddr,
+}
+
+/// The AirPlay speakers that answer within `wait`, by name. Blocks, so run
+/// it off the async runtime.
+pub fn discover(wait: Duration) -> Result<Vec<AirPlayDevice>> {
+    // Asking from a port other than 5353 gets answers sent straight back
+    // rather than to the whole group (RFC 6762 section 6.7)
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+    socket.set_read_timeout(Some(Duration::from_millis(200)))?;
+    socket.send_to(&query(SERVICE), MDNS_GROUP)?;
+
+    let mut records = Vec::new();
+    let mut buf = [0; 9000];
+    let deadline = Instant::now() + wait;
+    while Instant::now() < deadline {
+        match socket.recv_from(&mut buf) {
+            Ok((len, _)) => records.extend(parse_records(&buf[..len]).unwrap_or_default()),
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(devices(&records))
+}
+
+/// Start `raop_play` sending `audio`, 44.1 kHz 16-bit stereo PCM, to the
+/// speaker at `addr`. It stops when `audio` ends or it's killed.
+pub fn spawn_sender(addr: SocketAddr, audio: Stdio) -> Result<Child> {
+    Command::new("raop_play")
+        .args(["-p", &addr.port().to_string(), &addr.ip().to_string(), "-"])
+        .stdin(audio)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| anyhow!("couldn't start raop_play: {}", err))
+}
+
+/// An mDNS question for PTR records of `service`
+fn query(service: &str) -> Vec<u8> {
+    // Id, flags, then one question and no records
+    let mut packet = vec![0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in service.split('.') {
+        packet.push(label.len() as u8);
+        packet.extend_from_slice(label.as_bytes());
+    }
+    packet.push(0);
+    packet.extend_from_slice(&TYPE_PTR.to_be_bytes());
+    // Class IN, with the top bit asking for a unicast answer
+    packet.extend_from_slice(&0x8001_u16.to_be_bytes());
+    packet
+}
+
+/// The records in an mDNS answer that discovery needs
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Record {
+    /// A service instance: `name` is the service, `target` the instance
+    Ptr {
+        name: String,
+        target: String,
+    },
+    /// Where an instance runs
+    Srv {
+        name: String,
+        port: u16,
+        target: String,
+    },
+    A {
+        name: String,
+        addr: Ipv4Addr,
+    },
+}
+
+/// Every PTR, SRV and A record in `packet`, from the answers and the
+/// additional records alike
+fn parse_records(packet: &[u8]) -> Option<Vec<Record>> {
+    let count = |at: usize| Some(u16::from_be_bytes([*packet.get(at)?, *packet.get(at + 1)?]));
+    let questions = count(4)?;
+    let records = count(6)? as usize + count(8)? as usize + count(10)? as usize;
+
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = read_name(packet, pos)?.1 + 4;
+    }
+    let mut found = Vec::new();
+    for _ in 0..records {
+        let (name, end) = read_name(packet, pos)?;
+        let kind = count(end)?;
+        let len = count(end + 8)? as usize;
+        let data = end + 10;
+        let rdata = packet.get(data..data + len)?;
+        match kind {
+            TYPE_PTR => found.push(Record::Ptr {
+                name,
+                target: read_name(packet, data)?.0,
+            }),
+            TYPE_SRV if len > 6 => found.push(Record::Srv {
+                name,
+                port: u16::from_be_bytes([rdata[4], rdata[5]]),
+                target: read_name(packet, data + 6)?.0,
+            }),
+            TYPE_A if len == 4 => found.push(Record::A {
+                name,
+                addr: Ipv4Addr::new(rdata[0], rdata[1], rdata[2], rdata[3]),
+            }),
+            _ => {}
+        }
+        pos = data + len;
+    }
+    Some(found)
+}
+
+/// The dotted name at `pos`, following compression pointers, and where the
+/// name ends in the packet
+fn read_name(packet: &[u8], mut pos: usize) -> Option<(String, usize)> {
+    let mut labels = Vec::new();
+    let mut end = None;
+    // Pointers only go backwards in well-formed packets; this stops loops
+    for _ in 0..64 {
+        let len = *packet.get(pos)? as usize;
+        match len {
+            0 => {
+                return Some((labels.join("."), end.unwrap_or(pos + 1)));
+            }
+            _ if len & 0xC0 == 0xC0 => {
+                let target = (len & 0x3F) << 8 | *packet.get(pos + 1)? as usize;
+                end.get_or_insert(pos + 2);
+                pos = target;
+            }
+            _ => {
+                let label = packet.get(pos + 1..pos + 1 + len)?;
+                labels.push(String::from_utf8_lossy(label).into_owned());
+                pos += 1 + len;
+            }
+        }
+    }
+    None
+}
+
+/// Speakers whose instance, port and address are all in `records`
+fn devices(records: &[Record]) -> Vec<AirPlayDevice> {
+    let same = |a: &str, b: &str| a.eq_ignore_ascii_case(b);
+    let mut devices: Vec<AirPlayDevice> = records
+        .iter()
+        .filter_map(|record| match record {
+            Record::Ptr { name, target } if same(name, SERVICE) => Some(target),
+            _ => None,
+        })
+        .filter_map(|instance| {
+            let (port, host) = records.iter().find_map(|record| match record {
+                Record::Srv { name, port, target } if same(name, instance) => Some((port, target)),
+                _ => None,
+            })?;
+            let addr = records.iter().find_map(|record| match record {
+                Record::A { name, addr } if same(name, host) => Some(*addr),
+                _ => None,
+            })?;
+            Some(AirPlayDevice {
+                name: device_name(instance),
+                addr: SocketAddr::from((addr, *port)),
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.name.cmp(&b.name));
+    devices.dedup();
+    devices
+}
+
+/// "Living Room" from "A1B2C3D4E5F6@Living Room._raop._tcp.local"
+fn device_name(instance: &str) -> String {
+    let label = instance
+        .strip_suffix(SERVICE)
+        .map_or(instance, |label| label.trim_end_matches('.'));
+    label
+        .split_once('@')
+        .map_or(label, |(_, name)| name)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(name: &str) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        for label in name.split('.') {
+            bytes.push(label.len() as u8);
+            bytes.extend_from_slice(label.as_bytes());
+        }
+        bytes.push(0);
+        bytes
+    }
+
+    fn record(owner: &[u8], kind: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = owner.to_vec();
+        bytes.extend_from_slice(&kind.to_be_bytes());
+        bytes.extend_from_slice(&[0x80, 1, 0, 0, 0x11, 0x94]);
+        bytes.extend_from_slice(&(data.len() as u16).to_be_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    #[test]
+    fn test_discovery_answer() {
+        let mut packet = vec![0, 0, 0x84, 0, 0, 0, 0, 1, 0, 0, 0, 2];
+        // The PTR's owner is at offset 12, so later names can point at it
+        let instance = name("F0B479@Living Room._raop._tcp.local");
+        packet.extend(record(&name(SERVICE), TYPE_PTR, &instance));
+        let mut srv = vec![0, 0, 0, 0, 0x1B, 0x58];
+        srv.extend(name("living-room.local"));
+        packet.extend(record(&instance, TYPE_SRV, &srv));
+        packet.extend(record(
+            &name("living-room.local"),
+            TYPE_A,
+            &[192, 168, 1, 20],
+        ));
+
+        let records = parse_records(&packet).unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            devices(&records),
+            [AirPlayDevice {
+                name: "Living Room".to_string(),
+                addr: "192.168.1.20:7000".parse().unwrap(),
+            }]
+        );
+
+        // A compressed name ends at its pointer
+        let mut compressed = name(SERVICE);
+        compressed.extend([0xC0, 0]);
+        assert_eq!(read_name(&compressed, 18), Some((SERVICE.to_string(), 20)));
+        assert_eq!(read_name(&[0xC0, 0], 0), None);
+        assert!(parse_records(&packet[..40]).is_none());
+    }
+}
@@ -0,0 +1,379 @@
+//! Every station from every source merged into one list, and the order
+//! that list is shown in.
+
+use std::collections::{BTreeMap, HashSet};
+use std::ops::{Index, Range};
+
+use super::search::match_score;
+use super::types::Channel;
+use crate::i18n::t;
+
+/// Where a station comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub enum StationSource {
+    #[default]
+    SomaFm,
+    /// Listed under `custom_stations` in the config
+    Custom,
+}
+
+impl StationSource {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::SomaFm => "SomaFM",
+            Self::Custom => t("source-custom"),
+        }
+    }
+
+    /// Tag next to each station when the list mixes sources
+    pub fn badge(self) -> &'static str {
+        match self {
+            Self::SomaFm => "S",
+            Self::Custom => "C",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    FavoritesThenListeners,
+    FavoritesThenAlphabetical,
+    Alphabetical,
+    ListenersOnly,
+    /// By genre, then title
+    Genre,
+    /// Stations listened to most recently first
+    Recent,
+}
+
+impl SortMode {
+    /// Every sort mode, in the order `s` cycles through them
+    pub const ALL: [SortMode; 6] = [
+        Self::FavoritesThenListeners,
+        Self::FavoritesThenAlphabetical,
+        Self::Alphabetical,
+        Self::ListenersOnly,
+        Self::Genre,
+        Self::Recent,
+    ];
+
+    /// Name saved in the config
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FavoritesThenListeners => "favorites",
+            Self::FavoritesThenAlphabetical => "favorites_alphabetical",
+            Self::Alphabetical => "alphabetical",
+            Self::ListenersOnly => "listeners",
+            Self::Genre => "genre",
+            Self::Recent => "recent",
+        }
+    }
+
+    /// Shown in the station list title
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FavoritesThenListeners => t("sort-favorites"),
+            Self::FavoritesThenAlphabetical => t("sort-favorites-az"),
+            Self::Alphabetical => t("sort-az"),
+            Self::ListenersOnly => t("sort-listeners"),
+            Self::Genre => t("sort-genre"),
+            Self::Recent => t("sort-recent"),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name)
+    }
+
+    pub fn next(self) -> Self {
+        let index = Self::ALL.iter().position(|&mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// The stations of every source, grouped by source, with the indices of
+/// the ones listed in list order. Indices into the catalog change when a
+/// source's stations are replaced, so hold on to station ids instead.
+#[derive(Debug, Default)]
+pub struct StationCatalog {
+    /// Each source's stations as it gave them
+    sources: BTreeMap<StationSource, Vec<Channel>>,
+    /// All of them, without stations an earlier source has too
+    channels: Vec<Channel>,
+    /// Indices into `channels` in list order, without filtered-out stations
+    sorted: Vec<usize>,
+    /// Only list stations from this source
+    source_filter: Option<StationSource>,
+}
+
+impl StationCatalog {
+    /// Replace the stations from `source`, keeping every other source's.
+    /// A station with the same id as one from an earlier source is left
+    /// out. Call `sort` afterwards.
+    pub fn set_source(&mut self, source: StationSource, mut channels: Vec<Channel>) {
+        for channel in &mut channels {
+            channel.source = source;
+        }
+        self.sources.insert(source, channels);
+        let mut seen = HashSet::new();
+        self.channels = self
+            .sources
+            .values()
+            .flatten()
+            .filter(|channel| seen.insert(channel.id.as_str()))
+            .cloned()
+            .collect();
+        self.sorted.clear();
+        // A source that's gone can't be the only one listed
+        if let Some(filter) = self.source_filter {
+            if !self.sources().contains(&filter) {
+                self.source_filter = None;
+            }
+        }
+    }
+
+    pub fn channels(&self) -> &[Channel] {
+        &self.channels
+    }
+
+    /// Change every station of `source` in place
+    pub fn update(&mut self, source: StationSource, change: impl Fn(&mut Channel)) {
+        let listed = self
+            .channels
+            .iter_mut()
+            .filter(|channel| channel.source == source);
+        let stations = self.sources.get_mut(&source).into_iter().flatten();
+        for channel in stations.chain(listed) {
+            change(channel);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    /// Index of the station with `id`
+    pub fn position(&self, id: &str) -> Option<usize> {
+        self.channels.iter().position(|channel| channel.id == id)
+    }
+
+    pub fn find(&self, id: &str) -> Option<&Channel> {
+        self.channels.iter().find(|channel| channel.id == id)
+    }
+
+    /// Indices of the listed stations, in list order
+    pub fn sorted(&self) -> &[usize] {
+        &self.sorted
+    }
+
+    /// The listed stations at `rows` of the list order
+    pub fn listed(&self, rows: Range<usize>) -> impl Iterator<Item = &Channel> {
+        self.sorted[rows].iter().map(|&i| &self.channels[i])
+    }
+
+    /// The rows of the list to draw in a view `height` rows tall, scrolled
+    /// from `offset` only as far as it takes to bring `selected` into view.
+    /// Only these rows need building, however long the list is.
+    pub fn window(&self, offset: usize, selected: Option<usize>, height: usize) -> Range<usize> {
+        let len = self.sorted.len();
+        let height = height.max(1);
+        // Don't leave blank rows at the bottom after the list shrinks
+        let mut offset = offset.min(len.saturating_sub(height));
+        if let Some(selected) = selected.filter(|&selected| selected < len) {
+            if selected < offset {
+                offset = selected;
+            } else if selected >= offset + height {
+                offset = selected + 1 - height;
+            }
+        }
+        offset..(offset + height).min(len)
+    }
+
+    /// Sources with at least one station, in list order
+    pub fn sources(&self) -> Vec<StationSource> {
+        let mut sources: Vec<StationSource> =
+            self.channels.iter().map(|channel| channel.source).collect();
+        sources.dedup();
+        sources
+    }
+
+    pub fn source_filter(&self) -> Option<StationSource> {
+        self.source_filter
+    }
+
+    /// List every source, then each source on its own in turn. Call `sort`
+    /// afterwards.
+    pub fn cycle_source_filter(&mut self) -> Option<StationSource> {
+        let sources = self.sources();
+        self.source_filter = match self.source_filter {
+            None => sources.first().copied(),
+            Some(current) => sources
+                .iter()
+                .position(|&source| source == current)
+                .and_then(|i| sources.get(i + 1))
+                .copied(),
+        };
+        self.source_filter
+    }
+
+    /// Put the listed stations in `mode` order. Favorites go in the user's
+    /// order; with a search `query` only matches are listed, best first.
+    pub fn sort(
+        &mut self,
+        mode: SortMode,
+        favorites: &[String],
+        last_listened: impl Fn(&str) -> Option<i64>,
+        query: &str,
+    ) {
+        let channels = &self.channels;
+        let mut indices: Vec<usize> = (0..channels.len())
+            .filter(|&i| {
+                self.source_filter
+                    .is_none_or(|source| channels[i].source == source)
+            })
+            .collect();
+
+        let by_listeners = |a: usize, b: usize| channels[b].listeners.cmp(&channels[a].listeners);
+        let by_title = |a: usize, b: usize| channels[a].title.cmp(&channels[b].title);
+        // Favorites in the user's order, then the rest by `rest`
+        let favorites_then =
+            |a: usize, b: usize, rest: &dyn Fn(usize, usize) -> std::cmp::Ordering| {
+                let a_fav = favorites.iter().position(|id| *id == channels[a].id);
+                let b_fav = favorites.iter().position(|id| *id == channels[b].id);
+
+                match (a_fav, b_fav) {
+                    (Some(a_pos), Some(b_pos)) => a_pos.cmp(&b_pos),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    _ => rest(a, b),
+                }
+            };
+
+        match mode {
+            SortMode::FavoritesThenListeners => {
+                indices.sort_by(|&a, &b| favorites_then(a, b, &by_listeners));
+            }
+            SortMode::FavoritesThenAlphabetical => {
+                indices.sort_by(|&a, &b| favorites_then(a, b, &by_title));
+            }
+            SortMode::Alphabetical => indices.sort_by(|&a, &b| by_title(a, b)),
+            SortMode::ListenersOnly => indices.sort_by(|&a, &b| by_listeners(a, b)),
+            SortMode::Genre => {
+                indices.sort_by(|&a, &b| {
+                    channels[a]
+                        .genre
+                        .to_lowercase()
+                        .cmp(&channels[b].genre.to_lowercase())
+                        .then_with(|| by_title(a, b))
+                });
+            }
+            SortMode::Recent => {
+                // Never-played stations go last, by listeners
+                let last = |i: usize| last_listened(&channels[i].id);
+                indices.sort_by(|&a, &b| last(b).cmp(&last(a)).then_with(|| by_listeners(a, b)));
+            }
+        }
+
+        // While searching, show only matches, best first; ties keep the sort order
+        if !query.trim().is_empty() {
+            let mut scored: Vec<(u32, usize)> = indices
+                .into_iter()
+                .filter_map(|i| match_score(&channels[i], query).map(|score| (score, i)))
+                .collect();
+            scored.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+            indices = scored.into_iter().map(|(_, i)| i).collect();
+        }
+
+        self.sorted = indices;
+    }
+}
+
+impl Index<usize> for StationCatalog {
+    type Output = Channel;
+
+    fn index(&self, index: usize) -> &Channel {
+        &self.channels[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support;
+
+    fn channel(id: &str, listeners: u32) -> Channel {
+        Channel {
+            listeners,
+            ..test_support::channel(id)
+        }
+    }
+
+    fn listed(catalog: &StationCatalog) -> Vec<&str> {
+        catalog
+            .sorted()
+            .iter()
+            .map(|&i| catalog[i].id.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_sources() {
+        let mut catalog = StationCatalog::default();
+        catalog.set_source(
+            StationSource::Custom,
+            vec![channel("pirate", 0), channel("lush", 0)],
+        );
+        catalog.set_source(
+            StationSource::SomaFm,
+            vec![channel("lush", 200), channel("groovesalad", 900)],
+        );
+        let sort = |catalog: &mut StationCatalog| {
+            catalog.sort(
+                SortMode::FavoritesThenListeners,
+                &["pirate".to_string()],
+                |_| None,
+                "",
+            )
+        };
+        sort(&mut catalog);
+        // SomaFM's lush wins over the custom one with the same id
+        assert_eq!(listed(&catalog), ["pirate", "groovesalad", "lush"]);
+        assert_eq!(catalog[catalog.position("lush").unwrap()].listeners, 200);
+        assert_eq!(
+            catalog.sources(),
+            [StationSource::SomaFm, StationSource::Custom]
+        );
+
+        assert_eq!(catalog.cycle_source_filter(), Some(StationSource::SomaFm));
+        sort(&mut catalog);
+        assert_eq!(listed(&catalog), ["groovesalad", "lush"]);
+        assert_eq!(catalog.cycle_source_filter(), Some(StationSource::Custom));
+        assert_eq!(catalog.cycle_source_filter(), None);
+
+        // A fresh SomaFM list leaves the custom stations alone
+        catalog.set_source(StationSource::SomaFm, vec![channel("dronezone", 50)]);
+        sort(&mut catalog);
+        assert_eq!(listed(&catalog), ["pirate", "dronezone", "lush"]);
+        assert_eq!(catalog.find("lush").unwrap().source, StationSource::Custom);
+    }
+
+    #[test]
+    fn test_window() {
+        let mut catalog = StationCatalog::default();
+        catalog.set_source(
+            StationSource::SomaFm,
+            (0..10).map(|i| channel(&i.to_string(), 100 - i)).collect(),
+        );
+        catalog.sort(SortMode::ListenersOnly, &[], |_| None, "");
+
+        assert_eq!(catalog.window(0, Some(2), 4), 0..4);
+        // Scrolls just far enough to reach the selection, either way
+        assert_eq!(catalog.window(0, Some(6), 4), 3..7);
+        assert_eq!(catalog.window(5, Some(2), 4), 2..6);
+        // Never past the end, even if the list got shorter
+        assert_eq!(catalog.window(9, None, 4), 6..10);
+        assert_eq!(catalog.window(0, Some(1), 20), 0..10);
+        let ids: Vec<_> = catalog.listed(3..5).map(|c| c.id.as_str()).collect();
+        assert_eq!(ids, ["3", "4"]);
+    }
+}
@@ -0,0 +1,90 @@
+//! HTTP clients for every request vibecast makes, built with the `network`
+//! settings: how long a DNS lookup may take and whether IPv4 addresses are
+//! tried first.
+
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use reqwest::{Client, ClientBuilder};
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+static SETTINGS: OnceLock<NetworkSettings> = OnceLock::new();
+
+/// Address family and DNS behavior for outgoing connections
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NetworkSettings {
+    /// Connect over IPv4 first, falling back to IPv6, for networks with
+    /// broken IPv6 routes
+    pub prefer_ipv4: bool,
+    pub dns_timeout: Duration,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            prefer_ipv4: false,
+            dns_timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+/// Use `settings` for clients built from now on. Only the first call counts;
+/// clients built before it use the defaults.
+pub fn init(settings: NetworkSettings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// The settings passed to `init`, or the defaults
+pub fn settings() -> NetworkSettings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+/// A client builder set up with the network settings
+pub fn client_builder() -> ClientBuilder {
+    Client::builder().dns_resolver(Arc::new(Resolver(settings())))
+}
+
+/// A client with the network settings and nothing else changed
+pub fn client() -> Client {
+    client_builder().build().unwrap_or_default()
+}
+
+/// The system resolver, with a time limit and IPv4 first when preferred
+struct Resolver(NetworkSettings);
+
+impl Resolve for Resolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let settings = self.0;
+        Box::pin(async move {
+            let lookup = tokio::net::lookup_host((name.as_str(), 0));
+            let addrs = tokio::time::timeout(settings.dns_timeout, lookup)
+                .await
+                .map_err(|_| format!("looking up {} timed out", name.as_str()))??;
+            let addrs: Addrs = Box::new(order(addrs.collect(), settings.prefer_ipv4).into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Addresses in the order to try them. The connector tries the first
+/// address's family first and the other family shortly after.
+fn order(mut addrs: Vec<SocketAddr>, prefer_ipv4: bool) -> Vec<SocketAddr> {
+    if prefer_ipv4 {
+        addrs.sort_by_key(|addr| !addr.is_ipv4());
+    }
+    addrs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order() {
+        let v6: SocketAddr = "[2001:db8::1]:0".parse().unwrap();
+        let v4: SocketAddr = "192.0.2.1:0".parse().unwrap();
+        let v4_other: SocketAddr = "192.0.2.2:0".parse().unwrap();
+        assert_eq!(order(vec![v6, v4, v4_other], true), [v4, v4_other, v6]);
+        assert_eq!(order(vec![v6, v4], false), [v6, v4]);
+    }
+}
@@ -0,0 +1,181 @@
+//! Stream titles read straight from a stream's ICY metadata. Shoutcast and
+//! Icecast servers asked for `Icy-MetaData: 1` put a metadata block after
+//! every `icy-metaint` bytes of audio, and each new track's block carries its
+//! `StreamTitle`.
+
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use std::time::{Duration, Instant};
+use tokio::time;
+
+/// Longest wait for the server to answer or send more of the stream. Audio
+/// arrives several times a second, so this only trips on a dead connection.
+const STALL_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Failed connections in a row before giving up on ICY titles
+const RECONNECT_ATTEMPTS: u32 = 5;
+
+/// A connection that lasted this long was working, so its end counts as the
+/// first failure rather than another in a row
+const HEALTHY_CONNECTION: Duration = Duration::from_secs(60);
+
+/// Splits an ICY stream into audio and metadata blocks
+pub struct IcyParser {
+    metaint: usize,
+    /// Audio bytes left before the next metadata block
+    audio_left: usize,
+    /// Metadata bytes left in the current block, once its length is read
+    meta_left: Option<usize>,
+    meta: Vec<u8>,
+}
+
+impl IcyParser {
+    pub fn new(metaint: usize) -> Self {
+        Self {
+            metaint,
+            audio_left: metaint,
+            meta_left: None,
+            meta: Vec::new(),
+        }
+    }
+
+    /// Take the next bytes of the stream, returning the titles of any
+    /// metadata blocks they complete
+    pub fn feed(&mut self, mut bytes: &[u8]) -> Vec<String> {
+        let mut titles = Vec::new();
+        while !bytes.is_empty() {
+            match self.meta_left {
+                None if self.audio_left > 0 => {
+                    let skip = self.audio_left.min(bytes.len());
+                    self.audio_left -= skip;
+                    bytes = &bytes[skip..];
+                }
+                None => {
+                    // The length byte counts 16-byte units; most blocks are
+                    // empty, meaning nothing changed
+                    let len = bytes[0] as usize * 16;
+                    bytes = &bytes[1..];
+                    if len == 0 {
+                        self.audio_left = self.metaint;
+                    } else {
+                        self.meta_left = Some(len);
+                    }
+                }
+                Some(left) => {
+                    let take = left.min(bytes.len());
+                    self.meta.extend_from_slice(&bytes[..take]);
+                    bytes = &bytes[take..];
+                    if take == left {
+                        titles.extend(stream_title(&self.meta));
+                        self.meta.clear();
+                        self.meta_left = None;
+                        self.audio_left = self.metaint;
+                    } else {
+                        self.meta_left = Some(left - take);
+                    }
+                }
+            }
+        }
+        titles
+    }
+}
+
+/// The `StreamTitle='...';` value in a metadata block, if it's not empty
+fn stream_title(meta: &[u8]) -> Option<String> {
+    let text = String::from_utf8_lossy(meta);
+    let start = text.find("StreamTitle='")? + "StreamTitle='".len();
+    let rest = &text[start..];
+    // Titles can contain quotes, so only a quote followed by ';' ends it
+    let end = rest.find("';").unwrap_or(rest.trim_end_matches('\0').len());
+    let title = rest[..end].trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Connect to `url` and pass each stream title to `on_title` until the
+/// stream ends. Returns `false` straight away if the server doesn't send ICY
+/// metadata, and fails if the connection drops or stalls.
+pub async fn read_titles(
+    client: &Client,
+    url: &str,
+    mut on_title: impl FnMut(String),
+) -> Result<bool> {
+    let request = client.get(url).header("Icy-MetaData", "1").send();
+    let mut response = time::timeout(STALL_TIMEOUT, request)
+        .await
+        .map_err(|_| anyhow!("{} didn't answer", url))??
+        .error_for_status()?;
+    let Some(metaint) = response
+        .headers()
+        .get("icy-metaint")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .filter(|&metaint: &usize| metaint > 0)
+    else {
+        return Ok(false);
+    };
+
+    let mut parser = IcyParser::new(metaint);
+    loop {
+        let chunk = time::timeout(STALL_TIMEOUT, response.chunk())
+            .await
+            .map_err(|_| anyhow!("{} stalled", url))??;
+        let Some(chunk) = chunk else {
+            return Ok(true);
+        };
+        for title in parser.feed(&chunk) {
+            on_title(title);
+        }
+    }
+}
+
+/// Follow the stream titles at `url`, reconnecting after a drop or stall
+/// with a longer wait each time. Returns when the server doesn't send ICY
+/// metadata or keeps failing, so the caller can fall back to mpv.
+pub async fn follow_titles(client: &Client, url: &str, mut on_title: impl FnMut(String)) {
+    let mut failures = 0;
+    loop {
+        let connected = Instant::now();
+        match read_titles(client, url, &mut on_title).await {
+            Ok(false) => return,
+            _ if connected.elapsed() >= HEALTHY_CONNECTION => failures = 1,
+            _ => failures += 1,
+        }
+        if failures >= RECONNECT_ATTEMPTS {
+            return;
+        }
+        time::sleep(Duration::from_secs(1 << failures)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parser() {
+        let meta = b"StreamTitle='Bonobo - Kerala';StreamUrl='';";
+        let mut block = vec![meta.len().div_ceil(16) as u8];
+        block.extend_from_slice(meta);
+        block.resize(1 + block[0] as usize * 16, 0);
+
+        let mut stream = vec![0xAA; 8];
+        stream.push(0); // empty block: title unchanged
+        stream.extend([0xAA; 8]);
+        stream.extend(&block);
+        stream.extend([0xAA; 4]);
+
+        // Fed in awkward pieces, as chunks arrive off the network
+        let mut parser = IcyParser::new(8);
+        let titles: Vec<String> = stream
+            .chunks(5)
+            .flat_map(|chunk| parser.feed(chunk))
+            .collect();
+        assert_eq!(titles, ["Bonobo - Kerala"]);
+
+        assert_eq!(
+            stream_title(b"StreamTitle='Guns N' Roses - Patience';"),
+            Some("Guns N' Roses - Patience".to_string())
+        );
+        assert_eq!(stream_title(b"StreamTitle='';\0\0"), None);
+    }
+}
@@ -0,0 +1,213 @@
+use anyhow::{anyhow, Result};
+use reqwest::Client;
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::sleep;
+
+use super::http;
+
+/// Scrobbles per request; the most Last.fm allows
+const PAGE_SIZE: u32 = 200;
+
+/// Pause between pages, keeping well under Last.fm's 5 requests a second
+const PAGE_DELAY: Duration = Duration::from_millis(250);
+
+/// Wait after a "rate limit exceeded" response before trying again
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Last.fm error code for too many requests
+const RATE_LIMIT_ERROR: u32 = 29;
+
+/// One listen from a Last.fm profile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scrobble {
+    pub artist: String,
+    pub title: String,
+    /// Unix time of the listen
+    pub played_at: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Response {
+    Page { recenttracks: RecentTracks },
+    Error { error: u32, message: String },
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentTracks {
+    #[serde(default)]
+    track: Tracks,
+    #[serde(rename = "@attr")]
+    attr: PageAttr,
+}
+
+/// A page with a single track has it as an object rather than a list
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Tracks {
+    Many(Vec<RecentTrack>),
+    One(RecentTrack),
+}
+
+impl Default for Tracks {
+    fn default() -> Self {
+        Self::Many(Vec::new())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PageAttr {
+    #[serde(rename = "totalPages")]
+    total_pages: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecentTrack {
+    artist: Text,
+    name: String,
+    /// Missing for the track playing right now
+    date: Option<Date>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Text {
+    #[serde(rename = "#text")]
+    text: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Date {
+    uts: String,
+}
+
+impl RecentTracks {
+    fn into_page(self) -> (Vec<Scrobble>, u32) {
+        let total_pages = self.attr.total_pages.parse().unwrap_or(0);
+        let tracks = match self.track {
+            Tracks::Many(tracks) => tracks,
+            Tracks::One(track) => vec![track],
+        };
+        let scrobbles = tracks
+            .into_iter()
+            .filter_map(|track| {
+                Some(Scrobble {
+                    played_at: track.date?.uts.parse().ok()?,
+                    artist: track.artist.text,
+                    title: track.name,
+                })
+            })
+            .collect();
+        (scrobbles, total_pages)
+    }
+}
+
+pub struct LastFmClient {
+    client: Client,
+    api_key: String,
+}
+
+impl LastFmClient {
+    pub fn new(api_key: &str) -> Self {
+        Self {
+            client: http::client(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// One page of `user`'s scrobbles, newest first, with the total number
+    /// of pages. Retries a few times when rate limited.
+    async fn recent_tracks(
+        &self,
+        user: &str,
+        from: i64,
+        page: u32,
+    ) -> Result<(Vec<Scrobble>, u32)> {
+        let mut attempts = 0;
+        loop {
+            let response: Response = self
+                .client
+                .get("https://ws.audioscrobbler.com/2.0/")
+                .query(&[
+                    ("method", "user.getrecenttracks"),
+                    ("format", "json"),
+                    ("user", user),
+                    ("api_key", &self.api_key),
+                    ("from", &from.to_string()),
+                    ("limit", &PAGE_SIZE.to_string()),
+                    ("page", &page.to_string()),
+                ])
+                .send()
+                .await?
+                .json()
+                .await?;
+            match response {
+                Response::Page { recenttracks } => return Ok(recenttracks.into_page()),
+                Response::Error { error, .. } if error == RATE_LIMIT_ERROR && attempts < 3 => {
+                    attempts += 1;
+                    sleep(RATE_LIMIT_BACKOFF).await;
+                }
+                Response::Error { error, message } => {
+                    return Err(anyhow!("Last.fm error {}: {}", error, message))
+                }
+            }
+        }
+    }
+
+    /// Every scrobble on `user`'s profile from unix time `from` on, oldest
+    /// first. `progress` is called with the page just fetched and the page
+    /// count.
+    pub async fn scrobbles_since(
+        &self,
+        user: &str,
+        from: i64,
+        mut progress: impl FnMut(u32, u32),
+    ) -> Result<Vec<Scrobble>> {
+        let mut scrobbles = Vec::new();
+        let mut page = 1;
+        loop {
+            let (mut tracks, total_pages) = self.recent_tracks(user, from, page).await?;
+            scrobbles.append(&mut tracks);
+            progress(page, total_pages);
+            if page >= total_pages {
+                break;
+            }
+            page += 1;
+            sleep(PAGE_DELAY).await;
+        }
+        scrobbles.sort_by_key(|s| s.played_at);
+        Ok(scrobbles)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_recent_tracks() {
+        let json = r##"{"recenttracks":{"track":[
+            {"artist":{"#text":"Bonobo"},"name":"Kerala","@attr":{"nowplaying":"true"}},
+            {"artist":{"#text":"Boards of Canada"},"name":"Roygbiv","date":{"uts":"1700000000","#text":"14 Nov 2023"}}
+        ],"@attr":{"page":"1","totalPages":"3","total":"401"}}}"##;
+        let Response::Page { recenttracks } = serde_json::from_str(json).unwrap() else {
+            panic!("expected a page");
+        };
+        let (scrobbles, total_pages) = recenttracks.into_page();
+        assert_eq!(total_pages, 3);
+        assert_eq!(
+            scrobbles,
+            vec![Scrobble {
+                artist: "Boards of Canada".to_string(),
+                title: "Roygbiv".to_string(),
+                played_at: 1_700_000_000,
+            }]
+        );
+
+        let error = r#"{"error":29,"message":"Rate Limit Exceeded"}"#;
+        assert!(matches!(
+            serde_json::from_str(error).unwrap(),
+            Response::Error { error: 29, .. }
+        ));
+    }
+}
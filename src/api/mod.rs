@@ -1,5 +1,23 @@
+pub mod catalog;
+pub mod http;
+pub mod icy;
+pub mod lastfm;
+pub mod musicbrainz;
+pub mod search;
 pub mod somafm;
+#[cfg(test)]
+pub mod test_support;
+pub mod timeline;
+pub mod track;
+pub mod trends;
 pub mod types;
 
+pub use catalog::{SortMode, StationCatalog, StationSource};
+pub use lastfm::LastFmClient;
+pub use musicbrainz::{MusicBrainzClient, ReleaseInfo};
+pub use search::similar_channels;
 pub use somafm::SomaFmClient;
+pub use timeline::{StationTimeline, TimelineEntry};
+pub use track::{Track, TrackChangeDetector};
+pub use trends::{ListenerTrends, Trend};
 pub use types::{AudioQuality, Channel, Song};
@@ -0,0 +1,236 @@
+//! Release details for a track (canonical album, year and label) from
+//! MusicBrainz. Answers are cached on disk, misses included, so each song is
+//! only looked up once.
+
+use anyhow::Result;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+use super::http;
+use super::track::Track;
+
+const BASE_URL: &str = "https://musicbrainz.org/ws/2";
+
+/// MusicBrainz asks clients to identify themselves
+const USER_AGENT: &str = concat!(
+    "vibecast/",
+    env!("CARGO_PKG_VERSION"),
+    " ( https://github.com/bscoggins/vibecast )"
+);
+
+/// MusicBrainz allows one request a second
+const REQUEST_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Where a track was released
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseInfo {
+    pub album: String,
+    pub year: Option<String>,
+    pub label: Option<String>,
+    /// Length of the recording in milliseconds
+    #[serde(default)]
+    pub length_ms: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RecordingSearch {
+    #[serde(default)]
+    recordings: Vec<Recording>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Recording {
+    /// Milliseconds
+    #[serde(default)]
+    length: Option<u64>,
+    #[serde(default)]
+    releases: Vec<Release>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    id: String,
+    title: String,
+    #[serde(default)]
+    date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseLookup {
+    #[serde(rename = "label-info", default)]
+    label_info: Vec<LabelInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelInfo {
+    label: Option<Label>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Label {
+    name: String,
+}
+
+/// The original release among those of the best matching recording: the
+/// earliest dated one
+fn original_release(search: RecordingSearch) -> Option<Release> {
+    search
+        .recordings
+        .into_iter()
+        .next()?
+        .releases
+        .into_iter()
+        .min_by_key(|release| match release.date.as_deref() {
+            Some(date) if !date.is_empty() => date.to_string(),
+            // Undated releases only if there's nothing else
+            _ => "9999".to_string(),
+        })
+}
+
+/// Quote a value for a MusicBrainz search query
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub struct MusicBrainzClient {
+    client: Client,
+    cache_path: Option<PathBuf>,
+    /// Release details by song, `None` when MusicBrainz had nothing
+    cache: HashMap<String, Option<ReleaseInfo>>,
+    last_request: Option<Instant>,
+}
+
+impl MusicBrainzClient {
+    pub fn new() -> Self {
+        let cache_path = directories::ProjectDirs::from("com", "vibecast", "vibecast")
+            .map(|dirs| dirs.cache_dir().join("musicbrainz.json"));
+        let cache = cache_path
+            .as_ref()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        Self {
+            client: http::client(),
+            cache_path,
+            cache,
+            last_request: None,
+        }
+    }
+
+    /// Release details for `track`, from the cache when it's been looked up
+    /// before. Network errors aren't cached, so the song is tried again the
+    /// next time it plays.
+    pub async fn lookup(&mut self, track: &Track) -> Result<Option<ReleaseInfo>> {
+        let key = track.song_key();
+        if let Some(info) = self.cache.get(&key) {
+            return Ok(info.clone());
+        }
+        let info = self.fetch(track).await?;
+        self.cache.insert(key, info.clone());
+        self.save();
+        Ok(info)
+    }
+
+    async fn fetch(&mut self, track: &Track) -> Result<Option<ReleaseInfo>> {
+        if track.artist.is_empty() {
+            return Ok(None);
+        }
+        let query = format!(
+            "recording:{} AND artist:{}",
+            quote(&track.title),
+            quote(&track.artist)
+        );
+        let search: RecordingSearch = self
+            .get("recording", &[("query", &query), ("limit", "1")])
+            .await?;
+        let length_ms = search
+            .recordings
+            .first()
+            .and_then(|recording| recording.length);
+        let Some(release) = original_release(search) else {
+            return Ok(None);
+        };
+        let lookup: ReleaseLookup = self
+            .get(&format!("release/{}", release.id), &[("inc", "labels")])
+            .await?;
+        Ok(Some(ReleaseInfo {
+            album: release.title,
+            year: release
+                .date
+                .and_then(|date| date.get(..4).map(str::to_string)),
+            label: lookup
+                .label_info
+                .into_iter()
+                .find_map(|info| info.label)
+                .map(|label| label.name),
+            length_ms,
+        }))
+    }
+
+    /// GET `path` as JSON, waiting out the rate limit first
+    async fn get<T: DeserializeOwned>(&mut self, path: &str, query: &[(&str, &str)]) -> Result<T> {
+        if let Some(last) = self.last_request {
+            sleep((last + REQUEST_INTERVAL).saturating_duration_since(Instant::now())).await;
+        }
+        self.last_request = Some(Instant::now());
+        Ok(self
+            .client
+            .get(format!("{}/{}", BASE_URL, path))
+            .header(reqwest::header::USER_AGENT, USER_AGENT)
+            .query(query)
+            .query(&[("fmt", "json")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.cache_path else {
+            return;
+        };
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(json) = serde_json::to_string(&self.cache) {
+            let _ = std::fs::write(path, json);
+        }
+    }
+}
+
+impl Default for MusicBrainzClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_original_release() {
+        let search: RecordingSearch = serde_json::from_str(
+            r#"{"recordings": [{"releases": [
+                {"id": "b", "title": "Late Night Tales", "date": "2009-03-02"},
+                {"id": "u", "title": "Bootleg"},
+                {"id": "a", "title": "Days to Come", "date": "2006-10-02"}
+            ]}]}"#,
+        )
+        .unwrap();
+        let release = original_release(search).unwrap();
+        assert_eq!(
+            (release.id.as_str(), release.title.as_str()),
+            ("a", "Days to Come")
+        );
+
+        assert!(original_release(RecordingSearch { recordings: vec![] }).is_none());
+        assert_eq!(quote(r#"12" Mix"#), r#""12\" Mix""#);
+    }
+}
@@ -0,0 +1,151 @@
+use super::types::Channel;
+
+/// How much a query term counts for, by the field it matches
+const TITLE_WEIGHT: u32 = 8;
+const GENRE_WEIGHT: u32 = 4;
+const DJ_WEIGHT: u32 = 4;
+const DESCRIPTION_WEIGHT: u32 = 1;
+
+/// Score `channel` against a search query. Every whitespace-separated term
+/// must appear (case-insensitively) in the title, genre, DJ or description;
+/// matches on a whole word count double. Returns `None` if any term is
+/// missing, so a higher score means a better match.
+pub fn match_score(channel: &Channel, query: &str) -> Option<u32> {
+    let fields = [
+        (channel.title.to_lowercase(), TITLE_WEIGHT),
+        (channel.genre.to_lowercase(), GENRE_WEIGHT),
+        (channel.dj.to_lowercase(), DJ_WEIGHT),
+        (channel.description.to_lowercase(), DESCRIPTION_WEIGHT),
+    ];
+
+    let mut total = 0;
+    for term in query.split_whitespace() {
+        let term = term.to_lowercase();
+        let score: u32 = fields
+            .iter()
+            .map(|(text, weight)| weight * term_score(text, &term))
+            .sum();
+        if score == 0 {
+            return None;
+        }
+        total += score;
+    }
+    Some(total)
+}
+
+/// 2 for a whole-word match, 1 for a match inside a word, 0 for none
+fn term_score(text: &str, term: &str) -> u32 {
+    if !text.contains(term) {
+        0
+    } else if text
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word == term)
+    {
+        2
+    } else {
+        1
+    }
+}
+
+/// A channel's genres, lowercased. SomaFM separates them with '|'.
+pub fn genres(channel: &Channel) -> Vec<String> {
+    channel
+        .genre
+        .split('|')
+        .map(|genre| genre.trim().to_lowercase())
+        .filter(|genre| !genre.is_empty())
+        .collect()
+}
+
+/// Channels sharing a genre with `channels[index]`, with the genres they
+/// share: most shared genres first, then most listeners
+pub fn similar_channels(channels: &[Channel], index: usize) -> Vec<(usize, Vec<String>)> {
+    let Some(channel) = channels.get(index) else {
+        return Vec::new();
+    };
+    let wanted = genres(channel);
+    let mut similar: Vec<(usize, Vec<String>)> = channels
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != index)
+        .filter_map(|(i, other)| {
+            let shared: Vec<String> = genres(other)
+                .into_iter()
+                .filter(|genre| wanted.contains(genre))
+                .collect();
+            (!shared.is_empty()).then_some((i, shared))
+        })
+        .collect();
+    similar.sort_by(|(a, a_shared), (b, b_shared)| {
+        b_shared
+            .len()
+            .cmp(&a_shared.len())
+            .then(channels[*b].listeners.cmp(&channels[*a].listeners))
+    });
+    similar
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support;
+
+    fn channel(title: &str, genre: &str, dj: &str, description: &str) -> Channel {
+        Channel {
+            title: title.to_string(),
+            description: description.to_string(),
+            genre: genre.to_string(),
+            dj: dj.to_string(),
+            ..test_support::channel(&title.to_lowercase())
+        }
+    }
+
+    #[test]
+    fn test_match_score() {
+        let groove_salad = channel(
+            "Groove Salad",
+            "ambient|electronica",
+            "Rusty Hodge",
+            "A nicely chilled plate of ambient/downtempo beats and grooves.",
+        );
+        let beat_blender = channel(
+            "Beat Blender",
+            "electronica",
+            "DJ Shine",
+            "A late night blend of deep-house and downtempo chill.",
+        );
+
+        assert!(match_score(&groove_salad, "ambient beats").is_some());
+        assert_eq!(match_score(&beat_blender, "ambient beats"), None);
+        assert!(match_score(&groove_salad, "rusty").is_some());
+
+        // A title match outranks a description match
+        let title = match_score(&beat_blender, "beat").unwrap();
+        let description = match_score(&groove_salad, "beat").unwrap();
+        assert!(title > description);
+    }
+
+    #[test]
+    fn test_similar_channels() {
+        let mut channels = vec![
+            channel("Groove Salad", "ambient|electronica", "", ""),
+            channel("Beat Blender", "electronica", "", ""),
+            channel("Drone Zone", "Ambient|Space", "", ""),
+            channel("Secret Agent", "lounge", "", ""),
+            channel("Deep Space One", "ambient|electronica|space", "", ""),
+        ];
+        channels[1].listeners = 50;
+        channels[2].listeners = 10;
+
+        let similar: Vec<(usize, Vec<String>)> = similar_channels(&channels, 0);
+        assert_eq!(
+            similar,
+            [
+                (4, vec!["ambient".to_string(), "electronica".to_string()]),
+                (1, vec!["electronica".to_string()]),
+                (2, vec!["ambient".to_string()]),
+            ]
+        );
+        assert!(similar_channels(&channels, 3).is_empty());
+    }
+}
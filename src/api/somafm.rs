@@ -3,6 +3,7 @@
 use anyhow::Result;
 use reqwest::Client;
 
+use super::http;
 use super::types::{Channel, ChannelsResponse, Song, SongsResponse};
 
 pub struct SomaFmClient {
@@ -13,7 +14,7 @@ pub struct SomaFmClient {
 impl SomaFmClient {
     pub fn new() -> Self {
         Self {
-            client: Client::new(),
+            client: http::client(),
             base_url: "https://api.somafm.com".to_string(),
         }
     }
@@ -30,6 +31,12 @@ impl SomaFmClient {
         Ok(resp.songs)
     }
 
+    /// Server URLs listed in a station's .pls playlist
+    pub async fn get_stream_servers(&self, playlist_url: &str) -> Result<Vec<String>> {
+        let content = self.client.get(playlist_url).send().await?.text().await?;
+        Ok(parse_pls(&content))
+    }
+
     pub async fn get_current_song(&self, channel_id: &str) -> Result<Option<Song>> {
         let songs = self.get_songs(channel_id).await?;
         Ok(songs.into_iter().next())
@@ -41,3 +48,35 @@ impl Default for SomaFmClient {
         Self::new()
     }
 }
+
+/// Stream URLs in a .pls playlist, in entry order
+pub fn parse_pls(content: &str) -> Vec<String> {
+    let mut entries: Vec<(u32, String)> = content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            let number = key.trim().strip_prefix("File")?.parse().ok()?;
+            Some((number, value.trim().to_string()))
+        })
+        .collect();
+    entries.sort_by_key(|(number, _)| *number);
+    entries.into_iter().map(|(_, url)| url).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pls() {
+        let pls = "[playlist]\nnumberofentries=2\nFile2=http://ice4.somafm.com/groovesalad-128-aac\nTitle2=SomaFM: Groove Salad\nFile1=http://ice2.somafm.com/groovesalad-128-aac\nTitle1=SomaFM: Groove Salad\nVersion=2\n";
+        assert_eq!(
+            parse_pls(pls),
+            [
+                "http://ice2.somafm.com/groovesalad-128-aac",
+                "http://ice4.somafm.com/groovesalad-128-aac"
+            ]
+        );
+        assert!(parse_pls("not a playlist").is_empty());
+    }
+}
@@ -0,0 +1,34 @@
+//! Station and song fixtures for tests, with every field empty except the
+//! ones given. Tests that need more fill it in with struct update syntax.
+
+use super::types::{Channel, Song};
+
+/// A SomaFM station titled after its id
+pub fn channel(id: &str) -> Channel {
+    Channel {
+        id: id.to_string(),
+        title: id.to_string(),
+        description: String::new(),
+        genre: String::new(),
+        dj: String::new(),
+        djmail: None,
+        listeners: 0,
+        image: String::new(),
+        largeimage: String::new(),
+        xlimage: None,
+        last_playing: String::new(),
+        playlists: Vec::new(),
+        stream_override: None,
+        source: Default::default(),
+    }
+}
+
+pub fn song(artist: &str, title: &str) -> Song {
+    Song {
+        title: title.to_string(),
+        artist: artist.to_string(),
+        album: None,
+        album_art: None,
+        date: None,
+    }
+}
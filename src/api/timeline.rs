@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use super::types::Song;
+
+/// Entries kept per station, oldest dropped first
+const MAX_ENTRIES: usize = 2000;
+
+/// One play seen in a station's songs feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimelineEntry {
+    /// Unix time the track started, or when it was first seen if the feed
+    /// gave none
+    pub played_at: i64,
+    pub artist: String,
+    pub title: String,
+    pub album: Option<String>,
+}
+
+impl TimelineEntry {
+    fn from_song(song: &Song, now: i64) -> Self {
+        Self {
+            played_at: song.date.map_or(now, |date| date as i64),
+            artist: song.artist.clone(),
+            title: song.title.clone(),
+            album: song.album.clone(),
+        }
+    }
+
+    fn same_track(&self, other: &Self) -> bool {
+        self.artist == other.artist && self.title == other.title
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [
+            self.artist.as_str(),
+            self.title.as_str(),
+            self.album.as_deref().unwrap_or_default(),
+        ]
+        .iter()
+        .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+/// Everything each station played, built up from successive polls of the
+/// songs feed, which only ever returns the last few tracks
+#[derive(Debug, Default)]
+pub struct StationTimeline {
+    stations: HashMap<String, Vec<TimelineEntry>>,
+}
+
+impl StationTimeline {
+    /// Add the tracks from one songs poll, skipping ones already recorded
+    pub fn merge(&mut self, channel_id: &str, current: Option<&Song>, history: &[Song], now: i64) {
+        let entries = self.stations.entry(channel_id.to_string()).or_default();
+        // The feed is newest first; add oldest first so undated tracks are
+        // compared against the one just before them
+        for song in history.iter().rev().chain(current) {
+            let entry = TimelineEntry::from_song(song, now);
+            let known = if song.date.is_some() {
+                entries
+                    .iter()
+                    .rev()
+                    .take_while(|e| e.played_at >= entry.played_at)
+                    .any(|e| e.played_at == entry.played_at && e.same_track(&entry))
+            } else {
+                entries.last().is_some_and(|e| e.same_track(&entry))
+            };
+            if !known {
+                let at = entries.partition_point(|e| e.played_at <= entry.played_at);
+                entries.insert(at, entry);
+            }
+        }
+        if entries.len() > MAX_ENTRIES {
+            entries.drain(..entries.len() - MAX_ENTRIES);
+        }
+    }
+
+    /// Plays on `channel_id` since unix time `since` matching `query`, newest
+    /// first
+    pub fn entries(&self, channel_id: &str, since: i64, query: &str) -> Vec<&TimelineEntry> {
+        self.stations
+            .get(channel_id)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .rev()
+                    .take_while(|e| e.played_at >= since)
+                    .filter(|e| query.is_empty() || e.matches(query))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support;
+
+    fn song(title: &str, date: u64) -> Song {
+        Song {
+            date: Some(date),
+            ..test_support::song("Artist", title)
+        }
+    }
+
+    #[test]
+    fn test_merge_overlapping_polls() {
+        let mut timeline = StationTimeline::default();
+        timeline.merge(
+            "groovesalad",
+            Some(&song("Second", 200)),
+            &[song("First", 100)],
+            250,
+        );
+        timeline.merge(
+            "groovesalad",
+            Some(&song("Third", 300)),
+            &[song("Second", 200), song("First", 100)],
+            350,
+        );
+
+        let titles: Vec<&str> = timeline
+            .entries("groovesalad", 0, "")
+            .iter()
+            .map(|e| e.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Third", "Second", "First"]);
+        assert_eq!(timeline.entries("groovesalad", 150, "").len(), 2);
+        assert_eq!(timeline.entries("groovesalad", 0, "sec").len(), 1);
+        assert!(timeline.entries("dronezone", 0, "").is_empty());
+    }
+}
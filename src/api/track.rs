@@ -0,0 +1,145 @@
+use std::fmt;
+
+use super::types::Song;
+
+/// The track playing on a station, from the songs API or the stream title
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track {
+    pub channel_id: String,
+    pub artist: String,
+    pub title: String,
+}
+
+impl Track {
+    pub fn from_song(channel_id: &str, song: &Song) -> Self {
+        Self {
+            channel_id: channel_id.to_string(),
+            artist: song.artist.clone(),
+            title: song.title.clone(),
+        }
+    }
+
+    /// Parse an ICY stream title, usually "Artist - Title"
+    pub fn from_stream_title(channel_id: &str, stream_title: &str) -> Self {
+        let (artist, title) = stream_title.split_once(" - ").unwrap_or(("", stream_title));
+        Self {
+            channel_id: channel_id.to_string(),
+            artist: artist.to_string(),
+            title: title.to_string(),
+        }
+    }
+
+    /// Identity of the recording regardless of station, for counting plays
+    pub fn song_key(&self) -> String {
+        format!(
+            "{}\u{1f}{}",
+            normalize(&self.artist),
+            normalize(&self.title)
+        )
+    }
+
+    /// Whether `artist` names this track's artist, ignoring case and
+    /// punctuation
+    pub fn by_artist(&self, artist: &str) -> bool {
+        !self.artist.is_empty() && normalize(&self.artist) == normalize(artist)
+    }
+
+    /// This track as a watch list entry, "Artist - Title"
+    pub fn entry(&self) -> String {
+        format!("{} - {}", self.artist, self.title)
+    }
+
+    /// Whether `entry`, as written by `entry()`, is this recording
+    pub fn is_entry(&self, entry: &str) -> bool {
+        Self::from_stream_title("", entry).song_key() == self.song_key()
+    }
+
+    /// Identity for comparison: case, spacing and punctuation differences
+    /// between the API and the stream title are ignored
+    fn key(&self) -> (String, String, String) {
+        (
+            self.channel_id.clone(),
+            normalize(&self.artist),
+            normalize(&self.title),
+        )
+    }
+}
+
+impl fmt::Display for Track {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.artist.is_empty() {
+            write!(f, "{}", self.title)
+        } else {
+            write!(f, "{} by {}", self.title, self.artist)
+        }
+    }
+}
+
+fn normalize(s: &str) -> String {
+    s.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Notices when the playing track actually changes, as opposed to the same
+/// track being reported again or by another source
+#[derive(Debug, Default)]
+pub struct TrackChangeDetector {
+    last: Option<(String, String, String)>,
+}
+
+impl TrackChangeDetector {
+    /// Returns the track if it differs from the last one observed
+    pub fn observe(&mut self, track: Track) -> Option<Track> {
+        let key = track.key();
+        if key.2.is_empty() || self.last.as_ref() == Some(&key) {
+            return None;
+        }
+        self.last = Some(key);
+        Some(track)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_changes_across_sources() {
+        let mut detector = TrackChangeDetector::default();
+        let song = Song {
+            title: "Kerala".to_string(),
+            artist: "Bonobo".to_string(),
+            album: None,
+            album_art: None,
+            date: None,
+        };
+
+        let track = detector.observe(Track::from_song("groovesalad", &song));
+        assert_eq!(track.unwrap().to_string(), "Kerala by Bonobo");
+
+        // Same track from the stream title, formatted differently
+        let same = Track::from_stream_title("groovesalad", "BONOBO -  Kerala!");
+        assert_eq!(detector.observe(same), None);
+
+        let next = Track::from_stream_title("groovesalad", "Tycho - Awake");
+        assert_eq!(detector.observe(next.clone()), Some(next));
+
+        // The same track on another station is a change too
+        let elsewhere = Track::from_stream_title("dronezone", "Tycho - Awake");
+        assert!(detector.observe(elsewhere).is_some());
+    }
+
+    #[test]
+    fn test_watch_entries() {
+        let track = Track::from_stream_title("groovesalad", "Bonobo - Kerala");
+        assert!(track.by_artist("bonobo"));
+        assert!(!track.by_artist("Tycho"));
+        assert!(track.is_entry(&track.entry()));
+        assert!(track.is_entry("BONOBO - kerala!"));
+        assert!(!track.is_entry("Bonobo - Cirrus"));
+        assert!(!Track::from_stream_title("lush", "Kerala").by_artist(""));
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::{HashMap, VecDeque};
+
+use super::types::Channel;
+
+/// Number of listener samples kept per station
+const HISTORY_LEN: usize = 10;
+
+/// Direction of a station's listener count since the previous refresh
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trend {
+    Steady,
+    Rising,
+    Falling,
+    /// Grown sharply across the recorded history
+    Surging,
+}
+
+/// Listener counts recorded across channel list refreshes
+#[derive(Debug, Default)]
+pub struct ListenerTrends {
+    history: HashMap<String, VecDeque<u32>>,
+}
+
+impl ListenerTrends {
+    pub fn record(&mut self, channels: &[Channel]) {
+        for channel in channels {
+            let samples = self.history.entry(channel.id.clone()).or_default();
+            if samples.len() == HISTORY_LEN {
+                samples.pop_front();
+            }
+            samples.push_back(channel.listeners);
+        }
+    }
+
+    pub fn trend(&self, channel_id: &str) -> Trend {
+        let Some(samples) = self.history.get(channel_id) else {
+            return Trend::Steady;
+        };
+        let (Some(&oldest), Some(&latest)) = (samples.front(), samples.back()) else {
+            return Trend::Steady;
+        };
+        let previous = samples
+            .get(samples.len().saturating_sub(2))
+            .copied()
+            .unwrap_or(latest);
+
+        // At least 25% and 20 listeners more than the oldest sample
+        if latest >= oldest + 20 && latest * 4 >= oldest * 5 {
+            Trend::Surging
+        } else if latest > previous {
+            Trend::Rising
+        } else if latest < previous {
+            Trend::Falling
+        } else {
+            Trend::Steady
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support;
+
+    fn channel(listeners: u32) -> Channel {
+        Channel {
+            listeners,
+            ..test_support::channel("groovesalad")
+        }
+    }
+
+    #[test]
+    fn test_trend() {
+        let mut trends = ListenerTrends::default();
+        assert_eq!(trends.trend("groovesalad"), Trend::Steady);
+
+        trends.record(&[channel(100)]);
+        assert_eq!(trends.trend("groovesalad"), Trend::Steady);
+
+        trends.record(&[channel(105)]);
+        assert_eq!(trends.trend("groovesalad"), Trend::Rising);
+
+        trends.record(&[channel(98)]);
+        assert_eq!(trends.trend("groovesalad"), Trend::Falling);
+
+        trends.record(&[channel(140)]);
+        assert_eq!(trends.trend("groovesalad"), Trend::Surging);
+    }
+}
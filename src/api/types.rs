@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize};
+
+use super::catalog::StationSource;
 
 #[derive(Debug, Deserialize)]
 pub struct ChannelsResponse {
@@ -42,6 +44,11 @@ pub struct Channel {
     #[serde(rename = "lastPlaying")]
     pub last_playing: String,
     pub playlists: Vec<Playlist>,
+    /// Stream to play instead of SomaFM's, from `audio.stream_overrides`
+    #[serde(skip)]
+    pub stream_override: Option<String>,
+    #[serde(skip)]
+    pub source: StationSource,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -68,7 +75,8 @@ pub struct Song {
 }
 
 /// Audio quality levels
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum AudioQuality {
     #[default]
     Highest,
@@ -113,8 +121,13 @@ impl AudioQuality {
 }
 
 impl Channel {
-    /// Get stream URL for specified quality
+    /// Get stream URL for specified quality. An override is used whatever
+    /// the quality.
     pub fn stream_url(&self, quality: AudioQuality) -> String {
+        if let Some(url) = &self.stream_override {
+            return url.clone();
+        }
+
         let quality_str = quality.quality_str();
 
         // Try AAC first at requested quality
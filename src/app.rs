@@ -1,47 +1,440 @@
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
+use crossterm::event::{Event, KeyEventKind};
+use futures::StreamExt;
+use image::RgbImage;
 use ratatui::widgets::ListState;
+use std::collections::hash_map::RandomState;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
+use tokio::time;
 
-use crate::api::{AudioQuality, Channel, SomaFmClient, Song};
-use crate::input::Action;
-use crate::player::{MpvController, PlaybackState};
-use crate::storage::{ConfigStore, FavoritesStore};
-use crate::ui::{ArtworkState, Theme, ThemeType, VisualizationMode};
-use crate::visualizer::{SpectrumAnalyzer, SpectrumData};
+use crate::airplay::{self, AirPlayDevice};
+use crate::api::{
+    http, similar_channels, AudioQuality, Channel, ListenerTrends, ReleaseInfo, SomaFmClient, Song,
+    SortMode, StationCatalog, StationSource, StationTimeline, TimelineEntry, Track,
+    TrackChangeDetector,
+};
+use crate::artwork::ImageCache;
+use crate::control::{Command, VolumeChange};
+use crate::event::{AppEvent, EventSender};
+use crate::i18n::{self, t, tf, Locale};
+use crate::input::{handle_key, Action, InputMode};
+use crate::links::{self, Link};
+use crate::metrics::Metrics;
+use crate::notify::{self, NotifyVia};
+use crate::player::{AudioLevels, MpvController, PlaybackState, StreamHealth};
+use crate::scheduler;
+use crate::storage::sync;
+use crate::storage::{
+    format_size, ConfigStore, FavoritesStore, FocusLoss, Job, JobStore, ListeningStore, PanelKind,
+    PlayHistoryStore, Recording, RecordingSchedule, RecordingStore, Session, SessionStore,
+    ThemeStore,
+};
+use crate::ui::header::format_listening;
+use crate::ui::{ArtworkState, ScheduleEntry, Theme, VisualizationMode, VuMeterState};
+use crate::visualizer::{
+    PluginRegistry, SpectrumAnalyzer, SpectrumData, SpectrumSettings, HISTORY_LEN,
+    SMOOTHING_PRESETS,
+};
 
+/// Accumulates time spent actually listening (playing and not paused)
+#[derive(Debug, Clone, Default)]
+pub struct SessionTimer {
+    accumulated: Duration,
+    running_since: Option<Instant>,
+}
+
+impl SessionTimer {
+    /// Start or stop the timer to match the current playback state
+    pub fn sync(&mut self, listening: bool) {
+        match (listening, self.running_since) {
+            (true, None) => self.running_since = Some(Instant::now()),
+            (false, Some(since)) => {
+                self.accumulated += since.elapsed();
+                self.running_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.accumulated + self.running_since.map(|s| s.elapsed()).unwrap_or_default()
+    }
+}
+
+/// Draw timings, to check how much redrawing is saved by skipping clean frames
+#[derive(Debug, Default)]
+pub struct FrameStats {
+    pub drawn: u64,
+    pub skipped: u64,
+    pub total_draw_time: Duration,
+    pub slowest_draw: Duration,
+}
+
+impl FrameStats {
+    pub fn record_draw(&mut self, elapsed: Duration) {
+        self.drawn += 1;
+        self.total_draw_time += elapsed;
+        self.slowest_draw = self.slowest_draw.max(elapsed);
+    }
+
+    pub fn record_skip(&mut self) {
+        self.skipped += 1;
+    }
+
+    pub fn average_draw(&self) -> Duration {
+        if self.drawn == 0 {
+            Duration::ZERO
+        } else {
+            self.total_draw_time / self.drawn as u32
+        }
+    }
+}
+
+impl std::fmt::Display for FrameStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} frames drawn, {} skipped; draw time avg {:.2?}, max {:.2?}",
+            self.drawn,
+            self.skipped,
+            self.average_draw(),
+            self.slowest_draw
+        )
+    }
+}
+
+/// Earlier tracks shown in the Previously Played panel
+const SONG_HISTORY_LEN: usize = 5;
+
+/// Frame interval while something on screen is animating (~60fps)
+const ACTIVE_TICK: Duration = Duration::from_millis(16);
+
+/// Frame interval when idle, paused or unfocused (~5fps)
+const IDLE_TICK: Duration = Duration::from_millis(200);
+
+/// Time without key presses after which the app counts as idle; losing
+/// focus starts the clock too
+const IDLE_AFTER: Duration = Duration::from_secs(5 * 60);
+
+/// Lines the help overlay moves per page
+const HELP_PAGE: usize = 10;
+
+/// Now playing update from the songs API or the stream title, held back
+/// until it's audible
+enum PendingTrack {
+    Songs(Option<Song>, Vec<Song>),
+    StreamTitle(String),
+}
+
+/// Longest wait for a station's playlist before leaving it to mpv
+const PLAYLIST_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often the recordings disk quota is checked while recording
+const QUOTA_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// How often scheduled recordings are checked for starting or finishing
+const SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Cached station artwork older than this is dropped and fetched again
+const ARTWORK_MAX_AGE: Duration = Duration::from_secs(30 * 24 * 60 * 60);
+
+/// How often the session snapshot is written, for crash recovery
+const SESSION_SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A gap between ticks longer than this is the machine sleeping, so it
+/// doesn't count as listening
+const LISTENING_GAP: Duration = Duration::from_secs(10);
+
+/// How long the "still listening?" prompt waits for a key before pausing
+const IDLE_CHECK_GRACE: Duration = Duration::from_secs(60);
+
+/// How often favorites are merged with the synced copy
+const FAVORITES_SYNC_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Wait after a favorites change before syncing it, so a run of changes
+/// goes in one sync
+const FAVORITES_SYNC_DELAY: Duration = Duration::from_secs(10);
+
+/// How often the favorite stations are checked for watched artists
+const WATCH_SCAN_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long a song fetched for the detailed list stands before it's asked
+/// for again
+const SONG_PREVIEW_MAX_AGE: Duration = Duration::from_secs(60);
+
+/// Stations asked at once what they're playing, for the detailed list
+const SONG_PREVIEW_CONCURRENCY: usize = 4;
+
+/// Sleep timer lengths `z` steps through, in minutes, before turning it off
+const SLEEP_STEPS: [i64; 4] = [15, 30, 60, 90];
+
+/// Stations listed in the recent stations popup, one per number key
+const RECENT_STATIONS: usize = 9;
+
+/// How long the volume popup stays up after the last volume key
+const VOLUME_POPUP_TIME: Duration = Duration::from_millis(1500);
+
+/// The last part of that, when the popup is drawn dimmed
+const VOLUME_POPUP_FADE: Duration = Duration::from_millis(400);
+
+/// How long ago something happened, roughly: "5m ago", "3h ago", "2d ago"
+fn format_ago(secs: i64) -> String {
+    match secs.max(0) {
+        secs if secs < 60 => t("ago-now").to_string(),
+        secs if secs < 3600 => tf("ago-minutes", &[("count", &(secs / 60))]),
+        secs if secs < 86_400 => tf("ago-hours", &[("count", &(secs / 3600))]),
+        secs => tf("ago-days", &[("count", &(secs / 86_400))]),
+    }
+}
+
+/// Unix time in milliseconds, the clock jobs are scheduled on
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// The usual gap between songs starting, from their start times newest
+/// first; gaps too short or long to be one track are left out
+fn typical_track_length(starts: impl Iterator<Item = u64>) -> Option<Duration> {
+    let starts: Vec<u64> = starts.collect();
+    let mut lengths: Vec<u64> = starts
+        .windows(2)
+        .map(|pair| pair[0].saturating_sub(pair[1]))
+        .filter(|secs| (30..=30 * 60).contains(secs))
+        .collect();
+    lengths.sort_unstable();
+    lengths
+        .get(lengths.len() / 2)
+        .map(|&secs| Duration::from_secs(secs))
+}
+
+/// A second station kept playing muted, so `Tab` can switch to it without
+/// waiting for the stream to buffer
+pub struct Comparison {
+    /// Station id of the muted side
+    pub standby_id: String,
+    /// Id of the side labelled A, the one playing before the comparison
+    pub a_id: String,
+    standby: MpvController,
+}
+
+/// State of the station timeline popup
+#[derive(Debug, Default)]
+pub struct TimelineView {
+    pub scroll: usize,
+    pub query: String,
+    /// Whether the filter is being typed
+    pub editing: bool,
+    /// Show everything since midnight rather than since launch
+    pub today: bool,
+}
+
+/// State of the theme picker, which previews the highlighted theme
+#[derive(Debug, Default)]
+pub struct ThemePickerView {
+    pub selected: usize,
+    /// Theme in use when the picker opened, put back if it's closed
+    pub original: String,
+}
+
+/// State of the "more like this" popup
+#[derive(Debug, Default)]
+pub struct SimilarView {
+    /// Station id the others are like
+    pub station_id: String,
+    /// Station ids sharing a genre with it, best match first, with the
+    /// genres they share
+    pub stations: Vec<(String, Vec<String>)>,
+    pub selected: usize,
+}
+
+/// State of the audio output picker
+#[derive(Debug, Default)]
+pub struct OutputView {
+    /// AirPlay speakers found, `None` while still looking
+    pub devices: Option<Vec<AirPlayDevice>>,
+    /// Selected row, where 0 is this computer and the rest are `devices`
+    pub selected: usize,
+}
+
+/// A scheduled recording in progress, on its own muted player so playback
+/// can carry on with other stations
+pub struct ScheduledRecording {
+    pub station_id: String,
+    pub ends_at: chrono::NaiveDateTime,
+    /// Whether playback was switched to the station too
+    play: bool,
+    player: MpvController,
+}
+
+/// An option on the settings screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum SortMode {
-    FavoritesThenListeners,
-    Alphabetical,
-    ListenersOnly,
+pub enum Setting {
+    Theme,
+    Visualization,
+    Quality,
+    Artwork,
+    Notifications,
+    NotifyVia,
 }
 
-impl SortMode {
-    pub fn next(self) -> Self {
+impl Setting {
+    pub const ALL: [Setting; 6] = [
+        Setting::Theme,
+        Setting::Visualization,
+        Setting::Quality,
+        Setting::Artwork,
+        Setting::Notifications,
+        Setting::NotifyVia,
+    ];
+
+    pub fn label(self) -> &'static str {
         match self {
-            Self::FavoritesThenListeners => Self::Alphabetical,
-            Self::Alphabetical => Self::ListenersOnly,
-            Self::ListenersOnly => Self::FavoritesThenListeners,
+            Self::Theme => t("setting-theme"),
+            Self::Visualization => t("setting-visualization"),
+            Self::Quality => t("setting-quality"),
+            Self::Artwork => t("setting-artwork"),
+            Self::Notifications => t("setting-notifications"),
+            Self::NotifyVia => t("setting-notify-via"),
         }
     }
 }
 
+/// State of the recordings browser
+#[derive(Debug, Default)]
+pub struct RecordingsView {
+    pub recordings: Vec<Recording>,
+    pub selected: usize,
+    /// New name being typed for the selected recording
+    pub renaming: Option<String>,
+    /// `d` was pressed once; pressing it again deletes
+    pub confirm_delete: bool,
+}
+
 pub struct App {
-    pub channels: Vec<Channel>,
-    pub sorted_indices: Vec<usize>,
+    /// Every station, from every source, and the order they're listed in
+    pub catalog: StationCatalog,
+    /// No channel list has arrived yet
+    pub channels_loading: bool,
+    /// Why the last attempt at the first channel list failed
+    pub channels_error: Option<String>,
+    /// The last channel list request succeeded
+    pub api_reachable: bool,
     pub list_state: ListState,
     pub current_channel: Option<usize>,
+    /// Bumped each time a station starts playing, so metadata requested for
+    /// an earlier one can be told apart
+    pub station_generation: u64,
     pub current_song: Option<Song>,
     pub song_history: Vec<Song>,
     pub stream_title: Option<String>,
+    /// Updates to the three above, each with when it reaches the speakers
+    pending_tracks: VecDeque<(Instant, PendingTrack)>,
+    /// MusicBrainz release details and the track they're for
+    pub release: Option<(Track, ReleaseInfo)>,
     pub config: ConfigStore,
     pub favorites: FavoritesStore,
+    pub play_history: PlayHistoryStore,
     pub sort_mode: SortMode,
     pub show_help: bool,
+    /// Lines the help overlay is scrolled down
+    pub help_scroll: usize,
+    /// New-user tips are on this session
+    pub show_hints: bool,
+    /// Help has been opened this session, so the tip pointing at it is done
+    opened_help: bool,
+    /// Station search; the list only shows matches while it's non-empty
+    pub search_query: String,
+    /// Whether the search query is being typed
+    pub searching: bool,
+    /// Timers and periodic checks, run on the tick when they fall due
+    pub jobs: JobStore,
+    /// Selected row of the jobs view, when it's open
+    pub jobs_view: Option<usize>,
+    /// Station ids waiting to be played, next first
+    pub queue: VecDeque<String>,
+    /// Selected row of the queue popup, while it's open
+    pub queue_view: Option<usize>,
+    pub similar_view: Option<SimilarView>,
+    /// Selected row of the recent stations popup, while it's open
+    pub recent_view: Option<usize>,
+    pub session: SessionStore,
+    /// Listening time per station, across sessions
+    pub listening: ListeningStore,
+    /// Listening time per station id this session
+    pub station_listening: HashMap<String, Duration>,
+    /// When listening time was last counted
+    listening_mark: Instant,
+    /// Listening since the last key press, for the idle check
+    unattended: Duration,
+    /// When the "still listening?" prompt opened, while it's open
+    pub idle_prompt: Option<Instant>,
+    /// Running as `vibecast daemon`, with nobody at a keyboard
+    pub headless: bool,
+    /// Whether the last favorites sync failed, so the error shows once
+    sync_failed: bool,
+    /// Song key each favorite station was last seen playing, so a watched
+    /// artist is only announced when they start
+    watch_seen: HashMap<String, String>,
+    /// Station a watched artist was last spotted on, for `y` to tune in
+    watch_hit: Option<String>,
+    /// Session being restored, waiting for the station list to pick its
+    /// station
+    resume: Option<Session>,
+    pub comparison: Option<Comparison>,
+    /// Hold back track change notifications, on top of the OS setting
+    pub do_not_disturb: bool,
+    /// Every track seen in each station's songs feed
+    pub timeline: StationTimeline,
+    /// The timeline popup, when it's open
+    pub timeline_view: Option<TimelineView>,
+    /// Unix time vibecast started
+    pub launched_at: i64,
+    pub recording_store: RecordingStore,
+    /// The recordings browser, when it's open
+    pub recordings_view: Option<RecordingsView>,
+    /// Selected row of the links popup, when it's open
+    pub links_view: Option<usize>,
+    /// Selected row of the settings screen, when it's open
+    pub settings_view: Option<usize>,
+    pub output_view: Option<OutputView>,
+    /// AirPlay speaker audio is going to, `None` to play here
+    pub airplay_device: Option<AirPlayDevice>,
+    /// Name of the recording playing, when it's not a station
+    pub playing_recording: Option<String>,
+    /// Servers from the playing station's playlist, the one in use, and how
+    /// many have been tried since one last played
+    stream_servers: Vec<String>,
+    server_index: usize,
+    servers_tried: usize,
+    /// Scheduled recordings running now, by index in `recording.schedules`
+    pub scheduled_recordings: HashMap<usize, ScheduledRecording>,
+    /// Whether the nearly-full warning has been shown since usage last
+    /// dropped below it
+    quota_warned: bool,
+    /// Selected row of the visualization picker, when it's open
+    pub visualizer_picker: Option<usize>,
+    pub theme_picker: Option<ThemePickerView>,
     pub show_visualizer: bool,
+    pub show_vu_meter: bool,
     pub show_artwork: bool,
     pub show_history: bool,
+    pub show_grid: bool,
+    pub detailed_list: bool,
+    pub grid_columns: usize,
+    /// Station list rows on screen, as of the last frame
+    pub list_rows: usize,
+    /// What the stations shown in the detailed list are playing, by id
+    pub song_previews: HashMap<String, Song>,
+    /// When each station's song was last asked for, answered or not
+    song_preview_requested: HashMap<String, Instant>,
+    pub thumbnails: HashMap<String, RgbImage>,
+    pub listener_trends: ListenerTrends,
     pub audio_quality: AudioQuality,
     pub player: Arc<Mutex<MpvController>>,
     pub playback_state: PlaybackState,
@@ -49,165 +442,2349 @@ pub struct App {
     pub should_quit: bool,
     pub last_volume: u8,
     pub is_muted: bool,
+    /// Digits typed for an exact volume, while entering one
+    pub volume_input: Option<String>,
+    /// When a volume key was last pressed, while the volume popup is up
+    pub volume_popup: Option<Instant>,
+    /// Station number being typed to jump to, while the prompt is open
+    pub jump_input: Option<String>,
     pub artwork_state: ArtworkState,
     pub spectrum_analyzer: SpectrumAnalyzer,
     pub spectrum_data: SpectrumData,
-    pub audio_levels: Option<(f32, f32)>,
+    pub spectrum_history: Vec<Vec<f32>>,
+    pub audio_levels: Option<AudioLevels>,
+    pub vu_meter: VuMeterState,
     pub visualization_mode: VisualizationMode,
+    pub visualizer_plugins: PluginRegistry,
     pub frame: u64,
-    pub theme_type: ThemeType,
+    pub themes: ThemeStore,
     pub theme: Theme,
+    pub artwork_palette: Vec<(u8, u8, u8)>,
+    pub ascii_ui: bool,
+    pub reduced_motion: bool,
+    pub screen_reader: bool,
+    pub announcement: Option<String>,
+    track_changes: TrackChangeDetector,
+    /// Where events the app raises itself are sent, once the bus is running
+    events: Option<EventSender>,
+    last_reload_check: Instant,
+    pub session_timer: SessionTimer,
+    /// Counters served by `vibecast daemon` when `daemon.metrics_port` is set
+    pub metrics: Arc<Metrics>,
+    /// Whether the terminal window has focus
+    pub focused: bool,
+    /// Last key press or focus change
+    last_input: Instant,
+    /// Volume to go back to when focus returns, while ducked or muted for
+    /// being in the background
+    focus_restore: Option<u8>,
+    /// Feedback shown briefly in the status bar, with when it was set
+    pub message: Option<(String, Instant)>,
+    /// Clock and listening minutes shown in the header when last drawn
+    last_header_minute: (String, u64),
+    /// Seconds into the song the progress bar showed when last drawn
+    last_progress_second: Option<u64>,
+    /// Set when something visible changed and the next loop must redraw
+    pub dirty: bool,
+    pub frame_stats: FrameStats,
 }
 
 impl App {
     pub fn new() -> Self {
         let config = ConfigStore::default();
-        let theme_type = config.theme_type();
-        let theme = Theme::from_type(theme_type);
-        let visualization_mode = config.visualization_mode();
+        i18n::init(Locale::detect(config.language()));
+        http::init(config.network());
+        let mut themes = ThemeStore::default();
+        themes.set_prefer_light(config.prefer_light());
+        let theme = themes
+            .resolve(config.theme_name())
+            .for_terminal()
+            .with_overrides(config.theme_overrides());
+        let visualizer_plugins = PluginRegistry::load().unwrap_or_default();
+        let visualization_mode = match visualizer_plugins.index_of(config.visualization_name()) {
+            Some(index) => VisualizationMode::Plugin(index),
+            None => config.visualization_mode(),
+        };
+        let ascii_ui = config.ascii_ui();
+        let reduced_motion = config.reduced_motion();
+        let screen_reader = config.screen_reader();
+        let show_visualizer = config.layout().starts_enabled(PanelKind::Visualizer);
+        let show_history = config.layout().starts_enabled(PanelKind::History);
+        let show_vu_meter = config.layout().starts_enabled(PanelKind::VuMeter);
+        let show_artwork = config.show_artwork();
+        let sort_mode =
+            SortMode::from_name(config.sort_name()).unwrap_or(SortMode::FavoritesThenListeners);
+        let audio_quality = config.audio_quality();
+        let mut spectrum_analyzer = SpectrumAnalyzer::new();
+        spectrum_analyzer.set_settings(config.spectrum_settings());
+        let recording_store = RecordingStore::new(config.recordings_dir());
+        let mut jobs = JobStore::default();
+        jobs.schedule(
+            Job::RecordingSchedules,
+            now_ms(),
+            Some(SCHEDULE_CHECK_INTERVAL),
+        );
+        jobs.schedule(
+            Job::RecordingQuota,
+            now_ms() + QUOTA_CHECK_INTERVAL.as_millis() as i64,
+            Some(QUOTA_CHECK_INTERVAL),
+        );
+        jobs.schedule(
+            Job::EvictArtwork,
+            now_ms() + 60_000,
+            Some(Duration::from_secs(24 * 60 * 60)),
+        );
+        if config.sync_target().is_some() {
+            jobs.schedule(Job::SyncFavorites, now_ms(), Some(FAVORITES_SYNC_INTERVAL));
+        }
+        jobs.schedule(
+            Job::ScanWatched,
+            now_ms() + WATCH_SCAN_INTERVAL.as_millis() as i64,
+            Some(WATCH_SCAN_INTERVAL),
+        );
+        jobs.schedule(
+            Job::SaveSession,
+            now_ms() + SESSION_SAVE_INTERVAL.as_millis() as i64,
+            Some(SESSION_SAVE_INTERVAL),
+        );
+        let mut player = MpvController::new();
+        player.volume_curve = config.volume_curve();
+        player.snapcast_fifo = config.snapcast_fifo();
+        let custom_stations = config.custom_stations();
 
-        Self {
-            channels: Vec::new(),
-            sorted_indices: Vec::new(),
+        let mut app = Self {
+            catalog: StationCatalog::default(),
+            channels_loading: true,
+            channels_error: None,
+            api_reachable: true,
             list_state: ListState::default(),
             current_channel: None,
+            station_generation: 0,
             current_song: None,
             song_history: Vec::new(),
             stream_title: None,
+            pending_tracks: VecDeque::new(),
+            release: None,
             config,
             favorites: FavoritesStore::default(),
-            sort_mode: SortMode::FavoritesThenListeners,
+            play_history: PlayHistoryStore::default(),
+            sort_mode,
             show_help: false,
-            show_visualizer: true,
-            show_artwork: true,
-            show_history: true,
-            audio_quality: AudioQuality::default(),
-            player: Arc::new(Mutex::new(MpvController::new())),
+            help_scroll: 0,
+            show_hints: false,
+            opened_help: false,
+            search_query: String::new(),
+            searching: false,
+            jobs,
+            jobs_view: None,
+            queue: VecDeque::new(),
+            queue_view: None,
+            similar_view: None,
+            recent_view: None,
+            session: SessionStore::default(),
+            listening: ListeningStore::default(),
+            station_listening: HashMap::new(),
+            listening_mark: Instant::now(),
+            unattended: Duration::ZERO,
+            idle_prompt: None,
+            headless: false,
+            sync_failed: false,
+            watch_seen: HashMap::new(),
+            watch_hit: None,
+            resume: None,
+            comparison: None,
+            do_not_disturb: false,
+            timeline: StationTimeline::default(),
+            timeline_view: None,
+            launched_at: chrono::Utc::now().timestamp(),
+            recording_store,
+            recordings_view: None,
+            links_view: None,
+            settings_view: None,
+            output_view: None,
+            airplay_device: None,
+            playing_recording: None,
+            stream_servers: Vec::new(),
+            server_index: 0,
+            servers_tried: 0,
+            scheduled_recordings: HashMap::new(),
+            quota_warned: false,
+            visualizer_picker: None,
+            theme_picker: None,
+            show_visualizer,
+            show_vu_meter,
+            show_artwork,
+            show_history,
+            show_grid: false,
+            detailed_list: false,
+            grid_columns: 1,
+            list_rows: 0,
+            song_previews: HashMap::new(),
+            song_preview_requested: HashMap::new(),
+            thumbnails: HashMap::new(),
+            listener_trends: ListenerTrends::default(),
+            audio_quality,
+            player: Arc::new(Mutex::new(player)),
             playback_state: PlaybackState::default(),
             api_client: SomaFmClient::new(),
             should_quit: false,
             last_volume: 80,
+            volume_input: None,
+            volume_popup: None,
+            jump_input: None,
             is_muted: false,
             artwork_state: ArtworkState::new(),
-            spectrum_analyzer: SpectrumAnalyzer::new(),
+            spectrum_analyzer,
             spectrum_data: SpectrumData::default(),
+            spectrum_history: Vec::new(),
             audio_levels: None,
+            vu_meter: VuMeterState::default(),
             visualization_mode,
+            visualizer_plugins,
             frame: 0,
-            theme_type,
+            themes,
             theme,
+            artwork_palette: Vec::new(),
+            ascii_ui,
+            reduced_motion,
+            screen_reader,
+            announcement: None,
+            track_changes: TrackChangeDetector::default(),
+            events: None,
+            last_reload_check: Instant::now(),
+            session_timer: SessionTimer::default(),
+            metrics: Arc::default(),
+            focused: true,
+            last_input: Instant::now(),
+            focus_restore: None,
+            message: None,
+            last_header_minute: (String::new(), 0),
+            last_progress_second: None,
+            dirty: true,
+            frame_stats: FrameStats::default(),
+        };
+        // Custom stations are listed while SomaFM's load, or if they can't
+        app.set_stations(StationSource::Custom, custom_stations);
+        app
+    }
+
+    /// Resolve the named theme and apply artwork tinting when enabled, then
+    /// the configured per-widget overrides
+    fn apply_theme(&mut self, name: &str) {
+        let mut theme = self.themes.resolve(name);
+        // Tint first: only RGB colors blend, and the terminal may not keep them
+        if self.config.dynamic_colors() {
+            theme = theme.tinted(&self.artwork_palette);
         }
+        self.theme = theme
+            .for_terminal()
+            .with_overrides(self.config.theme_overrides());
     }
 
-    pub fn cycle_theme(&mut self) {
-        self.theme_type = self.theme_type.next();
-        self.theme = Theme::from_type(self.theme_type);
-        // Save theme preference
-        self.config.set_theme(self.theme_type);
-        let _ = self.config.save();
+    pub fn set_artwork_palette(&mut self, palette: Vec<(u8, u8, u8)>) {
+        if palette != self.artwork_palette {
+            self.artwork_palette = palette;
+            let name = self.theme.name.clone();
+            self.apply_theme(&name);
+        }
     }
 
-    pub async fn init(&mut self) -> Result<()> {
-        self.channels = self.api_client.get_channels().await?;
-        self.update_sorted_indices();
-        if !self.sorted_indices.is_empty() {
-            self.list_state.select(Some(0));
+    /// Play at `quality` from now on, restarting the stream if needed
+    async fn set_audio_quality(&mut self, quality: AudioQuality) -> Result<()> {
+        if quality == self.audio_quality {
+            return Ok(());
+        }
+        self.audio_quality = quality;
+        self.config.set_audio_quality(quality);
+        let _ = self.config.save();
+        if self.playback_state.playing {
+            self.end_comparison().await?;
+            if let Some(channel) = self.current_channel().cloned() {
+                self.play_stream(&channel.stream_url(self.audio_quality))
+                    .await?;
+                self.audio_levels = None;
+            }
         }
         Ok(())
     }
 
-    fn update_sorted_indices(&mut self) {
-        let favorites = self.favorites.favorites();
-        let mut indices: Vec<usize> = (0..self.channels.len()).collect();
-
-        match self.sort_mode {
-            SortMode::FavoritesThenListeners => {
-                indices.sort_by(|&a, &b| {
-                    let a_fav = favorites.contains(&self.channels[a].id);
-                    let b_fav = favorites.contains(&self.channels[b].id);
-
-                    match (a_fav, b_fav) {
-                        (true, false) => std::cmp::Ordering::Less,
-                        (false, true) => std::cmp::Ordering::Greater,
-                        _ => self.channels[b].listeners.cmp(&self.channels[a].listeners),
-                    }
+    /// Show or hide a right-hand panel, remembered for the next launch
+    fn set_panel_shown(&mut self, kind: PanelKind, show: bool) {
+        match kind {
+            PanelKind::Visualizer => self.show_visualizer = show,
+            PanelKind::VuMeter => self.show_vu_meter = show,
+            PanelKind::History => self.show_history = show,
+            PanelKind::NowPlaying | PanelKind::Schedule => return,
+        }
+        self.config.layout_mut().set_enabled(kind, show);
+        let _ = self.config.save();
+    }
+
+    /// Open the output picker and look for AirPlay speakers in the
+    /// background. They arrive as `AppEvent::AirPlayDevices`.
+    fn open_outputs(&mut self) {
+        // Both would want mpv's output
+        if self.config.snapcast_fifo().is_some() {
+            self.flash(t("msg-airplay-snapcast").to_string());
+            return;
+        }
+        self.output_view = Some(OutputView::default());
+        if let Some(events) = self.events.clone() {
+            tokio::task::spawn_blocking(move || {
+                let result =
+                    airplay::discover(airplay::DISCOVERY_TIME).map_err(|err| err.to_string());
+                let _ = events.send(AppEvent::AirPlayDevices(result));
+            });
+        }
+    }
+
+    /// Fill the output picker with the speakers discovery found
+    fn show_airplay_devices(&mut self, mut devices: Vec<AirPlayDevice>) {
+        let Some(view) = self.output_view.as_mut() else {
+            return;
+        };
+        // The speaker in use may be too busy to answer, but stays a choice
+        if let Some(current) = &self.airplay_device {
+            if !devices.contains(current) {
+                devices.insert(0, current.clone());
+            }
+        }
+        view.selected = self
+            .airplay_device
+            .as_ref()
+            .and_then(|current| devices.iter().position(|device| device == current))
+            .map_or(0, |index| index + 1);
+        view.devices = Some(devices);
+    }
+
+    /// Apply `action` to the output picker if it's open, returning whether
+    /// it was used there
+    async fn outputs_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(view) = self.output_view.as_mut() else {
+            return Ok(false);
+        };
+        let count = view.devices.as_ref().map_or(0, Vec::len);
+        match action {
+            Action::OverlayDown | Action::NextStation => {
+                view.selected = (view.selected + 1).min(count);
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                view.selected = view.selected.saturating_sub(1);
+            }
+            Action::OverlaySelect => {
+                let device = view.selected.checked_sub(1).and_then(|index| {
+                    view.devices
+                        .as_ref()
+                        .and_then(|devices| devices.get(index).cloned())
                 });
+                self.output_view = None;
+                self.set_airplay_device(device).await?;
             }
-            SortMode::Alphabetical => {
-                indices.sort_by(|&a, &b| self.channels[a].title.cmp(&self.channels[b].title));
+            Action::CloseOverlay => self.output_view = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Play through `device`, or here when `None`, restarting the stream so
+    /// it moves. A speaker that won't play leaves audio here.
+    async fn set_airplay_device(&mut self, device: Option<AirPlayDevice>) -> Result<()> {
+        if device == self.airplay_device {
+            return Ok(());
+        }
+        self.player.lock().await.airplay = device.as_ref().map(|device| device.addr);
+        self.airplay_device = device;
+        if self.playback_state.playing {
+            self.end_comparison().await?;
+            if let Some(channel) = self.current_channel().cloned() {
+                let url = channel.stream_url(self.audio_quality);
+                self.audio_levels = None;
+                if let Err(err) = self.play_stream(&url).await {
+                    self.player.lock().await.airplay = None;
+                    self.airplay_device = None;
+                    self.flash(tf("msg-airplay-failed", &[("error", &err)]));
+                    return self.play_stream(&url).await;
+                }
+            }
+        }
+        self.flash(match &self.airplay_device {
+            Some(device) => tf("msg-airplay-on", &[("speaker", &device.name)]),
+            None => t("msg-airplay-off").to_string(),
+        });
+        Ok(())
+    }
+
+    fn set_show_artwork(&mut self, show: bool) {
+        self.show_artwork = show;
+        if !show {
+            self.artwork_state.clear();
+            self.set_artwork_palette(Vec::new());
+        }
+        self.config.set_show_artwork(show);
+        let _ = self.config.save();
+    }
+
+    /// Apply `action` to the theme picker if it's open, returning whether
+    /// it was used there. Moving previews a theme; only Enter keeps it.
+    fn theme_picker_action(&mut self, action: &Action) -> bool {
+        let Some(view) = &mut self.theme_picker else {
+            return false;
+        };
+        let names = self.themes.names();
+        match action {
+            Action::OverlayDown => {
+                view.selected = (view.selected + 1).min(names.len().saturating_sub(1));
+            }
+            Action::OverlayUp => view.selected = view.selected.saturating_sub(1),
+            Action::OverlaySelect => {
+                self.theme_picker = None;
+                self.config.set_theme_name(&self.theme.name);
+                let _ = self.config.save();
+                return true;
             }
-            SortMode::ListenersOnly => {
-                indices
-                    .sort_by(|&a, &b| self.channels[b].listeners.cmp(&self.channels[a].listeners));
+            Action::CloseOverlay => {
+                let original = view.original.clone();
+                self.theme_picker = None;
+                self.apply_theme(&original);
+                return true;
             }
+            _ => return false,
         }
+        if let Some(name) = names.get(view.selected) {
+            self.apply_theme(name);
+        }
+        true
+    }
 
-        self.sorted_indices = indices;
+    /// Pick up added or edited theme files, re-applying the active theme,
+    /// and settings or favorites changed by another tool or a sync client
+    pub fn poll_file_reload(&mut self) {
+        if self.last_reload_check.elapsed() < Duration::from_secs(2) {
+            return;
+        }
+        self.last_reload_check = Instant::now();
+        if self.themes.reload_if_changed() {
+            let name = self.theme.name.clone();
+            self.apply_theme(&name);
+        }
+        if self.config.reload_if_changed() {
+            let config = &self.config;
+            self.catalog.update(StationSource::SomaFm, |channel| {
+                channel.stream_override = config.stream_override(&channel.id).map(str::to_string);
+            });
+            self.set_stations(StationSource::Custom, self.config.custom_stations());
+            // Picks up a new bin count too; the analyzer resizes on its next frame
+            self.spectrum_analyzer
+                .set_settings(self.config.spectrum_settings());
+            self.themes.set_prefer_light(self.config.prefer_light());
+            let name = self.config.theme_name().to_string();
+            self.apply_theme(&name);
+            self.dirty = true;
+        }
+        if self.favorites.reload_if_changed() {
+            self.favorites_changed();
+            let selected = self.selected_channel_index();
+            self.update_sorted_indices();
+            if let Some(index) = selected {
+                self.select_channel(index);
+            }
+            self.dirty = true;
+        }
     }
 
-    pub fn sorted_channels(&self) -> Vec<&Channel> {
-        self.sorted_indices
-            .iter()
-            .map(|&i| &self.channels[i])
-            .collect()
+    /// How often to tick and redraw: full rate only while an animated panel
+    /// is on screen for active playback in a focused terminal
+    pub fn tick_rate(&self) -> Duration {
+        if self.is_animating() && self.focused {
+            ACTIVE_TICK
+        } else {
+            IDLE_TICK
+        }
+    }
+
+    /// Whether an animated panel is on screen during active playback
+    fn is_animating(&self) -> bool {
+        let listening = self.playback_state.playing && !self.playback_state.paused;
+        listening
+            && !self.show_grid
+            && !self.screen_reader
+            && (self.show_visualizer || self.show_vu_meter)
+    }
+
+    /// Swap in a freshly fetched SomaFM channel list
+    pub fn replace_channels(&mut self, mut channels: Vec<Channel>) {
+        for channel in &mut channels {
+            channel.stream_override = self.config.stream_override(&channel.id).map(str::to_string);
+        }
+        self.listener_trends.record(&channels);
+        self.channels_loading = false;
+        self.channels_error = None;
+        self.set_stations(StationSource::SomaFm, channels);
+    }
+
+    /// Replace the stations from `source`, keeping the playing and selected
+    /// stations pointed at the same channels
+    fn set_stations(&mut self, source: StationSource, channels: Vec<Channel>) {
+        let playing_id = self.current_channel().map(|c| c.id.clone());
+        let selected_id = self.selected_channel().map(|c| c.id.clone());
+
+        self.catalog.set_source(source, channels);
+        self.update_sorted_indices();
+        if self.list_state.selected().is_none() && !self.catalog.sorted().is_empty() {
+            self.list_state.select(Some(0));
+        }
+
+        self.current_channel = playing_id.and_then(|id| self.catalog.position(&id));
+        if let Some(id) = selected_id {
+            if let Some(pos) = self
+                .catalog
+                .sorted()
+                .iter()
+                .position(|&i| self.catalog[i].id == id)
+            {
+                self.list_state.select(Some(pos));
+            }
+        }
+    }
+
+    fn update_sorted_indices(&mut self) {
+        self.catalog.sort(
+            self.sort_mode,
+            self.favorites.favorites(),
+            |id| self.listening.last_listened(id),
+            &self.search_query,
+        );
+    }
+
+    /// List the next station source on its own, or every source again,
+    /// keeping the highlighted station if it's still listed
+    fn cycle_source(&mut self) {
+        let sources = self.catalog.sources();
+        if sources.len() < 2 {
+            let source = sources.first().copied().unwrap_or_default();
+            self.flash(tf("msg-one-source", &[("source", &source.label())]));
+            return;
+        }
+        let selected_id = self.selected_channel().map(|c| c.id.clone());
+        let message = match self.catalog.cycle_source_filter() {
+            Some(source) => tf("msg-source-only", &[("source", &source.label())]),
+            None => t("msg-source-all").to_string(),
+        };
+        self.update_sorted_indices();
+        let pos = selected_id.and_then(|id| {
+            self.catalog
+                .sorted()
+                .iter()
+                .position(|&i| self.catalog[i].id == id)
+        });
+        let first = (!self.catalog.sorted().is_empty()).then_some(0);
+        self.list_state.select(pos.or(first));
+        self.flash(message);
     }
 
     pub fn selected_channel(&self) -> Option<&Channel> {
         self.list_state
             .selected()
-            .and_then(|i| self.sorted_indices.get(i))
-            .map(|&idx| &self.channels[idx])
+            .and_then(|i| self.catalog.sorted().get(i))
+            .map(|&idx| &self.catalog[idx])
     }
 
     pub fn selected_channel_index(&self) -> Option<usize> {
         self.list_state
             .selected()
-            .and_then(|i| self.sorted_indices.get(i).copied())
+            .and_then(|i| self.catalog.sorted().get(i).copied())
+    }
+
+    /// Highlight the channel at `index`, clearing a search that hides it
+    fn select_channel(&mut self, index: usize) {
+        if !self.catalog.sorted().contains(&index) {
+            self.set_search_query(String::new());
+        }
+        let pos = self.catalog.sorted().iter().position(|&i| i == index);
+        self.list_state.select(pos);
+    }
+
+    /// Move the selection by `delta` entries, stopping at either end
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.catalog.sorted().len();
+        if len > 0 {
+            let current = self.list_state.selected().unwrap_or(0) as isize;
+            let next = (current + delta).clamp(0, len as isize - 1);
+            self.list_state.select(Some(next as usize));
+        }
     }
 
     pub fn current_channel(&self) -> Option<&Channel> {
-        self.current_channel.map(|i| &self.channels[i])
+        self.current_channel.map(|i| &self.catalog[i])
+    }
+
+    async fn quit(&mut self) -> Result<()> {
+        self.should_quit = true;
+        let _ = self.session.save(self.session_snapshot(true));
+        self.count_listening();
+        let _ = self.listening.save();
+        self.end_comparison().await?;
+        for (_, mut recording) in self.scheduled_recordings.drain() {
+            recording.player.stop().await?;
+        }
+        let mut player = self.player.lock().await;
+        player.stop().await?;
+        self.playback_state = player.state.clone();
+        self.audio_levels = None;
+        Ok(())
+    }
+
+    /// Filter the station list to `query`, selecting the best match
+    fn set_search_query(&mut self, query: String) {
+        self.search_query = query;
+        self.update_sorted_indices();
+        let first = (!self.catalog.sorted().is_empty()).then_some(0);
+        self.list_state.select(first);
+    }
+
+    /// Stations a random pick can land on: those in the list (so a search
+    /// narrows it), limited by the `surf` config, other than the one playing
+    fn random_candidates(&self) -> Vec<usize> {
+        let surf = self.config.surf();
+        let genre = surf.genre.as_deref().map(str::to_lowercase);
+        (0..self.catalog.sorted().len())
+            .filter(|&pos| {
+                let index = self.catalog.sorted()[pos];
+                let channel = &self.catalog[index];
+                Some(index) != self.current_channel
+                    && (!surf.favorites_only || self.favorites.is_favorite(&channel.id))
+                    && match &genre {
+                        Some(genre) => channel.genre.to_lowercase().contains(genre.as_str()),
+                        None => true,
+                    }
+            })
+            .collect()
+    }
+
+    async fn play_random_station(&mut self) -> Result<()> {
+        let candidates = self.random_candidates();
+        if candidates.is_empty() {
+            self.flash(t("msg-no-other-stations").to_string());
+            return Ok(());
+        }
+        // A fresh RandomState is randomly keyed, which is all the randomness needed here
+        let roll = RandomState::new().build_hasher().finish();
+        let pos = candidates[roll as usize % candidates.len()];
+        self.list_state.select(Some(pos));
+        self.play_current_station().await
+    }
+
+    /// Start the selected station muted alongside the playing one
+    async fn start_comparison(&mut self) -> Result<()> {
+        // Both sides would write into the one FIFO
+        if self.config.snapcast_fifo().is_some() {
+            self.flash(t("msg-compare-snapcast").to_string());
+            return Ok(());
+        }
+        // The standby player would play here while the speaker plays A
+        if self.airplay_device.is_some() {
+            self.flash(t("msg-compare-airplay").to_string());
+            return Ok(());
+        }
+        let (Some(a_channel), Some(selected)) =
+            (self.current_channel, self.selected_channel_index())
+        else {
+            self.flash(t("msg-compare-needs-playing").to_string());
+            return Ok(());
+        };
+        if selected == a_channel {
+            self.flash(t("msg-compare-needs-other").to_string());
+            return Ok(());
+        }
+
+        let mut standby = MpvController::named("compare");
+        standby.state.volume = self.playback_state.volume;
+        standby.volume_curve = self.config.volume_curve();
+        standby.state.muted = true;
+        let url = self.catalog[selected].stream_url(self.audio_quality);
+        if let Err(err) = standby.play(&url).await {
+            self.flash(tf("msg-compare-failed", &[("error", &err)]));
+            return Ok(());
+        }
+        self.comparison = Some(Comparison {
+            standby_id: self.catalog[selected].id.clone(),
+            a_id: self.catalog[a_channel].id.clone(),
+            standby,
+        });
+        self.flash(t("msg-comparing").to_string());
+        Ok(())
+    }
+
+    /// Stop the muted side of a comparison, keeping the audible one
+    async fn end_comparison(&mut self) -> Result<()> {
+        if let Some(mut comparison) = self.comparison.take() {
+            comparison.standby.stop().await?;
+        }
+        Ok(())
+    }
+
+    /// Make the muted side of the comparison the one playing
+    async fn swap_comparison(&mut self) -> Result<()> {
+        let Some(mut comparison) = self.comparison.take() else {
+            self.flash(t("msg-no-comparison").to_string());
+            return Ok(());
+        };
+        let (Some(current), Some(next)) = (
+            self.current_channel,
+            self.catalog.position(&comparison.standby_id),
+        ) else {
+            // The station list changed under the comparison
+            comparison.standby.stop().await?;
+            return Ok(());
+        };
+        let unmuted = {
+            let mut player = self.player.lock().await;
+            comparison.standby.set_volume(player.state.volume).await?;
+            std::mem::swap(&mut *player, &mut comparison.standby);
+            let unmuted = player.set_mute(false).await;
+            let _ = comparison.standby.set_mute(true).await;
+            self.playback_state = player.state.clone();
+            unmuted
+        };
+        comparison.standby_id = self.catalog[current].id.clone();
+        let side = if self.catalog[next].id == comparison.a_id {
+            "A"
+        } else {
+            "B"
+        };
+        match unmuted {
+            Ok(()) => self.flash(tf(
+                "msg-compare-side",
+                &[("side", &side), ("station", &self.catalog[next].title)],
+            )),
+            Err(err) => self.flash(tf("msg-unmute-failed", &[("side", &side), ("error", &err)])),
+        }
+        self.comparison = Some(comparison);
+        self.set_current_channel(Some(next));
+        Ok(())
     }
 
     async fn play_current_station(&mut self) -> Result<()> {
         if let Some(channel) = self.selected_channel().cloned() {
             let url = channel.stream_url(self.audio_quality);
             let idx = self.selected_channel_index();
-            let mut player = self.player.lock().await;
-            player.play(&url).await?;
-            self.playback_state = player.state.clone();
-            self.current_channel = idx;
-            self.stream_title = None;
-            self.current_song = None;
-            self.song_history.clear();
-            self.artwork_state.clear();
-            self.audio_levels = None;
+            self.end_comparison().await?;
+            self.play_stream(&url).await?;
+            self.set_current_channel(idx);
         }
         Ok(())
     }
 
-    pub async fn handle_action(&mut self, action: Action) -> Result<()> {
+    /// Play a station's playlist on the main player. The playlist is read
+    /// here, rather than by mpv, so `fail_over` can move through its servers.
+    async fn play_stream(&mut self, playlist_url: &str) -> Result<()> {
+        // Overrides can be a stream rather than a playlist, which would
+        // never finish downloading
+        let servers = if playlist_url.ends_with(".pls") {
+            time::timeout(
+                PLAYLIST_TIMEOUT,
+                self.api_client.get_stream_servers(playlist_url),
+            )
+            .await
+            .ok()
+            .and_then(Result::ok)
+        } else {
+            None
+        };
+        self.stream_servers = match servers {
+            Some(servers) if !servers.is_empty() => servers,
+            // mpv can still read the playlist itself
+            _ => vec![playlist_url.to_string()],
+        };
+        self.server_index = 0;
+        self.servers_tried = 1;
+        let mut player = self.player.lock().await;
+        player.play(&self.stream_servers[0]).await?;
+        self.playback_state = player.state.clone();
+        Ok(())
+    }
+
+    /// Server the playing station is streaming from
+    pub fn stream_server(&self) -> Option<&str> {
+        if self.current_channel.is_none()
+            || self.playing_recording.is_some()
+            || !self.playback_state.playing
+        {
+            return None;
+        }
+        self.stream_servers
+            .get(self.server_index)
+            .map(String::as_str)
+    }
+
+    /// Move on to the station's next server once mpv has given up on the
+    /// current one, until each has had a try
+    async fn fail_over(&mut self) -> Result<()> {
+        if self.playing_recording.is_some() || self.servers_tried >= self.stream_servers.len() {
+            return Ok(());
+        }
+        let mut player = self.player.lock().await;
+        if !player.has_exited() {
+            return Ok(());
+        }
+        self.server_index = (self.server_index + 1) % self.stream_servers.len();
+        self.servers_tried += 1;
+        let url = self.stream_servers[self.server_index].clone();
+        let result = player.play(&url).await;
+        self.playback_state = player.state.clone();
+        drop(player);
+        let server = reqwest::Url::parse(&url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .unwrap_or(url);
+        match result {
+            Ok(()) => self.flash(tf("msg-server-failover", &[("server", &server)])),
+            Err(err) => self.flash(tf(
+                "msg-server-failover-failed",
+                &[("server", &server), ("error", &err)],
+            )),
+        }
+        Ok(())
+    }
+
+    /// Links for the links popup: the playing station's, or the highlighted
+    /// one's when nothing is playing
+    pub fn station_links(&self) -> Vec<Link> {
+        links::station_links(self.timeline_channel())
+    }
+
+    /// Apply `action` to the jobs view if it's open, returning whether it was
+    /// used there
+    fn jobs_action(&mut self, action: &Action) -> bool {
+        let Some(selected) = self.jobs_view else {
+            return false;
+        };
+        let pending = self.jobs.pending();
         match action {
-            Action::Quit => {
-                self.should_quit = true;
-                let mut player = self.player.lock().await;
-                player.stop().await?;
-                self.playback_state = player.state.clone();
-                self.audio_levels = None;
+            Action::OverlayDown | Action::NextStation => {
+                self.jobs_view = Some((selected + 1).min(pending.len().saturating_sub(1)));
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                self.jobs_view = Some(selected.saturating_sub(1));
+            }
+            Action::CancelJob => {
+                // Only timers the user set; the periodic checks keep the app
+                // working
+                if let Some(job) = pending.get(selected).filter(|job| job.job.is_saved()) {
+                    let (id, label) = (job.id, job.job.label());
+                    self.jobs.cancel(id);
+                    self.jobs_view =
+                        Some(selected.min(self.jobs.pending().len().saturating_sub(1)));
+                    self.flash(tf("msg-job-cancelled", &[("job", &label)]));
+                }
+            }
+            Action::CloseOverlay => self.jobs_view = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply `action` to the links popup if it's open, returning whether it
+    /// was used there
+    fn links_action(&mut self, action: &Action) -> bool {
+        let Some(selected) = self.links_view else {
+            return false;
+        };
+        let links = self.station_links();
+        match action {
+            Action::OverlayDown | Action::NextStation => {
+                self.links_view = Some((selected + 1).min(links.len().saturating_sub(1)));
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                self.links_view = Some(selected.saturating_sub(1));
+            }
+            Action::OverlaySelect => {
+                if let Some(link) = links.get(selected) {
+                    if let Err(err) = links::open_in_browser(&link.url) {
+                        self.flash(tf("msg-link-open-failed", &[("error", &err)]));
+                    }
+                }
+            }
+            Action::CopyLink => {
+                if let Some(link) = links.get(selected) {
+                    if links::copy_to_clipboard(&link.url).is_ok() {
+                        self.flash(tf("msg-link-copied", &[("url", &link.url)]));
+                    }
+                }
+            }
+            Action::CloseOverlay => self.links_view = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply `action` to the timeline popup if it's open, returning whether
+    /// it was used there
+    fn timeline_action(&mut self, action: &Action) -> bool {
+        let len = self.timeline_entries().len();
+        let Some(view) = self.timeline_view.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::OverlayDown | Action::NextStation => {
+                view.scroll = (view.scroll + 1).min(len.saturating_sub(1));
+            }
+            Action::OverlayUp | Action::PrevStation => view.scroll = view.scroll.saturating_sub(1),
+            Action::TimelineScope => {
+                view.today = !view.today;
+                view.scroll = 0;
+            }
+            Action::OpenSearch => view.editing = true,
+            Action::SearchInput(c) => {
+                view.query.push(*c);
+                view.scroll = 0;
+            }
+            Action::SearchBackspace => {
+                view.query.pop();
+                view.scroll = 0;
+            }
+            Action::SearchConfirm => view.editing = false,
+            Action::SearchCancel => {
+                view.editing = false;
+                view.query.clear();
+            }
+            Action::CloseOverlay => self.timeline_view = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Apply `action` to the volume prompt if it's open, returning whether
+    /// it was used there
+    async fn volume_input_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(input) = self.volume_input.as_mut() else {
+            return Ok(false);
+        };
+        match action {
+            Action::SearchInput(c) => {
+                if c.is_ascii_digit() && input.len() < 3 {
+                    input.push(*c);
+                }
+            }
+            Action::SearchBackspace => {
+                input.pop();
+            }
+            Action::SearchConfirm => {
+                let input = self.volume_input.take().unwrap_or_default();
+                match input.parse::<u8>() {
+                    Ok(volume) if volume <= 100 => {
+                        self.is_muted = false;
+                        let mut player = self.player.lock().await;
+                        player.set_volume(volume).await?;
+                        self.playback_state = player.state.clone();
+                    }
+                    _ if input.is_empty() => {}
+                    _ => self.flash(t("msg-volume-range").to_string()),
+                }
+            }
+            Action::SearchCancel => self.volume_input = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Apply `action` to the volume popup if it's up, returning whether it
+    /// was used there. Left and right change the volume a point at a time.
+    async fn volume_popup_action(&mut self, action: &Action) -> Result<bool> {
+        if self.volume_popup.is_none() {
+            return Ok(false);
+        }
+        let step = match action {
+            Action::VolumeFine(step) => *step as i16,
+            Action::CloseOverlay => {
+                self.volume_popup = None;
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+        let volume = if self.is_muted {
+            self.is_muted = false;
+            self.last_volume
+        } else {
+            self.playback_state.volume
+        };
+        let mut player = self.player.lock().await;
+        player
+            .set_volume((volume as i16 + step).clamp(0, 100) as u8)
+            .await?;
+        self.playback_state = player.state.clone();
+        self.volume_popup = Some(Instant::now());
+        Ok(true)
+    }
+
+    /// Close the volume popup once it has been up long enough
+    fn poll_volume_popup(&mut self) {
+        if self
+            .volume_popup
+            .is_some_and(|shown_at| shown_at.elapsed() >= VOLUME_POPUP_TIME)
+        {
+            self.volume_popup = None;
+            self.dirty = true;
+        }
+    }
+
+    /// Whether the volume popup is in its last moments, drawn dimmed
+    pub fn volume_popup_fading(&self) -> bool {
+        self.volume_popup
+            .is_some_and(|shown_at| shown_at.elapsed() + VOLUME_POPUP_FADE >= VOLUME_POPUP_TIME)
+    }
+
+    /// Apply `action` to the jump-to-number prompt if it's open, returning
+    /// whether it was used there
+    fn jump_input_action(&mut self, action: &Action) -> bool {
+        let Some(input) = self.jump_input.as_mut() else {
+            return false;
+        };
+        match action {
+            Action::SearchInput(c) => {
+                if c.is_ascii_digit() && input.len() < 4 {
+                    input.push(*c);
+                }
+            }
+            Action::SearchBackspace => {
+                input.pop();
+            }
+            Action::SearchConfirm => {
+                let input = self.jump_input.take().unwrap_or_default();
+                match input.parse::<usize>() {
+                    Ok(number) if (1..=self.catalog.sorted().len()).contains(&number) => {
+                        self.list_state.select(Some(number - 1));
+                    }
+                    _ if input.is_empty() => {}
+                    _ => self.flash(tf("msg-no-station-number", &[("number", &input)])),
+                }
+            }
+            Action::SearchCancel => self.jump_input = None,
+            _ => return false,
+        }
+        true
+    }
+
+    /// Translation id of the new-user tip that fits what's on screen, until
+    /// the user has done what it suggests
+    pub fn onboarding_hint(&self) -> Option<&'static str> {
+        if !self.show_hints || self.catalog.is_empty() {
+            return None;
+        }
+        if self.current_channel.is_none() && !self.playback_state.playing {
+            Some("tip-play")
+        } else if self.favorites.favorites().is_empty() {
+            Some("tip-favorite")
+        } else if !self.opened_help {
+            Some("tip-help")
+        } else {
+            None
+        }
+    }
+
+    /// Current value of each setting, for the settings screen
+    pub fn settings_entries(&self) -> Vec<(&'static str, String)> {
+        let on_off = |on: bool| {
+            if on {
+                t("setting-on")
+            } else {
+                t("setting-off")
+            }
+            .to_string()
+        };
+        Setting::ALL
+            .iter()
+            .map(|&setting| {
+                let value = match setting {
+                    Setting::Theme => self.theme.name.clone(),
+                    Setting::Visualization => self.visualization_name(self.visualization_mode),
+                    Setting::Quality => self.audio_quality.label().to_string(),
+                    Setting::Artwork => on_off(self.show_artwork),
+                    Setting::Notifications => on_off(self.config.notifications()),
+                    Setting::NotifyVia => self.config.notify_via().label().to_string(),
+                };
+                (setting.label(), value)
+            })
+            .collect()
+    }
+
+    /// Apply `action` to the settings screen if it's open, returning whether
+    /// it was used there. Changes are saved straight away.
+    async fn settings_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(selected) = self.settings_view else {
+            return Ok(false);
+        };
+        let forward = match action {
+            Action::OverlayDown => {
+                self.settings_view = Some((selected + 1).min(Setting::ALL.len() - 1));
+                return Ok(true);
+            }
+            Action::OverlayUp => {
+                self.settings_view = Some(selected.saturating_sub(1));
+                return Ok(true);
+            }
+            Action::CloseOverlay => {
+                self.settings_view = None;
+                return Ok(true);
+            }
+            Action::MoveRight => true,
+            Action::MoveLeft => false,
+            _ => return Ok(false),
+        };
+        match Setting::ALL[selected] {
+            Setting::Theme => {
+                let name = if forward {
+                    self.themes.next_name(&self.theme.name)
+                } else {
+                    self.themes.prev_name(&self.theme.name)
+                };
+                self.apply_theme(&name);
+                self.config.set_theme_name(&self.theme.name);
+                let _ = self.config.save();
+            }
+            Setting::Visualization => self.step_visualization(forward),
+            Setting::Quality => {
+                let quality = if forward {
+                    self.audio_quality.higher()
+                } else {
+                    self.audio_quality.lower()
+                };
+                self.set_audio_quality(quality).await?;
+            }
+            Setting::Artwork => self.set_show_artwork(!self.show_artwork),
+            Setting::Notifications => {
+                let on = !self.config.notifications();
+                self.config.set_notifications(on);
+                let _ = self.config.save();
+            }
+            Setting::NotifyVia => {
+                let via = self.config.notify_via().next();
+                self.config.set_notify_via(via);
+                let _ = self.config.save();
+            }
+        }
+        Ok(true)
+    }
+
+    /// Apply `action` to the recordings browser if it's open, returning
+    /// whether it was used there
+    async fn recordings_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(view) = self.recordings_view.as_mut() else {
+            return Ok(false);
+        };
+        let confirming = std::mem::take(&mut view.confirm_delete);
+        if let Some(name) = view.renaming.as_mut() {
+            match action {
+                Action::SearchInput(c) => name.push(*c),
+                Action::SearchBackspace => {
+                    name.pop();
+                }
+                Action::SearchConfirm => {
+                    let name = view.renaming.take().unwrap_or_default();
+                    if let Some(recording) = view.recordings.get(view.selected) {
+                        let result = self.recording_store.rename(recording, &name);
+                        match result {
+                            Ok(_) => self.refresh_recordings(),
+                            Err(err) => self.flash(tf("msg-rename-failed", &[("error", &err)])),
+                        }
+                    }
+                }
+                Action::SearchCancel => view.renaming = None,
+                _ => return Ok(false),
+            }
+            return Ok(true);
+        }
+
+        let last = view.recordings.len().saturating_sub(1);
+        match action {
+            Action::OverlayDown => view.selected = (view.selected + 1).min(last),
+            Action::OverlayUp => view.selected = view.selected.saturating_sub(1),
+            Action::RenameRecording => {
+                view.renaming = view.recordings.get(view.selected).map(|r| r.name.clone());
+            }
+            Action::DeleteRecording if confirming => {
+                if let Some(recording) = view.recordings.get(view.selected) {
+                    match self.recording_store.delete(recording) {
+                        Ok(()) => self.refresh_recordings(),
+                        Err(err) => self.flash(tf("msg-delete-failed", &[("error", &err)])),
+                    }
+                }
+            }
+            Action::DeleteRecording => view.confirm_delete = !view.recordings.is_empty(),
+            Action::OverlaySelect => {
+                if let Some(recording) = view.recordings.get(view.selected).cloned() {
+                    self.recordings_view = None;
+                    self.play_recording(&recording).await?;
+                }
+            }
+            // Esc first backs out of a pending delete
+            Action::CloseOverlay if confirming => {}
+            Action::CloseOverlay => self.recordings_view = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Re-read the recordings directory, keeping the selection in range
+    fn refresh_recordings(&mut self) {
+        let recordings = self.recording_store.list();
+        if let Some(view) = self.recordings_view.as_mut() {
+            view.selected = view.selected.min(recordings.len().saturating_sub(1));
+            view.recordings = recordings;
+        }
+    }
+
+    async fn play_recording(&mut self, recording: &Recording) -> Result<()> {
+        self.end_comparison().await?;
+        {
+            let mut player = self.player.lock().await;
+            player.play(&recording.path.to_string_lossy()).await?;
+            self.playback_state = player.state.clone();
+        }
+        self.set_current_channel(None);
+        self.playing_recording = Some(recording.name.clone());
+        Ok(())
+    }
+
+    /// Start saving the playing station's stream, or stop and keep the file
+    async fn toggle_recording(&mut self) -> Result<()> {
+        let mut player = self.player.lock().await;
+        if let Some(path) = player.state.recording.clone() {
+            let result = player.stop_recording().await;
+            self.playback_state = player.state.clone();
+            drop(player);
+            match result {
+                Ok(()) => self.flash(tf(
+                    "msg-recording-saved",
+                    &[(
+                        "file",
+                        &path.file_name().unwrap_or_default().to_string_lossy(),
+                    )],
+                )),
+                Err(err) => self.flash(tf("msg-stop-recording-failed", &[("error", &err)])),
+            }
+            return Ok(());
+        }
+
+        let Some(channel) = self.current_channel().filter(|_| player.state.playing) else {
+            drop(player);
+            self.flash(t("msg-record-needs-playing").to_string());
+            return Ok(());
+        };
+        let url = channel.stream_url(self.audio_quality);
+        let result = match self
+            .recording_store
+            .new_path(&channel.id, recording_extension(channel, &url))
+        {
+            Ok(path) => player.start_recording(&path).await,
+            Err(err) => Err(err),
+        };
+        self.playback_state = player.state.clone();
+        drop(player);
+        if let Err(err) = result {
+            self.flash(tf("msg-record-failed", &[("error", &err)]));
+        }
+        Ok(())
+    }
+
+    /// Keep recordings under `recording.max_disk_usage_mb` while anything is
+    /// recording: warn as they near it, delete the oldest past it, and stop
+    /// recording if what's being written is itself too big
+    async fn enforce_recording_quota(&mut self) -> Result<()> {
+        let Some(quota) = self.config.recording_quota() else {
+            return Ok(());
+        };
+        let in_progress: Vec<PathBuf> = self
+            .playback_state
+            .recording
+            .iter()
+            .chain(
+                self.scheduled_recordings
+                    .values()
+                    .filter_map(|r| r.player.state.recording.as_ref()),
+            )
+            .cloned()
+            .collect();
+        if in_progress.is_empty() {
+            return Ok(());
+        }
+
+        let deleted = match self.recording_store.rotate(quota, &in_progress) {
+            Ok(deleted) => deleted,
+            Err(err) => {
+                self.flash(tf("msg-rotate-failed", &[("error", &err)]));
+                Vec::new()
+            }
+        };
+        let usage = self.recording_store.usage();
+        if !deleted.is_empty() && self.recordings_view.is_some() {
+            self.refresh_recordings();
+        }
+
+        if usage > quota {
+            let mut player = self.player.lock().await;
+            let result = if player.state.recording.is_some() {
+                player.stop_recording().await
+            } else {
+                Ok(())
+            };
+            self.playback_state = player.state.clone();
+            drop(player);
+            for recording in self.scheduled_recordings.values_mut() {
+                if recording.player.state.recording.is_some() {
+                    let _ = recording.player.stop_recording().await;
+                }
+            }
+            self.flash(match result {
+                Ok(()) => tf("msg-quota-stopped", &[("quota", &format_size(quota))]),
+                Err(err) => tf("msg-stop-recording-failed", &[("error", &err)]),
+            });
+        } else if !deleted.is_empty() {
+            let id = if deleted.len() == 1 {
+                "msg-quota-deleted-one"
+            } else {
+                "msg-quota-deleted"
+            };
+            self.flash(tf(
+                id,
+                &[("count", &deleted.len()), ("quota", &format_size(quota))],
+            ));
+        } else if usage >= quota / 10 * 9 {
+            if !self.quota_warned {
+                self.quota_warned = true;
+                self.flash(tf(
+                    "msg-quota-warning",
+                    &[
+                        ("usage", &format_size(usage)),
+                        ("quota", &format_size(quota)),
+                    ],
+                ));
+            }
+        } else {
+            self.quota_warned = false;
+        }
+        Ok(())
+    }
+
+    /// Do a job that has fallen due
+    async fn run_job(&mut self, job: Job) -> Result<()> {
+        match job {
+            Job::Sleep => {
+                if self.playback_state.playing && !self.playback_state.paused {
+                    let mut player = self.player.lock().await;
+                    player.toggle_pause().await?;
+                    self.playback_state = player.state.clone();
+                }
+                self.flash(t("msg-sleep-done").to_string());
+            }
+            Job::Alarm { station } => {
+                if let Some(id) = station {
+                    let Some(index) = self.catalog.position(&id) else {
+                        self.flash(tf("msg-alarm-no-station", &[("station", &id)]));
+                        return Ok(());
+                    };
+                    self.select_channel(index);
+                }
+                if let Err(err) = self.play_current_station().await {
+                    self.flash(tf("msg-alarm-failed", &[("error", &err)]));
+                }
+            }
+            Job::Surf => self.play_random_station().await?,
+            Job::AdvanceQueue => {
+                self.play_next_in_queue().await?;
+            }
+            Job::RecordingSchedules => self.run_schedules().await?,
+            Job::RecordingQuota => self.enforce_recording_quota().await?,
+            Job::SyncFavorites => self.sync_favorites(),
+            Job::ScanWatched => self.scan_watched(),
+            Job::SaveSession => {
+                let _ = self.session.save(self.session_snapshot(false));
+                let _ = self.listening.save();
+            }
+            Job::EvictArtwork => {
+                tokio::task::spawn_blocking(|| ImageCache::default().evict(ARTWORK_MAX_AGE));
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the sleep timer to `minutes` from now, or turn it off
+    pub fn set_sleep_timer(&mut self, minutes: Option<i64>) {
+        self.jobs.cancel_all(&Job::Sleep);
+        match minutes {
+            Some(minutes) => {
+                self.jobs
+                    .schedule(Job::Sleep, now_ms() + minutes * 60_000, None);
+                self.flash(tf("msg-sleep-set", &[("minutes", &minutes)]));
+            }
+            None => self.flash(t("msg-sleep-off").to_string()),
+        }
+    }
+
+    /// Minutes left on the sleep timer, rounded up
+    pub fn sleep_minutes_left(&self) -> Option<i64> {
+        self.jobs
+            .next(&Job::Sleep)
+            .map(|job| (job.due_ms - now_ms() + 59_999).max(0) / 60_000)
+    }
+
+    /// The queued stations, next first
+    pub fn queued_stations(&self) -> Vec<&Channel> {
+        self.queue
+            .iter()
+            .filter_map(|id| self.catalog.find(id))
+            .collect()
+    }
+
+    /// Add the time since it was last counted, once a second, to the
+    /// playing station's listening time, if it's playing
+    fn count_listening(&mut self) {
+        let elapsed = self.listening_mark.elapsed();
+        if elapsed < Duration::from_secs(1) {
+            return;
+        }
+        self.listening_mark = Instant::now();
+        if !self.playback_state.playing || self.playback_state.paused || elapsed > LISTENING_GAP {
+            return;
+        }
+        let Some(id) = self.current_channel().map(|c| c.id.clone()) else {
+            return;
+        };
+        *self.station_listening.entry(id.clone()).or_default() += elapsed;
+        self.unattended += elapsed;
+        self.listening
+            .add(&id, elapsed, chrono::Local::now().date_naive());
+    }
+
+    /// Ask whether anyone's still listening after `audio.idle_check_hours`
+    /// of playback without a key press, pausing if nobody answers in time
+    async fn check_idle(&mut self) -> Result<()> {
+        let listening = self.playback_state.playing && !self.playback_state.paused;
+        let Some(opened) = self.idle_prompt else {
+            if let Some(after) = self.config.idle_check().filter(|_| !self.headless) {
+                if listening && self.unattended >= after {
+                    self.idle_prompt = Some(Instant::now());
+                    self.dirty = true;
+                }
+            }
+            return Ok(());
+        };
+        if !listening {
+            self.idle_prompt = None;
+            self.dirty = true;
+        } else if opened.elapsed() >= IDLE_CHECK_GRACE {
+            self.idle_prompt = None;
+            let mut player = self.player.lock().await;
+            player.toggle_pause().await?;
+            self.playback_state = player.state.clone();
+            drop(player);
+            self.flash(t("msg-idle-paused").to_string());
+        }
+        Ok(())
+    }
+
+    /// Time left before the "still listening?" prompt pauses playback
+    pub fn idle_pauses_in(&self) -> Option<Duration> {
+        self.idle_prompt
+            .map(|opened| IDLE_CHECK_GRACE.saturating_sub(opened.elapsed()))
+    }
+
+    /// Answer the "still listening?" prompt, which takes any key
+    fn idle_action(&mut self, action: &Action) -> bool {
+        if self.idle_prompt.is_none() || *action != Action::CloseOverlay {
+            return false;
+        }
+        self.idle_prompt = None;
+        true
+    }
+
+    /// How long `station_id` has been listened to this week and this
+    /// session, for the station footer; `None` before the first minute
+    pub fn listening_summary(&self, station_id: &str) -> Option<String> {
+        let week = self
+            .listening
+            .week(station_id, chrono::Local::now().date_naive());
+        if week.as_secs() < 60 {
+            return None;
+        }
+        let mut summary = tf("footer-week", &[("time", &format_listening(week))]);
+        let session = self
+            .station_listening
+            .get(station_id)
+            .copied()
+            .unwrap_or_default();
+        if session.as_secs() >= 60 {
+            summary.push_str(" · ");
+            summary.push_str(&tf(
+                "footer-session",
+                &[("time", &format_listening(session))],
+            ));
+        }
+        Some(summary)
+    }
+
+    /// Merge the favorites with the synced copy in the background. The
+    /// result comes back as `AppEvent::FavoritesSynced`.
+    fn sync_favorites(&mut self) {
+        let (Some(target), Some(events)) = (self.config.sync_target(), self.events.clone()) else {
+            return;
+        };
+        let doc = self.favorites.sync_doc();
+        tokio::spawn(async move {
+            let result = sync::sync(&target, doc)
+                .await
+                .map_err(|err| err.to_string());
+            let _ = events.send(AppEvent::FavoritesSynced(result));
+        });
+    }
+
+    /// Fetch what the other favorite stations are playing in the
+    /// background, if anything is watched. The songs come back as
+    /// `AppEvent::FavoriteSongs`.
+    fn scan_watched(&mut self) {
+        let Some(events) = self.events.clone().filter(|_| self.config.watching()) else {
+            return;
+        };
+        let playing = self.current_channel().map(|c| c.id.clone());
+        let stations: Vec<String> = self
+            .favorites
+            .favorites()
+            .iter()
+            .filter(|id| Some(*id) != playing.as_ref())
+            .filter(|id| {
+                self.catalog
+                    .find(id)
+                    .is_some_and(|channel| channel.source == StationSource::SomaFm)
+            })
+            .cloned()
+            .collect();
+        if stations.is_empty() {
+            return;
+        }
+        tokio::spawn(async move {
+            let client = SomaFmClient::new();
+            let mut songs = Vec::new();
+            for id in stations {
+                if let Ok(Some(song)) = client.get_current_song(&id).await {
+                    songs.push((id, song));
+                }
+            }
+            let _ = events.send(AppEvent::FavoriteSongs(songs));
+        });
+    }
+
+    /// Ask the stations in view in the detailed list what they're playing, a
+    /// few at a time, unless they were asked within the last minute. Each
+    /// answer arrives as `AppEvent::SongPreview`.
+    fn prefetch_song_previews(&mut self) {
+        if !self.detailed_list || self.show_grid {
+            return;
+        }
+        let Some(events) = self.events.clone() else {
+            return;
+        };
+        let rows = self.catalog.window(
+            self.list_state.offset(),
+            self.list_state.selected(),
+            self.list_rows,
+        );
+        let stations: Vec<String> = self
+            .catalog
+            .listed(rows)
+            .filter(|channel| channel.source == StationSource::SomaFm)
+            .filter(|channel| {
+                self.song_preview_requested
+                    .get(&channel.id)
+                    .is_none_or(|asked| asked.elapsed() >= SONG_PREVIEW_MAX_AGE)
+            })
+            .map(|channel| channel.id.clone())
+            .collect();
+        if stations.is_empty() {
+            return;
+        }
+        for id in &stations {
+            self.song_preview_requested
+                .insert(id.clone(), Instant::now());
+        }
+        tokio::spawn(async move {
+            let client = SomaFmClient::new();
+            let client = &client;
+            futures::stream::iter(stations)
+                .for_each_concurrent(SONG_PREVIEW_CONCURRENCY, |channel_id| {
+                    let events = events.clone();
+                    async move {
+                        if let Ok(Ok(Some(song))) = time::timeout(
+                            Duration::from_secs(10),
+                            client.get_current_song(&channel_id),
+                        )
+                        .await
+                        {
+                            let _ = events.send(AppEvent::SongPreview { channel_id, song });
+                        }
+                    }
+                })
+                .await;
+        });
+    }
+
+    /// Announce a watched artist or track that has started on one of the
+    /// favorite stations
+    fn check_watched(&mut self, songs: Vec<(String, Song)>) {
+        let playing = self.current_channel().map(|c| c.id.clone());
+        for (id, song) in songs {
+            let track = Track::from_song(&id, &song);
+            let key = track.song_key();
+            if self.watch_seen.get(&id) == Some(&key) {
+                continue;
+            }
+            self.watch_seen.insert(id.clone(), key);
+            if Some(&id) == playing.as_ref() || !self.config.is_watched(&track) {
+                continue;
+            }
+            let Some(station) = self.catalog.find(&id) else {
+                continue;
+            };
+            let station = station.title.clone();
+            self.flash(tf(
+                "msg-watched-playing",
+                &[("track", &track), ("station", &station)],
+            ));
+            self.notify(station, track.to_string());
+            self.watch_hit = Some(id);
+        }
+    }
+
+    /// Watch the playing artist, or with `track` the playing track, or
+    /// stop watching it
+    fn toggle_watched(&mut self, track: bool) {
+        let Some(playing) = self
+            .current_track()
+            .filter(|playing| !playing.artist.is_empty())
+        else {
+            self.flash(t("msg-nothing-to-watch").to_string());
+            return;
+        };
+        let (watched, name) = if track {
+            (
+                self.config.toggle_watched_track(&playing),
+                playing.to_string(),
+            )
+        } else {
+            (
+                self.config.toggle_watched_artist(&playing),
+                playing.artist.clone(),
+            )
+        };
+        let _ = self.config.save();
+        let message = if watched {
+            "msg-watching"
+        } else {
+            "msg-unwatched"
+        };
+        self.flash(tf(message, &[("name", &name)]));
+    }
+
+    /// Play the station a watched artist was last spotted on
+    async fn play_watched(&mut self) -> Result<()> {
+        let index = self
+            .watch_hit
+            .take()
+            .and_then(|id| self.catalog.position(&id));
+        let Some(index) = index else {
+            self.flash(t("msg-no-watched").to_string());
+            return Ok(());
+        };
+        self.select_channel(index);
+        self.play_current_station().await
+    }
+
+    /// Save the favorites after a change, and sync them soon
+    fn favorites_changed(&mut self) {
+        let _ = self.favorites.save();
+        if self.config.sync_target().is_some() {
+            self.jobs.cancel_all(&Job::SyncFavorites);
+            self.jobs.schedule(
+                Job::SyncFavorites,
+                now_ms() + FAVORITES_SYNC_DELAY.as_millis() as i64,
+                Some(FAVORITES_SYNC_INTERVAL),
+            );
+        }
+    }
+
+    /// Move the highlighted favorite earlier (`up`) or later in the
+    /// favorites order, keeping it highlighted
+    fn shift_favorite(&mut self, up: bool) {
+        let Some(channel) = self.selected_channel() else {
+            return;
+        };
+        let (id, title) = (channel.id.clone(), channel.title.clone());
+        if !self.favorites.is_favorite(&id) {
+            self.flash(tf("msg-not-favorite", &[("station", &title)]));
+            return;
+        }
+        let Some(position) = self.favorites.shift(&id, up) else {
+            return;
+        };
+        self.favorites_changed();
+        self.update_sorted_indices();
+        if let Some(index) = self.catalog.position(&id) {
+            self.select_channel(index);
+        }
+        self.flash(tf(
+            "msg-favorite-moved",
+            &[("station", &title), ("position", &(position + 1))],
+        ));
+    }
+
+    /// Add the highlighted station to the end of the queue
+    fn enqueue_selected(&mut self) {
+        if let Some(index) = self.selected_channel_index() {
+            self.enqueue(index);
+        }
+    }
+
+    /// Add the channel at `index` to the end of the queue
+    fn enqueue(&mut self, index: usize) {
+        let channel = &self.catalog[index];
+        let (id, title) = (channel.id.clone(), channel.title.clone());
+        if self.queue.contains(&id) {
+            self.flash(tf("msg-already-queued", &[("station", &title)]));
+        } else {
+            self.queue.push_back(id);
+            self.flash(tf(
+                "msg-queued",
+                &[("station", &title), ("position", &self.queue.len())],
+            ));
+        }
+    }
+
+    /// Play the station at the front of the queue, returning whether there
+    /// was one. With `surf.queue_minutes` set, the one after follows on its
+    /// own.
+    async fn play_next_in_queue(&mut self) -> Result<bool> {
+        self.jobs.cancel_all(&Job::AdvanceQueue);
+        // Stations can drop out of the list between refreshes
+        let Some(index) =
+            std::iter::from_fn(|| self.queue.pop_front()).find_map(|id| self.catalog.position(&id))
+        else {
+            self.flash(t("msg-queue-empty").to_string());
+            return Ok(false);
+        };
+        self.select_channel(index);
+        self.play_current_station().await?;
+        if let (Some(interval), false) = (self.config.queue_interval(), self.queue.is_empty()) {
+            self.jobs.schedule(
+                Job::AdvanceQueue,
+                now_ms() + interval.as_millis() as i64,
+                None,
+            );
+        }
+        self.queue_view = self
+            .queue_view
+            .map(|selected| selected.min(self.queue.len().saturating_sub(1)));
+        Ok(true)
+    }
+
+    /// Apply `action` to the queue popup if it's open, returning whether it
+    /// was used there
+    async fn queue_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(selected) = self.queue_view else {
+            return Ok(false);
+        };
+        let last = self.queue.len().saturating_sub(1);
+        match action {
+            Action::OverlayDown | Action::NextStation => {
+                self.queue_view = Some((selected + 1).min(last));
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                self.queue_view = Some(selected.saturating_sub(1));
+            }
+            Action::MoveItemDown if selected < last => {
+                self.queue.swap(selected, selected + 1);
+                self.queue_view = Some(selected + 1);
+            }
+            Action::MoveItemUp if selected > 0 => {
+                self.queue.swap(selected, selected - 1);
+                self.queue_view = Some(selected - 1);
+            }
+            Action::Dequeue => {
+                self.queue.remove(selected);
+                self.queue_view = Some(selected.min(self.queue.len().saturating_sub(1)));
+            }
+            Action::OverlaySelect => {
+                // Jump the queue: play it now and carry on from there
+                if let Some(id) = self.queue.remove(selected) {
+                    self.queue.push_front(id);
+                    self.queue_view = None;
+                    self.play_next_in_queue().await?;
+                }
+            }
+            Action::CloseOverlay => self.queue_view = None,
+            Action::MoveItemUp | Action::MoveItemDown => {}
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Open the "more like this" popup for the playing station, or the
+    /// highlighted one when nothing is playing
+    fn open_similar(&mut self) {
+        let Some(index) = self.current_channel.or(self.selected_channel_index()) else {
+            return;
+        };
+        let stations: Vec<(String, Vec<String>)> = similar_channels(self.catalog.channels(), index)
+            .into_iter()
+            .map(|(i, shared)| (self.catalog[i].id.clone(), shared))
+            .collect();
+        let channel = &self.catalog[index];
+        if stations.is_empty() {
+            self.flash(tf("msg-no-similar", &[("station", &channel.title)]));
+            return;
+        }
+        self.similar_view = Some(SimilarView {
+            station_id: channel.id.clone(),
+            stations,
+            selected: 0,
+        });
+    }
+
+    /// The station the "more like this" popup is for, and the stations in
+    /// it with their shared genres
+    pub fn similar_entries(&self) -> Option<(&Channel, Vec<(&Channel, String)>)> {
+        let view = self.similar_view.as_ref()?;
+        let find = |id: &str| self.catalog.find(id);
+        let entries = view
+            .stations
+            .iter()
+            .filter_map(|(id, shared)| Some((find(id)?, shared.join(", "))))
+            .collect();
+        Some((find(&view.station_id)?, entries))
+    }
+
+    /// Apply `action` to the "more like this" popup if it's open, returning
+    /// whether it was used there
+    async fn similar_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(view) = self.similar_view.as_mut() else {
+            return Ok(false);
+        };
+        let index = view
+            .stations
+            .get(view.selected)
+            .and_then(|(id, _)| self.catalog.position(id));
+        match action {
+            Action::OverlayDown | Action::NextStation => {
+                view.selected = (view.selected + 1).min(view.stations.len().saturating_sub(1));
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                view.selected = view.selected.saturating_sub(1);
+            }
+            Action::OverlaySelect => {
+                self.similar_view = None;
+                if let Some(index) = index {
+                    self.select_channel(index);
+                    self.play_current_station().await?;
+                }
+            }
+            Action::Enqueue => {
+                if let Some(index) = index {
+                    self.enqueue(index);
+                }
+            }
+            Action::CloseOverlay => self.similar_view = None,
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Stations listened to last, most recent first, not counting the one
+    /// playing, with how long ago each was heard
+    pub fn recent_entries(&self) -> Vec<(&Channel, String)> {
+        let playing = self.current_channel().map(|c| c.id.as_str());
+        let now = chrono::Utc::now().timestamp();
+        self.listening
+            .recent(RECENT_STATIONS + 1)
+            .into_iter()
+            .filter(|&(id, _)| Some(id) != playing)
+            .filter_map(|(id, at)| {
+                let channel = self.catalog.find(id)?;
+                Some((channel, format_ago(now - at)))
+            })
+            .take(RECENT_STATIONS)
+            .collect()
+    }
+
+    /// Apply `action` to the recent stations popup if it's open, returning
+    /// whether it was used there
+    async fn recent_action(&mut self, action: &Action) -> Result<bool> {
+        let Some(selected) = self.recent_view else {
+            return Ok(false);
+        };
+        let ids: Vec<String> = self
+            .recent_entries()
+            .iter()
+            .map(|(channel, _)| channel.id.clone())
+            .collect();
+        let pick = match action {
+            Action::OverlayDown | Action::NextStation => {
+                self.recent_view = Some((selected + 1).min(ids.len().saturating_sub(1)));
+                return Ok(true);
+            }
+            Action::OverlayUp | Action::PrevStation => {
+                self.recent_view = Some(selected.saturating_sub(1));
+                return Ok(true);
+            }
+            Action::OverlaySelect => selected,
+            Action::OverlayPick(number) => number - 1,
+            Action::Enqueue => {
+                if let Some(index) = ids.get(selected).and_then(|id| self.catalog.position(id)) {
+                    self.enqueue(index);
+                }
+                return Ok(true);
+            }
+            Action::CloseOverlay => {
+                self.recent_view = None;
+                return Ok(true);
+            }
+            _ => return Ok(false),
+        };
+        if let Some(index) = ids.get(pick).and_then(|id| self.catalog.position(id)) {
+            self.recent_view = None;
+            self.select_channel(index);
+            self.play_current_station().await?;
+        }
+        Ok(true)
+    }
+
+    /// The state a crash shouldn't lose, with `clean_exit` set when quitting
+    fn session_snapshot(&self, clean_exit: bool) -> Session {
+        Session {
+            station: self.current_channel().map(|channel| channel.id.clone()),
+            playing: self.playback_state.playing && !self.playback_state.paused,
+            selected: self.selected_channel().map(|channel| channel.id.clone()),
+            list_offset: self.list_state.offset(),
+            volume: if self.is_muted {
+                self.last_volume
+            } else {
+                self.playback_state.volume
+            },
+            muted: self.is_muted,
+            show_visualizer: self.show_visualizer,
+            show_vu_meter: self.show_vu_meter,
+            show_artwork: self.show_artwork,
+            show_history: self.show_history,
+            show_grid: self.show_grid,
+            detailed_list: self.detailed_list,
+            queue: self.queue.iter().cloned().collect(),
+            clean_exit,
+        }
+    }
+
+    /// Restore the last run's session if it crashed, or whenever `requested`
+    /// (`--resume`), returning whether there was one to restore. Its station
+    /// is picked once the station list arrives.
+    pub async fn resume_session(&mut self, requested: bool) -> Result<bool> {
+        let Some(session) = self.session.previous().cloned() else {
+            return Ok(false);
+        };
+        if session.clean_exit && !requested {
+            return Ok(false);
+        }
+
+        self.show_visualizer = session.show_visualizer;
+        self.show_vu_meter = session.show_vu_meter;
+        self.show_artwork = session.show_artwork;
+        self.show_history = session.show_history;
+        self.show_grid = session.show_grid;
+        self.detailed_list = session.detailed_list;
+        self.queue = session.queue.iter().cloned().collect();
+        self.is_muted = session.muted;
+        self.last_volume = session.volume;
+        let mut player = self.player.lock().await;
+        player
+            .set_volume(if session.muted { 0 } else { session.volume })
+            .await?;
+        self.playback_state = player.state.clone();
+        drop(player);
+
+        self.flash(
+            t(if session.clean_exit {
+                "msg-session-resumed"
+            } else {
+                "msg-session-recovered"
+            })
+            .to_string(),
+        );
+        self.resume = Some(session);
+        self.dirty = true;
+        Ok(true)
+    }
+
+    /// Play the resumed session's station if it was playing, and put the
+    /// highlight and scroll position back
+    async fn resume_station(&mut self, session: Session) -> Result<()> {
+        let position = |app: &Self, id: &Option<String>| {
+            let id = id.as_deref()?;
+            app.catalog
+                .sorted()
+                .iter()
+                .position(|&i| app.catalog[i].id == id)
+        };
+        if let (true, Some(pos)) = (session.playing, position(self, &session.station)) {
+            self.list_state.select(Some(pos));
+            if let Err(err) = self.play_current_station().await {
+                let station = session.station.as_deref().unwrap_or_default();
+                self.flash(tf(
+                    "msg-resume-failed",
+                    &[("station", &station), ("error", &err)],
+                ));
+            }
+        }
+        if let Some(pos) = position(self, &session.selected) {
+            self.list_state.select(Some(pos));
+        }
+        *self.list_state.offset_mut() = session.list_offset;
+        self.dirty = true;
+        Ok(())
+    }
+
+    /// Set an alarm for the next `at` local time, playing `station` or
+    /// whatever is highlighted then. Returns when it goes off.
+    pub fn set_alarm(
+        &mut self,
+        at: chrono::NaiveTime,
+        station: Option<String>,
+    ) -> chrono::DateTime<chrono::Local> {
+        let now = chrono::Local::now();
+        let due = (0..=1)
+            .filter_map(|days| {
+                (now.date_naive() + chrono::Days::new(days))
+                    .and_time(at)
+                    .and_local_timezone(chrono::Local)
+                    .earliest()
+            })
+            .find(|due| *due > now)
+            .unwrap_or(now);
+        self.jobs
+            .schedule(Job::Alarm { station }, due.timestamp_millis(), None);
+        due
+    }
+
+    /// Start scheduled recordings that are due and finish ones that are over
+    async fn run_schedules(&mut self) -> Result<()> {
+        let now = chrono::Local::now().naive_local();
+
+        let finished: Vec<usize> = self
+            .scheduled_recordings
+            .iter()
+            .filter(|(_, recording)| now >= recording.ends_at)
+            .map(|(&index, _)| index)
+            .collect();
+        for index in finished {
+            let Some(mut recording) = self.scheduled_recordings.remove(&index) else {
+                continue;
+            };
+            let saved = recording.player.state.recording.is_some();
+            recording.player.stop().await?;
+            if recording.play
+                && self.current_channel().map(|c| c.id.as_str()) == Some(&recording.station_id)
+            {
+                let mut player = self.player.lock().await;
+                player.stop().await?;
+                self.playback_state = player.state.clone();
+            }
+            if saved {
+                self.flash(tf(
+                    "msg-schedule-saved",
+                    &[("station", &recording.station_id)],
+                ));
+            }
+        }
+
+        if self.catalog.is_empty() {
+            return Ok(());
+        }
+        let schedules = self.config.recording_schedules().to_vec();
+        for (index, schedule) in schedules.iter().enumerate() {
+            if self.scheduled_recordings.contains_key(&index) {
+                continue;
+            }
+            if let Some(window) = scheduler::active_window(schedule, now) {
+                self.start_scheduled_recording(index, schedule, window.end)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Begin one run of a schedule. A run that can't start is still tracked
+    /// until its end so it isn't retried every second.
+    async fn start_scheduled_recording(
+        &mut self,
+        index: usize,
+        schedule: &RecordingSchedule,
+        ends_at: chrono::NaiveDateTime,
+    ) -> Result<()> {
+        let mut player = MpvController::named(&format!("schedule{}", index));
+        player.state.muted = true;
+
+        let channel = self.catalog.find(&schedule.station).cloned();
+        let result = match &channel {
+            Some(channel) => {
+                let url = channel.stream_url(self.audio_quality);
+                match self
+                    .recording_store
+                    .new_path(&channel.id, recording_extension(channel, &url))
+                {
+                    Ok(path) => match player.play(&url).await {
+                        Ok(()) => player.start_recording(&path).await,
+                        Err(err) => Err(err),
+                    },
+                    Err(err) => Err(err),
+                }
+            }
+            None => Err(anyhow::anyhow!("no station \"{}\"", schedule.station)),
+        };
+        self.scheduled_recordings.insert(
+            index,
+            ScheduledRecording {
+                station_id: schedule.station.clone(),
+                ends_at,
+                play: schedule.play,
+                player,
+            },
+        );
+
+        let Some(channel) = channel.filter(|_| result.is_ok()) else {
+            if let Err(err) = result {
+                self.flash(tf("msg-schedule-failed", &[("error", &err)]));
+            }
+            return Ok(());
+        };
+        self.flash(tf(
+            "msg-schedule-started",
+            &[
+                ("station", &channel.title),
+                ("time", &ends_at.format("%H:%M")),
+            ],
+        ));
+        if schedule.play && self.current_channel().map(|c| &c.id) != Some(&channel.id) {
+            if let Some(pos) = self
+                .catalog
+                .sorted()
+                .iter()
+                .position(|&i| self.catalog[i].id == channel.id)
+            {
+                self.list_state.select(Some(pos));
+                self.play_current_station().await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Scheduled recordings running now, then the next run of each other
+    /// schedule, soonest first
+    pub fn upcoming_recordings(&self) -> Vec<ScheduleEntry> {
+        let now = chrono::Local::now().naive_local();
+        let title = |id: &str| {
+            self.catalog
+                .find(id)
+                .map_or_else(|| id.to_string(), |c| c.title.clone())
+        };
+        let mut runs: Vec<(chrono::NaiveDateTime, ScheduleEntry)> = self
+            .config
+            .recording_schedules()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, schedule)| {
+                if let Some(recording) = self.scheduled_recordings.get(&index) {
+                    return Some((
+                        now,
+                        ScheduleEntry {
+                            station: title(&schedule.station),
+                            when: format!("until {}", recording.ends_at.format("%H:%M")),
+                            active: recording.player.state.recording.is_some(),
+                        },
+                    ));
+                }
+                let window = scheduler::next_window(schedule, now)?;
+                Some((
+                    window.start,
+                    ScheduleEntry {
+                        station: title(&schedule.station),
+                        when: format!(
+                            "{}-{}",
+                            window.start.format("%a %H:%M"),
+                            window.end.format("%H:%M")
+                        ),
+                        active: false,
+                    },
+                ))
+            })
+            .collect();
+        runs.sort_by_key(|(start, _)| *start);
+        runs.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// The station the timeline shows: the one playing, else the selected one
+    pub fn timeline_channel(&self) -> Option<&Channel> {
+        self.current_channel().or_else(|| self.selected_channel())
+    }
+
+    /// Timeline entries for the open popup, newest first
+    pub fn timeline_entries(&self) -> Vec<&TimelineEntry> {
+        let (Some(view), Some(channel)) = (&self.timeline_view, self.timeline_channel()) else {
+            return Vec::new();
+        };
+        let since = if view.today {
+            chrono::Local::now()
+                .date_naive()
+                .and_hms_opt(0, 0, 0)
+                .and_then(|midnight| midnight.and_local_timezone(chrono::Local).earliest())
+                .map_or(0, |midnight| midnight.timestamp())
+        } else {
+            self.launched_at
+        };
+        self.timeline.entries(&channel.id, since, &view.query)
+    }
+
+    /// Title label for the timeline's time range
+    pub fn timeline_scope(&self) -> String {
+        match &self.timeline_view {
+            Some(view) if view.today => t("timeline-today").to_string(),
+            _ => chrono::DateTime::from_timestamp(self.launched_at, 0)
+                .map(|launched| {
+                    let time = launched.with_timezone(&chrono::Local).format("%H:%M");
+                    tf("timeline-since", &[("time", &time)])
+                })
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Point the display at a newly playing station, dropping the previous
+    /// one's metadata
+    fn set_current_channel(&mut self, idx: Option<usize>) {
+        self.current_channel = idx;
+        self.playing_recording = None;
+        self.station_generation += 1;
+        self.stream_title = None;
+        self.current_song = None;
+        self.song_history.clear();
+        self.pending_tracks.clear();
+        self.artwork_state.clear();
+        self.set_artwork_palette(Vec::new());
+        self.audio_levels = None;
+    }
+
+    pub async fn handle_action(&mut self, action: Action) -> Result<()> {
+        if self.idle_action(&action)
+            || self.theme_picker_action(&action)
+            || self.timeline_action(&action)
+            || self.links_action(&action)
+            || self.jobs_action(&action)
+            || self.queue_action(&action).await?
+            || self.similar_action(&action).await?
+            || self.recent_action(&action).await?
+            || self.recordings_action(&action).await?
+            || self.settings_action(&action).await?
+            || self.outputs_action(&action).await?
+            || self.volume_input_action(&action).await?
+            || self.volume_popup_action(&action).await?
+            || self.jump_input_action(&action)
+        {
+            return Ok(());
+        }
+        match action {
+            Action::Quit => self.quit().await?,
+            Action::Back => {
+                if self.search_query.is_empty() {
+                    self.quit().await?;
+                } else {
+                    self.set_search_query(String::new());
+                }
+            }
+            Action::TogglePlayPause => {
+                if self.playback_state.playing {
+                    let mut player = self.player.lock().await;
+                    player.toggle_pause().await?;
+                    self.playback_state = player.state.clone();
+                } else {
+                    self.play_current_station().await?;
+                }
+            }
+            Action::SelectStation => {
+                self.play_current_station().await?;
+            }
+            Action::RandomStation => {
+                self.play_random_station().await?;
+            }
+            Action::Compare => {
+                if self.comparison.is_some() {
+                    self.end_comparison().await?;
+                    self.flash(t("msg-comparison-ended").to_string());
+                } else {
+                    self.start_comparison().await?;
+                }
+            }
+            Action::SwapCompare => {
+                self.swap_comparison().await?;
+            }
+            Action::JumpToLive => {
+                let mut player = self.player.lock().await;
+                let result = player.jump_to_live().await;
+                self.playback_state = player.state.clone();
+                drop(player);
+                if let Err(err) = result {
+                    self.flash(tf("msg-jump-to-live-failed", &[("error", &err)]));
+                }
+            }
+            Action::ToggleRecording => self.toggle_recording().await?,
+            Action::OpenSettings => self.settings_view = Some(0),
+            Action::DismissHints => {
+                if self.show_hints {
+                    self.show_hints = false;
+                    self.config.set_hints_shown();
+                    let _ = self.config.save();
+                    self.flash(t("msg-tips-hidden").to_string());
+                }
             }
-            Action::TogglePlayPause => {
-                if self.playback_state.playing {
-                    let mut player = self.player.lock().await;
-                    player.toggle_pause().await?;
-                    self.playback_state = player.state.clone();
+            Action::OpenRecordings => {
+                self.recordings_view = Some(RecordingsView {
+                    recordings: self.recording_store.list(),
+                    ..Default::default()
+                });
+            }
+            // Handled by `recordings_action` while the browser is open
+            Action::RenameRecording | Action::DeleteRecording => {}
+            Action::ToggleDoNotDisturb => {
+                self.do_not_disturb = !self.do_not_disturb;
+                self.flash(
+                    t(if self.do_not_disturb {
+                        "msg-dnd-on"
+                    } else {
+                        "msg-dnd-off"
+                    })
+                    .to_string(),
+                );
+            }
+            Action::ToggleSurf => {
+                if self.jobs.cancel_all(&Job::Surf) {
+                    self.flash(t("msg-surf-off").to_string());
                 } else {
-                    self.play_current_station().await?;
+                    let interval = self.config.surf_interval();
+                    self.jobs.schedule(
+                        Job::Surf,
+                        now_ms() + interval.as_millis() as i64,
+                        Some(interval),
+                    );
+                    self.flash(tf(
+                        "msg-surf-on",
+                        &[("minutes", &(interval.as_secs() / 60))],
+                    ));
+                    self.play_random_station().await?;
                 }
             }
-            Action::SelectStation => {
-                self.play_current_station().await?;
-            }
             Action::VolumeUp => {
+                self.volume_popup = Some(Instant::now());
                 if self.is_muted {
                     self.is_muted = false;
                     let mut player = self.player.lock().await;
@@ -215,16 +2792,22 @@ impl App {
                     self.playback_state = player.state.clone();
                 } else {
                     let mut player = self.player.lock().await;
-                    player.volume_up().await?;
+                    player.volume_up(self.config.volume_step()).await?;
                     self.playback_state = player.state.clone();
                 }
             }
             Action::VolumeDown => {
+                self.volume_popup = Some(Instant::now());
                 let mut player = self.player.lock().await;
-                player.volume_down().await?;
+                player.volume_down(self.config.volume_step()).await?;
                 self.playback_state = player.state.clone();
             }
+            Action::EnterVolume => self.volume_input = Some(String::new()),
+            Action::EnterJump(digit) => {
+                self.jump_input = Some(digit.map(String::from).unwrap_or_default());
+            }
             Action::ToggleMute => {
+                self.volume_popup = Some(Instant::now());
                 if self.is_muted {
                     self.is_muted = false;
                     let mut player = self.player.lock().await;
@@ -238,123 +2821,1000 @@ impl App {
                     self.playback_state = player.state.clone();
                 }
             }
+            Action::WatchArtist => self.toggle_watched(false),
+            Action::WatchTrack => self.toggle_watched(true),
+            Action::PlayWatched => self.play_watched().await?,
             Action::ToggleFavorite => {
                 if let Some(channel) = self.selected_channel() {
                     let id = channel.id.clone();
                     self.favorites.toggle(&id);
-                    let _ = self.favorites.save();
+                    self.favorites_changed();
                     self.update_sorted_indices();
                 }
             }
+            Action::NextStation if self.show_grid => {
+                self.move_selection(self.grid_columns as isize)
+            }
+            Action::PrevStation if self.show_grid => {
+                self.move_selection(-(self.grid_columns as isize))
+            }
             Action::NextStation => {
-                let len = self.sorted_indices.len();
+                let len = self.catalog.sorted().len();
                 if len > 0 {
                     let current = self.list_state.selected().unwrap_or(0);
                     self.list_state.select(Some((current + 1) % len));
                 }
             }
             Action::PrevStation => {
-                let len = self.sorted_indices.len();
+                let len = self.catalog.sorted().len();
                 if len > 0 {
                     let current = self.list_state.selected().unwrap_or(0);
                     self.list_state
                         .select(Some(current.checked_sub(1).unwrap_or(len - 1)));
                 }
             }
+            Action::MoveLeft => {
+                if self.show_grid {
+                    self.move_selection(-1);
+                }
+            }
+            Action::MoveRight => {
+                if self.show_grid {
+                    self.move_selection(1);
+                }
+            }
+            Action::ToggleGrid => {
+                self.show_grid = !self.show_grid;
+            }
+            Action::ToggleDetailedList => {
+                self.detailed_list = !self.detailed_list;
+            }
             Action::GoToTop => {
-                if !self.sorted_indices.is_empty() {
+                if !self.catalog.sorted().is_empty() {
                     self.list_state.select(Some(0));
                 }
             }
             Action::GoToBottom => {
-                if !self.sorted_indices.is_empty() {
-                    self.list_state.select(Some(self.sorted_indices.len() - 1));
+                if !self.catalog.sorted().is_empty() {
+                    self.list_state
+                        .select(Some(self.catalog.sorted().len() - 1));
                 }
             }
             Action::ToggleSortMode => {
                 self.sort_mode = self.sort_mode.next();
                 self.update_sorted_indices();
+                self.config.set_sort_name(self.sort_mode.name());
+                let _ = self.config.save();
             }
+            Action::CycleSource => self.cycle_source(),
             Action::ToggleVisualizer => {
-                self.show_visualizer = !self.show_visualizer;
+                self.set_panel_shown(PanelKind::Visualizer, !self.show_visualizer);
+            }
+            Action::ToggleVuMeter => {
+                self.set_panel_shown(PanelKind::VuMeter, !self.show_vu_meter);
             }
             Action::CycleVisualization => {
-                self.visualization_mode = self.visualization_mode.next();
-                // Save preference
-                self.config.set_visualization(self.visualization_mode);
-                let _ = self.config.save();
+                self.step_visualization(true);
+            }
+            Action::PrevVisualization => {
+                self.step_visualization(false);
+            }
+            Action::OpenVisualizerPicker => {
+                let modes = self.visualization_modes();
+                let current = modes.iter().position(|&m| m == self.visualization_mode);
+                self.visualizer_picker = Some(current.unwrap_or(0));
+            }
+            Action::GainUp | Action::GainDown => {
+                let mut settings = self.spectrum_analyzer.settings();
+                let step = if action == Action::GainUp { 3.0 } else { -3.0 };
+                settings.gain_db = (settings.gain_db + step).clamp(-12.0, 30.0);
+                self.flash(tf(
+                    "msg-gain",
+                    &[("db", &format!("{:+.0}", settings.gain_db))],
+                ));
+                self.save_spectrum_settings(settings);
+            }
+            Action::FloorUp | Action::FloorDown => {
+                let mut settings = self.spectrum_analyzer.settings();
+                let step = if action == Action::FloorUp { 6.0 } else { -6.0 };
+                settings.floor_db = (settings.floor_db + step).clamp(-90.0, -24.0);
+                self.flash(tf(
+                    "msg-floor",
+                    &[("db", &format!("{:.0}", settings.floor_db))],
+                ));
+                self.save_spectrum_settings(settings);
+            }
+            Action::CycleSmoothing => {
+                let mut settings = self.spectrum_analyzer.settings();
+                // Step to the preset after the closest one
+                let current = SMOOTHING_PRESETS
+                    .iter()
+                    .position(|&(_, attack, decay)| {
+                        (attack - settings.attack).abs() < 0.01
+                            && (decay - settings.decay).abs() < 0.01
+                    })
+                    .unwrap_or(SMOOTHING_PRESETS.len() - 1);
+                let (name, attack, decay) =
+                    SMOOTHING_PRESETS[(current + 1) % SMOOTHING_PRESETS.len()];
+                settings.attack = attack;
+                settings.decay = decay;
+                self.flash(tf("msg-smoothing", &[("name", &t(name))]));
+                self.save_spectrum_settings(settings);
+            }
+            Action::OverlayUp | Action::OverlayDown if self.show_help => {
+                // Clamped to the list's length when drawn
+                self.help_scroll = if action == Action::OverlayUp {
+                    self.help_scroll.saturating_sub(1)
+                } else {
+                    self.help_scroll + 1
+                };
             }
-            Action::ToggleArtwork => {
-                self.show_artwork = !self.show_artwork;
-                if !self.show_artwork {
-                    self.artwork_state.clear();
+            Action::OverlayPageUp => self.help_scroll = self.help_scroll.saturating_sub(HELP_PAGE),
+            Action::OverlayPageDown => self.help_scroll += HELP_PAGE,
+            Action::OverlayUp | Action::OverlayDown => {
+                if let Some(selected) = self.visualizer_picker {
+                    let last = self.visualization_modes().len().saturating_sub(1);
+                    self.visualizer_picker = Some(if action == Action::OverlayUp {
+                        selected.saturating_sub(1)
+                    } else {
+                        (selected + 1).min(last)
+                    });
                 }
             }
-            Action::ToggleHistory => {
-                self.show_history = !self.show_history;
-            }
-            Action::QualityUp => {
-                let new_quality = self.audio_quality.higher();
-                if new_quality != self.audio_quality {
-                    self.audio_quality = new_quality;
-                    // If playing, restart with new quality
-                    if self.playback_state.playing {
-                        if let Some(channel) = self.current_channel().cloned() {
-                            let url = channel.stream_url(self.audio_quality);
-                            let mut player = self.player.lock().await;
-                            player.play(&url).await?;
-                            self.playback_state = player.state.clone();
-                            self.audio_levels = None;
-                        }
+            Action::OverlaySelect => {
+                if let Some(selected) = self.visualizer_picker.take() {
+                    if let Some(&mode) = self.visualization_modes().get(selected) {
+                        self.set_visualization(mode);
                     }
                 }
             }
-            Action::QualityDown => {
-                let new_quality = self.audio_quality.lower();
-                if new_quality != self.audio_quality {
-                    self.audio_quality = new_quality;
-                    // If playing, restart with new quality
-                    if self.playback_state.playing {
-                        if let Some(channel) = self.current_channel().cloned() {
-                            let url = channel.stream_url(self.audio_quality);
-                            let mut player = self.player.lock().await;
-                            player.play(&url).await?;
-                            self.playback_state = player.state.clone();
-                            self.audio_levels = None;
-                        }
+            Action::OverlayToggle => {
+                if let Some(selected) = self.visualizer_picker {
+                    if let Some(&mode) = self.visualization_modes().get(selected) {
+                        let name = self.visualization_name(mode);
+                        let enabled = self.config.visualization_enabled(&name);
+                        self.config.set_visualization_enabled(&name, !enabled);
+                        let _ = self.config.save();
                     }
                 }
             }
-            Action::ToggleTheme => {
-                self.cycle_theme();
+            Action::ToggleArtwork => self.set_show_artwork(!self.show_artwork),
+            Action::ToggleHistory => {
+                self.set_panel_shown(PanelKind::History, !self.show_history);
+            }
+            Action::GrowStationList | Action::ShrinkStationList => {
+                let delta = if action == Action::GrowStationList {
+                    5
+                } else {
+                    -5
+                };
+                self.config.layout_mut().resize_station_list(delta);
+                let _ = self.config.save();
+            }
+            Action::GrowVisualizer | Action::ShrinkVisualizer => {
+                let delta = if action == Action::GrowVisualizer {
+                    1
+                } else {
+                    -1
+                };
+                self.config
+                    .layout_mut()
+                    .resize_panel(PanelKind::Visualizer, delta);
+                let _ = self.config.save();
+            }
+            Action::QualityUp => self.set_audio_quality(self.audio_quality.higher()).await?,
+            Action::QualityDown => self.set_audio_quality(self.audio_quality.lower()).await?,
+            Action::OpenThemePicker => {
+                let selected = self
+                    .themes
+                    .names()
+                    .iter()
+                    .position(|name| *name == self.theme.name)
+                    .unwrap_or(0);
+                self.theme_picker = Some(ThemePickerView {
+                    selected,
+                    original: self.theme.name.clone(),
+                });
+            }
+            Action::OpenTimeline => {
+                self.timeline_view = Some(TimelineView::default());
+            }
+            // Handled by `timeline_action` while the timeline is open
+            Action::TimelineScope => {}
+            Action::OpenLinks => self.links_view = Some(0),
+            Action::OpenJobs => self.jobs_view = Some(0),
+            Action::Enqueue => self.enqueue_selected(),
+            Action::NextInQueue => {
+                self.play_next_in_queue().await?;
+            }
+            Action::OpenQueue => self.queue_view = Some(0),
+            Action::OpenSimilar => self.open_similar(),
+            Action::OpenRecent => self.recent_view = Some(0),
+            // Handled by `recent_action` while the recent stations popup is open
+            Action::OverlayPick(_) => {}
+            // Handled by `volume_popup_action` while the volume popup is up
+            Action::VolumeFine(_) => {}
+            Action::MoveItemUp => self.shift_favorite(true),
+            Action::MoveItemDown => self.shift_favorite(false),
+            // Handled by `queue_action` while the queue popup is open
+            Action::Dequeue => {}
+            // Handled by `jobs_action` while the jobs view is open
+            Action::CancelJob => {}
+            Action::CycleSleepTimer => {
+                let left = self.sleep_minutes_left();
+                let next = match left {
+                    None => Some(SLEEP_STEPS[0]),
+                    Some(left) => SLEEP_STEPS.iter().copied().find(|&step| step > left),
+                };
+                self.set_sleep_timer(next);
             }
+            // Handled by `links_action` while the links popup is open
+            Action::CopyLink => {}
+            Action::OpenOutputs => self.open_outputs(),
             Action::ToggleHelp => {
                 self.show_help = !self.show_help;
+                self.help_scroll = 0;
+                self.opened_help = true;
             }
             Action::CloseOverlay => {
                 self.show_help = false;
+                self.visualizer_picker = None;
+            }
+            Action::OpenSearch => {
+                self.searching = true;
+            }
+            Action::SearchInput(c) => {
+                let mut query = self.search_query.clone();
+                query.push(c);
+                self.set_search_query(query);
+            }
+            Action::SearchBackspace => {
+                let mut query = self.search_query.clone();
+                query.pop();
+                self.set_search_query(query);
+            }
+            Action::SearchConfirm => {
+                self.searching = false;
+                self.play_current_station().await?;
+            }
+            Action::SearchCancel => {
+                self.searching = false;
+                self.set_search_query(String::new());
             }
             Action::Refresh => {
                 if let Ok(channels) = self.api_client.get_channels().await {
-                    self.channels = channels;
+                    self.replace_channels(channels);
+                }
+            }
+        }
+        self.session_timer
+            .sync(self.playback_state.playing && !self.playback_state.paused);
+        Ok(())
+    }
+
+    /// Duck or mute playback as `audio.on_focus_loss` asks
+    async fn quiet_for_focus_loss(&mut self) -> Result<()> {
+        if self.focus_restore.is_some() || self.is_muted || !self.playback_state.playing {
+            return Ok(());
+        }
+        let volume = self.playback_state.volume;
+        let mut player = self.player.lock().await;
+        let result = match self.config.focus_loss() {
+            FocusLoss::Nothing => return Ok(()),
+            FocusLoss::Duck => player.set_volume(self.config.ducked_volume(volume)).await,
+            FocusLoss::Mute => player.set_mute(true).await,
+        };
+        self.playback_state = player.state.clone();
+        if result.is_ok() {
+            self.focus_restore = Some(volume);
+        }
+        Ok(())
+    }
+
+    /// Undo `quiet_for_focus_loss`
+    async fn restore_after_focus_loss(&mut self) -> Result<()> {
+        let Some(volume) = self.focus_restore.take() else {
+            return Ok(());
+        };
+        let mut player = self.player.lock().await;
+        if player.state.muted {
+            let _ = player.set_mute(false).await;
+        }
+        player.set_volume(volume).await?;
+        self.playback_state = player.state.clone();
+        Ok(())
+    }
+
+    /// Apply one event. Station data that arrives after switching to a
+    /// different station is dropped.
+    pub async fn on_event(&mut self, event: AppEvent) -> Result<()> {
+        match event {
+            AppEvent::Terminal(Event::FocusGained) => {
+                self.focused = true;
+                self.last_input = Instant::now();
+                self.restore_after_focus_loss().await?;
+            }
+            AppEvent::Terminal(Event::FocusLost) => {
+                self.focused = false;
+                self.last_input = Instant::now();
+                self.quiet_for_focus_loss().await?;
+            }
+            AppEvent::Terminal(Event::Resize(_, _)) => self.dirty = true,
+            AppEvent::Terminal(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                self.dirty = true;
+                self.last_input = Instant::now();
+                self.unattended = Duration::ZERO;
+                if let Some(action) = handle_key(key, self.input_mode()) {
+                    self.handle_action(action).await?;
+                }
+            }
+            AppEvent::Terminal(_) => {}
+            AppEvent::Tick => {
+                // Keep the loading spinner and artwork shimmer moving
+                let artwork_loading = self.show_artwork
+                    && self.current_channel.is_some()
+                    && self.artwork_state.is_loading()
+                    && !self.reduced_motion;
+                // and the jobs and queue countdowns
+                let counting_down = self.jobs_view.is_some()
+                    || self.queue_view.is_some()
+                    || self.idle_prompt.is_some()
+                    // and the volume popup fading out
+                    || self.volume_popup.is_some();
+                if self.channels_loading || artwork_loading || counting_down {
+                    self.dirty = true;
+                }
+                if self.playback_state.stream_health == Some(StreamHealth::Lost) {
+                    self.fail_over().await?;
+                }
+                for job in self.jobs.take_due(now_ms()) {
+                    self.run_job(job).await?;
+                }
+                self.metrics
+                    .listening_seconds
+                    .store(self.session_timer.elapsed().as_secs(), Ordering::Relaxed);
+                self.apply_due_tracks();
+                self.update_spectrum().await;
+                self.count_listening();
+                self.check_idle().await?;
+                self.poll_file_reload();
+                self.poll_message();
+                self.poll_volume_popup();
+                self.prefetch_song_previews();
+                self.poll_header_minute();
+                self.poll_track_progress();
+            }
+            AppEvent::Songs {
+                generation,
+                channel_id,
+                current_song,
+                history,
+            } => {
+                if self.is_current(generation, &channel_id) {
+                    self.timeline.merge(
+                        &channel_id,
+                        current_song.as_ref(),
+                        &history,
+                        chrono::Utc::now().timestamp(),
+                    );
+                    self.delay_track(PendingTrack::Songs(current_song, history));
+                }
+            }
+            AppEvent::StreamTitle {
+                generation,
+                channel_id,
+                title,
+            } => {
+                if self.is_current(generation, &channel_id) {
+                    self.delay_track(PendingTrack::StreamTitle(title));
+                }
+            }
+            AppEvent::Artwork {
+                generation,
+                channel_id,
+                image,
+                url,
+                palette,
+            } => {
+                if self.show_artwork && self.is_current(generation, &channel_id) {
+                    self.artwork_state.set_image(image, &url);
+                    self.set_artwork_palette(palette);
+                    self.dirty = true;
+                }
+            }
+            AppEvent::Thumbnail { channel_id, image } => {
+                self.thumbnails.insert(channel_id, image);
+                self.dirty = true;
+            }
+            AppEvent::SongPreview { channel_id, song } => {
+                self.song_previews.insert(channel_id, song);
+                if self.detailed_list {
+                    self.dirty = true;
+                }
+            }
+            AppEvent::Channels(channels) => {
+                self.replace_channels(channels);
+                self.api_reachable = true;
+                self.dirty = true;
+                if let Some(session) = self.resume.take() {
+                    self.resume_station(session).await?;
+                }
+            }
+            AppEvent::ChannelsFailed(err) => {
+                if self.channels_loading {
+                    self.channels_error = Some(err);
+                }
+                self.api_reachable = false;
+                self.dirty = true;
+            }
+            AppEvent::AudioLevels(levels) => self.audio_levels = levels,
+            AppEvent::AudioDevicesRemoved(devices) => {
+                if self.config.pause_on_device_removal()
+                    && self.playback_state.playing
+                    && !self.playback_state.paused
+                {
+                    let mut player = self.player.lock().await;
+                    player.toggle_pause().await?;
+                    self.playback_state = player.state.clone();
+                    drop(player);
+                    self.flash(tf(
+                        "msg-device-removed",
+                        &[("devices", &devices.join(", "))],
+                    ));
+                }
+            }
+            AppEvent::BehindLive(behind) => {
+                self.playback_state.behind_live = behind;
+                self.dirty = true;
+            }
+            AppEvent::Bitrate(bitrate) => {
+                self.playback_state.bitrate = bitrate;
+                self.dirty = true;
+            }
+            AppEvent::StreamHealth(health) => {
+                if health == Some(StreamHealth::Healthy) {
+                    if self.playback_state.stream_health == Some(StreamHealth::Lost) {
+                        self.metrics.reconnects.fetch_add(1, Ordering::Relaxed);
+                    }
+                    self.servers_tried = 1;
+                }
+                self.playback_state.stream_health = health;
+                self.dirty = true;
+            }
+            AppEvent::Release { track, info } => {
+                self.release = info.map(|info| (track, info));
+                self.dirty = true;
+            }
+            AppEvent::FavoritesSynced(Ok(doc)) => {
+                self.sync_failed = false;
+                // Merged again in case the favorites changed while syncing
+                let merged = sync::merge(&self.favorites.sync_doc(), &doc);
+                if self.favorites.apply(merged).unwrap_or(false) {
+                    let selected = self.selected_channel_index();
                     self.update_sorted_indices();
+                    if let Some(index) = selected {
+                        self.select_channel(index);
+                    }
+                    self.dirty = true;
+                }
+            }
+            AppEvent::FavoritesSynced(Err(err)) => {
+                // Once per failure streak, not on every retry
+                if !std::mem::replace(&mut self.sync_failed, true) {
+                    self.flash(tf("msg-sync-failed", &[("error", &err)]));
                 }
             }
+            AppEvent::FavoriteSongs(songs) => self.check_watched(songs),
+            AppEvent::AirPlayDevices(Ok(devices)) => self.show_airplay_devices(devices),
+            AppEvent::AirPlayDevices(Err(err)) => {
+                self.flash(tf("msg-airplay-discovery-failed", &[("error", &err)]));
+                self.show_airplay_devices(Vec::new());
+            }
+            AppEvent::TrackChanged(track) => {
+                self.play_history
+                    .record(&track, chrono::Utc::now().timestamp());
+                let _ = self.play_history.save();
+                self.notify_track(&track);
+                if self.screen_reader {
+                    self.announcement = Some(tf("sr-now-playing", &[("track", &track)]));
+                    self.dirty = true;
+                }
+            }
+            AppEvent::Control(request) => {
+                // Someone's there, if not at the keyboard; a visualizer
+                // reading the spectrum doesn't count
+                if !matches!(request.command, Command::Spectrum { .. }) {
+                    self.unattended = Duration::ZERO;
+                }
+                let result = self.control(request.command).await;
+                self.session_timer
+                    .sync(self.playback_state.playing && !self.playback_state.paused);
+                let _ = request.reply.send(result);
+            }
         }
         Ok(())
     }
 
+    /// Carry out a `vibecast ctl` command, returning the status to print.
+    /// Failures go back to `ctl` rather than ending the app.
+    async fn control(&mut self, command: Command) -> Result<String> {
+        // Asked for many times a second, and changes nothing on screen
+        if let Command::Spectrum { frames, .. } = command {
+            return Ok(self.spectrum_json(frames).await);
+        }
+        self.dirty = true;
+        match command {
+            Command::Play if !self.playback_state.playing => self.play_current_station().await?,
+            Command::Play | Command::Pause => {
+                let paused = command == Command::Pause;
+                if self.playback_state.playing && self.playback_state.paused != paused {
+                    let mut player = self.player.lock().await;
+                    player.toggle_pause().await?;
+                    self.playback_state = player.state.clone();
+                }
+            }
+            Command::NextFavorite => {
+                // In the favorites' own order, wrapping around after the last
+                let favorites: Vec<usize> = self
+                    .favorites
+                    .favorites()
+                    .iter()
+                    .filter_map(|id| self.catalog.position(id))
+                    .collect();
+                let current = self
+                    .current_channel
+                    .and_then(|current| favorites.iter().position(|&i| i == current));
+                let next = match current {
+                    Some(pos) => favorites.get(pos + 1).or(favorites.first()),
+                    None => favorites.first(),
+                }
+                .copied()
+                .ok_or_else(|| anyhow!(t("msg-no-favorites")))?;
+                self.select_channel(next);
+                self.play_current_station().await?;
+            }
+            Command::Volume(Some(change)) => {
+                let current = if self.is_muted {
+                    self.last_volume
+                } else {
+                    self.playback_state.volume
+                };
+                let volume = match change {
+                    VolumeChange::Set(volume) => volume,
+                    VolumeChange::By(delta) => (current as i16 + delta).clamp(0, 100) as u8,
+                };
+                self.is_muted = false;
+                let mut player = self.player.lock().await;
+                player.set_volume(volume).await?;
+                self.playback_state = player.state.clone();
+            }
+            Command::Sleep(minutes) => self.set_sleep_timer(minutes),
+            Command::Alarm { at, station } => {
+                if let Some(id) = &station {
+                    if self.catalog.find(id).is_none() {
+                        bail!(tf("msg-alarm-no-station", &[("station", id)]));
+                    }
+                }
+                let due = self.set_alarm(at, station);
+                let message = tf("msg-alarm-set", &[("time", &due.format("%a %H:%M"))]);
+                self.flash(message.clone());
+                return Ok(message);
+            }
+            Command::Jobs => {
+                let now = now_ms();
+                let lines: Vec<String> = self
+                    .jobs
+                    .pending()
+                    .iter()
+                    .map(|job| {
+                        let mut line = format!("in {:<8} {}", job.due_in(now), job.job.label());
+                        if let Some(repeats) = job.repeats() {
+                            line = format!("{} ({})", line, repeats);
+                        }
+                        line
+                    })
+                    .collect();
+                return Ok(lines.join("\n"));
+            }
+            Command::Volume(None) | Command::Status | Command::Spectrum { .. } => {}
+        }
+
+        let mut lines = vec![self.status_text()];
+        lines.extend(self.current_channel().map(|channel| channel.title.clone()));
+        lines.extend(self.now_playing_text());
+        lines.extend(
+            self.sleep_minutes_left()
+                .map(|minutes| tf("msg-sleep-set", &[("minutes", &minutes)])),
+        );
+        Ok(lines.join("\n"))
+    }
+
+    /// The last `frames` spectrum frames, oldest first, one JSON object per
+    /// line: `{"bins":[0.0 to 1.0, ...]}`
+    async fn spectrum_json(&self, frames: usize) -> String {
+        self.spectrum_analyzer
+            .history(frames)
+            .await
+            .iter()
+            .map(|bins| {
+                let bins: Vec<f64> = bins
+                    .iter()
+                    .map(|&bin| (bin as f64 * 1000.0).round() / 1000.0)
+                    .collect();
+                serde_json::json!({ "bins": bins }).to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Send events the app raises itself, like `TrackChanged`, to `events`
+    pub fn connect_events(&mut self, events: EventSender) {
+        self.events = Some(events);
+    }
+
+    /// The track playing now, preferring the songs API over the stream title
+    pub fn current_track(&self) -> Option<Track> {
+        let channel = self.current_channel()?;
+        match (self.audible_song(), &self.stream_title) {
+            (Some(song), _) => Some(Track::from_song(&channel.id, song)),
+            (None, Some(title)) => Some(Track::from_stream_title(&channel.id, title)),
+            (None, None) => None,
+        }
+    }
+
+    /// The song coming out of the speakers. The songs API often moves on
+    /// before the new track is audible, so when there's a stream title it's
+    /// the listed song that title names, if any.
+    pub fn audible_song(&self) -> Option<&Song> {
+        let Some(title) = &self.stream_title else {
+            return self.current_song.as_ref();
+        };
+        let key = Track::from_stream_title("", title).song_key();
+        self.current_song
+            .iter()
+            .chain(&self.song_history)
+            .find(|song| Track::from_song("", song).song_key() == key)
+    }
+
+    /// Time between songs API polls: stretched once nobody has touched the
+    /// app for a while
+    pub fn song_poll_interval(&self) -> Duration {
+        if self.last_input.elapsed() >= IDLE_AFTER {
+            self.config.idle_song_poll_interval()
+        } else {
+            self.config.song_poll_interval()
+        }
+    }
+
+    /// Roughly how far into the audible song playback is, and how long the
+    /// song runs: MusicBrainz's length when it has one, otherwise the
+    /// station's typical track length. `None` once well past that.
+    pub fn track_progress(&self) -> Option<(Duration, Duration)> {
+        let started = self.audible_song()?.date?;
+        let length = self
+            .release_info()
+            .and_then(|release| release.length_ms)
+            .map(Duration::from_millis)
+            .or_else(|| {
+                let starts = self.current_song.iter().chain(&self.song_history);
+                typical_track_length(starts.filter_map(|song| song.date))
+            })?;
+        // The station's start time, plus however late it reaches the speakers
+        let delay = self.config.track_delay() + self.playback_state.behind_live.unwrap_or_default();
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        let elapsed = Duration::from_secs(now.saturating_sub(started)).saturating_sub(delay);
+        (elapsed < length * 3 / 2).then_some((elapsed.min(length), length))
+    }
+
+    /// Release details for the track playing now, once they've been looked up
+    pub fn release_info(&self) -> Option<&ReleaseInfo> {
+        let (track, info) = self.release.as_ref()?;
+        let current = self.current_track()?;
+        (track.song_key() == current.song_key()).then_some(info)
+    }
+
+    /// Queue a now playing update for when it's audible: after
+    /// `audio.track_delay_secs`, plus however far playback trails live
+    fn delay_track(&mut self, update: PendingTrack) {
+        let delay = self.config.track_delay() + self.playback_state.behind_live.unwrap_or_default();
+        self.pending_tracks
+            .push_back((Instant::now() + delay, update));
+        self.apply_due_tracks();
+    }
+
+    /// Show the now playing updates that have become audible
+    fn apply_due_tracks(&mut self) {
+        let now = Instant::now();
+        let mut applied = false;
+        while let Some((_, update)) = self
+            .pending_tracks
+            .pop_front_if(|(audible_at, _)| *audible_at <= now)
+        {
+            match update {
+                PendingTrack::Songs(current_song, history) => {
+                    self.current_song = current_song;
+                    self.song_history = history;
+                    self.song_history.truncate(SONG_HISTORY_LEN);
+                }
+                PendingTrack::StreamTitle(title) => self.stream_title = Some(title),
+            }
+            applied = true;
+        }
+        if applied {
+            self.dirty = true;
+            self.detect_track_change();
+        }
+    }
+
+    fn detect_track_change(&mut self) {
+        let Some(track) = self.current_track() else {
+            return;
+        };
+        if let Some(track) = self.track_changes.observe(track) {
+            if let Some(events) = &self.events {
+                let _ = events.send(AppEvent::TrackChanged(track));
+            }
+        }
+    }
+
+    /// Terminal title from `ui.title_template`, or `None` when it's disabled
+    pub fn window_title(&self) -> Option<String> {
+        let template = self.config.title_template()?;
+        let state = if !self.playback_state.playing {
+            "■"
+        } else if self.playback_state.paused {
+            "⏸"
+        } else {
+            "▶"
+        };
+        let station = self.current_channel().map(|c| c.title.as_str());
+        Some(format_title(
+            template,
+            state,
+            self.current_track().as_ref(),
+            station,
+        ))
+    }
+
+    /// Desktop notification for a new track
+    fn notify_track(&self, track: &Track) {
+        let station = self
+            .current_channel()
+            .map(|c| c.title.clone())
+            .unwrap_or_default();
+        self.notify(station, track.to_string());
+    }
+
+    /// Desktop notification headed with the station, unless notifications
+    /// are off or Do Not Disturb is on here or in the OS
+    fn notify(&self, station: String, body: String) {
+        if !self.config.notifications() || self.do_not_disturb {
+            return;
+        }
+        let via = self.config.notify_via();
+        if via != NotifyVia::Desktop {
+            // The terminal's own OS decides whether to show it
+            let _ = notify::send_to_terminal(via, &station, &body);
+            return;
+        }
+        // The OS check and the notifier both run external commands
+        tokio::task::spawn_blocking(move || {
+            if !notify::os_do_not_disturb() {
+                let _ = notify::send(&station, &body);
+            }
+        });
+    }
+
+    /// Times each song in `song_history` has been heard
+    pub fn history_play_counts(&self) -> Vec<u32> {
+        self.song_history
+            .iter()
+            .map(|song| self.play_history.count(&Track::from_song("", song)))
+            .collect()
+    }
+
+    /// Whether metadata was requested for the station that's playing now,
+    /// rather than one played before it
+    fn is_current(&self, generation: u64, channel_id: &str) -> bool {
+        generation == self.station_generation
+            && self.current_channel().map(|c| c.id.as_str()) == Some(channel_id)
+    }
+
+    /// Redraw when the header clock or listening timer moves on a minute
+    fn poll_header_minute(&mut self) {
+        let minute = (
+            chrono::Local::now().format("%H:%M").to_string(),
+            self.session_timer.elapsed().as_secs() / 60,
+        );
+        if minute != self.last_header_minute {
+            self.last_header_minute = minute;
+            self.dirty = true;
+        }
+    }
+
+    /// Redraw when the song's progress bar moves on a second
+    fn poll_track_progress(&mut self) {
+        let second = self.track_progress().map(|(elapsed, _)| elapsed.as_secs());
+        if second != self.last_progress_second {
+            self.last_progress_second = second;
+            self.dirty = true;
+        }
+    }
+
+    /// Show a short message in the status bar
+    pub fn flash(&mut self, message: String) {
+        self.message = Some((message, Instant::now()));
+        self.dirty = true;
+    }
+
+    /// Clear the status bar message once it has been shown long enough
+    pub fn poll_message(&mut self) {
+        if let Some((_, shown_at)) = &self.message {
+            if shown_at.elapsed() >= Duration::from_secs(2) {
+                self.message = None;
+                self.dirty = true;
+            }
+        }
+    }
+
+    fn save_spectrum_settings(&mut self, settings: SpectrumSettings) {
+        self.spectrum_analyzer.set_settings(settings);
+        self.config.set_spectrum_settings(settings);
+        let _ = self.config.save();
+    }
+
+    /// Which screen key presses go to
+    pub fn input_mode(&self) -> InputMode {
+        if self.idle_prompt.is_some() {
+            InputMode::Idle
+        } else if self.show_help {
+            InputMode::Help
+        } else if self.visualizer_picker.is_some() {
+            InputMode::Picker
+        } else if self.theme_picker.is_some() {
+            InputMode::ThemePicker
+        } else if self.settings_view.is_some() {
+            InputMode::Settings
+        } else if self.links_view.is_some() {
+            InputMode::Links
+        } else if self.jobs_view.is_some() {
+            InputMode::Jobs
+        } else if self.queue_view.is_some() {
+            InputMode::Queue
+        } else if self.similar_view.is_some() {
+            InputMode::Similar
+        } else if self.recent_view.is_some() {
+            InputMode::Recent
+        } else if self.output_view.is_some() {
+            InputMode::Outputs
+        } else if let Some(view) = &self.recordings_view {
+            if view.renaming.is_some() {
+                InputMode::Search
+            } else {
+                InputMode::Recordings
+            }
+        } else if let Some(view) = &self.timeline_view {
+            if view.editing {
+                InputMode::Search
+            } else {
+                InputMode::Timeline
+            }
+        } else if self.searching || self.volume_input.is_some() || self.jump_input.is_some() {
+            InputMode::Search
+        } else if self.volume_popup.is_some() {
+            InputMode::VolumePopup
+        } else {
+            InputMode::Normal
+        }
+    }
+
+    /// All visualization modes in rotation order: built-ins, then plugins
+    pub fn visualization_modes(&self) -> Vec<VisualizationMode> {
+        VisualizationMode::BUILT_IN
+            .into_iter()
+            .chain((0..self.visualizer_plugins.len()).map(VisualizationMode::Plugin))
+            .collect()
+    }
+
+    /// Display name of a mode; plugins are named after their script file
+    pub fn visualization_name(&self, mode: VisualizationMode) -> String {
+        match mode {
+            VisualizationMode::Plugin(index) => self
+                .visualizer_plugins
+                .name(index)
+                .unwrap_or(mode.name())
+                .to_string(),
+            mode => mode.name().to_string(),
+        }
+    }
+
+    /// Picker rows: each mode's name and whether it's in the rotation
+    pub fn visualizer_entries(&self) -> Vec<(String, bool)> {
+        self.visualization_modes()
+            .into_iter()
+            .map(|mode| {
+                let name = self.visualization_name(mode);
+                let enabled = self.config.visualization_enabled(&name);
+                (name, enabled)
+            })
+            .collect()
+    }
+
+    /// Move to the next (or previous) mode, skipping modes disabled in config
+    fn step_visualization(&mut self, forward: bool) {
+        let modes = self.visualization_modes();
+        let len = modes.len();
+        let current = modes
+            .iter()
+            .position(|&m| m == self.visualization_mode)
+            .unwrap_or(0);
+
+        for step in 1..len {
+            let index = if forward {
+                (current + step) % len
+            } else {
+                (current + len - step) % len
+            };
+            let mode = modes[index];
+            if self
+                .config
+                .visualization_enabled(&self.visualization_name(mode))
+            {
+                self.set_visualization(mode);
+                return;
+            }
+        }
+    }
+
+    fn set_visualization(&mut self, mode: VisualizationMode) {
+        self.visualization_mode = mode;
+        // Save preference
+        match mode {
+            VisualizationMode::Plugin(_) => {
+                let name = self.visualization_name(mode);
+                self.config.set_visualization_name(&name);
+            }
+            mode => self.config.set_visualization(mode),
+        }
+        let _ = self.config.save();
+    }
+
+    /// Current track as "Title by Artist", preferring the songs API over the
+    /// stream title
+    pub fn now_playing_text(&self) -> Option<String> {
+        self.current_track().map(|track| track.to_string())
+    }
+
+    /// Playback status as words, for the linear screen-reader view
+    pub fn status_text(&self) -> String {
+        let state = t(if !self.playback_state.playing {
+            "sr-stopped"
+        } else if self.playback_state.paused {
+            "sr-paused"
+        } else {
+            "sr-playing"
+        });
+        if self.is_muted {
+            tf("sr-muted", &[("state", &state)])
+        } else {
+            tf(
+                "sr-volume",
+                &[("state", &state), ("volume", &self.playback_state.volume)],
+            )
+        }
+    }
+
     /// Update spectrum data for visualization
     pub async fn update_spectrum(&mut self) {
         // Increment frame counter for animations
-        self.frame = self.frame.wrapping_add(1);
+        if !self.reduced_motion {
+            self.frame = self.frame.wrapping_add(1);
+        }
 
         // Use cached audio stats from the background worker when available
-        if let Some((rms_db, peak_db)) = self.audio_levels {
+        if let Some(levels) = self.audio_levels {
             if self.playback_state.playing && !self.playback_state.paused {
-                self.spectrum_analyzer
-                    .update_from_levels(rms_db, peak_db)
-                    .await;
+                self.spectrum_analyzer.update_from_levels(&levels).await;
             } else {
                 self.spectrum_analyzer
                     .animate(self.playback_state.playing, self.playback_state.paused)
@@ -367,8 +3827,62 @@ impl App {
                 .await;
         }
 
+        // Mono streams and missing per-channel stats show the overall level on both meters
+        let listening = self.playback_state.playing && !self.playback_state.paused;
+        self.vu_meter
+            .update(self.audio_levels.filter(|_| listening).map(|levels| {
+                levels
+                    .stereo
+                    .unwrap_or([(levels.rms_db, levels.peak_db); 2])
+            }));
+
+        // Animations need a new frame every tick; stopped meters only until they settle
+        if self.is_animating()
+            || (self.show_vu_meter && !self.show_grid && !self.vu_meter.is_settled())
+        {
+            self.dirty = true;
+        }
+
         // Update the cached spectrum data for rendering
         self.spectrum_data = self.spectrum_analyzer.get_data().await;
+        if self.visualization_mode == VisualizationMode::Waterfall {
+            self.spectrum_history = self.spectrum_analyzer.history(HISTORY_LEN).await;
+        }
+    }
+}
+
+/// Fill in a title template's `{state}`, `{artist}`, `{title}` and
+/// `{station}`. Without a track the title is just the station, and without a
+/// station it's just "vibecast".
+fn format_title(
+    template: &str,
+    state: &str,
+    track: Option<&Track>,
+    station: Option<&str>,
+) -> String {
+    let Some(station) = station else {
+        return "vibecast".to_string();
+    };
+    let template = match track {
+        Some(track) if track.artist.is_empty() => template.replace("{artist} – ", ""),
+        Some(_) => template.to_string(),
+        None => "{state} {station} — vibecast".to_string(),
+    };
+    let (artist, title) = track.map_or(("", ""), |t| (t.artist.as_str(), t.title.as_str()));
+    template
+        .replace("{state}", state)
+        .replace("{artist}", artist)
+        .replace("{title}", title)
+        .replace("{station}", station)
+}
+
+/// File extension for a recording of `url`: the stream's own format, or
+/// Matroska, which holds anything
+fn recording_extension(channel: &Channel, url: &str) -> &'static str {
+    match channel.playlists.iter().find(|p| p.url == url) {
+        Some(playlist) if playlist.format == "mp3" => "mp3",
+        Some(playlist) if playlist.format.starts_with("aac") => "aac",
+        _ => "mka",
     }
 }
 
@@ -377,3 +3891,101 @@ impl Default for App {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::api::test_support::{channel, song};
+
+    #[test]
+    fn test_typical_track_length() {
+        // A long DJ set and a jingle don't count
+        let starts = [10_000, 9_760, 9_500, 9_490, 9_250, 5_000];
+        assert_eq!(
+            typical_track_length(starts.into_iter()),
+            Some(Duration::from_secs(240))
+        );
+        assert_eq!(typical_track_length([10_000].into_iter()), None);
+    }
+
+    #[tokio::test]
+    async fn test_audible_song_follows_stream_title() {
+        let mut app = App::new();
+        app.replace_channels(vec![channel("groovesalad")]);
+        app.current_channel = Some(0);
+
+        // The API has moved on while the stream still plays the last track
+        app.on_event(AppEvent::Songs {
+            generation: app.station_generation,
+            channel_id: "groovesalad".to_string(),
+            current_song: Some(song("", "Next")),
+            history: vec![song("", "Current")],
+        })
+        .await
+        .unwrap();
+        for (title, audible) in [("Current", "Current"), ("Next", "Next")] {
+            app.on_event(AppEvent::StreamTitle {
+                generation: app.station_generation,
+                channel_id: "groovesalad".to_string(),
+                title: title.to_string(),
+            })
+            .await
+            .unwrap();
+            assert_eq!(app.audible_song().map(|s| s.title.as_str()), Some(audible));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_drops_stale_station_metadata() {
+        let mut app = App::new();
+        app.replace_channels(vec![channel("groovesalad"), channel("dronezone")]);
+        app.current_channel = app.catalog.position("groovesalad");
+        app.station_generation = 2;
+
+        // Another station, and this station from an earlier time it played
+        for (generation, channel_id) in [(2, "dronezone"), (1, "groovesalad")] {
+            app.on_event(AppEvent::StreamTitle {
+                generation,
+                channel_id: channel_id.to_string(),
+                title: "Stale".to_string(),
+            })
+            .await
+            .unwrap();
+        }
+        assert_eq!(app.stream_title, None);
+
+        app.on_event(AppEvent::Songs {
+            generation: 2,
+            channel_id: "groovesalad".to_string(),
+            current_song: Some(song("", "Current")),
+            history: vec![song("", "Earlier")],
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            app.current_song.map(|s| s.title).as_deref(),
+            Some("Current")
+        );
+        assert_eq!(app.song_history.len(), 1);
+    }
+
+    #[test]
+    fn test_format_title() {
+        let template = crate::storage::config::DEFAULT_TITLE_TEMPLATE;
+        let track = Track::from_stream_title("groovesalad", "Bonobo - Kerala");
+        assert_eq!(
+            format_title(template, "▶", Some(&track), Some("Groove Salad")),
+            "▶ Bonobo – Kerala · Groove Salad — vibecast"
+        );
+        let untitled = Track::from_stream_title("groovesalad", "Station ID");
+        assert_eq!(
+            format_title(template, "▶", Some(&untitled), Some("Groove Salad")),
+            "▶ Station ID · Groove Salad — vibecast"
+        );
+        assert_eq!(
+            format_title(template, "⏸", None, Some("Groove Salad")),
+            "⏸ Groove Salad — vibecast"
+        );
+        assert_eq!(format_title(template, "■", None, None), "vibecast");
+    }
+}
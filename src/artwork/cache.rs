@@ -1,10 +1,44 @@
 #![allow(dead_code)]
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use reqwest::header::{self, HeaderMap};
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, SystemTime};
 use tokio::fs;
 
+use crate::api::http;
+
+/// How long an image counts as fresh when the server doesn't say
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What's known about a cached image, saved as `{key}.json` next to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    url: String,
+    content_type: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    /// Unix time the server last sent or confirmed the image, in seconds
+    checked_at: i64,
+    /// Seconds after `checked_at` the image is used without asking again
+    max_age: u64,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self, now: i64) -> bool {
+        now.saturating_sub(self.checked_at) < self.max_age as i64
+    }
+
+    /// File name of the image itself
+    fn file_name(&self, key: &str) -> String {
+        format!("{}.{}", key, extension(&self.content_type))
+    }
+}
+
+/// Images on disk, named after a hash of their URL so a station that
+/// changes artwork gets the new image
 pub struct ImageCache {
     cache_dir: PathBuf,
     client: reqwest::Client,
@@ -20,53 +54,240 @@ impl ImageCache {
 
         std::fs::create_dir_all(&cache_dir)?;
 
-        let client = reqwest::Client::builder()
+        let client = http::client_builder()
             .timeout(Duration::from_secs(10))
             .build()?;
 
         Ok(Self { cache_dir, client })
     }
 
-    fn cache_path(&self, station_id: &str) -> PathBuf {
-        self.cache_dir.join(format!("{}.png", station_id))
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.cache_dir.join(format!("{}.json", key))
     }
 
-    pub async fn get_or_fetch(&self, url: &str, station_id: &str) -> Result<Vec<u8>> {
-        let cache_path = self.cache_path(station_id);
+    /// The entry for `key` and its image, if both are there and the entry
+    /// is for `url` rather than a hash collision
+    async fn read_entry(&self, key: &str, url: &str) -> Option<(CacheEntry, Vec<u8>)> {
+        let content = fs::read_to_string(self.entry_path(key)).await.ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        if entry.url != url {
+            return None;
+        }
+        let bytes = fs::read(self.cache_dir.join(entry.file_name(key)))
+            .await
+            .ok()?;
+        Some((entry, bytes))
+    }
 
-        // Check if cached
-        if cache_path.exists() {
-            return Ok(fs::read(&cache_path).await?);
+    async fn write_entry(&self, key: &str, entry: &CacheEntry) -> Result<()> {
+        fs::write(self.entry_path(key), serde_json::to_string(entry)?).await?;
+        Ok(())
+    }
+
+    /// The image at `url`, from the cache while it's fresh. A stale copy is
+    /// checked with the server first, and still used if the server can't
+    /// be reached.
+    pub async fn get_or_fetch(&self, url: &str) -> Result<Vec<u8>> {
+        let key = cache_key(url);
+        let cached = match self.read_entry(&key, url).await {
+            Some((entry, bytes)) if entry.is_fresh(chrono::Utc::now().timestamp()) => {
+                return Ok(bytes);
+            }
+            cached => cached,
+        };
+
+        let fetched = self
+            .fetch(url, &key, cached.as_ref().map(|(entry, _)| entry))
+            .await;
+        match (fetched, cached) {
+            (Ok(Some(bytes)), _) => Ok(bytes),
+            // Unchanged, or the server's unreachable and old artwork beats none
+            (_, Some((_, bytes))) => Ok(bytes),
+            (Err(err), None) => Err(err),
+            (Ok(None), None) => Err(anyhow!("{} not modified, but not cached", url)),
+        }
+    }
+
+    /// Download `url` into the cache, or with `cached` only if it changed.
+    /// Returns `None` when the cached copy is still current.
+    async fn fetch(
+        &self,
+        url: &str,
+        key: &str,
+        cached: Option<&CacheEntry>,
+    ) -> Result<Option<Vec<u8>>> {
+        let mut request = self.client.get(url);
+        if let Some(entry) = cached {
+            if let Some(etag) = &entry.etag {
+                request = request.header(header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &entry.last_modified {
+                request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        // Timeout configured in the client
+        let response = request.send().await?;
+        let now = chrono::Utc::now().timestamp();
+        let max_age = header_value(response.headers(), header::CACHE_CONTROL)
+            .and_then(max_age)
+            .unwrap_or(DEFAULT_MAX_AGE.as_secs());
+
+        if let (StatusCode::NOT_MODIFIED, Some(entry)) = (response.status(), cached) {
+            let entry = CacheEntry {
+                checked_at: now,
+                max_age,
+                ..entry.clone()
+            };
+            self.write_entry(key, &entry).await?;
+            // Keep eviction, which goes by file age, off the image too
+            if let Ok(file) = std::fs::File::options()
+                .append(true)
+                .open(self.cache_dir.join(entry.file_name(key)))
+            {
+                let _ = file.set_modified(SystemTime::now());
+            }
+            return Ok(None);
         }
 
-        // Fetch from network with timeout (configured in client)
-        let response = self.client.get(url).send().await?.error_for_status()?;
+        let response = response.error_for_status()?;
+        let headers = response.headers().clone();
         let bytes = response.bytes().await?.to_vec();
 
-        // Cache it
-        let _ = fs::write(&cache_path, &bytes).await;
+        let entry = CacheEntry {
+            url: url.to_string(),
+            content_type: content_type(header_value(&headers, header::CONTENT_TYPE), &bytes),
+            etag: header_value(&headers, header::ETAG).map(str::to_string),
+            last_modified: header_value(&headers, header::LAST_MODIFIED).map(str::to_string),
+            checked_at: now,
+            max_age,
+        };
+        // The image may have come back in a different format
+        if let Some(old) = cached.filter(|old| old.file_name(key) != entry.file_name(key)) {
+            let _ = fs::remove_file(self.cache_dir.join(old.file_name(key))).await;
+        }
+        if fs::write(self.cache_dir.join(entry.file_name(key)), &bytes)
+            .await
+            .is_ok()
+        {
+            let _ = self.write_entry(key, &entry).await;
+        }
 
-        Ok(bytes)
+        Ok(Some(bytes))
     }
 
-    pub fn get_cached(&self, station_id: &str) -> Option<Vec<u8>> {
-        let cache_path = self.cache_path(station_id);
-        if cache_path.exists() {
-            std::fs::read(&cache_path).ok()
-        } else {
-            None
+    /// Delete artwork fetched longer than `max_age` ago, so it's fetched
+    /// fresh next time. Returns how many files went.
+    pub fn evict(&self, max_age: Duration) -> usize {
+        let Ok(entries) = std::fs::read_dir(&self.cache_dir) else {
+            return 0;
+        };
+        entries
+            .flatten()
+            .filter(|entry| {
+                entry
+                    .metadata()
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age > max_age)
+            })
+            .filter(|entry| std::fs::remove_file(entry.path()).is_ok())
+            .count()
+    }
+
+    /// The cached image at `url`, fresh or not, without touching the network
+    pub fn get_cached(&self, url: &str) -> Option<Vec<u8>> {
+        let key = cache_key(url);
+        let content = std::fs::read_to_string(self.entry_path(&key)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&content).ok()?;
+        if entry.url != url {
+            return None;
         }
+        std::fs::read(self.cache_dir.join(entry.file_name(&key))).ok()
     }
 }
 
+/// FNV-1a of `url` in hex. Unlike std's hasher it's the same from one
+/// build to the next, so cached files stay findable.
+fn cache_key(url: &str) -> String {
+    let hash = url.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+fn header_value(headers: &HeaderMap, name: header::HeaderName) -> Option<&str> {
+    headers.get(name)?.to_str().ok()
+}
+
+/// Seconds from a `Cache-Control` header; `no-cache` and `no-store` mean
+/// asking every time
+fn max_age(cache_control: &str) -> Option<u64> {
+    cache_control.split(',').find_map(|directive| {
+        let directive = directive.trim().to_ascii_lowercase();
+        match directive.as_str() {
+            "no-cache" | "no-store" => Some(0),
+            _ => directive.strip_prefix("max-age=")?.parse().ok(),
+        }
+    })
+}
+
+/// The image's type from the server, or from its first bytes when the
+/// server doesn't name an image type
+fn content_type(header: Option<&str>, bytes: &[u8]) -> String {
+    let declared = header
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .filter(|value| image::ImageFormat::from_mime_type(value).is_some());
+    declared
+        .or_else(|| {
+            image::guess_format(bytes)
+                .ok()
+                .map(|format| format.to_mime_type().to_string())
+        })
+        .unwrap_or_else(|| "application/octet-stream".to_string())
+}
+
+/// File extension for an image `content_type`
+fn extension(content_type: &str) -> &'static str {
+    image::ImageFormat::from_mime_type(content_type)
+        .and_then(|format| format.extensions_str().first().copied())
+        .unwrap_or("img")
+}
+
 impl Default for ImageCache {
     fn default() -> Self {
         Self::new().unwrap_or_else(|_| Self {
             cache_dir: PathBuf::from(".vibecast-cache").join("artwork"),
-            client: reqwest::Client::builder()
+            client: http::client_builder()
                 .timeout(Duration::from_secs(10))
                 .build()
                 .unwrap_or_default(),
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_entries() {
+        let key = cache_key("https://somafm.com/img/groovesalad120.png");
+        assert_eq!(key.len(), 16);
+        assert_eq!(key, cache_key("https://somafm.com/img/groovesalad120.png"));
+        assert_ne!(key, cache_key("https://somafm.com/img/groovesalad256.png"));
+
+        // The declared type wins; without one, the bytes say what it is
+        let png = b"\x89PNG\r\n\x1a\n\0\0\0\0";
+        assert_eq!(content_type(Some("image/jpeg; q=1"), png), "image/jpeg");
+        assert_eq!(content_type(Some("text/plain"), png), "image/png");
+        assert_eq!(content_type(None, b"nope"), "application/octet-stream");
+        assert_eq!(extension("image/jpeg"), "jpg");
+        assert_eq!(extension("application/octet-stream"), "img");
+
+        assert_eq!(max_age("public, max-age=3600"), Some(3600));
+        assert_eq!(max_age("no-cache"), Some(0));
+        assert_eq!(max_age("public"), None);
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::{anyhow, Result};
+use image::{imageops::FilterType, DynamicImage, ImageReader};
+use std::io::Cursor;
+
+use super::palette::{dominant_colors, Rgb};
+
+/// Decode downloaded artwork, scaled down to fit within `max_width` x
+/// `max_height` pixels, along with its dominant colors. This is slow for
+/// large images, so call it from a blocking task rather than the UI loop.
+pub fn decode_artwork(
+    bytes: &[u8],
+    max_width: u32,
+    max_height: u32,
+) -> Result<(DynamicImage, Vec<Rgb>)> {
+    // Sniff the format rather than trusting the URL; some covers are WebP
+    let reader = ImageReader::new(Cursor::new(bytes)).with_guessed_format()?;
+    let format = reader
+        .format()
+        .ok_or_else(|| anyhow!("unrecognized artwork format"))?;
+    let image = reader
+        .decode()
+        .map_err(|err| anyhow!("can't decode {:?} artwork: {}", format, err))?;
+    let image = if image.width() > max_width || image.height() > max_height {
+        image.resize(max_width, max_height, FilterType::Triangle)
+    } else {
+        image
+    };
+    let palette = dominant_colors(&image, 2);
+    Ok((image, palette))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+
+    #[test]
+    fn test_downscales_to_fit() {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(1200, 600))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png)
+            .unwrap();
+
+        let (image, _) = decode_artwork(&bytes, 400, 400).unwrap();
+        assert_eq!((image.width(), image.height()), (400, 200));
+
+        // Small images are left alone
+        let (image, _) = decode_artwork(&bytes, 2000, 2000).unwrap();
+        assert_eq!((image.width(), image.height()), (1200, 600));
+    }
+
+    #[test]
+    fn test_decodes_webp() {
+        let mut bytes = Vec::new();
+        DynamicImage::ImageRgb8(RgbImage::new(64, 32))
+            .write_to(&mut Cursor::new(&mut bytes), ImageFormat::WebP)
+            .unwrap();
+
+        let (image, _) = decode_artwork(&bytes, 32, 32).unwrap();
+        assert_eq!((image.width(), image.height()), (32, 16));
+        assert!(decode_artwork(b"not an image", 32, 32).is_err());
+    }
+}
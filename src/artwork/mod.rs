@@ -1,3 +1,6 @@
 pub mod cache;
+pub mod decode;
+pub mod palette;
 
 pub use cache::ImageCache;
+pub use decode::decode_artwork;
@@ -0,0 +1,63 @@
+use image::DynamicImage;
+use std::collections::HashMap;
+
+/// An RGB color extracted from artwork
+pub type Rgb = (u8, u8, u8);
+
+/// Find the most common vivid colors in an image, most dominant first.
+/// Near-gray, very dark, and washed-out pixels are ignored so the result is
+/// usable as an accent color.
+pub fn dominant_colors(image: &DynamicImage, count: usize) -> Vec<Rgb> {
+    let thumb = image.thumbnail(32, 32).to_rgb8();
+
+    // Bucket pixels into a 8x8x8 color cube, tracking sums for averaging
+    let mut buckets: HashMap<(u8, u8, u8), (u32, [u32; 3])> = HashMap::new();
+    for pixel in thumb.pixels() {
+        let [r, g, b] = pixel.0;
+        let (saturation, value) = saturation_value(r, g, b);
+        if saturation < 0.25 || value < 0.2 {
+            continue;
+        }
+        let entry = buckets
+            .entry((r >> 5, g >> 5, b >> 5))
+            .or_insert((0, [0; 3]));
+        entry.0 += 1;
+        entry.1[0] += r as u32;
+        entry.1[1] += g as u32;
+        entry.1[2] += b as u32;
+    }
+
+    let mut ranked: Vec<_> = buckets.into_iter().collect();
+    ranked.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+
+    let mut chosen: Vec<((u8, u8, u8), Rgb)> = Vec::new();
+    for (key, (n, sums)) in ranked {
+        if chosen.len() >= count {
+            break;
+        }
+        // Skip buckets adjacent to one already chosen so colors stay distinct
+        let distinct = chosen
+            .iter()
+            .all(|(k, _)| k.0.abs_diff(key.0) + k.1.abs_diff(key.1) + k.2.abs_diff(key.2) > 2);
+        if distinct {
+            let color = (
+                (sums[0] / n) as u8,
+                (sums[1] / n) as u8,
+                (sums[2] / n) as u8,
+            );
+            chosen.push((key, color));
+        }
+    }
+
+    chosen.into_iter().map(|(_, color)| color).collect()
+}
+
+/// HSV saturation and value in 0.0..=1.0
+fn saturation_value(r: u8, g: u8, b: u8) -> (f32, f32) {
+    let max = r.max(g).max(b) as f32;
+    let min = r.min(g).min(b) as f32;
+    if max == 0.0 {
+        return (0.0, 0.0);
+    }
+    ((max - min) / max, max / 255.0)
+}
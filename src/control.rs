@@ -0,0 +1,327 @@
+//! Control socket for driving a running instance from outside, e.g. a window
+//! manager key bound to `vibecast ctl pause`. Each connection sends one
+//! command line and gets back `ok` or `error` followed by the reply text.
+//! `spectrum follow` keeps the connection open, sending a frame per line.
+
+use anyhow::{anyhow, bail, Result};
+use chrono::NaiveTime;
+use tokio::sync::oneshot;
+
+use crate::event::{AppEvent, EventSender};
+#[cfg(unix)]
+use crate::systemd;
+use crate::visualizer::HISTORY_LEN;
+#[cfg(unix)]
+use tokio::io::{AsyncWrite, AsyncWriteExt};
+
+const USAGE: &str = "usage: vibecast ctl play | pause | next-favorite | volume [N | +N | -N] \
+| sleep MINUTES | sleep off | alarm HH:MM [STATION] | jobs | status | spectrum [N | follow]";
+
+/// Time between frames sent by `spectrum follow`
+#[cfg(unix)]
+const SPECTRUM_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
+
+/// A volume change asked for over the socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeChange {
+    Set(u8),
+    By(i16),
+}
+
+/// What `vibecast ctl` can ask a running instance to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Command {
+    /// Resume, or start the highlighted station if nothing is playing
+    Play,
+    Pause,
+    /// Play the next favorite station after the current one
+    NextFavorite,
+    /// Change the volume, or just report it
+    Volume(Option<VolumeChange>),
+    /// Pause after this many minutes, or turn the sleep timer off
+    Sleep(Option<i64>),
+    /// Start playing at the next `at`, the given station or the highlighted
+    /// one
+    Alarm {
+        at: NaiveTime,
+        station: Option<String>,
+    },
+    /// List pending timers and jobs
+    Jobs,
+    Status,
+    /// The last `frames` spectrum frames as JSON lines, oldest first; with
+    /// `follow`, the newest one as it comes until the client hangs up
+    Spectrum {
+        frames: usize,
+        follow: bool,
+    },
+}
+
+impl Command {
+    pub fn parse(words: &[&str]) -> Result<Self> {
+        let command = match words {
+            ["play"] => Self::Play,
+            ["pause"] => Self::Pause,
+            ["next-favorite"] => Self::NextFavorite,
+            ["status"] => Self::Status,
+            ["jobs"] => Self::Jobs,
+            ["sleep", "off"] => Self::Sleep(None),
+            ["sleep", minutes] => Self::Sleep(Some(
+                minutes
+                    .parse()
+                    .ok()
+                    .filter(|minutes| (1..=720).contains(minutes))
+                    .ok_or_else(|| anyhow!("sleep takes 1 to 720 minutes, or off"))?,
+            )),
+            ["alarm", at, station @ ..] if station.len() <= 1 => Self::Alarm {
+                at: NaiveTime::parse_from_str(at, "%H:%M")
+                    .map_err(|_| anyhow!("alarm time must be HH:MM"))?,
+                station: station.first().map(|station| station.to_string()),
+            },
+            ["spectrum"] => Self::Spectrum {
+                frames: 1,
+                follow: false,
+            },
+            ["spectrum", "follow"] => Self::Spectrum {
+                frames: 1,
+                follow: true,
+            },
+            ["spectrum", frames] => Self::Spectrum {
+                frames: frames
+                    .parse()
+                    .ok()
+                    .filter(|frames| (1..=HISTORY_LEN).contains(frames))
+                    .ok_or_else(|| anyhow!("spectrum takes 1 to {} frames", HISTORY_LEN))?,
+                follow: false,
+            },
+            ["volume"] => Self::Volume(None),
+            ["volume", amount] => {
+                let change = if let Some(up) = amount.strip_prefix('+') {
+                    up.parse().ok().map(VolumeChange::By)
+                } else if amount.starts_with('-') {
+                    amount.parse().ok().map(VolumeChange::By)
+                } else {
+                    amount
+                        .parse()
+                        .ok()
+                        .filter(|volume| *volume <= 100)
+                        .map(VolumeChange::Set)
+                };
+                Self::Volume(Some(
+                    change.ok_or_else(|| anyhow!("volume must be 0 to 100, or +N / -N"))?,
+                ))
+            }
+            _ => bail!("{}", USAGE),
+        };
+        Ok(command)
+    }
+}
+
+/// A command from the socket, answered through `reply`
+pub struct Request {
+    pub command: Command,
+    pub reply: oneshot::Sender<Result<String>>,
+}
+
+/// Pass `command` to the app and wait for its reply
+#[cfg(unix)]
+async fn ask(events: &EventSender, command: Command) -> Result<String> {
+    let (reply, response) = oneshot::channel();
+    events.send(AppEvent::Control(Request { command, reply }))?;
+    response
+        .await
+        .unwrap_or_else(|_| Err(anyhow!("vibecast is shutting down")))
+}
+
+/// Write the newest spectrum frame to `writer` every `SPECTRUM_INTERVAL`,
+/// until the client goes away or vibecast stops
+#[cfg(unix)]
+async fn follow_spectrum(events: &EventSender, writer: &mut (impl AsyncWrite + Unpin)) {
+    let mut ticks = tokio::time::interval(SPECTRUM_INTERVAL);
+    loop {
+        ticks.tick().await;
+        let command = Command::Spectrum {
+            frames: 1,
+            follow: false,
+        };
+        let Ok(frame) = ask(events, command).await else {
+            return;
+        };
+        if writer
+            .write_all(format!("{}\n", frame).as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Where a running instance listens
+#[cfg(unix)]
+fn socket_path() -> std::path::PathBuf {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) => std::path::PathBuf::from(dir).join("vibecast.sock"),
+        None => {
+            let user = std::env::var("USER").unwrap_or_default();
+            std::env::temp_dir().join(format!("vibecast-{}.sock", user))
+        }
+    }
+}
+
+/// Socket this process created, to remove on exit. One passed in by
+/// systemd stays for the next activation.
+#[cfg(unix)]
+static BOUND: std::sync::OnceLock<std::path::PathBuf> = std::sync::OnceLock::new();
+
+/// Start answering commands on the control socket, or on the one systemd
+/// passed in. Fails if another instance already has it.
+#[cfg(unix)]
+pub async fn listen(events: EventSender) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+
+    let listener = match systemd::inherited_listener() {
+        Some(listener) => UnixListener::from_std(listener)?,
+        None => {
+            let path = socket_path();
+            if UnixStream::connect(&path).await.is_ok() {
+                bail!("another vibecast is listening on {}", path.display());
+            }
+            // Left behind by an instance that didn't exit cleanly
+            let _ = std::fs::remove_file(&path);
+            let listener = UnixListener::bind(&path)?;
+            let _ = BOUND.set(path);
+            listener
+        }
+    };
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let events = events.clone();
+            tokio::spawn(async move {
+                let (reader, mut writer) = stream.into_split();
+                let mut line = String::new();
+                if BufReader::new(reader).read_line(&mut line).await.is_err() {
+                    return;
+                }
+                let words: Vec<&str> = line.split_whitespace().collect();
+                let result = match Command::parse(&words) {
+                    Ok(Command::Spectrum { follow: true, .. }) => {
+                        if writer.write_all(b"ok\n").await.is_ok() {
+                            follow_spectrum(&events, &mut writer).await;
+                        }
+                        return;
+                    }
+                    Ok(command) => ask(&events, command).await,
+                    Err(err) => Err(err),
+                };
+                let response = match result {
+                    Ok(text) => format!("ok\n{}\n", text),
+                    Err(err) => format!("error\n{}\n", err),
+                };
+                let _ = writer.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(windows)]
+pub async fn listen(_events: EventSender) -> Result<()> {
+    Ok(())
+}
+
+/// Remove the socket on exit, if this process created it
+pub fn remove_socket() {
+    #[cfg(unix)]
+    if let Some(path) = BOUND.get() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+/// `vibecast ctl <command>`: send the command to the running instance and
+/// print its reply
+#[cfg(unix)]
+pub async fn run(args: &[String]) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::UnixStream;
+
+    let words: Vec<&str> = args.iter().map(String::as_str).collect();
+    Command::parse(&words)?;
+
+    let path = socket_path();
+    let mut stream = UnixStream::connect(&path)
+        .await
+        .map_err(|_| anyhow!("vibecast isn't running (no socket at {})", path.display()))?;
+    stream
+        .write_all(format!("{}\n", words.join(" ")).as_bytes())
+        .await?;
+    let mut reader = BufReader::new(stream);
+    let mut status = String::new();
+    reader.read_line(&mut status).await?;
+
+    match status.trim_end() {
+        // Copied as it comes, for `spectrum follow`
+        "ok" => {
+            tokio::io::copy(&mut reader, &mut tokio::io::stdout()).await?;
+            Ok(())
+        }
+        "error" => {
+            let mut text = String::new();
+            reader.read_to_string(&mut text).await?;
+            Err(anyhow!("{}", text.trim_end()))
+        }
+        _ => Err(anyhow!("unexpected reply from vibecast")),
+    }
+}
+
+#[cfg(windows)]
+pub async fn run(_args: &[String]) -> Result<()> {
+    Err(anyhow!("vibecast ctl isn't supported on Windows"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse(&["pause"]).unwrap(), Command::Pause);
+        assert_eq!(
+            Command::parse(&["volume", "40"]).unwrap(),
+            Command::Volume(Some(VolumeChange::Set(40)))
+        );
+        assert_eq!(
+            Command::parse(&["volume", "+5"]).unwrap(),
+            Command::Volume(Some(VolumeChange::By(5)))
+        );
+        assert_eq!(
+            Command::parse(&["volume", "-10"]).unwrap(),
+            Command::Volume(Some(VolumeChange::By(-10)))
+        );
+        assert!(Command::parse(&["volume", "140"]).is_err());
+        assert_eq!(
+            Command::parse(&["alarm", "07:30", "groovesalad"]).unwrap(),
+            Command::Alarm {
+                at: NaiveTime::from_hms_opt(7, 30, 0).unwrap(),
+                station: Some("groovesalad".to_string()),
+            }
+        );
+        assert!(Command::parse(&["alarm", "7pm"]).is_err());
+        assert_eq!(
+            Command::parse(&["sleep", "off"]).unwrap(),
+            Command::Sleep(None)
+        );
+        assert!(Command::parse(&["next"]).is_err());
+        assert_eq!(
+            Command::parse(&["spectrum", "follow"]).unwrap(),
+            Command::Spectrum {
+                frames: 1,
+                follow: true
+            }
+        );
+        assert!(Command::parse(&["spectrum", "0"]).is_err());
+        assert!(Command::parse(&[]).is_err());
+    }
+}
@@ -0,0 +1,401 @@
+use anyhow::{anyhow, Result};
+use crossterm::event::{self, Event};
+use image::{DynamicImage, RgbImage};
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+
+use crate::airplay::AirPlayDevice;
+use crate::api::{Channel, ReleaseInfo, Song, Track};
+use crate::control::Request;
+use crate::player::{AudioLevels, StreamHealth};
+use crate::storage::sync::SyncDoc;
+
+/// Queued terminal input before the reader thread has to wait
+const CAPACITY: usize = 64;
+
+/// Everything that can change app state, from the terminal or a background
+/// worker. Each source's events are applied in the order it sent them, by
+/// `App::on_event`. Station metadata carries the `App::station_generation`
+/// it was requested for.
+pub enum AppEvent {
+    /// Key press, resize or focus change
+    Terminal(Event),
+    /// Animation tick
+    Tick,
+    Songs {
+        generation: u64,
+        channel_id: String,
+        current_song: Option<Song>,
+        history: Vec<Song>,
+    },
+    StreamTitle {
+        generation: u64,
+        channel_id: String,
+        title: String,
+    },
+    Artwork {
+        generation: u64,
+        channel_id: String,
+        image: DynamicImage,
+        url: String,
+        palette: Vec<(u8, u8, u8)>,
+    },
+    Thumbnail {
+        channel_id: String,
+        image: RgbImage,
+    },
+    /// What a station in the detailed list is playing
+    SongPreview {
+        channel_id: String,
+        song: Song,
+    },
+    Channels(Vec<Channel>),
+    /// Fetching the channel list failed; the error
+    ChannelsFailed(String),
+    AudioLevels(Option<AudioLevels>),
+    /// How far playback trails the live stream, `None` when live
+    BehindLive(Option<Duration>),
+    /// Measured stream bitrate in kbps
+    Bitrate(Option<u32>),
+    StreamHealth(Option<StreamHealth>),
+    /// Output devices disappeared, e.g. headphones were unplugged; their
+    /// descriptions
+    AudioDevicesRemoved(Vec<String>),
+    /// MusicBrainz release details for a track, `None` if it has none
+    Release {
+        track: Track,
+        info: Option<ReleaseInfo>,
+    },
+    /// Favorites merged with the synced copy, or why syncing failed
+    FavoritesSynced(Result<SyncDoc, String>),
+    /// What each favorite station is playing, by station id, for spotting
+    /// watched artists
+    FavoriteSongs(Vec<(String, Song)>),
+    /// AirPlay speakers found on the network, or why looking failed
+    AirPlayDevices(Result<Vec<AirPlayDevice>, String>),
+    /// The playing track changed; emitted by the app itself
+    TrackChanged(Track),
+    /// A command from `vibecast ctl`
+    Control(Request),
+}
+
+impl AppEvent {
+    /// Updates that only matter in their latest form share a key; a newer one
+    /// replaces an older one still waiting to be applied
+    fn coalesce_key(&self) -> Option<CoalesceKey> {
+        // Station metadata is keyed by generation too, so a late response for
+        // an earlier play can't replace the current one
+        match self {
+            Self::Terminal(_)
+            | Self::Tick
+            | Self::AudioDevicesRemoved(_)
+            | Self::TrackChanged(_)
+            | Self::Control(_) => None,
+            Self::Songs {
+                generation,
+                channel_id,
+                ..
+            } => Some(("songs", channel_id.clone(), *generation)),
+            Self::StreamTitle {
+                generation,
+                channel_id,
+                ..
+            } => Some(("stream_title", channel_id.clone(), *generation)),
+            Self::Artwork {
+                generation,
+                channel_id,
+                ..
+            } => Some(("artwork", channel_id.clone(), *generation)),
+            Self::Thumbnail { channel_id, .. } => Some(("thumbnail", channel_id.clone(), 0)),
+            Self::SongPreview { channel_id, .. } => Some(("song_preview", channel_id.clone(), 0)),
+            Self::Channels(_) => Some(("channels", String::new(), 0)),
+            Self::ChannelsFailed(_) => Some(("channels_failed", String::new(), 0)),
+            Self::AudioLevels(_) => Some(("audio_levels", String::new(), 0)),
+            Self::BehindLive(_) => Some(("behind_live", String::new(), 0)),
+            Self::Bitrate(_) => Some(("bitrate", String::new(), 0)),
+            Self::StreamHealth(_) => Some(("stream_health", String::new(), 0)),
+            Self::Release { .. } => Some(("release", String::new(), 0)),
+            Self::FavoritesSynced(_) => Some(("favorites_synced", String::new(), 0)),
+            Self::FavoriteSongs(_) => Some(("favorite_songs", String::new(), 0)),
+            Self::AirPlayDevices(_) => Some(("airplay_devices", String::new(), 0)),
+        }
+    }
+}
+
+/// Counts of updates that never reached the app
+#[derive(Debug, Default)]
+pub struct BusStats {
+    /// Replaced by a newer update with the same key before being applied
+    pub coalesced: AtomicU64,
+    /// Ticks discarded because the queue was full
+    pub dropped: AtomicU64,
+}
+
+impl std::fmt::Display for BusStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} updates superseded before display, {} dropped",
+            self.coalesced.load(Ordering::Relaxed),
+            self.dropped.load(Ordering::Relaxed)
+        )
+    }
+}
+
+/// Kind of update, channel id and station generation
+type CoalesceKey = (&'static str, String, u64);
+
+/// Worker updates waiting to be applied, with their keys oldest first
+#[derive(Default)]
+struct PendingUpdates {
+    events: HashMap<CoalesceKey, AppEvent>,
+    order: VecDeque<CoalesceKey>,
+}
+
+impl PendingUpdates {
+    /// Queue `event` behind everything else, returning whether it replaced
+    /// an older one with the same key
+    fn insert(&mut self, key: CoalesceKey, event: AppEvent) -> bool {
+        let replaced = self.events.insert(key.clone(), event).is_some();
+        if replaced {
+            self.order.retain(|queued| *queued != key);
+        }
+        self.order.push_back(key);
+        replaced
+    }
+
+    fn pop(&mut self) -> Option<AppEvent> {
+        let key = self.order.pop_front()?;
+        self.events.remove(&key)
+    }
+}
+
+type Pending = Arc<Mutex<PendingUpdates>>;
+
+/// Handle for feeding events to the bus
+#[derive(Clone)]
+pub struct EventSender {
+    tx: mpsc::Sender<AppEvent>,
+    /// Events that must arrive, like `vibecast ctl` requests, which aren't
+    /// held back by a full queue
+    must_deliver: mpsc::UnboundedSender<AppEvent>,
+    pending: Pending,
+    wake: Arc<Notify>,
+    stats: Arc<BusStats>,
+}
+
+impl EventSender {
+    /// Queue an event without waiting. Worker updates replace any older one
+    /// with the same key, and a tick is dropped if the queue is full.
+    pub fn send(&self, event: AppEvent) -> Result<()> {
+        if self.tx.is_closed() {
+            return Err(anyhow!("event bus closed"));
+        }
+        match event.coalesce_key() {
+            Some(key) => {
+                if self.pending.lock().unwrap().insert(key, event) {
+                    self.stats.coalesced.fetch_add(1, Ordering::Relaxed);
+                }
+                self.wake.notify_one();
+            }
+            // The next one will be along shortly
+            None if matches!(event, AppEvent::Tick) => {
+                if let Err(mpsc::error::TrySendError::Full(_)) = self.tx.try_send(event) {
+                    self.stats.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+            None => {
+                let _ = self.must_deliver.send(event);
+            }
+        }
+        Ok(())
+    }
+
+    /// Queue an event, blocking the calling thread while the queue is full.
+    /// Only for use outside the async runtime.
+    pub fn send_blocking(&self, event: AppEvent) -> Result<()> {
+        self.tx
+            .blocking_send(event)
+            .map_err(|_| anyhow!("event bus closed"))
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+}
+
+/// Single queue every event source feeds into. Terminal input is bounded and
+/// applied in order; worker updates are coalesced so a slow terminal only
+/// ever sees the newest of each, and come out in the order they were sent.
+pub struct EventBus {
+    sender: EventSender,
+    rx: mpsc::Receiver<AppEvent>,
+    must_deliver: mpsc::UnboundedReceiver<AppEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let (must_deliver_tx, must_deliver) = mpsc::unbounded_channel();
+        Self {
+            sender: EventSender {
+                tx,
+                must_deliver: must_deliver_tx,
+                pending: Pending::default(),
+                wake: Arc::new(Notify::new()),
+                stats: Arc::new(BusStats::default()),
+            },
+            rx,
+            must_deliver,
+        }
+    }
+
+    pub fn sender(&self) -> EventSender {
+        self.sender.clone()
+    }
+
+    pub fn stats(&self) -> &BusStats {
+        &self.sender.stats
+    }
+
+    /// Wait for the next event, preferring terminal input over updates.
+    /// Never returns `None` while the bus holds its own sender.
+    pub async fn next(&mut self) -> Option<AppEvent> {
+        loop {
+            if let Some(event) = self.try_next() {
+                return Some(event);
+            }
+            tokio::select! {
+                event = self.rx.recv() => return event,
+                event = self.must_deliver.recv() => return event,
+                _ = self.sender.wake.notified() => {}
+            }
+        }
+    }
+
+    /// Take an already queued event without waiting
+    pub fn try_next(&mut self) -> Option<AppEvent> {
+        if let Ok(event) = self.rx.try_recv() {
+            return Some(event);
+        }
+        if let Ok(event) = self.must_deliver.try_recv() {
+            return Some(event);
+        }
+        self.sender.pending.lock().unwrap().pop()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Forward terminal events onto the bus from a dedicated thread, since
+/// crossterm's reads block. Stops once the bus is dropped.
+pub fn spawn_terminal_reader(tx: EventSender) {
+    std::thread::spawn(move || {
+        while !tx.is_closed() {
+            match event::poll(Duration::from_millis(100)) {
+                Ok(true) => match event::read() {
+                    Ok(event) => {
+                        if tx.send_blocking(AppEvent::Terminal(event)).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                },
+                Ok(false) => {}
+                Err(_) => break,
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_coalesces_updates_per_channel() {
+        let mut bus = EventBus::new();
+        let tx = bus.sender();
+        for title in ["First", "Second", "Third"] {
+            tx.send(AppEvent::StreamTitle {
+                generation: 1,
+                channel_id: "groovesalad".to_string(),
+                title: title.to_string(),
+            })
+            .unwrap();
+        }
+        tx.send(AppEvent::StreamTitle {
+            generation: 1,
+            channel_id: "dronezone".to_string(),
+            title: "Other".to_string(),
+        })
+        .unwrap();
+        tx.send(AppEvent::Tick).unwrap();
+
+        // Unkeyed events come first, then the newest title per channel, in
+        // the order the newest ones were sent
+        assert!(matches!(bus.next().await, Some(AppEvent::Tick)));
+        let mut titles = Vec::new();
+        while let Some(event) = bus.try_next() {
+            if let AppEvent::StreamTitle {
+                channel_id, title, ..
+            } = event
+            {
+                titles.push((channel_id, title));
+            }
+        }
+        assert_eq!(
+            titles,
+            vec![
+                ("groovesalad".to_string(), "Third".to_string()),
+                ("dronezone".to_string(), "Other".to_string()),
+            ]
+        );
+        assert_eq!(bus.stats().coalesced.load(Ordering::Relaxed), 2);
+
+        // A failure can't land after the channel list that followed it
+        tx.send(AppEvent::Channels(Vec::new())).unwrap();
+        tx.send(AppEvent::ChannelsFailed("timeout".to_string()))
+            .unwrap();
+        tx.send(AppEvent::Channels(Vec::new())).unwrap();
+        assert!(matches!(
+            bus.next().await,
+            Some(AppEvent::ChannelsFailed(_))
+        ));
+        assert!(matches!(bus.next().await, Some(AppEvent::Channels(_))));
+        assert!(bus.try_next().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_only_ticks_are_dropped() {
+        let mut bus = EventBus::new();
+        let tx = bus.sender();
+        for _ in 0..CAPACITY + 3 {
+            tx.send(AppEvent::Tick).unwrap();
+        }
+        tx.send(AppEvent::AudioDevicesRemoved(
+            vec!["Headphones".to_string()],
+        ))
+        .unwrap();
+        assert_eq!(bus.stats().dropped.load(Ordering::Relaxed), 3);
+
+        let mut ticks = 0;
+        let mut removed = false;
+        while let Some(event) = bus.try_next() {
+            match event {
+                AppEvent::Tick => ticks += 1,
+                AppEvent::AudioDevicesRemoved(devices) => removed = devices == ["Headphones"],
+                _ => {}
+            }
+        }
+        assert_eq!(ticks, CAPACITY);
+        assert!(removed);
+    }
+}
@@ -0,0 +1,122 @@
+//! Translated user-facing text. Each locale is a catalog of `id = text`
+//! lines under `locales/`; text may contain `{name}` placeholders. Ids
+//! missing from a catalog fall back to English.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+const ENGLISH: &str = include_str!("../locales/en.ftl");
+const SPANISH: &str = include_str!("../locales/es.ftl");
+
+static CATALOG: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+
+/// A language with a catalog
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    English,
+    Spanish,
+}
+
+impl Locale {
+    /// Locale for a language tag like "es", "es_MX.UTF-8" or "C"; `None`
+    /// if there's no catalog for it
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let language = tag.split(['_', '-', '.', '@']).next()?.to_lowercase();
+        match language.as_str() {
+            "en" | "c" | "posix" => Some(Self::English),
+            "es" => Some(Self::Spanish),
+            _ => None,
+        }
+    }
+
+    /// Locale from `language` if set, otherwise from the environment the way
+    /// gettext picks it, falling back to English
+    pub fn detect(language: Option<&str>) -> Self {
+        language
+            .map(str::to_string)
+            .into_iter()
+            .chain(
+                ["LC_ALL", "LC_MESSAGES", "LANG"]
+                    .iter()
+                    .filter_map(|var| std::env::var(var).ok()),
+            )
+            .find(|tag| !tag.is_empty())
+            .and_then(|tag| Self::from_tag(&tag))
+            .unwrap_or_default()
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::English => ENGLISH,
+            Self::Spanish => SPANISH,
+        }
+    }
+}
+
+fn parse(source: &'static str) -> impl Iterator<Item = (&'static str, &'static str)> {
+    source.lines().filter_map(|line| {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (id, text) = line.split_once('=')?;
+        Some((id.trim(), text.trim()))
+    })
+}
+
+fn catalog(locale: Locale) -> HashMap<&'static str, &'static str> {
+    let mut strings: HashMap<_, _> = parse(ENGLISH).collect();
+    strings.extend(parse(locale.source()));
+    strings
+}
+
+/// Pick the language for the rest of the run. Only the first call counts;
+/// text looked up before it is English.
+pub fn init(locale: Locale) {
+    // Tests check English text whatever the machine's locale
+    let locale = if cfg!(test) { Locale::English } else { locale };
+    let _ = CATALOG.set(catalog(locale));
+}
+
+/// Text for `id`, or the id itself if no catalog has it
+pub fn t(id: &'static str) -> &'static str {
+    CATALOG
+        .get_or_init(|| catalog(Locale::English))
+        .get(id)
+        .copied()
+        .unwrap_or(id)
+}
+
+/// Text for `id` with each `{name}` replaced by its value
+pub fn tf(id: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    args.iter().fold(t(id).to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), &value.to_string())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalogs() {
+        assert_eq!(Locale::from_tag("es_MX.UTF-8"), Some(Locale::Spanish));
+        assert_eq!(Locale::from_tag("C"), Some(Locale::English));
+        assert_eq!(Locale::from_tag("tlh"), None);
+
+        // Every Spanish id has an English original, so none are typos
+        let english: HashMap<_, _> = parse(ENGLISH).collect();
+        for (id, _) in parse(SPANISH) {
+            assert!(english.contains_key(id), "{} isn't in en.ftl", id);
+        }
+
+        let spanish = catalog(Locale::Spanish);
+        assert_eq!(spanish["state-paused"], "En pausa");
+        assert_eq!(
+            tf("msg-surf-on", &[("minutes", &10)]),
+            "Surf mode on: new station every 10 min"
+        );
+    }
+}
@@ -1,71 +1,360 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+use super::keymap;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
     Quit,
+    /// Esc: clear an applied search, otherwise quit
+    Back,
     TogglePlayPause,
     VolumeUp,
     VolumeDown,
+    /// Change the volume by this many points, from the volume popup
+    VolumeFine(i8),
     ToggleMute,
+    /// Type an exact volume percentage
+    EnterVolume,
     ToggleFavorite,
+    /// Watch the playing artist on the favorite stations, or stop
+    WatchArtist,
+    /// Watch the playing track on the favorite stations, or stop
+    WatchTrack,
+    /// Play the station a watched artist was last spotted on
+    PlayWatched,
+    /// Type a station's number to go to it, starting with a digit already
+    /// typed if there is one
+    EnterJump(Option<char>),
     NextStation,
     PrevStation,
     SelectStation,
+    RandomStation,
+    ToggleSurf,
+    ToggleDoNotDisturb,
+    /// Skip ahead to the live stream after a pause
+    JumpToLive,
+    /// Start or stop saving the playing stream
+    ToggleRecording,
+    OpenRecordings,
+    OpenSettings,
+    /// Stop showing new-user tips
+    DismissHints,
+    RenameRecording,
+    DeleteRecording,
+    /// Start or end an A/B comparison with the selected station
+    Compare,
+    /// Switch which side of the comparison is heard
+    SwapCompare,
     GoToTop,
     GoToBottom,
     ToggleSortMode,
+    /// List one station source at a time, then all of them again
+    CycleSource,
     ToggleVisualizer,
+    ToggleVuMeter,
     CycleVisualization,
+    PrevVisualization,
+    OpenVisualizerPicker,
+    GainUp,
+    GainDown,
+    FloorUp,
+    FloorDown,
+    CycleSmoothing,
     ToggleArtwork,
     ToggleHistory,
+    MoveLeft,
+    MoveRight,
+    ToggleGrid,
+    ToggleDetailedList,
+    GrowStationList,
+    ShrinkStationList,
+    GrowVisualizer,
+    ShrinkVisualizer,
     QualityUp,
     QualityDown,
     ToggleHelp,
-    ToggleTheme,
+    OpenThemePicker,
     Refresh,
     CloseOverlay,
+    OverlayUp,
+    OverlayDown,
+    OverlayPageUp,
+    OverlayPageDown,
+    OverlaySelect,
+    OverlayToggle,
+    OpenSearch,
+    /// Show everything played on the station
+    OpenTimeline,
+    /// Switch the timeline between this session and today
+    TimelineScope,
+    /// Show the station's links and the donation page
+    OpenLinks,
+    /// Copy the selected link to the clipboard
+    CopyLink,
+    /// Step the sleep timer through its lengths, then off
+    CycleSleepTimer,
+    /// Show pending timers and periodic jobs
+    OpenJobs,
+    /// Cancel the selected timer in the jobs view
+    CancelJob,
+    /// Add the selected station to the play queue
+    Enqueue,
+    /// Play the next station in the queue
+    NextInQueue,
+    OpenQueue,
+    /// Take the selected station out of the queue
+    Dequeue,
+    /// Show stations sharing a genre with the playing one
+    OpenSimilar,
+    /// Show the stations listened to last
+    OpenRecent,
+    /// Pick the numbered entry of a popup, counting from 1
+    OverlayPick(usize),
+    /// Move the selected entry of a reorderable list up or down
+    MoveItemUp,
+    MoveItemDown,
+    /// Pick where audio plays: here or an AirPlay speaker
+    OpenOutputs,
+    SearchInput(char),
+    SearchBackspace,
+    SearchConfirm,
+    SearchCancel,
+}
+
+/// Which screen currently receives key presses
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMode {
+    Normal,
+    Help,
+    /// The "still listening?" prompt
+    Idle,
+    /// A popup list (e.g. the visualization picker)
+    Picker,
+    /// The theme picker
+    ThemePicker,
+    /// Typing a station search
+    Search,
+    /// The station timeline popup
+    Timeline,
+    /// The station links popup
+    Links,
+    /// The pending jobs view
+    Jobs,
+    /// The play queue popup
+    Queue,
+    /// The "more like this" popup
+    Similar,
+    /// The recent stations popup
+    Recent,
+    /// The audio output picker
+    Outputs,
+    /// The recordings browser
+    Recordings,
+    /// The settings screen
+    Settings,
+    /// The volume popup, shown briefly after a volume key
+    VolumePopup,
+}
+
+pub fn handle_key(key: KeyEvent, mode: InputMode) -> Option<Action> {
+    match mode {
+        InputMode::Help => handle_help_key(key),
+        InputMode::Idle => handle_idle_key(key),
+        InputMode::Picker => handle_picker_key(key),
+        InputMode::ThemePicker => handle_theme_picker_key(key),
+        InputMode::Search => handle_search_key(key),
+        InputMode::Timeline => handle_timeline_key(key),
+        InputMode::Links => handle_links_key(key),
+        InputMode::Jobs => handle_jobs_key(key),
+        InputMode::Queue => handle_queue_key(key),
+        InputMode::Similar => handle_similar_key(key),
+        InputMode::Recent => handle_recent_key(key),
+        InputMode::Outputs => handle_outputs_key(key),
+        InputMode::Recordings => handle_recordings_key(key),
+        InputMode::Settings => handle_settings_key(key),
+        InputMode::VolumePopup => handle_volume_popup_key(key),
+        InputMode::Normal => keymap::lookup(&key).or(match key.code {
+            // Typing a station's number starts the jump prompt too
+            KeyCode::Char(c @ '1'..='9') => Some(Action::EnterJump(Some(c))),
+            _ => None,
+        }),
+    }
+}
+
+/// Scroll keys move through help; any other key closes it
+fn handle_help_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::PageDown | KeyCode::Char(' ') => Some(Action::OverlayPageDown),
+        KeyCode::PageUp => Some(Action::OverlayPageUp),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => Some(Action::CloseOverlay),
+    }
+}
+
+/// Any key answers the prompt
+fn handle_idle_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => Some(Action::CloseOverlay),
+    }
+}
+
+fn handle_picker_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Char(' ') => Some(Action::OverlayToggle),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('e') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_theme_picker_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_timeline_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Char('/') => Some(Action::OpenSearch),
+        KeyCode::Tab => Some(Action::TimelineScope),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('T') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_links_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter | KeyCode::Char('o') => Some(Action::OverlaySelect),
+        KeyCode::Char('y') => Some(Action::CopyLink),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_jobs_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Char('d') => Some(Action::CancelJob),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Z') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
 }
 
-pub fn handle_key(key: KeyEvent, show_help: bool) -> Option<Action> {
-    // If help is shown, any key closes it
-    if show_help {
-        return Some(Action::CloseOverlay);
+fn handle_queue_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Char('J') => Some(Action::MoveItemDown),
+        KeyCode::Char('K') => Some(Action::MoveItemUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Char('d') => Some(Action::Dequeue),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
     }
+}
 
+fn handle_similar_key(key: KeyEvent) -> Option<Action> {
     match key.code {
-        // Quit
-        KeyCode::Char('q') => Some(Action::Quit),
-        KeyCode::Esc => Some(Action::Quit),
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Char('A') => Some(Action::Enqueue),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('M') => Some(Action::CloseOverlay),
         KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_recent_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Char(c @ '1'..='9') => Some(Action::OverlayPick(c as usize - '0' as usize)),
+        KeyCode::Char('A') => Some(Action::Enqueue),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
 
-        // Playback
-        KeyCode::Char('p') | KeyCode::Char(' ') => Some(Action::TogglePlayPause),
-        KeyCode::Enter => Some(Action::SelectStation),
-
-        // Volume
-        KeyCode::Char('+') | KeyCode::Char('=') => Some(Action::VolumeUp),
-        KeyCode::Char('-') | KeyCode::Char('_') => Some(Action::VolumeDown),
-        KeyCode::Char('m') => Some(Action::ToggleMute),
-
-        // Navigation
-        KeyCode::Down | KeyCode::Char('j') => Some(Action::NextStation),
-        KeyCode::Up | KeyCode::Char('k') => Some(Action::PrevStation),
-        KeyCode::Char('g') => Some(Action::GoToTop),
-        KeyCode::Char('G') => Some(Action::GoToBottom),
-
-        // Actions
-        KeyCode::Char('f') => Some(Action::ToggleFavorite),
-        KeyCode::Char('s') => Some(Action::ToggleSortMode),
-        KeyCode::Char('t') => Some(Action::ToggleTheme),
-        KeyCode::Char('v') => Some(Action::CycleVisualization),
-        KeyCode::Char('V') => Some(Action::ToggleVisualizer),
-        KeyCode::Char('a') => Some(Action::ToggleArtwork),
-        KeyCode::Char('r') => Some(Action::ToggleHistory),
-        KeyCode::Char('>') | KeyCode::Char('.') => Some(Action::QualityUp),
-        KeyCode::Char('<') | KeyCode::Char(',') => Some(Action::QualityDown),
-        KeyCode::Char('R') => Some(Action::Refresh),
-        KeyCode::Char('?') => Some(Action::ToggleHelp),
+/// Arrows fine-tune the volume; other keys work as usual
+fn handle_volume_popup_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Left => Some(Action::VolumeFine(-1)),
+        KeyCode::Right => Some(Action::VolumeFine(1)),
+        KeyCode::Esc => Some(Action::CloseOverlay),
+        _ => handle_key(key, InputMode::Normal),
+    }
+}
 
+fn handle_outputs_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('O') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_recordings_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Enter => Some(Action::OverlaySelect),
+        KeyCode::Char('r') => Some(Action::RenameRecording),
+        KeyCode::Char('d') => Some(Action::DeleteRecording),
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('W') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_settings_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Down | KeyCode::Char('j') => Some(Action::OverlayDown),
+        KeyCode::Up | KeyCode::Char('k') => Some(Action::OverlayUp),
+        KeyCode::Left | KeyCode::Char('h') => Some(Action::MoveLeft),
+        KeyCode::Right | KeyCode::Char('l') | KeyCode::Enter | KeyCode::Char(' ') => {
+            Some(Action::MoveRight)
+        }
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('o') => Some(Action::CloseOverlay),
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        _ => None,
+    }
+}
+
+fn handle_search_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Some(Action::Quit),
+        KeyCode::Char(c) => Some(Action::SearchInput(c)),
+        KeyCode::Backspace => Some(Action::SearchBackspace),
+        KeyCode::Enter => Some(Action::SearchConfirm),
+        KeyCode::Esc => Some(Action::SearchCancel),
+        KeyCode::Down => Some(Action::NextStation),
+        KeyCode::Up => Some(Action::PrevStation),
         _ => None,
     }
 }
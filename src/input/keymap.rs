@@ -0,0 +1,528 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use super::handler::Action;
+
+/// A key, with whether Ctrl is held
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Key {
+    pub code: KeyCode,
+    pub ctrl: bool,
+}
+
+const fn key(c: char) -> Key {
+    Key {
+        code: KeyCode::Char(c),
+        ctrl: false,
+    }
+}
+
+const fn code(code: KeyCode) -> Key {
+    Key { code, ctrl: false }
+}
+
+const fn ctrl(code: KeyCode) -> Key {
+    Key { code, ctrl: true }
+}
+
+impl Key {
+    fn matches(&self, event: &KeyEvent) -> bool {
+        self.code == event.code && self.ctrl == event.modifiers.contains(KeyModifiers::CONTROL)
+    }
+
+    /// How the key is written in the help overlay
+    pub fn label(&self) -> String {
+        let name = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Up => "Up".to_string(),
+            KeyCode::Down => "Down".to_string(),
+            KeyCode::Left => "Left".to_string(),
+            KeyCode::Right => "Right".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::End => "End".to_string(),
+            other => format!("{:?}", other),
+        };
+        if self.ctrl {
+            format!("Ctrl+{}", name)
+        } else {
+            name
+        }
+    }
+}
+
+/// Keys for one action on the main screen, with the help section and text
+/// (both translation ids) it's listed under
+pub struct Binding {
+    pub keys: &'static [Key],
+    pub action: Action,
+    pub section: &'static str,
+    pub help: &'static str,
+}
+
+impl Binding {
+    /// The keys as written in the help overlay, e.g. "p / Space"
+    pub fn keys_label(&self) -> String {
+        self.keys
+            .iter()
+            .map(Key::label)
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+const PLAYBACK: &str = "help-section-playback";
+const NAVIGATION: &str = "help-section-navigation";
+const VOLUME: &str = "help-section-volume";
+const STATIONS: &str = "help-section-stations";
+const DISPLAY: &str = "help-section-display";
+const AUDIO: &str = "help-section-audio";
+
+/// Every key on the main screen. `handle_key` and the help overlay both
+/// read it, so help can't fall out of step with the keys.
+pub const BINDINGS: &[Binding] = &[
+    // Playback
+    Binding {
+        keys: &[key('p'), key(' ')],
+        action: Action::TogglePlayPause,
+        section: PLAYBACK,
+        help: "help-play-pause",
+    },
+    Binding {
+        keys: &[code(KeyCode::End)],
+        action: Action::JumpToLive,
+        section: PLAYBACK,
+        help: "help-jump-to-live",
+    },
+    Binding {
+        keys: &[code(KeyCode::Enter)],
+        action: Action::SelectStation,
+        section: PLAYBACK,
+        help: "help-play-selected",
+    },
+    Binding {
+        keys: &[key('x')],
+        action: Action::RandomStation,
+        section: PLAYBACK,
+        help: "help-random",
+    },
+    Binding {
+        keys: &[key('X')],
+        action: Action::ToggleSurf,
+        section: PLAYBACK,
+        help: "help-surf",
+    },
+    Binding {
+        keys: &[key('n')],
+        action: Action::NextInQueue,
+        section: PLAYBACK,
+        help: "help-next-in-queue",
+    },
+    Binding {
+        keys: &[key('z')],
+        action: Action::CycleSleepTimer,
+        section: PLAYBACK,
+        help: "help-sleep",
+    },
+    Binding {
+        keys: &[key('c')],
+        action: Action::Compare,
+        section: PLAYBACK,
+        help: "help-compare",
+    },
+    Binding {
+        keys: &[code(KeyCode::Tab)],
+        action: Action::SwapCompare,
+        section: PLAYBACK,
+        help: "help-swap-compare",
+    },
+    Binding {
+        keys: &[key('w')],
+        action: Action::ToggleRecording,
+        section: PLAYBACK,
+        help: "help-record",
+    },
+    Binding {
+        keys: &[key('W')],
+        action: Action::OpenRecordings,
+        section: PLAYBACK,
+        help: "help-recordings",
+    },
+    Binding {
+        keys: &[key('q'), ctrl(KeyCode::Char('c'))],
+        action: Action::Quit,
+        section: PLAYBACK,
+        help: "help-quit",
+    },
+    Binding {
+        keys: &[code(KeyCode::Esc)],
+        action: Action::Back,
+        section: PLAYBACK,
+        help: "help-back",
+    },
+    // Navigation
+    Binding {
+        keys: &[key('j'), code(KeyCode::Down)],
+        action: Action::NextStation,
+        section: NAVIGATION,
+        help: "help-down",
+    },
+    Binding {
+        keys: &[key('k'), code(KeyCode::Up)],
+        action: Action::PrevStation,
+        section: NAVIGATION,
+        help: "help-up",
+    },
+    Binding {
+        keys: &[key('g')],
+        action: Action::GoToTop,
+        section: NAVIGATION,
+        help: "help-top",
+    },
+    Binding {
+        keys: &[key('G')],
+        action: Action::GoToBottom,
+        section: NAVIGATION,
+        help: "help-bottom",
+    },
+    Binding {
+        keys: &[key('h'), code(KeyCode::Left)],
+        action: Action::MoveLeft,
+        section: NAVIGATION,
+        help: "help-left",
+    },
+    Binding {
+        keys: &[key('l'), code(KeyCode::Right)],
+        action: Action::MoveRight,
+        section: NAVIGATION,
+        help: "help-right",
+    },
+    Binding {
+        keys: &[key('b')],
+        action: Action::ToggleGrid,
+        section: NAVIGATION,
+        help: "help-grid",
+    },
+    // Volume
+    Binding {
+        keys: &[key('+'), key('=')],
+        action: Action::VolumeUp,
+        section: VOLUME,
+        help: "help-volume-up",
+    },
+    Binding {
+        keys: &[key('-'), key('_')],
+        action: Action::VolumeDown,
+        section: VOLUME,
+        help: "help-volume-down",
+    },
+    Binding {
+        keys: &[key('m')],
+        action: Action::ToggleMute,
+        section: VOLUME,
+        help: "help-mute",
+    },
+    Binding {
+        keys: &[key('%')],
+        action: Action::EnterVolume,
+        section: VOLUME,
+        help: "help-volume-exact",
+    },
+    // Stations
+    Binding {
+        keys: &[key(':')],
+        action: Action::EnterJump(None),
+        section: STATIONS,
+        help: "help-jump",
+    },
+    Binding {
+        keys: &[key('/')],
+        action: Action::OpenSearch,
+        section: STATIONS,
+        help: "help-search",
+    },
+    Binding {
+        keys: &[key('f')],
+        action: Action::ToggleFavorite,
+        section: STATIONS,
+        help: "help-favorite",
+    },
+    Binding {
+        keys: &[key('s')],
+        action: Action::ToggleSortMode,
+        section: STATIONS,
+        help: "help-sort",
+    },
+    Binding {
+        keys: &[key('C')],
+        action: Action::CycleSource,
+        section: STATIONS,
+        help: "help-source",
+    },
+    Binding {
+        keys: &[key('d')],
+        action: Action::ToggleDetailedList,
+        section: STATIONS,
+        help: "help-detailed",
+    },
+    Binding {
+        keys: &[key('T')],
+        action: Action::OpenTimeline,
+        section: STATIONS,
+        help: "help-timeline",
+    },
+    Binding {
+        keys: &[key('D')],
+        action: Action::OpenLinks,
+        section: STATIONS,
+        help: "help-links",
+    },
+    Binding {
+        keys: &[key('A')],
+        action: Action::Enqueue,
+        section: STATIONS,
+        help: "help-enqueue",
+    },
+    Binding {
+        keys: &[key('Q')],
+        action: Action::OpenQueue,
+        section: STATIONS,
+        help: "help-queue",
+    },
+    Binding {
+        keys: &[key('M')],
+        action: Action::OpenSimilar,
+        section: STATIONS,
+        help: "help-similar",
+    },
+    Binding {
+        keys: &[key('B')],
+        action: Action::OpenRecent,
+        section: STATIONS,
+        help: "help-recent",
+    },
+    Binding {
+        keys: &[key('F')],
+        action: Action::WatchArtist,
+        section: STATIONS,
+        help: "help-watch-artist",
+    },
+    Binding {
+        keys: &[ctrl(KeyCode::Char('f'))],
+        action: Action::WatchTrack,
+        section: STATIONS,
+        help: "help-watch-track",
+    },
+    Binding {
+        keys: &[key('y')],
+        action: Action::PlayWatched,
+        section: STATIONS,
+        help: "help-play-watched",
+    },
+    Binding {
+        keys: &[ctrl(KeyCode::Char('k'))],
+        action: Action::MoveItemUp,
+        section: STATIONS,
+        help: "help-favorite-up",
+    },
+    Binding {
+        keys: &[ctrl(KeyCode::Char('j'))],
+        action: Action::MoveItemDown,
+        section: STATIONS,
+        help: "help-favorite-down",
+    },
+    Binding {
+        keys: &[key('R')],
+        action: Action::Refresh,
+        section: STATIONS,
+        help: "help-refresh",
+    },
+    // Display
+    Binding {
+        keys: &[key('v'), key(']')],
+        action: Action::CycleVisualization,
+        section: DISPLAY,
+        help: "help-next-viz",
+    },
+    Binding {
+        keys: &[key('[')],
+        action: Action::PrevVisualization,
+        section: DISPLAY,
+        help: "help-prev-viz",
+    },
+    Binding {
+        keys: &[key('e')],
+        action: Action::OpenVisualizerPicker,
+        section: DISPLAY,
+        help: "help-pick-viz",
+    },
+    Binding {
+        keys: &[key('}')],
+        action: Action::GainUp,
+        section: DISPLAY,
+        help: "help-gain-up",
+    },
+    Binding {
+        keys: &[key('{')],
+        action: Action::GainDown,
+        section: DISPLAY,
+        help: "help-gain-down",
+    },
+    Binding {
+        keys: &[key(')')],
+        action: Action::FloorUp,
+        section: DISPLAY,
+        help: "help-floor-up",
+    },
+    Binding {
+        keys: &[key('(')],
+        action: Action::FloorDown,
+        section: DISPLAY,
+        help: "help-floor-down",
+    },
+    Binding {
+        keys: &[key('S')],
+        action: Action::CycleSmoothing,
+        section: DISPLAY,
+        help: "help-smoothing",
+    },
+    Binding {
+        keys: &[key('V')],
+        action: Action::ToggleVisualizer,
+        section: DISPLAY,
+        help: "help-show-viz",
+    },
+    Binding {
+        keys: &[key('u')],
+        action: Action::ToggleVuMeter,
+        section: DISPLAY,
+        help: "help-meters",
+    },
+    Binding {
+        keys: &[key('a')],
+        action: Action::ToggleArtwork,
+        section: DISPLAY,
+        help: "help-artwork",
+    },
+    Binding {
+        keys: &[key('r')],
+        action: Action::ToggleHistory,
+        section: DISPLAY,
+        help: "help-history",
+    },
+    Binding {
+        keys: &[key('t')],
+        action: Action::OpenThemePicker,
+        section: DISPLAY,
+        help: "help-theme",
+    },
+    Binding {
+        keys: &[key('L'), ctrl(KeyCode::Right)],
+        action: Action::GrowStationList,
+        section: DISPLAY,
+        help: "help-grow-station-list",
+    },
+    Binding {
+        keys: &[key('H'), ctrl(KeyCode::Left)],
+        action: Action::ShrinkStationList,
+        section: DISPLAY,
+        help: "help-shrink-station-list",
+    },
+    Binding {
+        keys: &[key('K'), ctrl(KeyCode::Up)],
+        action: Action::GrowVisualizer,
+        section: DISPLAY,
+        help: "help-grow-visualizer",
+    },
+    Binding {
+        keys: &[key('J'), ctrl(KeyCode::Down)],
+        action: Action::ShrinkVisualizer,
+        section: DISPLAY,
+        help: "help-shrink-visualizer",
+    },
+    // Audio
+    Binding {
+        keys: &[key('<'), key(',')],
+        action: Action::QualityDown,
+        section: AUDIO,
+        help: "help-quality-down",
+    },
+    Binding {
+        keys: &[key('>'), key('.')],
+        action: Action::QualityUp,
+        section: AUDIO,
+        help: "help-quality-up",
+    },
+    Binding {
+        keys: &[key('N')],
+        action: Action::ToggleDoNotDisturb,
+        section: AUDIO,
+        help: "help-dnd",
+    },
+    Binding {
+        keys: &[key('O')],
+        action: Action::OpenOutputs,
+        section: AUDIO,
+        help: "help-outputs",
+    },
+    Binding {
+        keys: &[key('o')],
+        action: Action::OpenSettings,
+        section: AUDIO,
+        help: "help-settings",
+    },
+    Binding {
+        keys: &[key('Z')],
+        action: Action::OpenJobs,
+        section: AUDIO,
+        help: "help-jobs",
+    },
+    Binding {
+        keys: &[key('i')],
+        action: Action::DismissHints,
+        section: AUDIO,
+        help: "help-dismiss-tips",
+    },
+    Binding {
+        keys: &[key('?')],
+        action: Action::ToggleHelp,
+        section: AUDIO,
+        help: "help-help",
+    },
+];
+
+/// Action bound to `event` on the main screen
+pub fn lookup(event: &KeyEvent) -> Option<Action> {
+    BINDINGS
+        .iter()
+        .find(|binding| binding.keys.iter().any(|key| key.matches(event)))
+        .map(|binding| binding.action.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_bound_once() {
+        let keys: Vec<Key> = BINDINGS
+            .iter()
+            .flat_map(|binding| binding.keys.iter().copied())
+            .collect();
+        for (i, key) in keys.iter().enumerate() {
+            assert!(
+                !keys[i + 1..].contains(key),
+                "{} is bound twice",
+                key.label()
+            );
+        }
+
+        let ctrl_c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(lookup(&ctrl_c), Some(Action::Quit));
+        let c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert_eq!(lookup(&c), Some(Action::Compare));
+        let shift_l = KeyEvent::new(KeyCode::Char('L'), KeyModifiers::SHIFT);
+        assert_eq!(lookup(&shift_l), Some(Action::GrowStationList));
+    }
+}
@@ -1,3 +1,5 @@
 pub mod handler;
+pub mod keymap;
 
-pub use handler::{handle_key, Action};
+pub use handler::{handle_key, Action, InputMode};
+pub use keymap::BINDINGS;
@@ -0,0 +1,145 @@
+//! Links for a station (its SomaFM page, playlist files and the donation
+//! page) and the two things the links popup does with them: open one in the
+//! browser or copy it to the clipboard.
+
+use anyhow::{anyhow, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::api::Channel;
+use crate::i18n::{t, tf};
+
+/// SomaFM is listener supported
+pub const DONATE_URL: &str = "https://somafm.com/support/";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Link {
+    pub label: String,
+    pub url: String,
+}
+
+impl Link {
+    fn new(label: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            url: url.into(),
+        }
+    }
+}
+
+/// The station's page and playlists, then the donation page. With no
+/// station there's only the donation page.
+pub fn station_links(channel: Option<&Channel>) -> Vec<Link> {
+    let mut links = Vec::new();
+    if let Some(channel) = channel {
+        links.push(Link::new(
+            t("link-station-page"),
+            format!("https://somafm.com/{}/", channel.id),
+        ));
+        links.extend(channel.playlists.iter().map(|playlist| {
+            Link::new(
+                tf(
+                    "link-playlist",
+                    &[
+                        ("format", &playlist.format.to_uppercase()),
+                        ("quality", &playlist.quality),
+                    ],
+                ),
+                playlist.url.clone(),
+            )
+        }));
+    }
+    links.push(Link::new(t("link-donate"), DONATE_URL));
+    links
+}
+
+/// Open `url` with the system's default handler
+pub fn open_in_browser(url: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = Command::new("open");
+        command.arg(url);
+        command
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("xdg-open");
+        command.arg(url);
+        command
+    };
+    #[cfg(windows)]
+    let mut command = {
+        // The empty argument is the window title `start` expects first
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", "", url]);
+        command
+    };
+
+    // Not waited on; browsers often keep running from the launching process
+    command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+        .map_err(|err| anyhow!("couldn't open a browser: {}", err))
+}
+
+/// Copy `text` to the clipboard with an OSC 52 escape, which the terminal
+/// handles, so it works over SSH too
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    let tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    stdout.write_all(osc52_sequence(text, tmux).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// OSC 52 "set clipboard" escape for `text`. Inside tmux it's wrapped to pass
+/// through to the outer terminal.
+fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52_sequence() {
+        assert_eq!(base64(b"somafm"), "c29tYWZt");
+        assert_eq!(base64(b"soma"), "c29tYQ==");
+        assert_eq!(
+            osc52_sequence("https://somafm.com/", false),
+            "\x1b]52;c;aHR0cHM6Ly9zb21hZm0uY29tLw==\x07"
+        );
+        assert_eq!(
+            osc52_sequence("a", true),
+            "\x1bPtmux;\x1b\x1b]52;c;YQ==\x07\x1b\\"
+        );
+    }
+}
@@ -1,136 +1,197 @@
+mod airplay;
 mod api;
 mod app;
 mod artwork;
+mod control;
+mod event;
+mod i18n;
 mod input;
+mod links;
+mod metrics;
+mod notify;
 mod player;
+mod profile;
+mod scheduler;
 mod storage;
+mod systemd;
 mod ui;
 mod visualizer;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    event::{DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture},
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
+use futures::StreamExt;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Layout},
     Terminal,
 };
-use std::io;
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::{mpsc, watch, Mutex};
+use tokio::sync::{watch, Mutex};
 use tokio::time;
 
-use api::{SomaFmClient, Song};
+use api::{
+    http, icy, Channel, LastFmClient, MusicBrainzClient, SomaFmClient, StationSource, Track,
+};
 use app::App;
-use artwork::ImageCache;
-use image::DynamicImage;
-use input::handle_key;
+use artwork::{decode_artwork, ImageCache};
+use event::{spawn_terminal_reader, AppEvent, EventBus, EventSender};
+use image::imageops::FilterType;
+use input::Action;
+use metrics::Metrics;
 use player::MpvController;
+use storage::{ConfigStore, Job, PanelConfig, PanelKind, PlayHistoryStore};
 use ui::{
-    init_picker, Header, HelpOverlay, NowPlaying, SongHistory, StationList, StatusBar, Visualizer,
+    asciify, has_quality_protocol, init_picker, max_artwork_size, Header, HelpOverlay, IdlePrompt,
+    ImageProtocol, JobsPopup, LinearView, LinksPopup, NowPlaying, OutputPicker, QueuePopup,
+    RecentPopup, RecordingsBrowser, SchedulePanel, SettingsScreen, SimilarPopup, SongHistory,
+    StationFooter, StationGrid, StationList, StatusBar, ThemePicker, TimelinePopup, TipBar,
+    Visualizer, VisualizerPicker, VolumePopup, VuMeter, STATION_FOOTER_HEIGHT, THUMBNAIL_SIZE,
 };
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct MetadataRequest {
+    /// Which station switch this request belongs to; echoed back in updates
+    generation: u64,
     channel_id: Option<String>,
+    /// Whether the station's songs are on SomaFM's songs API
+    songs_api: bool,
     image_url: Option<String>,
     show_artwork: bool,
+    /// Server the station is playing from
+    stream_url: Option<String>,
+    /// Track to look up on MusicBrainz, when that's enabled
+    track: Option<Track>,
+    /// Time between songs API polls, longer while the app is idle
+    song_poll: Duration,
 }
 
-enum AppUpdate {
-    Songs {
-        channel_id: String,
-        current_song: Option<Song>,
-        history: Vec<Song>,
-    },
-    StreamTitle {
-        channel_id: Option<String>,
-        title: Option<String>,
-    },
-    Artwork {
-        channel_id: String,
-        image: DynamicImage,
-        url: String,
-    },
+/// The largest artwork a station offers
+fn artwork_url(channel: &Channel) -> String {
+    channel
+        .xlimage
+        .as_ref()
+        .unwrap_or(&channel.largeimage)
+        .to_string()
 }
 
 fn build_metadata_request(app: &App) -> MetadataRequest {
     let (channel_id, image_url) = match app.current_channel() {
-        Some(channel) => {
-            let image_url = channel
-                .xlimage
-                .as_ref()
-                .unwrap_or(&channel.largeimage)
-                .to_string();
-            (Some(channel.id.clone()), Some(image_url))
-        }
+        Some(channel) => (
+            Some(channel.id.clone()),
+            Some(artwork_url(channel)).filter(|url| !url.is_empty()),
+        ),
         None => (None, None),
     };
 
     MetadataRequest {
+        generation: app.station_generation,
         channel_id,
+        songs_api: app
+            .current_channel()
+            .is_some_and(|channel| channel.source == StationSource::SomaFm),
         image_url,
         show_artwork: app.show_artwork,
+        stream_url: app.stream_server().map(str::to_string),
+        track: app
+            .config
+            .musicbrainz_enabled()
+            .then(|| app.current_track())
+            .flatten(),
+        song_poll: app.song_poll_interval(),
     }
 }
 
 async fn metadata_worker(
     mut req_rx: watch::Receiver<MetadataRequest>,
-    player: Arc<Mutex<MpvController>>,
-    update_tx: mpsc::UnboundedSender<AppUpdate>,
+    events: EventSender,
+    metrics: Arc<Metrics>,
 ) {
     let api_client = SomaFmClient::new();
     let image_cache = ImageCache::default();
-    let mut interval = time::interval(Duration::from_secs(10));
+    let mut req = req_rx.borrow_and_update().clone();
+    let mut last_poll = time::Instant::now();
+    let mut next_poll = last_poll;
     let mut last_artwork_url: Option<String> = None;
 
     loop {
         tokio::select! {
-            _ = interval.tick() => {}
+            _ = time::sleep_until(next_poll) => {}
             changed = req_rx.changed() => {
                 if changed.is_err() {
                     break;
                 }
+                let next = req_rx.borrow_and_update().clone();
+                // A new station or artwork setting is fetched straight away;
+                // anything else only moves the next poll
+                let refetch = (next.generation, &next.channel_id, &next.image_url, next.show_artwork)
+                    != (req.generation, &req.channel_id, &req.image_url, req.show_artwork);
+                req = next;
+                if !refetch {
+                    next_poll = last_poll + req.song_poll;
+                    continue;
+                }
             }
         }
+        last_poll = time::Instant::now();
+        next_poll = last_poll + req.song_poll;
 
-        let req = req_rx.borrow().clone();
         let Some(channel_id) = req.channel_id.clone() else {
             last_artwork_url = None;
             continue;
         };
 
-        if let Ok(Ok(songs)) =
-            time::timeout(Duration::from_secs(5), api_client.get_songs(&channel_id)).await
-        {
-            let current_song = songs.first().cloned();
-            let history = songs.into_iter().skip(1).take(5).collect();
-            let _ = update_tx.send(AppUpdate::Songs {
-                channel_id: channel_id.clone(),
-                current_song,
-                history,
-            });
+        // Other sources' stations only have the stream title
+        if req.songs_api {
+            match time::timeout(Duration::from_secs(5), api_client.get_songs(&channel_id)).await {
+                Ok(Ok(songs)) => {
+                    let current_song = songs.first().cloned();
+                    let history = songs.into_iter().skip(1).collect();
+                    let _ = events.send(AppEvent::Songs {
+                        generation: req.generation,
+                        channel_id: channel_id.clone(),
+                        current_song,
+                        history,
+                    });
+                }
+                _ => {
+                    metrics.api_errors.fetch_add(1, Ordering::Relaxed);
+                }
+            }
         }
 
         if req.show_artwork {
             if let Some(image_url) = req.image_url.clone() {
                 if last_artwork_url.as_deref() != Some(image_url.as_str()) {
                     last_artwork_url = Some(image_url.clone());
-                    if let Ok(Ok(bytes)) = time::timeout(
-                        Duration::from_secs(5),
-                        image_cache.get_or_fetch(&image_url, &channel_id),
-                    )
-                    .await
+                    if let Ok(Ok(bytes)) =
+                        time::timeout(Duration::from_secs(5), image_cache.get_or_fetch(&image_url))
+                            .await
                     {
-                        if let Ok(img) = image::load_from_memory(&bytes) {
-                            let _ = update_tx.send(AppUpdate::Artwork {
+                        // Decoding and scaling a large image takes a while;
+                        // keep it off the async threads
+                        let (max_width, max_height) = max_artwork_size();
+                        let decoded = tokio::task::spawn_blocking(move || {
+                            decode_artwork(&bytes, max_width, max_height)
+                        })
+                        .await;
+                        if let Ok(Ok((image, palette))) = decoded {
+                            let _ = events.send(AppEvent::Artwork {
+                                generation: req.generation,
                                 channel_id: channel_id.clone(),
-                                image: img,
+                                image,
                                 url: image_url,
+                                palette,
                             });
                         }
                     }
@@ -139,268 +200,1062 @@ async fn metadata_worker(
         } else {
             last_artwork_url = None;
         }
+    }
+}
 
-        if let Ok(mut locked) = player.try_lock() {
-            if locked.state.playing {
-                if let Ok(Ok(Some((artist, title)))) =
-                    time::timeout(Duration::from_millis(500), locked.get_metadata()).await
-                {
-                    if !title.is_empty() {
-                        let stream_title = if artist.is_empty() {
-                            title
-                        } else {
-                            format!("{} - {}", artist, title)
-                        };
-                        let _ = update_tx.send(AppUpdate::StreamTitle {
-                            channel_id: Some(channel_id.clone()),
-                            title: Some(stream_title),
-                        });
-                    }
+/// Follow the playing stream's title: from the stream's own ICY metadata
+/// when `icy` is on and the server sends it, otherwise by asking mpv
+async fn stream_title_worker(
+    mut req_rx: watch::Receiver<MetadataRequest>,
+    player: Arc<Mutex<MpvController>>,
+    events: EventSender,
+    icy: bool,
+) {
+    let client = http::client();
+
+    loop {
+        let req = req_rx.borrow_and_update().clone();
+        let station = (req.generation, req.stream_url.clone());
+        // Only a new station or server matters here, not artwork changes
+        let station_changed = async {
+            loop {
+                if req_rx.changed().await.is_err() {
+                    return false;
                 }
+                let req = req_rx.borrow_and_update();
+                if (req.generation, req.stream_url.clone()) != station {
+                    return true;
+                }
+            }
+        };
+        let send = |title: String| {
+            if let Some(channel_id) = &req.channel_id {
+                let _ = events.send(AppEvent::StreamTitle {
+                    generation: req.generation,
+                    channel_id: channel_id.clone(),
+                    title,
+                });
+            }
+        };
+        let follow = async {
+            let Some(url) = req.stream_url.as_deref() else {
+                return std::future::pending().await;
+            };
+            if icy {
+                icy::follow_titles(&client, url, send).await;
+            }
+            poll_mpv_title(&player, send).await
+        };
+
+        tokio::select! {
+            changed = station_changed => {
+                if !changed {
+                    break;
+                }
+            }
+            _ = follow => {}
+        }
+    }
+}
+
+/// Ask mpv for the stream title every few seconds, for streams without ICY
+/// metadata
+async fn poll_mpv_title(player: &Mutex<MpvController>, send: impl Fn(String)) {
+    let mut interval = time::interval(Duration::from_secs(10));
+    loop {
+        interval.tick().await;
+        let Ok(mut locked) = player.try_lock() else {
+            continue;
+        };
+        if let Ok(Ok(Some((artist, title)))) =
+            time::timeout(Duration::from_millis(500), locked.get_metadata()).await
+        {
+            if !title.is_empty() {
+                send(if artist.is_empty() {
+                    title
+                } else {
+                    format!("{} - {}", artist, title)
+                });
             }
         }
     }
 }
 
+/// Look up each new track's release on MusicBrainz
+async fn release_worker(mut req_rx: watch::Receiver<MetadataRequest>, events: EventSender) {
+    let mut client = MusicBrainzClient::new();
+    let mut last_track: Option<Track> = None;
+
+    while req_rx.changed().await.is_ok() {
+        let track = req_rx.borrow_and_update().track.clone();
+        if track == last_track {
+            continue;
+        }
+        last_track = track.clone();
+        let Some(track) = track else {
+            continue;
+        };
+        if let Ok(Ok(info)) = time::timeout(Duration::from_secs(15), client.lookup(&track)).await {
+            let _ = events.send(AppEvent::Release { track, info });
+        }
+    }
+}
+
+/// Download artwork for favorite stations into the image cache, so switching
+/// between them doesn't wait on the network
+async fn preload_artwork_worker(image_urls: Vec<String>) {
+    let image_cache = ImageCache::default();
+    let image_cache = &image_cache;
+
+    futures::stream::iter(image_urls)
+        .for_each_concurrent(2, |image_url| async move {
+            let _ = time::timeout(
+                Duration::from_secs(20),
+                image_cache.get_or_fetch(&image_url),
+            )
+            .await;
+        })
+        .await;
+}
+
+/// Fetch the small station images for the grid browser, a few at a time
+async fn thumbnail_worker(channels: Vec<(String, String)>, events: EventSender) {
+    let image_cache = ImageCache::default();
+    let image_cache = &image_cache;
+
+    futures::stream::iter(channels)
+        .for_each_concurrent(4, |(channel_id, image_url)| {
+            let events = events.clone();
+            async move {
+                let Ok(Ok(bytes)) = time::timeout(
+                    Duration::from_secs(10),
+                    image_cache.get_or_fetch(&image_url),
+                )
+                .await
+                else {
+                    return;
+                };
+                if let Ok(img) = image::load_from_memory(&bytes) {
+                    let image = img
+                        .resize_exact(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Triangle)
+                        .to_rgb8();
+                    let _ = events.send(AppEvent::Thumbnail { channel_id, image });
+                }
+            }
+        })
+        .await;
+}
+
+/// Refetch the channel list periodically so listener counts stay current
+async fn channels_worker(events: EventSender, metrics: Arc<Metrics>) {
+    let api_client = SomaFmClient::new();
+
+    // The UI starts without stations; keep trying until the first list arrives
+    loop {
+        let error = match time::timeout(Duration::from_secs(10), api_client.get_channels()).await {
+            Ok(Ok(channels)) => {
+                if events.send(AppEvent::Channels(channels)).is_err() {
+                    return;
+                }
+                break;
+            }
+            Ok(Err(err)) => err.to_string(),
+            Err(_) => "timed out".to_string(),
+        };
+        metrics.api_errors.fetch_add(1, Ordering::Relaxed);
+        if events.send(AppEvent::ChannelsFailed(error)).is_err() {
+            return;
+        }
+        time::sleep(Duration::from_secs(5)).await;
+    }
+
+    let mut interval = time::interval(Duration::from_secs(60));
+    // The first tick completes immediately, right after the first list
+    interval.tick().await;
+
+    loop {
+        interval.tick().await;
+        let event = match time::timeout(Duration::from_secs(10), api_client.get_channels()).await {
+            Ok(Ok(channels)) => AppEvent::Channels(channels),
+            Ok(Err(err)) => AppEvent::ChannelsFailed(err.to_string()),
+            Err(_) => AppEvent::ChannelsFailed("timed out".to_string()),
+        };
+        if matches!(event, AppEvent::ChannelsFailed(_)) {
+            metrics.api_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        if events.send(event).is_err() {
+            break;
+        }
+    }
+}
+
 async fn audio_worker(
     player: Arc<Mutex<MpvController>>,
-    audio_tx: watch::Sender<Option<(f32, f32)>>,
+    events: EventSender,
+    metrics: Arc<Metrics>,
+    poll: Duration,
 ) {
-    let mut interval = time::interval(Duration::from_millis(50));
+    let mut interval = time::interval(poll);
+    // Polls between checks of the slower-changing stats, about a second
+    let slow_every = (1000 / poll.as_millis().max(1)).max(1) as u32;
+    let mut last_position = 0;
+    let mut last_levels = None;
+    let mut last_behind = None;
+    let mut last_bitrate = None;
+    let mut last_health = None;
+    let mut last_devices: Option<Vec<(String, String)>> = None;
+    let mut ticks: u32 = 0;
 
     loop {
         interval.tick().await;
+        ticks = ticks.wrapping_add(1);
 
         let mut locked = match player.try_lock() {
             Ok(locked) => locked,
             Err(_) => continue,
         };
 
-        if !locked.state.playing || locked.state.paused {
-            let _ = audio_tx.send(None);
-            continue;
+        let levels = if !locked.state.playing || locked.state.paused {
+            None
+        } else {
+            locked.get_audio_stats().await
+        };
+        // The timeshift indicator only shows whole seconds, and device
+        // changes don't need noticing any faster
+        let (behind, bitrate, health, devices, position) = if ticks.is_multiple_of(slow_every) {
+            let devices = if locked.is_playing() {
+                locked.audio_devices().await.ok()
+            } else {
+                // Only changes during playback count
+                last_devices = None;
+                None
+            };
+            (
+                locked.update_behind_live().await.ok(),
+                locked.update_bitrate().await.ok(),
+                Some(locked.update_stream_health().await),
+                devices,
+                locked.stream_position().await.ok().flatten(),
+            )
+        } else {
+            (None, None, None, None, None)
+        };
+        drop(locked);
+
+        if let Some(position) = position {
+            // A smaller position means a new stream started from zero
+            let read = position.checked_sub(last_position).unwrap_or(position);
+            metrics.bytes_streamed.fetch_add(read, Ordering::Relaxed);
+            last_position = position;
         }
 
-        let _ = audio_tx.send(locked.get_audio_stats().await);
+        if let Some(devices) = devices {
+            let removed: Vec<String> = last_devices
+                .iter()
+                .flatten()
+                .filter(|device| !devices.contains(device))
+                .map(|(name, description)| {
+                    if description.is_empty() {
+                        name.clone()
+                    } else {
+                        description.clone()
+                    }
+                })
+                .collect();
+            last_devices = Some(devices);
+            if !removed.is_empty() && events.send(AppEvent::AudioDevicesRemoved(removed)).is_err() {
+                break;
+            }
+        }
+
+        if let Some(behind) = behind.filter(|&behind| behind != last_behind) {
+            last_behind = behind;
+            if events.send(AppEvent::BehindLive(behind)).is_err() {
+                break;
+            }
+        }
+
+        if let Some(bitrate) = bitrate.filter(|&bitrate| bitrate != last_bitrate) {
+            last_bitrate = bitrate;
+            if events.send(AppEvent::Bitrate(bitrate)).is_err() {
+                break;
+            }
+        }
+
+        if let Some(health) = health.filter(|&health| health != last_health) {
+            last_health = health;
+            if events.send(AppEvent::StreamHealth(health)).is_err() {
+                break;
+            }
+        }
+
+        // Only wake the UI when the levels actually moved
+        if levels != last_levels {
+            last_levels = levels;
+            if events.send(AppEvent::AudioLevels(levels)).is_err() {
+                break;
+            }
+        }
     }
 }
 
+/// `vibecast export-profile [FILE]` and `import-profile [FILE]`
+fn profile_command(command: &str, archive: &Path) -> Result<()> {
+    if command == "export-profile" {
+        let files = profile::export(archive)?;
+        println!("Saved {} files to {}", files.len(), archive.display());
+    } else {
+        let files = profile::import(archive)?;
+        for file in &files {
+            println!("  {}", file);
+        }
+        println!("Restored {} files from {}", files.len(), archive.display());
+    }
+    Ok(())
+}
+
+/// `vibecast import-lastfm <user>`: add a Last.fm profile's scrobbles to the
+/// local play counts. Later runs only fetch scrobbles newer than the last one
+/// imported.
+async fn import_lastfm(user: &str) -> Result<()> {
+    let mut config = ConfigStore::load()?;
+    http::init(config.network());
+    let api_key = std::env::var("LASTFM_API_KEY")
+        .ok()
+        .or_else(|| config.config.lastfm.api_key.clone())
+        .ok_or_else(|| {
+            anyhow!("set LASTFM_API_KEY or lastfm.api_key in the config to a Last.fm API key")
+        })?;
+    let from = config.config.lastfm.imported_until + 1;
+
+    let client = LastFmClient::new(&api_key);
+    let scrobbles = client
+        .scrobbles_since(user, from, |page, total| {
+            eprint!("\rFetching scrobbles: page {}/{}", page, total);
+        })
+        .await?;
+    eprintln!();
+
+    let mut history = PlayHistoryStore::load()?;
+    for scrobble in &scrobbles {
+        let track = Track {
+            channel_id: String::new(),
+            artist: scrobble.artist.clone(),
+            title: scrobble.title.clone(),
+        };
+        history.record(&track, scrobble.played_at);
+    }
+    history.save()?;
+
+    if let Some(last) = scrobbles.last() {
+        config.config.lastfm.imported_until = last.played_at;
+        config.save()?;
+    }
+    println!("Imported {} scrobbles from {}", scrobbles.len(), user);
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let [command, user] = args.as_slice() {
+        if command == "import-lastfm" {
+            return import_lastfm(user).await;
+        }
+    }
+    if let Some(command @ ("export-profile" | "import-profile")) = args.first().map(String::as_str)
+    {
+        let archive = PathBuf::from(args.get(1).map_or(profile::DEFAULT_ARCHIVE, String::as_str));
+        return profile_command(command, &archive);
+    }
+    if args.first().is_some_and(|command| command == "ctl") {
+        return control::run(&args[1..]).await;
+    }
+    let resume = args.iter().any(|arg| arg == "--resume");
+    if args.first().is_some_and(|command| command == "daemon") {
+        let mut app = App::new();
+        app.resume_session(resume).await?;
+        // Nothing is drawn, so there's no artwork to fetch or animation to run
+        app.show_artwork = false;
+        app.focused = false;
+        app.headless = true;
+        let mut bus = EventBus::new();
+        let res = run_daemon(&mut app, &mut bus).await;
+        control::remove_socket();
+        return res;
+    }
+
+    let no_artwork = std::env::args().skip(1).any(|arg| arg == "--no-artwork");
+    let image_protocol = if no_artwork {
+        Some(ImageProtocol::None)
+    } else {
+        ConfigStore::load()
+            .ok()
+            .and_then(|config| config.config.ui.image_protocol)
+    };
+
     // Initialize image picker before entering TUI to avoid escape sequence leaks
-    init_picker();
+    init_picker(image_protocol);
+
+    // Create the app before taking over the terminal, so a failed resume
+    // leaves the shell as it was
+    let mut app = App::new();
+    app.resume_session(resume).await?;
 
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableFocusChange
+    )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // Create app and run
-    let mut app = App::new();
-    let res = run_app(&mut terminal, &mut app).await;
+    if std::env::args().skip(1).any(|arg| arg == "--screen-reader") {
+        app.screen_reader = true;
+    }
+    // Nothing could show the artwork, so don't fetch it
+    if image_protocol == Some(ImageProtocol::None) {
+        app.show_artwork = false;
+    }
+    let frame_stats = std::env::args().skip(1).any(|arg| arg == "--frame-stats");
+    // Save the terminal's own title on its title stack (xterm and most
+    // others) so it can be put back on exit
+    let sets_title = app.config.title_template().is_some();
+    if sets_title {
+        terminal.backend_mut().write_all(b"\x1b[22;0t")?;
+    }
+    let mut bus = EventBus::new();
+    let res = run_app(&mut terminal, &mut app, &mut bus).await;
+    control::remove_socket();
 
     // Restore terminal
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
-        DisableMouseCapture
+        DisableMouseCapture,
+        DisableFocusChange
     )?;
+    if sets_title {
+        terminal.backend_mut().write_all(b"\x1b[23;0t")?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = res {
         eprintln!("Error: {}", err);
     }
 
+    if frame_stats {
+        eprintln!("{}", app.frame_stats);
+        eprintln!("{}", bus.stats());
+    }
+
+    Ok(())
+}
+
+/// Start the background workers shared by the TUI and the daemon, returning
+/// the channel that tells the metadata worker what to fetch
+fn spawn_workers(app: &mut App, bus: &EventBus) -> watch::Sender<MetadataRequest> {
+    app.connect_events(bus.sender());
+    let (metadata_tx, metadata_rx) = watch::channel(build_metadata_request(app));
+
+    tokio::spawn(metadata_worker(
+        metadata_rx.clone(),
+        bus.sender(),
+        app.metrics.clone(),
+    ));
+    if app.config.musicbrainz_enabled() {
+        tokio::spawn(release_worker(metadata_rx.clone(), bus.sender()));
+    }
+    tokio::spawn(stream_title_worker(
+        metadata_rx,
+        app.player.clone(),
+        bus.sender(),
+        app.config.icy_metadata(),
+    ));
+    tokio::spawn(audio_worker(
+        app.player.clone(),
+        bus.sender(),
+        app.metrics.clone(),
+        app.config.audio_poll_interval(),
+    ));
+    tokio::spawn(channels_worker(bus.sender(), app.metrics.clone()));
+    metadata_tx
+}
+
+/// Resolves on Ctrl-C, or SIGTERM where there is one
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        if let Ok(mut terminate) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = terminate.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
+
+/// Headless run for `vibecast daemon`: nothing is drawn, `vibecast ctl`
+/// drives playback, and Ctrl-C or SIGTERM stops it
+async fn run_daemon(app: &mut App, bus: &mut EventBus) -> Result<()> {
+    let metadata_tx = spawn_workers(app, bus);
+    control::listen(bus.sender()).await?;
+    if let Some(port) = app.config.metrics_port() {
+        metrics::serve(app.metrics.clone(), port).await?;
+    }
+    systemd::notify("READY=1");
+
+    let mut last_tick = Instant::now();
+    let mut last_request = metadata_tx.borrow().clone();
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
+    while !app.should_quit {
+        let timeout = app.tick_rate().saturating_sub(last_tick.elapsed());
+        let event = tokio::select! {
+            biased;
+            _ = &mut shutdown => {
+                systemd::notify("STOPPING=1");
+                return app.handle_action(Action::Quit).await;
+            }
+            _ = time::sleep(timeout) => {
+                last_tick = Instant::now();
+                AppEvent::Tick
+            }
+            Some(event) = bus.next() => event,
+        };
+        app.on_event(event).await?;
+        while let Some(event) = bus.try_next() {
+            app.on_event(event).await?;
+        }
+
+        let next_request = build_metadata_request(app);
+        if next_request != last_request {
+            let _ = metadata_tx.send(next_request.clone());
+            last_request = next_request;
+        }
+    }
     Ok(())
 }
 
 async fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    bus: &mut EventBus,
 ) -> Result<()> {
-    // Initialize app
-    app.init().await?;
-
-    let initial_request = build_metadata_request(app);
-    let (metadata_tx, metadata_rx) = watch::channel(initial_request.clone());
-    let (update_tx, mut update_rx) = mpsc::unbounded_channel();
-    let (audio_tx, mut audio_rx) = watch::channel::<Option<(f32, f32)>>(None);
-
-    tokio::spawn(metadata_worker(metadata_rx, app.player.clone(), update_tx));
-    tokio::spawn(audio_worker(app.player.clone(), audio_tx));
+    // Only sessions in the terminal count towards the new-user tips
+    app.show_hints = app.config.begin_hint_session();
+    if app.show_hints {
+        let _ = app.config.save();
+    }
+    let metadata_tx = spawn_workers(app, bus);
+    spawn_terminal_reader(bus.sender());
+    if let Err(err) = control::listen(bus.sender()).await {
+        app.flash(i18n::tf("msg-control-unavailable", &[("error", &err)]));
+    }
 
-    let tick_rate = Duration::from_millis(16); // ~60fps for smooth visualizer
     let mut last_tick = Instant::now();
-    let mut last_request = initial_request;
+    let mut last_request = metadata_tx.borrow().clone();
+    // Stations whose grid thumbnail has been asked for
+    let mut thumbnails_requested = HashSet::new();
+    let mut artwork_preloaded = false;
+    let mut last_title = None;
 
     loop {
-        while let Ok(update) = update_rx.try_recv() {
-            match update {
-                AppUpdate::Songs {
-                    channel_id,
-                    current_song,
-                    history,
-                } => {
-                    if app.current_channel().map(|c| c.id.as_str()) == Some(channel_id.as_str()) {
-                        app.current_song = current_song;
-                        app.song_history = history;
-                    }
+        let mut list_state = app.list_state.clone();
+
+        // Draw UI, skipping frames where nothing visible changed
+        if !app.dirty {
+            app.frame_stats.record_skip();
+        } else {
+            let draw_started = Instant::now();
+            terminal.draw(|f| {
+                let area = f.area();
+                let theme = &app.theme;
+
+                if app.screen_reader {
+                    let status = app.status_text();
+                    let track = app.now_playing_text();
+                    let selected = app.selected_channel().map(|channel| {
+                        (
+                            app.list_state.selected().unwrap_or(0),
+                            app.catalog.sorted().len(),
+                            channel,
+                            app.favorites.is_favorite(&channel.id),
+                        )
+                    });
+                    let view = LinearView::new(
+                        &status,
+                        app.current_channel(),
+                        track.as_deref(),
+                        selected,
+                        app.announcement.as_deref(),
+                        app.show_help,
+                        theme,
+                    );
+                    f.render_widget(view, area);
+                    f.set_cursor_position((area.x, area.y));
+                    return;
                 }
-                AppUpdate::StreamTitle { channel_id, title } => {
-                    if channel_id.as_deref() == app.current_channel().map(|c| c.id.as_str()) {
-                        if let Some(title) = title {
-                            app.stream_title = Some(title);
-                        }
-                    }
+
+                // Main layout, with a row for the new-user tip when there is one
+                let tip = app.onboarding_hint();
+                let chunks = Layout::vertical([
+                    Constraint::Length(3), // Header
+                    Constraint::Min(10),   // Main content
+                    Constraint::Length(tip.is_some() as u16),
+                    Constraint::Length(1), // Status bar
+                ])
+                .split(area);
+                if let Some(tip) = tip {
+                    f.render_widget(
+                        TipBar::new(i18n::t(tip), theme.widget("tip_bar")),
+                        chunks[2],
+                    );
                 }
-                AppUpdate::Artwork {
-                    channel_id,
-                    image,
-                    url,
-                } => {
-                    if app.show_artwork
-                        && app.current_channel().map(|c| c.id.as_str()) == Some(channel_id.as_str())
+
+                // Header
+                let station_name = app
+                    .current_channel()
+                    .map(|c| c.title.as_str())
+                    .or(app.playing_recording.as_deref());
+                let clock = chrono::Local::now().format("%H:%M").to_string();
+                let header = Header::new(
+                    station_name,
+                    &clock,
+                    app.session_timer.elapsed(),
+                    theme.widget("header"),
+                )
+                .connectivity(app.api_reachable, app.playback_state.stream_health);
+                f.render_widget(header, chunks[0]);
+
+                // Grid browser takes over the whole content area
+                if app.show_grid {
+                    app.grid_columns = StationGrid::columns_for(chunks[1].width);
+                    let grid = StationGrid::new(
+                        &app.catalog,
+                        &app.thumbnails,
+                        app.favorites.favorites(),
+                        app.current_channel().map(|c| c.id.as_str()),
+                        list_state.selected().unwrap_or(0),
+                        theme.widget("station_list"),
+                    );
+                    f.render_widget(grid, chunks[1]);
+                } else {
+                    // Main content - split horizontally
+                    let layout = app.config.layout();
+                    let list_percent = layout.station_list_percent();
+                    let content_chunks = Layout::horizontal([
+                        Constraint::Percentage(list_percent),       // Station list
+                        Constraint::Percentage(100 - list_percent), // Right panel
+                    ])
+                    .split(chunks[1]);
+
+                    // Station list - only the rows in view are built
+                    let current_station_id = app.current_channel().map(|c| c.id.as_str());
+                    let station_list = StationList::new(
+                        &app.catalog,
+                        app.favorites.favorites(),
+                        &app.listener_trends,
+                        current_station_id,
+                        true,
+                        app.detailed_list,
+                        theme.widget("station_list"),
+                    )
+                    .now_playing(&app.song_previews)
+                    .search(&app.search_query, app.searching)
+                    .sort_label(app.sort_mode.label())
+                    .source_label(app.catalog.source_filter().map(|source| source.label()))
+                    .badges(app.catalog.sources().len() > 1)
+                    .standby(app.comparison.as_ref().map(|c| c.standby_id.as_str()))
+                    .loading(
+                        app.channels_loading
+                            .then_some((app.frame, app.channels_error.as_deref())),
+                    );
+                    // The highlighted station's description goes below the
+                    // list when there's room for both
+                    let mut list_area = content_chunks[0];
+                    if let Some(channel) = app
+                        .selected_channel()
+                        .filter(|_| list_area.height >= 4 * STATION_FOOTER_HEIGHT)
                     {
-                        app.artwork_state.set_image(image, &url);
+                        let [list, footer] = Layout::vertical([
+                            Constraint::Fill(1),
+                            Constraint::Length(STATION_FOOTER_HEIGHT),
+                        ])
+                        .areas(list_area);
+                        list_area = list;
+                        f.render_widget(
+                            StationFooter::new(channel, theme.widget("station_footer"))
+                                .listened(app.listening_summary(&channel.id)),
+                            footer,
+                        );
                     }
-                }
-            }
-        }
-
-        if audio_rx.has_changed().unwrap_or(false) {
-            app.audio_levels = *audio_rx.borrow_and_update();
-        }
+                    f.render_stateful_widget(station_list, list_area, &mut list_state);
+                    app.list_rows = StationList::rows_for(list_area.height, app.detailed_list);
 
-        let mut list_state = app.list_state.clone();
+                    // Right panel - configured panels stacked vertically, skipping hidden ones
+                    let show_history = app.show_history && !app.song_history.is_empty();
+                    let schedule = app.upcoming_recordings();
+                    let mut panels: Vec<_> = layout
+                        .panels
+                        .iter()
+                        .filter(|panel| match panel.kind {
+                            PanelKind::NowPlaying => panel.enabled,
+                            PanelKind::History => show_history,
+                            PanelKind::Visualizer => app.show_visualizer,
+                            PanelKind::VuMeter => app.show_vu_meter,
+                            PanelKind::Schedule => panel.enabled,
+                        })
+                        .cloned()
+                        .collect();
+                    // Level meters toggled on without a configured slot go at the bottom
+                    if app.show_vu_meter && layout.panel(PanelKind::VuMeter).is_none() {
+                        panels.push(PanelConfig {
+                            kind: PanelKind::VuMeter,
+                            height: Some(5),
+                            enabled: true,
+                        });
+                    }
+                    // Likewise scheduled recordings, once there are any
+                    if !schedule.is_empty() && layout.panel(PanelKind::Schedule).is_none() {
+                        panels.push(PanelConfig {
+                            kind: PanelKind::Schedule,
+                            height: Some(schedule.len().min(4) as u16 + 2),
+                            enabled: true,
+                        });
+                    }
+                    let right_chunks =
+                        Layout::vertical(panels.iter().map(|panel| match panel.height {
+                            Some(height) => Constraint::Length(height),
+                            None => Constraint::Min(8),
+                        }))
+                        .split(content_chunks[1]);
 
-        // Draw UI
-        terminal.draw(|f| {
-            let area = f.area();
-            let theme = &app.theme;
-
-            // Main layout
-            let chunks = Layout::vertical([
-                Constraint::Length(3), // Header
-                Constraint::Min(10),   // Main content
-                Constraint::Length(1), // Status bar
-            ])
-            .split(area);
-
-            // Header
-            let station_name = app.current_channel().map(|c| c.title.as_str());
-            let header = Header::new(station_name, theme);
-            f.render_widget(header, chunks[0]);
-
-            // Main content - split horizontally
-            let content_chunks = Layout::horizontal([
-                Constraint::Percentage(35), // Station list
-                Constraint::Percentage(65), // Right panel
-            ])
-            .split(chunks[1]);
-
-            // Station list - render sorted channels
-            let sorted_channels: Vec<_> = app.sorted_channels().into_iter().cloned().collect();
-            let current_station_id = app.current_channel().map(|c| c.id.as_str());
-            let station_list = StationList::new(
-                &sorted_channels,
-                app.favorites.favorites(),
-                current_station_id,
-                true,
-                theme,
-            );
-            f.render_stateful_widget(station_list, content_chunks[0], &mut list_state);
-
-            // Right panel - split vertically for now playing, history, and visualizer
-            let show_history = app.show_history && !app.song_history.is_empty();
-            let right_chunks = Layout::vertical([
-                Constraint::Min(8),                                           // Now playing
-                Constraint::Length(if show_history { 8 } else { 0 }),         // Song history
-                Constraint::Length(if app.show_visualizer { 12 } else { 0 }), // Visualizer (doubled)
-            ])
-            .split(content_chunks[1]);
-
-            // Now playing
-            let current_channel = app.current_channel().cloned();
-            let current_song = app.current_song.clone();
-            let stream_title = app.stream_title.clone();
-            let is_paused = app.playback_state.paused;
-
-            let now_playing = NowPlaying::new(
-                current_channel.as_ref(),
-                current_song.as_ref(),
-                stream_title.as_deref(),
-                is_paused,
-                app.audio_quality,
-                app.show_artwork,
-                theme,
-            );
-            f.render_stateful_widget(now_playing, right_chunks[0], &mut app.artwork_state);
-
-            // Song history panel
-            if show_history {
-                let song_history = SongHistory::new(&app.song_history, theme);
-                f.render_widget(song_history, right_chunks[1]);
-            }
+                    for (panel, &panel_area) in panels.iter().zip(right_chunks.iter()) {
+                        match panel.kind {
+                            PanelKind::NowPlaying => {
+                                let current_channel = app.current_channel().cloned();
+                                let current_song = app.audible_song().cloned();
+                                let stream_title = app.stream_title.clone();
+                                let is_paused = app.playback_state.paused;
+                                let release = app.release_info().cloned();
 
-            // Visualizer
-            if app.show_visualizer {
-                let visualizer = Visualizer::new(
-                    &app.spectrum_data,
-                    app.playback_state.playing,
-                    app.playback_state.paused,
-                    app.visualization_mode,
-                    app.frame,
-                    theme,
-                );
-                f.render_widget(visualizer, right_chunks[2]);
-            }
+                                let now_playing = NowPlaying::new(
+                                    current_channel.as_ref(),
+                                    current_song.as_ref(),
+                                    stream_title.as_deref(),
+                                    is_paused,
+                                    app.audio_quality,
+                                    app.show_artwork && has_quality_protocol(),
+                                    theme.widget("now_playing"),
+                                )
+                                .shimmer(
+                                    (app.artwork_state.is_loading() && !app.reduced_motion)
+                                        .then_some(app.frame),
+                                )
+                                .release(release.as_ref())
+                                .progress(app.track_progress());
+                                f.render_stateful_widget(
+                                    now_playing,
+                                    panel_area,
+                                    &mut app.artwork_state,
+                                );
+                            }
+                            PanelKind::History => {
+                                let play_counts = app.history_play_counts();
+                                let song_history =
+                                    SongHistory::new(&app.song_history, theme.widget("history"))
+                                        .play_counts(&play_counts);
+                                f.render_widget(song_history, panel_area);
+                            }
+                            PanelKind::Visualizer => {
+                                let visualizer = Visualizer::new(
+                                    &app.spectrum_data,
+                                    app.playback_state.playing,
+                                    app.playback_state.paused,
+                                    app.visualization_mode,
+                                    app.frame,
+                                    app.reduced_motion,
+                                    theme.widget("visualizer"),
+                                )
+                                .history(&app.spectrum_history)
+                                .plugins(&app.visualizer_plugins);
+                                f.render_widget(visualizer, panel_area);
+                            }
+                            PanelKind::VuMeter => {
+                                f.render_stateful_widget(
+                                    VuMeter::new(theme.widget("vu_meter")),
+                                    panel_area,
+                                    &mut app.vu_meter,
+                                );
+                            }
+                            PanelKind::Schedule => {
+                                f.render_widget(
+                                    SchedulePanel::new(&schedule, theme.widget("schedule")),
+                                    panel_area,
+                                );
+                            }
+                        }
+                    }
+                }
 
-            // Status bar
-            let status_bar = StatusBar::new(
-                app.playback_state.playing,
-                app.playback_state.paused,
-                if app.is_muted {
+                // Status bar
+                // The volume prompt takes the message slot while it's open
+                let volume_prompt = app
+                    .volume_input
+                    .as_ref()
+                    .map(|input| i18n::tf("prompt-volume", &[("input", &format!("{}▏", input))]))
+                    .or_else(|| {
+                        app.jump_input.as_ref().map(|input| {
+                            i18n::tf("prompt-jump", &[("input", &format!("{}▏", input))])
+                        })
+                    });
+                let volume = if app.is_muted {
                     0
                 } else {
                     app.playback_state.volume
-                },
-                app.theme.name,
-                theme,
-            );
-            f.render_widget(status_bar, chunks[2]);
-
-            // Help overlay
-            if app.show_help {
-                f.render_widget(HelpOverlay::new(theme), area);
+                };
+                let status_bar = StatusBar::new(
+                    app.playback_state.playing,
+                    app.playback_state.paused,
+                    volume,
+                    &app.theme.name,
+                    theme.widget("status_bar"),
+                )
+                .raw_volume(app.config.volume_curve().apply(volume))
+                .message(
+                    volume_prompt
+                        .as_deref()
+                        .or(app.message.as_ref().map(|(message, _)| message.as_str())),
+                )
+                .surfing(app.jobs.next(&Job::Surf).is_some())
+                .queued(app.queue.len())
+                .recording(app.playback_state.recording.is_some())
+                .behind_live(app.playback_state.behind_live)
+                .quality(app.audio_quality.label())
+                .bitrate(app.playback_state.bitrate)
+                .template(app.config.status_template());
+                f.render_widget(status_bar, chunks[3]);
+
+                // Visualization picker
+                if let Some(selected) = app.visualizer_picker {
+                    let entries = app.visualizer_entries();
+                    let current = app
+                        .visualization_modes()
+                        .iter()
+                        .position(|&m| m == app.visualization_mode)
+                        .unwrap_or(0);
+                    f.render_widget(
+                        VisualizerPicker::new(&entries, selected, current, theme),
+                        area,
+                    );
+                }
+
+                // Theme picker
+                if let Some(view) = &app.theme_picker {
+                    let themes: Vec<_> = app
+                        .themes
+                        .names()
+                        .iter()
+                        .map(|name| app.themes.resolve(name).for_terminal())
+                        .collect();
+                    let current = themes
+                        .iter()
+                        .position(|t| t.name == view.original)
+                        .unwrap_or(0);
+                    f.render_widget(
+                        ThemePicker::new(&themes, view.selected, current, theme),
+                        area,
+                    );
+                }
+
+                // Recordings browser
+                if let Some(view) = &app.recordings_view {
+                    f.render_widget(
+                        RecordingsBrowser::new(&view.recordings, view.selected, theme)
+                            .renaming(view.renaming.as_deref())
+                            .confirm_delete(view.confirm_delete),
+                        area,
+                    );
+                }
+
+                // Settings screen
+                if let Some(selected) = app.settings_view {
+                    let entries = app.settings_entries();
+                    f.render_widget(SettingsScreen::new(&entries, selected, theme), area);
+                }
+
+                // Station links
+                if let Some(selected) = app.links_view {
+                    let links = app.station_links();
+                    let station = app.timeline_channel().map(|channel| channel.title.as_str());
+                    f.render_widget(LinksPopup::new(station, &links, selected, theme), area);
+                }
+
+                // Pending timers and jobs
+                if let Some(selected) = app.jobs_view {
+                    let now_ms = chrono::Utc::now().timestamp_millis();
+                    f.render_widget(
+                        JobsPopup::new(app.jobs.pending(), selected, now_ms, theme),
+                        area,
+                    );
+                }
+
+                // Play queue
+                if let Some(selected) = app.queue_view {
+                    let stations = app.queued_stations();
+                    let now_ms = chrono::Utc::now().timestamp_millis();
+                    let advances_in = app
+                        .jobs
+                        .next(&Job::AdvanceQueue)
+                        .map(|job| job.due_in(now_ms));
+                    f.render_widget(
+                        QueuePopup::new(&stations, selected, theme).advances_in(advances_in),
+                        area,
+                    );
+                }
+
+                // More like this
+                if let (Some(view), Some((station, entries))) =
+                    (&app.similar_view, app.similar_entries())
+                {
+                    f.render_widget(
+                        SimilarPopup::new(&station.title, &entries, view.selected, theme),
+                        area,
+                    );
+                }
+
+                // Recent stations
+                if let Some(selected) = app.recent_view {
+                    let entries = app.recent_entries();
+                    f.render_widget(RecentPopup::new(&entries, selected, theme), area);
+                }
+
+                // Audio outputs
+                if let Some(view) = &app.output_view {
+                    f.render_widget(
+                        OutputPicker::new(
+                            view.devices.as_deref(),
+                            app.airplay_device.as_ref(),
+                            view.selected,
+                            theme,
+                        ),
+                        area,
+                    );
+                }
+
+                // Station timeline
+                if let (Some(view), Some(channel)) = (&app.timeline_view, app.timeline_channel()) {
+                    let entries = app.timeline_entries();
+                    let scope = app.timeline_scope();
+                    f.render_widget(
+                        TimelinePopup::new(&channel.title, &entries, view.scroll, &scope, theme)
+                            .search(&view.query, view.editing),
+                        area,
+                    );
+                }
+
+                // Volume popup
+                if app.volume_popup.is_some() {
+                    f.render_widget(
+                        VolumePopup::new(
+                            if app.is_muted {
+                                app.last_volume
+                            } else {
+                                app.playback_state.volume
+                            },
+                            theme,
+                        )
+                        .muted(app.is_muted)
+                        .curve(app.config.volume_curve())
+                        .fading(app.volume_popup_fading()),
+                        area,
+                    );
+                }
+
+                // Help overlay
+                if app.show_help {
+                    app.help_scroll = app.help_scroll.min(HelpOverlay::max_scroll(area));
+                    f.render_widget(HelpOverlay::new(theme).scroll(app.help_scroll), area);
+                }
+
+                // Still listening?
+                if let Some(pauses_in) = app.idle_pauses_in() {
+                    f.render_widget(IdlePrompt::new(pauses_in.as_secs(), theme), area);
+                }
+
+                if app.ascii_ui {
+                    asciify(f.buffer_mut(), area);
+                }
+            })?;
+            app.frame_stats.record_draw(draw_started.elapsed());
+            app.dirty = false;
+        }
+
+        let title = app.window_title();
+        if title != last_title {
+            if let Some(title) = &title {
+                execute!(io::stdout(), SetTitle(title))?;
             }
-        })?;
+            last_title = title;
+        }
 
         app.list_state = list_state;
 
-        // Handle events
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
-        if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if let Some(action) = handle_key(key, app.show_help) {
-                        app.handle_action(action).await?;
-                        let next_request = build_metadata_request(app);
-                        if next_request != last_request {
-                            let _ = metadata_tx.send(next_request.clone());
-                            last_request = next_request;
-                        }
-                    }
-                }
+        // Wait for the next event, or the next tick if nothing arrives first
+        let timeout = app.tick_rate().saturating_sub(last_tick.elapsed());
+        let event = tokio::select! {
+            biased;
+            _ = time::sleep(timeout) => {
+                last_tick = Instant::now();
+                AppEvent::Tick
             }
+            Some(event) = bus.next() => event,
+        };
+        app.on_event(event).await?;
+        // Apply whatever else queued up meanwhile before drawing again
+        while let Some(event) = bus.try_next() {
+            app.on_event(event).await?;
         }
 
-        // Tick - update visualizer spectrum
-        if last_tick.elapsed() >= tick_rate {
-            app.update_spectrum().await;
-            last_tick = Instant::now();
+        if app.show_artwork && !artwork_preloaded && !app.channels_loading {
+            artwork_preloaded = true;
+            let favorites = app
+                .catalog
+                .channels()
+                .iter()
+                .filter(|c| app.favorites.is_favorite(&c.id))
+                .map(artwork_url)
+                .collect();
+            tokio::spawn(preload_artwork_worker(favorites));
+        }
+        // Stations can arrive after the grid opens, or later on in a refresh
+        if app.show_grid && !app.channels_loading {
+            let channels: Vec<_> = app
+                .catalog
+                .channels()
+                .iter()
+                .filter(|c| !app.thumbnails.contains_key(&c.id))
+                .filter(|c| thumbnails_requested.insert(c.id.clone()))
+                .map(|c| (c.id.clone(), c.image.clone()))
+                .collect();
+            if !channels.is_empty() {
+                tokio::spawn(thumbnail_worker(channels, bus.sender()));
+            }
+        }
+        let next_request = build_metadata_request(app);
+        if next_request != last_request {
+            let _ = metadata_tx.send(next_request.clone());
+            last_request = next_request;
         }
 
         // Check if should quit
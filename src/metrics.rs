@@ -0,0 +1,121 @@
+//! Prometheus metrics for `vibecast daemon`, served on a localhost port when
+//! `daemon.metrics_port` is set.
+
+use anyhow::Result;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Counters updated by the app and its workers
+#[derive(Debug)]
+pub struct Metrics {
+    started: Instant,
+    /// Seconds spent playing and not paused
+    pub listening_seconds: AtomicU64,
+    /// Times the stream came back after being lost
+    pub reconnects: AtomicU64,
+    /// Failed SomaFM API requests
+    pub api_errors: AtomicU64,
+    /// Bytes read from streams
+    pub bytes_streamed: AtomicU64,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            listening_seconds: AtomicU64::new(0),
+            reconnects: AtomicU64::new(0),
+            api_errors: AtomicU64::new(0),
+            bytes_streamed: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Metrics {
+    /// The counters in Prometheus' text format
+    pub fn render(&self) -> String {
+        let metrics = [
+            (
+                "vibecast_uptime_seconds",
+                "gauge",
+                "Seconds since vibecast started",
+                self.started.elapsed().as_secs(),
+            ),
+            (
+                "vibecast_listening_seconds_total",
+                "counter",
+                "Seconds spent playing a stream",
+                self.listening_seconds.load(Ordering::Relaxed),
+            ),
+            (
+                "vibecast_reconnects_total",
+                "counter",
+                "Times the stream recovered after being lost",
+                self.reconnects.load(Ordering::Relaxed),
+            ),
+            (
+                "vibecast_api_errors_total",
+                "counter",
+                "Failed SomaFM API requests",
+                self.api_errors.load(Ordering::Relaxed),
+            ),
+            (
+                "vibecast_streamed_bytes_total",
+                "counter",
+                "Bytes read from streams",
+                self.bytes_streamed.load(Ordering::Relaxed),
+            ),
+        ];
+        let mut out = String::new();
+        for (name, kind, help, value) in metrics {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} {}", name, kind);
+            let _ = writeln!(out, "{} {}", name, value);
+        }
+        out
+    }
+}
+
+/// Serve `/metrics` on 127.0.0.1:`port` in the background
+pub async fn serve(metrics: Arc<Metrics>, port: u16) -> Result<()> {
+    let listener = TcpListener::bind(("127.0.0.1", port)).await?;
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let metrics = metrics.clone();
+            tokio::spawn(async move {
+                // Only the request line matters; every path gets the metrics
+                let mut request = [0; 1024];
+                if stream.read(&mut request).await.is_err() {
+                    return;
+                }
+                let body = metrics.render();
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            });
+        }
+    });
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let metrics = Metrics::default();
+        metrics.bytes_streamed.store(4096, Ordering::Relaxed);
+        let text = metrics.render();
+        assert!(text.contains("# TYPE vibecast_streamed_bytes_total counter\n"));
+        assert!(text.contains("\nvibecast_streamed_bytes_total 4096\n"));
+        assert!(text.contains("\nvibecast_reconnects_total 0\n"));
+    }
+}
@@ -0,0 +1,219 @@
+//! Desktop notifications for track changes, sent through the platform's own
+//! tool so no notification daemon library is needed, or as terminal escape
+//! sequences for terminals that turn them into notifications.
+
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+#[cfg(unix)]
+use std::process::{Command, Stdio};
+
+use crate::i18n::t;
+
+/// Whether the OS is suppressing notifications (Do Not Disturb / Focus).
+/// Unknown states count as not suppressed.
+pub fn os_do_not_disturb() -> bool {
+    #[cfg(target_os = "macos")]
+    {
+        directories::BaseDirs::new()
+            .map(|dirs| {
+                dirs.home_dir()
+                    .join("Library/DoNotDisturb/DB/Assertions.json")
+            })
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .is_some_and(|json| focus_active(&json))
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        // GNOME turns banners off for Do Not Disturb; other desktops expose
+        // it as the notification server's Inhibited property
+        let banners_off = output(
+            "gsettings",
+            &["get", "org.gnome.desktop.notifications", "show-banners"],
+        )
+        .is_some_and(|out| out.trim() == "false");
+        banners_off
+            || output(
+                "busctl",
+                &[
+                    "--user",
+                    "get-property",
+                    "org.freedesktop.Notifications",
+                    "/org/freedesktop/Notifications",
+                    "org.freedesktop.Notifications",
+                    "Inhibited",
+                ],
+            )
+            .is_some_and(|out| out.trim() == "b true")
+    }
+    #[cfg(windows)]
+    {
+        false
+    }
+}
+
+/// Whether macOS's Focus assertions file lists an active Focus
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn focus_active(assertions_json: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(assertions_json)
+        .ok()
+        .and_then(|value| {
+            value["data"].as_array().map(|data| {
+                data.iter().any(|entry| {
+                    entry["storeAssertionRecords"]
+                        .as_array()
+                        .is_some_and(|records| !records.is_empty())
+                })
+            })
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Show a desktop notification
+pub fn send(summary: &str, body: &str) -> Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut command = Command::new("osascript");
+        command.args([
+            "-e",
+            &format!(
+                "display notification {} with title {}",
+                quote(body),
+                quote(summary)
+            ),
+        ]);
+        command
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = {
+        let mut command = Command::new("notify-send");
+        command.args(["--app-name=vibecast", summary, body]);
+        command
+    };
+    #[cfg(windows)]
+    {
+        let _ = (summary, body);
+        return Err(anyhow!("desktop notifications aren't supported on Windows"));
+    }
+
+    #[cfg(unix)]
+    {
+        let status = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(anyhow!("notification command failed: {}", status))
+        }
+    }
+}
+
+/// How track change notifications are delivered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyVia {
+    /// notify-send or osascript on the machine vibecast runs on
+    #[default]
+    Desktop,
+    /// OSC 9 escape (iTerm2, WezTerm, Windows Terminal, ...), which works
+    /// over SSH
+    Osc9,
+    /// OSC 777 escape (foot, Ghostty, rxvt-unicode, ...)
+    Osc777,
+}
+
+impl NotifyVia {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Desktop => Self::Osc9,
+            Self::Osc9 => Self::Osc777,
+            Self::Osc777 => Self::Desktop,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Desktop => t("notify-desktop"),
+            Self::Osc9 => t("notify-osc9"),
+            Self::Osc777 => t("notify-osc777"),
+        }
+    }
+}
+
+/// Escape sequence asking the terminal to show a notification. Inside tmux
+/// it's wrapped to pass through to the outer terminal.
+fn terminal_sequence(via: NotifyVia, summary: &str, body: &str, tmux: bool) -> String {
+    // Control characters would end the sequence early; ';' splits OSC 777
+    let clean = |s: &str| -> String {
+        s.chars()
+            .filter(|c| !c.is_control())
+            .map(|c| if c == ';' { ',' } else { c })
+            .collect()
+    };
+    let sequence = match via {
+        NotifyVia::Osc777 => format!("\x1b]777;notify;{};{}\x07", clean(summary), clean(body)),
+        _ => format!("\x1b]9;{}: {}\x07", clean(summary), clean(body)),
+    };
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+/// Show a notification through the terminal, for `Osc9` and `Osc777`
+pub fn send_to_terminal(via: NotifyVia, summary: &str, body: &str) -> Result<()> {
+    let tmux = std::env::var_os("TMUX").is_some();
+    let mut stdout = std::io::stdout();
+    stdout.write_all(terminal_sequence(via, summary, body, tmux).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_active() {
+        let active = r#"{"data":[{"storeAssertionRecords":[{"assertionDetails":{}}]}]}"#;
+        let inactive = r#"{"data":[{"storeAssertionRecords":[]}]}"#;
+        assert!(focus_active(active));
+        assert!(!focus_active(inactive));
+        assert!(!focus_active("not json"));
+    }
+
+    #[test]
+    fn test_terminal_sequence() {
+        assert_eq!(
+            terminal_sequence(NotifyVia::Osc9, "Groove Salad", "Bonobo - Kerala", false),
+            "\x1b]9;Groove Salad: Bonobo - Kerala\x07"
+        );
+        assert_eq!(
+            terminal_sequence(NotifyVia::Osc777, "Groove; Salad", "Bell\x07", false),
+            "\x1b]777;notify;Groove, Salad;Bell\x07"
+        );
+        assert_eq!(
+            terminal_sequence(NotifyVia::Osc9, "A", "B", true),
+            "\x1bPtmux;\x1b\x1b]9;A: B\x07\x1b\\"
+        );
+    }
+}
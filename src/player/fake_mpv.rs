@@ -0,0 +1,130 @@
+//! Stand-in for mpv's JSON IPC server, so `MpvController` can be tested
+//! without an mpv binary.
+
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixListener;
+use tokio::task::JoinHandle;
+
+/// How the fake answers one command
+pub enum Reply {
+    Data(Value),
+    Error(&'static str),
+    /// Answer only after the next command's reply, to exercise responses
+    /// arriving out of order
+    Held(Value),
+    /// Never answer, to exercise timeouts
+    Silent,
+    /// Close the connection instead of answering
+    Hangup,
+}
+
+type Handler = dyn Fn(&[Value]) -> Reply + Send + Sync;
+
+pub struct FakeMpv {
+    pub socket_path: PathBuf,
+    commands: Arc<Mutex<Vec<Vec<Value>>>>,
+    connections: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl FakeMpv {
+    /// Listen on a fresh socket, answering each command with `handler`. An
+    /// unrelated event is sent ahead of every reply, as real mpv interleaves
+    /// them.
+    pub fn start(name: &str, handler: impl Fn(&[Value]) -> Reply + Send + Sync + 'static) -> Self {
+        let socket_path = std::env::temp_dir().join(format!(
+            "vibecast_fake_mpv_{}_{}.sock",
+            std::process::id(),
+            name
+        ));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path).unwrap();
+
+        let handler: Arc<Handler> = Arc::new(handler);
+        let commands = Arc::new(Mutex::new(Vec::new()));
+        let connections = Arc::new(AtomicUsize::new(0));
+
+        let task = tokio::spawn({
+            let commands = commands.clone();
+            let connections = connections.clone();
+            async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let handler = handler.clone();
+                    let commands = commands.clone();
+                    tokio::spawn(async move {
+                        let (read_half, mut write_half) = stream.into_split();
+                        let mut lines = BufReader::new(read_half).lines();
+                        let mut held: Option<Value> = None;
+                        while let Ok(Some(line)) = lines.next_line().await {
+                            let Ok(request) = serde_json::from_str::<Value>(&line) else {
+                                continue;
+                            };
+                            let command =
+                                request["command"].as_array().cloned().unwrap_or_default();
+                            commands.lock().unwrap().push(command.clone());
+
+                            let response = match handler(&command) {
+                                Reply::Held(data) => {
+                                    held = Some(json!({
+                                        "request_id": request["request_id"],
+                                        "error": "success",
+                                        "data": data,
+                                    }));
+                                    continue;
+                                }
+                                Reply::Data(data) => json!({
+                                    "request_id": request["request_id"],
+                                    "error": "success",
+                                    "data": data,
+                                }),
+                                Reply::Error(error) => json!({
+                                    "request_id": request["request_id"],
+                                    "error": error,
+                                }),
+                                Reply::Silent => continue,
+                                Reply::Hangup => return,
+                            };
+                            let event = json!({ "event": "audio-reconfig" });
+                            let mut out = format!("{}\n{}\n", event, response);
+                            if let Some(held) = held.take() {
+                                out.push_str(&format!("{}\n", held));
+                            }
+                            if write_half.write_all(out.as_bytes()).await.is_err() {
+                                return;
+                            }
+                        }
+                    });
+                }
+            }
+        });
+
+        Self {
+            socket_path,
+            commands,
+            connections,
+            task,
+        }
+    }
+
+    /// Every command received so far, in order
+    pub fn commands(&self) -> Vec<Vec<Value>> {
+        self.commands.lock().unwrap().clone()
+    }
+
+    /// Number of client connections accepted so far
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for FakeMpv {
+    fn drop(&mut self) {
+        self.task.abort();
+        let _ = std::fs::remove_file(&self.socket_path);
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(all(test, unix))]
+mod fake_mpv;
 pub mod mpv;
 
-pub use mpv::{MpvController, PlaybackState};
+pub use mpv::{AudioLevels, MpvController, PlaybackState, StreamHealth, VolumeCurve};
@@ -3,16 +3,23 @@
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
 use tokio::process::{Child, Command};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
 use tokio::time::{sleep, timeout, Duration};
 
+use crate::airplay;
+use crate::api::http;
+
 // Platform-specific imports
 #[cfg(unix)]
-use std::path::PathBuf;
-#[cfg(unix)]
 use tokio::net::unix::{OwnedReadHalf, OwnedWriteHalf};
 #[cfg(unix)]
 use tokio::net::UnixStream;
@@ -22,6 +29,42 @@ use tokio::io::{ReadHalf, WriteHalf};
 #[cfg(windows)]
 use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient};
 
+/// How far behind live a paused stream can fall; mpv keeps downloading into
+/// its cache while paused, up to this much audio
+pub const TIMESHIFT_SECS: u64 = 300;
+
+/// Sample format written to a Snapcast FIFO: snapserver's default
+/// 48000:16:2
+const SNAPCAST_ARGS: [&str; 3] = [
+    "--audio-samplerate=48000",
+    "--audio-format=s16",
+    "--audio-channels=stereo",
+];
+
+/// Behind live by less than this is normal buffering, not timeshift
+const LIVE_MARGIN: Duration = Duration::from_secs(5);
+
+/// How the volume setting maps onto mpv's linear 0-100 volume
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VolumeCurve {
+    /// Setting passed straight through
+    #[default]
+    Linear,
+    /// Cubed, so each step sounds about as loud a change as the last
+    Cubic,
+}
+
+impl VolumeCurve {
+    /// mpv volume for a 0-100 setting
+    pub fn apply(self, volume: u8) -> u8 {
+        match self {
+            Self::Linear => volume,
+            Self::Cubic => ((volume as f32 / 100.0).powi(3) * 100.0).round() as u8,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 struct MpvCommand {
     command: Vec<Value>,
@@ -40,11 +83,40 @@ struct MpvResponse {
     event: Option<String>,
 }
 
+/// Audio levels in dB from the astats filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AudioLevels {
+    pub rms_db: f32,
+    pub peak_db: f32,
+    /// Left and right `(rms_db, peak_db)`, when per-channel stats are available
+    pub stereo: Option<[(f32, f32); 2]>,
+}
+
+/// How a playing stream is doing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamHealth {
+    Healthy,
+    /// mpv paused to refill its cache
+    Buffering,
+    /// mpv stopped answering; commands are reconnecting
+    Lost,
+}
+
 #[derive(Debug, Clone)]
 pub struct PlaybackState {
     pub playing: bool,
     pub paused: bool,
     pub volume: u8,
+    /// Output silenced while the stream keeps running
+    pub muted: bool,
+    /// File the stream is being saved to
+    pub recording: Option<PathBuf>,
+    /// How far playback trails the live stream after a pause
+    pub behind_live: Option<Duration>,
+    /// Stream bitrate in kbps, once mpv has measured it
+    pub bitrate: Option<u32>,
+    /// Whether audio is flowing, `None` when not playing
+    pub stream_health: Option<StreamHealth>,
     pub title: Option<String>,
     pub artist: Option<String>,
 }
@@ -55,6 +127,11 @@ impl Default for PlaybackState {
             playing: false,
             paused: false,
             volume: 80,
+            muted: false,
+            recording: None,
+            behind_live: None,
+            bitrate: None,
+            stream_health: None,
             title: None,
             artist: None,
         }
@@ -72,26 +149,93 @@ type IpcReader = BufReader<ReadHalf<NamedPipeClient>>;
 #[cfg(windows)]
 type IpcWriter = BufWriter<WriteHalf<NamedPipeClient>>;
 
+/// Commands waiting on their response, by request id; `None` once the
+/// connection has closed
+type Waiting = Arc<Mutex<Option<HashMap<u64, oneshot::Sender<MpvResponse>>>>>;
+
+/// Read mpv's responses, handing each to the command waiting for it. Events
+/// and responses nobody's waiting for any more are dropped.
+async fn read_responses(mut reader: IpcReader, waiting: Waiting) {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        match reader.read_line(&mut line).await {
+            Ok(0) | Err(_) => break,
+            Ok(_) => {}
+        }
+        let Ok(response) = serde_json::from_str::<MpvResponse>(&line) else {
+            continue;
+        };
+        if response.event.is_some() {
+            continue;
+        }
+        let sender = waiting
+            .lock()
+            .unwrap()
+            .as_mut()
+            .and_then(|waiting| waiting.remove(&response.request_id));
+        if let Some(sender) = sender {
+            let _ = sender.send(response);
+        }
+    }
+    // Dropping the senders tells everyone still waiting
+    *waiting.lock().unwrap() = None;
+}
+
+/// Whether `path` is a named pipe
+fn is_fifo(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        std::fs::metadata(path).is_ok_and(|meta| meta.file_type().is_fifo())
+    }
+    #[cfg(windows)]
+    {
+        let _ = path;
+        false
+    }
+}
+
 pub struct MpvController {
     #[cfg(unix)]
     socket_path: PathBuf,
     #[cfg(windows)]
     pipe_name: String,
     child: Option<Child>,
-    reader: Option<IpcReader>,
+    /// Task reading responses off the IPC connection
+    reader: Option<JoinHandle<()>>,
     writer: Option<IpcWriter>,
+    waiting: Waiting,
     request_id: AtomicU64,
     pub state: PlaybackState,
+    pub volume_curve: VolumeCurve,
+    /// Write decoded audio to this FIFO for a Snapcast server to read,
+    /// instead of playing it here
+    pub snapcast_fifo: Option<PathBuf>,
+    /// Send decoded audio to this AirPlay speaker instead of playing it
+    /// here. The Snapcast FIFO wins if both are set.
+    pub airplay: Option<SocketAddr>,
+    /// `raop_play`, sending mpv's audio to the AirPlay speaker
+    airplay_sender: Option<Child>,
 }
 
 impl MpvController {
     pub fn new() -> Self {
+        Self::with_ipc_name(format!("vibecast_mpv_{}", std::process::id()))
+    }
+
+    /// A controller with its own IPC socket, so it can run alongside the
+    /// main one
+    pub fn named(name: &str) -> Self {
+        Self::with_ipc_name(format!("vibecast_mpv_{}_{}", std::process::id(), name))
+    }
+
+    fn with_ipc_name(ipc_name: String) -> Self {
         #[cfg(unix)]
-        let socket_path =
-            std::env::temp_dir().join(format!("vibecast_mpv_{}.sock", std::process::id()));
+        let socket_path = std::env::temp_dir().join(format!("{}.sock", ipc_name));
 
         #[cfg(windows)]
-        let pipe_name = format!(r"\\.\pipe\vibecast_mpv_{}", std::process::id());
+        let pipe_name = format!(r"\\.\pipe\{}", ipc_name);
 
         Self {
             #[cfg(unix)]
@@ -101,8 +245,13 @@ impl MpvController {
             child: None,
             reader: None,
             writer: None,
+            waiting: Arc::new(Mutex::new(None)),
             request_id: AtomicU64::new(1),
             state: PlaybackState::default(),
+            volume_curve: VolumeCurve::default(),
+            snapcast_fifo: None,
+            airplay: None,
+            airplay_sender: None,
         }
     }
 
@@ -129,25 +278,92 @@ impl MpvController {
         }
         // Windows: Named pipes are automatically cleaned up when all handles are closed
 
+        let mut output = Vec::new();
+        if let Some(fifo) = &self.snapcast_fifo {
+            // mpv would happily create a regular file and fill the disk
+            if !is_fifo(fifo) {
+                return Err(anyhow!(
+                    "{} isn't a FIFO; is snapserver running?",
+                    fifo.display()
+                ));
+            }
+            output.extend([
+                "--ao=pcm".to_string(),
+                "--ao-pcm-waveheader=no".to_string(),
+                format!("--ao-pcm-file={}", fifo.display()),
+            ]);
+            output.extend(SNAPCAST_ARGS.map(str::to_string));
+        }
+        // mpv's stdout is free, since it's told to be quiet
+        let speaker = self.airplay.filter(|_| self.snapcast_fifo.is_none());
+        if speaker.is_some() {
+            if cfg!(windows) {
+                return Err(anyhow!("AirPlay needs Linux or macOS"));
+            }
+            output.extend([
+                "--ao=pcm".to_string(),
+                "--ao-pcm-waveheader=no".to_string(),
+                "--ao-pcm-file=/dev/stdout".to_string(),
+            ]);
+            output.extend(airplay::PCM_ARGS.map(str::to_string));
+        }
+        // Only reaches youtube-dl; mpv's own connections follow the system
+        // resolver's address order
+        if http::settings().prefer_ipv4 {
+            output.push("--ytdl-raw-options-append=force-ipv4=".to_string());
+        }
+
         // Spawn mpv with the stream URL and audio stats filter for visualization
         let child = Command::new("mpv")
+            .args(&output)
             .args([
                 "--no-video",
                 "--no-terminal",
                 "--really-quiet",
                 &self.ipc_server_arg(),
-                &format!("--volume={}", self.state.volume),
+                &format!("--volume={}", self.volume_curve.apply(self.state.volume)),
+                if self.state.muted {
+                    "--mute=yes"
+                } else {
+                    "--mute=no"
+                },
+                // Keep reading the stream while paused, so playback can
+                // resume where it left off rather than at live
+                "--cache=yes",
+                &format!("--cache-secs={}", TIMESHIFT_SECS),
+                "--demuxer-max-bytes=32MiB",
                 // Audio stats filter for RMS/peak level monitoring
-                "--af=lavfi=[astats=metadata=1:reset=1:measure_perchannel=none]",
+                "--af=lavfi=[astats=metadata=1:reset=1:measure_perchannel=RMS_level+Peak_level]",
                 url,
             ])
             .stdin(Stdio::null())
-            .stdout(Stdio::null())
+            .stdout(if speaker.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
             .stderr(Stdio::null())
             .spawn()?;
 
         self.child = Some(child);
 
+        if let Some(addr) = speaker {
+            let sent = match self.child.as_mut().and_then(|child| child.stdout.take()) {
+                Some(pcm) => pcm
+                    .try_into()
+                    .map_err(anyhow::Error::from)
+                    .and_then(|pcm| airplay::spawn_sender(addr, pcm)),
+                None => Err(anyhow!("mpv has no audio output to send")),
+            };
+            match sent {
+                Ok(sender) => self.airplay_sender = Some(sender),
+                Err(err) => {
+                    self.stop().await?;
+                    return Err(err);
+                }
+            }
+        }
+
         // Platform-specific connection
         #[cfg(unix)]
         {
@@ -161,6 +377,14 @@ impl MpvController {
         // Give mpv a moment to start playing
         sleep(Duration::from_millis(500)).await;
 
+        // raop_play exits straight away if the speaker turns it down
+        if let Some(sender) = self.airplay_sender.as_mut() {
+            if sender.try_wait()?.is_some() {
+                self.stop().await?;
+                return Err(anyhow!("the AirPlay speaker didn't accept the stream"));
+            }
+        }
+
         self.state.playing = true;
         self.state.paused = false;
 
@@ -186,20 +410,65 @@ impl MpvController {
         }
 
         // Connect to socket
-        let stream = match UnixStream::connect(&self.socket_path).await {
-            Ok(stream) => stream,
-            Err(err) => {
-                self.stop().await?;
-                return Err(err.into());
-            }
-        };
+        if let Err(err) = self.connect_socket().await {
+            self.stop().await?;
+            return Err(err);
+        }
+
+        Ok(())
+    }
+
+    /// Open the IPC connection to an mpv already listening on the socket
+    #[cfg(unix)]
+    async fn connect_socket(&mut self) -> Result<()> {
+        let stream = UnixStream::connect(&self.socket_path).await?;
         let (read_half, write_half) = stream.into_split();
-        self.reader = Some(BufReader::new(read_half));
-        self.writer = Some(BufWriter::new(write_half));
+        self.attach(BufReader::new(read_half), BufWriter::new(write_half));
+        Ok(())
+    }
 
+    /// Open the IPC connection to an mpv already listening on the pipe
+    #[cfg(windows)]
+    async fn connect_pipe(&mut self) -> Result<()> {
+        let client = ClientOptions::new().open(&self.pipe_name)?;
+        let (read_half, write_half) = tokio::io::split(client);
+        self.attach(BufReader::new(read_half), BufWriter::new(write_half));
         Ok(())
     }
 
+    /// Start using a fresh IPC connection, with a task reading its responses
+    fn attach(&mut self, reader: IpcReader, writer: IpcWriter) {
+        self.disconnect();
+        let waiting: Waiting = Arc::new(Mutex::new(Some(HashMap::new())));
+        self.reader = Some(tokio::spawn(read_responses(reader, waiting.clone())));
+        self.writer = Some(writer);
+        self.waiting = waiting;
+    }
+
+    /// Drop the IPC connection, failing any commands still waiting
+    fn disconnect(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
+        self.writer = None;
+        *self.waiting.lock().unwrap() = None;
+    }
+
+    /// Re-open a dropped IPC connection while mpv should still be running
+    async fn reconnect(&mut self) -> Result<()> {
+        if !self.state.playing {
+            return Err(anyhow!("Not connected to mpv"));
+        }
+        #[cfg(unix)]
+        {
+            self.connect_socket().await
+        }
+        #[cfg(windows)]
+        {
+            self.connect_pipe().await
+        }
+    }
+
     #[cfg(windows)]
     async fn connect_windows(&mut self) -> Result<()> {
         use std::io::ErrorKind;
@@ -238,16 +507,21 @@ impl MpvController {
 
         // Use tokio::io::split for NamedPipeClient (no into_split available)
         let (read_half, write_half) = tokio::io::split(client);
-        self.reader = Some(BufReader::new(read_half));
-        self.writer = Some(BufWriter::new(write_half));
+        self.attach(BufReader::new(read_half), BufWriter::new(write_half));
 
         Ok(())
     }
 
     pub async fn stop(&mut self) -> Result<()> {
+        // Let mpv finish writing the recording before it's killed
+        let _ = self.stop_recording().await;
+
         // Close socket connections first
-        self.reader = None;
-        self.writer = None;
+        self.disconnect();
+
+        if let Some(mut sender) = self.airplay_sender.take() {
+            let _ = sender.kill().await;
+        }
 
         if let Some(mut child) = self.child.take() {
             // Platform-specific process termination
@@ -275,6 +549,10 @@ impl MpvController {
 
         self.state.playing = false;
         self.state.paused = false;
+        self.state.recording = None;
+        self.state.behind_live = None;
+        self.state.bitrate = None;
+        self.state.stream_health = None;
 
         // Platform-specific cleanup
         #[cfg(unix)]
@@ -296,55 +574,64 @@ impl MpvController {
         command: Vec<Value>,
         read_timeout: Duration,
     ) -> Result<Value> {
-        let writer = self
-            .writer
-            .as_mut()
-            .ok_or_else(|| anyhow!("Not connected to mpv"))?;
-        let reader = self
-            .reader
-            .as_mut()
-            .ok_or_else(|| anyhow!("Not connected to mpv"))?;
+        let (request_id, response) = self.request(command).await?;
+        let response = match timeout(read_timeout, response).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => {
+                // The connection is gone; the next command reconnects
+                self.disconnect();
+                return Err(anyhow!("mpv connection closed"));
+            }
+            Err(_) => {
+                // A late response is dropped, and the connection kept
+                if let Some(waiting) = self.waiting.lock().unwrap().as_mut() {
+                    waiting.remove(&request_id);
+                }
+                return Err(anyhow!("Timeout waiting for mpv response"));
+            }
+        };
+        if response.error != "success" && !response.error.is_empty() {
+            return Err(anyhow!("mpv error: {}", response.error));
+        }
+        Ok(response.data)
+    }
+
+    /// Write a command, returning its request id and where its response
+    /// will arrive. Responses are matched up by the reader task, so several
+    /// commands can be waiting at once.
+    async fn request(
+        &mut self,
+        command: Vec<Value>,
+    ) -> Result<(u64, oneshot::Receiver<MpvResponse>)> {
+        if self.writer.is_none() || self.waiting.lock().unwrap().is_none() {
+            self.disconnect();
+            self.reconnect().await?;
+        }
 
         let request_id = self.request_id.fetch_add(1, Ordering::SeqCst);
         let cmd = MpvCommand {
             command,
             request_id,
         };
-
         let mut msg = serde_json::to_vec(&cmd)?;
         msg.push(b'\n');
 
-        writer.write_all(&msg).await?;
-        writer.flush().await?;
-
-        // Read responses, skipping events until we get our response
-        loop {
-            let mut line = String::new();
-
-            match timeout(read_timeout, reader.read_line(&mut line)).await {
-                Ok(Ok(0)) => return Err(anyhow!("mpv connection closed")),
-                Ok(Ok(_)) => {
-                    // Try to parse the response
-                    if let Ok(resp) = serde_json::from_str::<MpvResponse>(&line) {
-                        // Skip event messages
-                        if resp.event.is_some() {
-                            continue;
-                        }
-
-                        // Check if this is our response
-                        if resp.request_id == request_id {
-                            if resp.error != "success" && !resp.error.is_empty() {
-                                return Err(anyhow!("mpv error: {}", resp.error));
-                            }
-                            return Ok(resp.data);
-                        }
-                    }
-                    // If we can't parse it or it's not our response, keep reading
-                }
-                Ok(Err(e)) => return Err(anyhow!("Read error: {}", e)),
-                Err(_) => return Err(anyhow!("Timeout waiting for mpv response")),
-            }
+        let (sender, response) = oneshot::channel();
+        if let Some(waiting) = self.waiting.lock().unwrap().as_mut() {
+            waiting.insert(request_id, sender);
+        }
+        let Some(writer) = self.writer.as_mut() else {
+            return Err(anyhow!("Not connected to mpv"));
+        };
+        let written = match writer.write_all(&msg).await {
+            Ok(()) => writer.flush().await,
+            Err(err) => Err(err),
+        };
+        if let Err(err) = written {
+            self.disconnect();
+            return Err(err.into());
         }
+        Ok((request_id, response))
     }
 
     pub async fn toggle_pause(&mut self) -> Result<()> {
@@ -376,8 +663,9 @@ impl MpvController {
             return Ok(());
         }
 
+        let output = self.volume_curve.apply(volume);
         match self
-            .send_command(vec![json!("set_property"), json!("volume"), json!(volume)])
+            .send_command(vec![json!("set_property"), json!("volume"), json!(output)])
             .await
         {
             Ok(_) => {
@@ -393,18 +681,180 @@ impl MpvController {
         }
     }
 
-    pub async fn volume_up(&mut self) -> Result<()> {
-        let new_volume = (self.state.volume + 5).min(100);
+    /// Silence or restore output without stopping the stream
+    pub async fn set_mute(&mut self, muted: bool) -> Result<()> {
+        if self.state.playing {
+            self.send_command(vec![json!("set_property"), json!("mute"), json!(muted)])
+                .await?;
+        }
+        self.state.muted = muted;
+        Ok(())
+    }
+
+    /// Save the stream to `path` as it plays, without re-encoding. The format
+    /// follows the file extension.
+    pub async fn start_recording(&mut self, path: &Path) -> Result<()> {
+        if !self.state.playing {
+            return Err(anyhow!("Nothing is playing"));
+        }
+        self.send_command(vec![
+            json!("set_property"),
+            json!("stream-record"),
+            json!(path.to_string_lossy()),
+        ])
+        .await?;
+        self.state.recording = Some(path.to_path_buf());
+        Ok(())
+    }
+
+    pub async fn stop_recording(&mut self) -> Result<()> {
+        if self.state.recording.take().is_some() && self.state.playing {
+            self.send_command(vec![
+                json!("set_property"),
+                json!("stream-record"),
+                json!(""),
+            ])
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Output devices mpv can see, as `(name, description)`
+    pub async fn audio_devices(&mut self) -> Result<Vec<(String, String)>> {
+        let list = self
+            .send_command_with_timeout(
+                vec![json!("get_property"), json!("audio-device-list")],
+                Duration::from_millis(200),
+            )
+            .await?;
+        Ok(list
+            .as_array()
+            .map(|devices| {
+                devices
+                    .iter()
+                    .filter_map(|device| {
+                        Some((
+                            device["name"].as_str()?.to_string(),
+                            device["description"]
+                                .as_str()
+                                .unwrap_or_default()
+                                .to_string(),
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Seconds between the playback position and the newest cached audio
+    async fn cache_lag(&mut self) -> Result<f64> {
+        let read_timeout = Duration::from_millis(200);
+        let mut times = [0.0; 2];
+        for (time, property) in times.iter_mut().zip(["demuxer-cache-time", "time-pos"]) {
+            *time = self
+                .send_command_with_timeout(
+                    vec![json!("get_property"), json!(property)],
+                    read_timeout,
+                )
+                .await?
+                .as_f64()
+                .ok_or_else(|| anyhow!("{} unavailable", property))?;
+        }
+        Ok((times[0] - times[1]).max(0.0))
+    }
+
+    /// Refresh how far behind live playback is, `None` when it's live
+    pub async fn update_behind_live(&mut self) -> Result<Option<Duration>> {
+        if !self.state.playing {
+            return Ok(None);
+        }
+        let lag = Duration::from_secs(self.cache_lag().await? as u64);
+        self.state.behind_live = Some(lag).filter(|&lag| lag >= LIVE_MARGIN);
+        Ok(self.state.behind_live)
+    }
+
+    /// Refresh the measured stream bitrate
+    pub async fn update_bitrate(&mut self) -> Result<Option<u32>> {
+        if !self.state.playing {
+            return Ok(None);
+        }
+        let bits = self
+            .send_command_with_timeout(
+                vec![json!("get_property"), json!("audio-bitrate")],
+                Duration::from_millis(200),
+            )
+            .await?
+            .as_f64();
+        self.state.bitrate = bits.map(|bits| (bits / 1000.0).round() as u32);
+        Ok(self.state.bitrate)
+    }
+
+    /// Bytes read from the current stream so far; starts again at zero with
+    /// each new stream
+    pub async fn stream_position(&mut self) -> Result<Option<u64>> {
+        if !self.state.playing {
+            return Ok(None);
+        }
+        Ok(self
+            .send_command_with_timeout(
+                vec![json!("get_property"), json!("stream-pos")],
+                Duration::from_millis(200),
+            )
+            .await?
+            .as_u64())
+    }
+
+    /// Check whether audio is still flowing
+    pub async fn update_stream_health(&mut self) -> Option<StreamHealth> {
+        if !self.state.playing {
+            return None;
+        }
+        let buffering = self
+            .send_command_with_timeout(
+                vec![json!("get_property"), json!("paused-for-cache")],
+                Duration::from_millis(200),
+            )
+            .await;
+        self.state.stream_health = Some(match buffering {
+            Ok(Value::Bool(true)) => StreamHealth::Buffering,
+            Ok(_) => StreamHealth::Healthy,
+            Err(_) => StreamHealth::Lost,
+        });
+        self.state.stream_health
+    }
+
+    /// Skip the cached audio and play the live stream, unpausing if needed
+    pub async fn jump_to_live(&mut self) -> Result<()> {
+        if !self.state.playing {
+            return Ok(());
+        }
+        let lag = self.cache_lag().await?;
+        // Stop just short of the end of the cache so playback doesn't stall
+        if lag > 1.0 {
+            self.send_command(vec![json!("seek"), json!(lag - 1.0), json!("relative")])
+                .await?;
+        }
+        if self.state.paused {
+            self.send_command(vec![json!("set_property"), json!("pause"), json!(false)])
+                .await?;
+            self.state.paused = false;
+        }
+        self.state.behind_live = None;
+        Ok(())
+    }
+
+    pub async fn volume_up(&mut self, step: u8) -> Result<()> {
+        let new_volume = self.state.volume.saturating_add(step).min(100);
         self.set_volume(new_volume).await
     }
 
-    pub async fn volume_down(&mut self) -> Result<()> {
-        let new_volume = self.state.volume.saturating_sub(5);
+    pub async fn volume_down(&mut self, step: u8) -> Result<()> {
+        let new_volume = self.state.volume.saturating_sub(step);
         self.set_volume(new_volume).await
     }
 
     pub async fn get_metadata(&mut self) -> Result<Option<(String, String)>> {
-        if self.reader.is_none() || self.writer.is_none() {
+        if !self.state.playing {
             return Ok(None);
         }
 
@@ -466,26 +916,49 @@ impl MpvController {
         Ok(None)
     }
 
+    /// Whether mpv has quit by itself, e.g. after the server refused the
+    /// connection or the stream ended
+    pub fn has_exited(&mut self) -> bool {
+        self.child
+            .as_mut()
+            .is_some_and(|child| child.try_wait().ok().flatten().is_some())
+    }
+
     pub fn is_playing(&self) -> bool {
         self.state.playing && !self.state.paused
     }
 
-    /// Get audio levels from the astats filter for visualization
-    /// Returns (rms_db, peak_db) if available
-    pub async fn get_audio_stats(&mut self) -> Option<(f32, f32)> {
-        if self.reader.is_none()
-            || self.writer.is_none()
-            || !self.state.playing
-            || self.state.paused
-        {
+    /// Read one `(rms_db, peak_db)` pair of astats values, e.g. for `Overall` or
+    /// a channel number
+    async fn get_astats_levels(
+        &mut self,
+        scope: &str,
+        read_timeout: Duration,
+    ) -> Option<(f32, f32)> {
+        let mut levels = [0.0f32; 2];
+        for (level, name) in levels.iter_mut().zip(["RMS_level", "Peak_level"]) {
+            let path = format!("af-metadata/lavfi.astats.{}.{}", scope, name);
+            match self
+                .send_command_with_timeout(vec![json!("get_property"), json!(path)], read_timeout)
+                .await
+            {
+                Ok(Value::String(s)) => *level = s.parse().ok()?,
+                _ => return None,
+            }
+        }
+        Some((levels[0], levels[1]))
+    }
+
+    /// Get audio levels from the astats filter for visualization, `None`
+    /// when mpv has none to give
+    pub async fn get_audio_stats(&mut self) -> Option<AudioLevels> {
+        if !self.state.playing || self.state.paused {
             return None;
         }
 
         let read_timeout = Duration::from_millis(200);
 
-        // Try multiple approaches to get audio level data
-
-        // Method 1: Try astats filter metadata with different property paths
+        // The overall level, or the first channel's for mono streams
         let rms_paths = [
             "af-metadata/lavfi.astats.Overall.RMS_level",
             "af-metadata/lavfi.astats.1.RMS_level",
@@ -510,34 +983,22 @@ impl MpvController {
                     } else {
                         rms + 3.0 // Estimate peak as 3dB above RMS
                     };
-                    return Some((rms, peak));
+                    let stereo = match (
+                        self.get_astats_levels("1", read_timeout).await,
+                        self.get_astats_levels("2", read_timeout).await,
+                    ) {
+                        (Some(left), Some(right)) => Some([left, right]),
+                        _ => None,
+                    };
+                    return Some(AudioLevels {
+                        rms_db: rms,
+                        peak_db: peak,
+                        stereo,
+                    });
                 }
             }
         }
 
-        // Method 2: Use playback-time changes as a proxy for activity
-        // This creates variation based on playback progress
-        if let Ok(Value::Number(time)) = self
-            .send_command_with_timeout(
-                vec![json!("get_property"), json!("playback-time")],
-                read_timeout,
-            )
-            .await
-        {
-            if let Some(t) = time.as_f64() {
-                // Use time to create pseudo-random but consistent audio levels
-                // This provides variation that looks like audio response
-                // Generate levels that produce good visualizer movement (-12dB to -3dB range)
-                let base = (t * 7.3).sin() * 0.3 + (t * 11.7).cos() * 0.2 + 0.5;
-                let beat = (t * 2.5).sin().abs().powf(2.0) * 0.3; // Beat-like pulses
-                let variation = (t * 23.1).sin() * 0.15;
-                let rms =
-                    -12.0 + (base * 8.0) as f32 + (beat * 6.0) as f32 + (variation * 4.0) as f32;
-                let peak = rms + 2.0 + ((t * 31.4).sin().abs() * 3.0) as f32;
-                return Some((rms.clamp(-18.0, -3.0), peak.clamp(-15.0, 0.0)));
-            }
-        }
-
         None
     }
 }
@@ -550,6 +1011,9 @@ impl Default for MpvController {
 
 impl Drop for MpvController {
     fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            reader.abort();
+        }
         if let Some(child) = self.child.take() {
             #[cfg(unix)]
             {
@@ -578,3 +1042,218 @@ impl Drop for MpvController {
         // Windows: Named pipe cleaned up automatically
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::player::fake_mpv::{FakeMpv, Reply};
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    /// A controller connected to `fake` as if `play` had started it
+    async fn connected(fake: &FakeMpv) -> MpvController {
+        let mut controller = MpvController::new();
+        controller.socket_path = fake.socket_path.clone();
+        controller.connect_socket().await.unwrap();
+        controller.state.playing = true;
+        controller
+    }
+
+    #[tokio::test]
+    async fn test_commands_and_responses() {
+        let fake = FakeMpv::start("commands", |command| match command {
+            [cmd, prop] if cmd == "get_property" && prop == "media-title" => {
+                Reply::Data(json!("Bonobo - Kerala"))
+            }
+            [cmd, ..] if cmd == "get_property" => Reply::Error("property unavailable"),
+            _ => Reply::Data(Value::Null),
+        });
+        let mut controller = connected(&fake).await;
+
+        controller.set_volume(40).await.unwrap();
+        assert_eq!(controller.state.volume, 40);
+        assert_eq!(
+            controller.get_metadata().await.unwrap(),
+            Some(("Bonobo".to_string(), "Kerala".to_string()))
+        );
+        let err = controller
+            .send_command(vec![json!("get_property"), json!("metadata")])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("property unavailable"));
+
+        assert_eq!(
+            fake.commands()[0],
+            vec![json!("set_property"), json!("volume"), json!(40)]
+        );
+    }
+
+    #[test]
+    fn test_volume_curve() {
+        assert_eq!(VolumeCurve::Linear.apply(50), 50);
+        assert_eq!(VolumeCurve::Cubic.apply(0), 0);
+        assert_eq!(VolumeCurve::Cubic.apply(50), 13);
+        assert_eq!(VolumeCurve::Cubic.apply(100), 100);
+    }
+
+    #[tokio::test]
+    async fn test_set_mute() {
+        let fake = FakeMpv::start("mute", |_| Reply::Data(Value::Null));
+        let mut controller = connected(&fake).await;
+
+        controller.set_mute(true).await.unwrap();
+        assert!(controller.state.muted);
+        assert_eq!(
+            fake.commands(),
+            vec![vec![json!("set_property"), json!("mute"), json!(true)]]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_jump_to_live() {
+        let fake = FakeMpv::start("timeshift", |command| match command {
+            [cmd, prop] if cmd == "get_property" && prop == "demuxer-cache-time" => {
+                Reply::Data(json!(251.0))
+            }
+            [cmd, prop] if cmd == "get_property" && prop == "time-pos" => Reply::Data(json!(100.0)),
+            _ => Reply::Data(Value::Null),
+        });
+        let mut controller = connected(&fake).await;
+        controller.state.paused = true;
+
+        assert_eq!(
+            controller.update_behind_live().await.unwrap(),
+            Some(Duration::from_secs(151))
+        );
+        controller.jump_to_live().await.unwrap();
+        assert!(!controller.state.paused);
+        assert_eq!(controller.state.behind_live, None);
+        let commands = fake.commands();
+        assert_eq!(
+            commands[commands.len() - 2..],
+            [
+                vec![json!("seek"), json!(150.0), json!("relative")],
+                vec![json!("set_property"), json!("pause"), json!(false)],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_stream_health() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let fake = FakeMpv::start("health", {
+            let calls = calls.clone();
+            move |_| match calls.fetch_add(1, Ordering::SeqCst) {
+                0 => Reply::Data(json!(false)),
+                1 => Reply::Data(json!(true)),
+                _ => Reply::Silent,
+            }
+        });
+        let mut controller = connected(&fake).await;
+
+        assert_eq!(
+            controller.update_stream_health().await,
+            Some(StreamHealth::Healthy)
+        );
+        assert_eq!(
+            controller.update_stream_health().await,
+            Some(StreamHealth::Buffering)
+        );
+        assert_eq!(
+            controller.update_stream_health().await,
+            Some(StreamHealth::Lost)
+        );
+        controller.state.playing = false;
+        assert_eq!(controller.update_stream_health().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_audio_devices() {
+        let fake = FakeMpv::start("devices", |_| {
+            Reply::Data(json!([
+                {"name": "auto", "description": "Autoselect device"},
+                {"name": "pulse/headphones", "description": "Headphones"},
+                {"description": "No name"},
+            ]))
+        });
+        let mut controller = connected(&fake).await;
+
+        assert_eq!(
+            controller.audio_devices().await.unwrap(),
+            vec![
+                ("auto".to_string(), "Autoselect device".to_string()),
+                ("pulse/headphones".to_string(), "Headphones".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timeout_keeps_connection() {
+        let fake = FakeMpv::start("timeout", |command| match command {
+            [cmd, ..] if cmd == "cycle" => Reply::Silent,
+            _ => Reply::Data(json!(50)),
+        });
+        let mut controller = connected(&fake).await;
+
+        let err = controller
+            .send_command_with_timeout(
+                vec![json!("cycle"), json!("pause")],
+                Duration::from_millis(100),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Timeout"));
+
+        let volume = controller
+            .send_command(vec![json!("get_property"), json!("volume")])
+            .await
+            .unwrap();
+        assert_eq!(volume, json!(50));
+        assert_eq!(fake.connections(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_responses() {
+        let fake = FakeMpv::start("out_of_order", |command| match command {
+            [_, prop] if prop == "volume" => Reply::Held(json!(50)),
+            _ => Reply::Data(json!("Bonobo - Kerala")),
+        });
+        let mut controller = connected(&fake).await;
+
+        let (_, volume) = controller
+            .request(vec![json!("get_property"), json!("volume")])
+            .await
+            .unwrap();
+        let title = controller
+            .send_command(vec![json!("get_property"), json!("media-title")])
+            .await
+            .unwrap();
+        assert_eq!(title, json!("Bonobo - Kerala"));
+        // The volume's response came in behind the title's, and still arrives
+        assert_eq!(volume.await.unwrap().data, json!(50));
+    }
+
+    #[tokio::test]
+    async fn test_reconnects_after_hangup() {
+        let hung_up = std::sync::atomic::AtomicBool::new(false);
+        let fake = FakeMpv::start("reconnect", move |_| {
+            if hung_up.swap(true, Ordering::SeqCst) {
+                Reply::Data(Value::Null)
+            } else {
+                Reply::Hangup
+            }
+        });
+        let mut controller = connected(&fake).await;
+
+        let err = controller
+            .send_command(vec![json!("cycle"), json!("pause")])
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("connection closed"));
+        assert!(controller.writer.is_none());
+
+        controller.toggle_pause().await.unwrap();
+        assert!(controller.state.paused);
+        assert_eq!(fake.connections(), 2);
+    }
+}
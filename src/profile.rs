@@ -0,0 +1,166 @@
+//! `vibecast export-profile` and `import-profile`: the whole config
+//! directory (settings, favorites, play history, timers, themes and
+//! visualizer scripts) as one tar archive, for moving to another machine.
+
+use anyhow::{anyhow, bail, Result};
+use std::path::{Component, Path, PathBuf};
+
+/// Archive name when none is given
+pub const DEFAULT_ARCHIVE: &str = "vibecast-profile.tar";
+
+const BLOCK: usize = 512;
+
+/// The directory every settings file lives in
+fn config_dir() -> PathBuf {
+    crate::storage::config_dir()
+}
+
+/// Write the profile to `archive`, returning the files it holds
+pub fn export(archive: &Path) -> Result<Vec<String>> {
+    let dir = config_dir();
+    let mut files = Vec::new();
+    collect(&dir, &dir, &mut files)?;
+    files.sort();
+    std::fs::write(archive, write_tar(&files)?)?;
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Unpack a profile from `archive` over the current one, returning the
+/// files written. Files the archive doesn't have are left alone.
+pub fn import(archive: &Path) -> Result<Vec<String>> {
+    let files = read_tar(&std::fs::read(archive)?)?;
+    let dir = config_dir();
+    for (name, content) in &files {
+        let path = dir.join(name);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, content)?;
+    }
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+/// Every file under `dir`, named relative to `root` with `/` separators
+fn collect(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect(root, &path, files)?;
+        } else if path.extension().is_none_or(|ext| ext != "tmp") {
+            let name = path
+                .strip_prefix(root)?
+                .components()
+                .map(|part| part.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            files.push((name, std::fs::read(&path)?));
+        }
+    }
+    Ok(())
+}
+
+/// A plain ustar archive of `files`
+fn write_tar(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mtime = chrono::Utc::now().timestamp();
+    let mut out = Vec::new();
+    for (name, content) in files {
+        if name.len() > 100 {
+            bail!("{} has too long a name to archive", name);
+        }
+        let mut header = [0u8; BLOCK];
+        header[..name.len()].copy_from_slice(name.as_bytes());
+        header[100..108].copy_from_slice(b"0000644\0");
+        header[108..116].copy_from_slice(b"0000000\0");
+        header[116..124].copy_from_slice(b"0000000\0");
+        header[124..136].copy_from_slice(format!("{:011o}\0", content.len()).as_bytes());
+        header[136..148].copy_from_slice(format!("{:011o}\0", mtime).as_bytes());
+        header[156] = b'0';
+        header[257..265].copy_from_slice(b"ustar\x0000");
+        // The checksum is taken with its own field as spaces
+        header[148..156].copy_from_slice(b"        ");
+        let checksum: u32 = header.iter().map(|&byte| byte as u32).sum();
+        header[148..156].copy_from_slice(format!("{:06o}\0 ", checksum).as_bytes());
+
+        out.extend_from_slice(&header);
+        out.extend_from_slice(content);
+        out.resize(out.len().next_multiple_of(BLOCK), 0);
+    }
+    // Two empty blocks end the archive
+    out.resize(out.len() + 2 * BLOCK, 0);
+    Ok(out)
+}
+
+/// The regular files in a tar archive. Names that would land outside the
+/// config directory are refused.
+fn read_tar(archive: &[u8]) -> Result<Vec<(String, Vec<u8>)>> {
+    let mut files = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = archive.get(offset..offset + BLOCK) {
+        if header.iter().all(|&byte| byte == 0) {
+            break;
+        }
+        let size = octal(&header[124..136]).ok_or_else(|| anyhow!("not a tar archive"))?;
+        let data = offset + BLOCK;
+        let content = archive
+            .get(data..data + size)
+            .ok_or_else(|| anyhow!("the archive is cut short"))?;
+        offset = data + size.next_multiple_of(BLOCK);
+
+        // Directories and links are skipped; directories come back with
+        // the files in them
+        if !matches!(header[156], b'0' | 0) {
+            continue;
+        }
+        let mut name = text(&header[..100]);
+        let prefix = text(&header[345..500]);
+        if !prefix.is_empty() {
+            name = format!("{}/{}", prefix, name);
+        }
+        let safe = Path::new(&name)
+            .components()
+            .all(|part| matches!(part, Component::Normal(_) | Component::CurDir));
+        if !safe {
+            bail!("the archive has a file outside the profile: {}", name);
+        }
+        files.push((name, content.to_vec()));
+    }
+    Ok(files)
+}
+
+/// A NUL-terminated header field
+fn text(field: &[u8]) -> String {
+    let end = field
+        .iter()
+        .position(|&byte| byte == 0)
+        .unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+/// A header number, written in octal
+fn octal(field: &[u8]) -> Option<usize> {
+    let digits = text(field);
+    usize::from_str_radix(digits.trim(), 8).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tar_round_trip() {
+        let files = vec![
+            (
+                "config.json".to_string(),
+                b"{\"ui\": {\"ascii\": true}}".to_vec(),
+            ),
+            ("themes/dusk.toml".to_string(), vec![b'x'; BLOCK + 1]),
+            ("favorites.json".to_string(), Vec::new()),
+        ];
+        let archive = write_tar(&files).unwrap();
+        assert_eq!(archive.len() % BLOCK, 0);
+        assert_eq!(read_tar(&archive).unwrap(), files);
+
+        let escape = write_tar(&[("../.bashrc".to_string(), b"oops".to_vec())]).unwrap();
+        assert!(read_tar(&escape).is_err());
+    }
+}
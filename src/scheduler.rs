@@ -0,0 +1,108 @@
+//! When scheduled recordings run. Times are local and schedules repeat
+//! weekly.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+
+use crate::storage::RecordingSchedule;
+
+/// One run of a schedule
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Window {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+}
+
+fn parse_time(time: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(time.trim(), "%H:%M").ok()
+}
+
+fn parse_day(day: &str) -> Option<Weekday> {
+    day.trim().parse().ok()
+}
+
+/// Whether `schedule` has a run starting on `weekday`
+fn runs_on(schedule: &RecordingSchedule, weekday: Weekday) -> bool {
+    schedule.days.is_empty()
+        || schedule
+            .days
+            .iter()
+            .any(|day| parse_day(day) == Some(weekday))
+}
+
+/// The run starting on `date`; an end before the start is the next day
+fn window_on(date: NaiveDate, start: NaiveTime, end: NaiveTime) -> Window {
+    let start = date.and_time(start);
+    let mut end = date.and_time(end);
+    if end <= start {
+        end += Duration::days(1);
+    }
+    Window { start, end }
+}
+
+/// The run of `schedule` in progress at `now`, if any. Unparseable times
+/// never run.
+pub fn active_window(schedule: &RecordingSchedule, now: NaiveDateTime) -> Option<Window> {
+    let (start, end) = (parse_time(&schedule.start)?, parse_time(&schedule.end)?);
+    // A run started today, or one started yesterday that's still going past
+    // midnight
+    [now.date(), now.date() - Duration::days(1)]
+        .into_iter()
+        .filter(|date| runs_on(schedule, date.weekday()))
+        .map(|date| window_on(date, start, end))
+        .find(|window| window.start <= now && now < window.end)
+}
+
+/// The next run of `schedule` starting after `now`, within a week
+pub fn next_window(schedule: &RecordingSchedule, now: NaiveDateTime) -> Option<Window> {
+    let (start, end) = (parse_time(&schedule.start)?, parse_time(&schedule.end)?);
+    (0..=7)
+        .map(|days| now.date() + Duration::days(days))
+        .filter(|date| runs_on(schedule, date.weekday()))
+        .map(|date| window_on(date, start, end))
+        .find(|window| window.start > now)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(days: &[&str], start: &str, end: &str) -> RecordingSchedule {
+        RecordingSchedule {
+            station: "groovesalad".to_string(),
+            days: days.iter().map(|d| d.to_string()).collect(),
+            start: start.to_string(),
+            end: end.to_string(),
+            play: false,
+        }
+    }
+
+    fn at(day: u32, time: &str) -> NaiveDateTime {
+        // 2024-01-04 is a Thursday
+        NaiveDate::from_ymd_opt(2024, 1, day)
+            .unwrap()
+            .and_time(parse_time(time).unwrap())
+    }
+
+    #[test]
+    fn test_windows() {
+        let thursday = schedule(&["thu"], "20:00", "22:00");
+        assert!(active_window(&thursday, at(4, "19:59")).is_none());
+        assert_eq!(
+            active_window(&thursday, at(4, "21:00")).map(|w| w.end),
+            Some(at(4, "22:00"))
+        );
+        assert!(active_window(&thursday, at(5, "21:00")).is_none());
+        assert_eq!(
+            next_window(&thursday, at(5, "21:00")).map(|w| w.start),
+            Some(at(11, "20:00"))
+        );
+
+        // Past midnight, started on the previous day
+        let late = schedule(&["thu"], "23:00", "01:00");
+        assert_eq!(
+            active_window(&late, at(5, "00:30")).map(|w| w.start),
+            Some(at(4, "23:00"))
+        );
+        assert!(active_window(&schedule(&[], "bad", "01:00"), at(4, "00:30")).is_none());
+    }
+}
@@ -2,21 +2,582 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use crate::ui::{ThemeType, VisualizationMode};
+use crate::api::http::NetworkSettings;
+use crate::api::{AudioQuality, Channel, StationSource, Track};
+use crate::notify::NotifyVia;
+use crate::player::VolumeCurve;
+use crate::ui::{terminal_is_light, ImageProtocol, ThemeType, VisualizationMode};
+use crate::visualizer::{SpectrumSettings, MAX_BINS, MIN_BINS};
+
+use super::sync::SyncTarget;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct Config {
     #[serde(default)]
     pub theme: String,
+    /// Colors to use instead of the theme's in one widget, by widget name
+    /// then color slot, e.g. a monochrome visualizer
+    #[serde(default)]
+    pub theme_overrides: HashMap<String, HashMap<String, String>>,
     #[serde(default)]
     pub visualization: String,
+    /// Station list order, e.g. "alphabetical"
+    #[serde(default)]
+    pub sort: String,
+    /// Visualization modes skipped when cycling with `v`
+    #[serde(default)]
+    pub disabled_visualizations: Vec<String>,
+    #[serde(default)]
+    pub ui: UiConfig,
+    #[serde(default)]
+    pub layout: LayoutConfig,
+    #[serde(default)]
+    pub visualizer: VisualizerConfig,
+    #[serde(default)]
+    pub surf: SurfConfig,
+    #[serde(default)]
+    pub lastfm: LastFmConfig,
+    #[serde(default)]
+    pub musicbrainz: MusicBrainzConfig,
+    #[serde(default)]
+    pub recording: RecordingConfig,
+    #[serde(default)]
+    pub audio: AudioConfig,
+    #[serde(default)]
+    pub daemon: DaemonConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub polling: PollingConfig,
+    #[serde(default)]
+    pub sync: SyncConfig,
+    #[serde(default)]
+    pub watch: WatchConfig,
+    /// Stations to list alongside SomaFM's
+    #[serde(default)]
+    pub custom_stations: Vec<CustomStation>,
+}
+
+/// A station of the user's own, e.g. an internet radio stream elsewhere
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomStation {
+    /// Unique id; one SomaFM already uses hides the station
+    pub id: String,
+    pub title: String,
+    /// Stream URL, played as it is
+    pub url: String,
+    #[serde(default)]
+    pub genre: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// Artists and tracks to hear about when a favorite station plays them,
+/// under the `watch` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WatchConfig {
+    /// Artist names, matched ignoring case and punctuation
+    #[serde(default)]
+    pub artists: Vec<String>,
+    /// Tracks as "Artist - Title"
+    #[serde(default)]
+    pub tracks: Vec<String>,
+}
+
+/// How often workers check for updates, under the `polling` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PollingConfig {
+    /// Seconds between songs API requests
+    #[serde(default = "default_songs_secs")]
+    pub songs_secs: u64,
+    /// Seconds between songs API requests once the app has been idle or in
+    /// the background for a while
+    #[serde(default = "default_idle_songs_secs")]
+    pub idle_songs_secs: u64,
+    /// Milliseconds between audio level reads from mpv
+    #[serde(default = "default_audio_ms")]
+    pub audio_ms: u64,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            songs_secs: default_songs_secs(),
+            idle_songs_secs: default_idle_songs_secs(),
+            audio_ms: default_audio_ms(),
+        }
+    }
+}
+
+fn default_songs_secs() -> u64 {
+    10
+}
+
+fn default_idle_songs_secs() -> u64 {
+    60
+}
+
+fn default_audio_ms() -> u64 {
+    50
+}
+
+/// Outgoing connections, under the `network` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Try IPv4 addresses before IPv6, for ISPs with broken IPv6 routes to
+    /// stream hosts
+    #[serde(default)]
+    pub prefer_ipv4: bool,
+    /// Seconds a DNS lookup may take before the request fails
+    #[serde(default = "default_dns_timeout_secs")]
+    pub dns_timeout_secs: f32,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            prefer_ipv4: false,
+            dns_timeout_secs: default_dns_timeout_secs(),
+        }
+    }
+}
+
+fn default_dns_timeout_secs() -> f32 {
+    5.0
+}
+
+/// Sharing favorites between machines, under the `sync` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SyncConfig {
+    /// File in a folder another tool keeps in sync, e.g. Syncthing or
+    /// Dropbox
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+    /// URL of the file on a WebDAV server, used when `path` isn't set
+    #[serde(default)]
+    pub webdav_url: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    /// WebDAV password; the `VIBECAST_SYNC_PASSWORD` environment variable
+    /// takes precedence
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+/// Headless running with `vibecast daemon`, under the `daemon` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct DaemonConfig {
+    /// Localhost port to serve Prometheus metrics on; off if unset
+    #[serde(default)]
+    pub metrics_port: Option<u16>,
+}
+
+/// Volume control, under the `audio` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioConfig {
+    /// Percentage points per `+` / `-` press
+    #[serde(default = "default_volume_step")]
+    pub volume_step: u8,
+    /// How the volume setting maps onto mpv's volume
+    #[serde(default)]
+    pub volume_curve: VolumeCurve,
+    /// What happens to playback while the terminal is in the background
+    #[serde(default)]
+    pub on_focus_loss: FocusLoss,
+    /// Stream quality to play at
+    #[serde(default)]
+    pub quality: AudioQuality,
+    /// Share of the volume kept when ducking, in percent
+    #[serde(default = "default_duck_percent")]
+    pub duck_percent: u8,
+    /// Pause when an output device goes away, rather than carrying on
+    /// through whatever the system switches to
+    #[serde(default = "default_true")]
+    pub pause_on_device_removal: bool,
+    /// FIFO a Snapcast server reads from; audio goes there instead of the
+    /// sound card when set
+    #[serde(default)]
+    pub snapcast_fifo: Option<PathBuf>,
+    /// Read track titles from the stream's ICY metadata over a second
+    /// connection, rather than polling mpv
+    #[serde(default = "default_true")]
+    pub icy_metadata: bool,
+    /// Seconds to hold back now playing updates, for streams that reach the
+    /// speakers later than their metadata
+    #[serde(default)]
+    pub track_delay_secs: f32,
+    /// Stream URLs to play instead of SomaFM's, by channel id, e.g. a local
+    /// Icecast relay
+    #[serde(default)]
+    pub stream_overrides: HashMap<String, String>,
+    /// Hours of playback without a key press before asking whether anyone's
+    /// still listening; 0 never asks
+    #[serde(default)]
+    pub idle_check_hours: f32,
+}
+
+/// Playback change while the terminal doesn't have focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FocusLoss {
+    #[default]
+    Nothing,
+    /// Lower the volume
+    Duck,
+    Mute,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            volume_step: default_volume_step(),
+            volume_curve: VolumeCurve::default(),
+            on_focus_loss: FocusLoss::default(),
+            quality: AudioQuality::default(),
+            duck_percent: default_duck_percent(),
+            pause_on_device_removal: true,
+            snapcast_fifo: None,
+            icy_metadata: true,
+            track_delay_secs: 0.0,
+            stream_overrides: HashMap::new(),
+            idle_check_hours: 0.0,
+        }
+    }
+}
+
+fn default_volume_step() -> u8 {
+    5
+}
+
+fn default_duck_percent() -> u8 {
+    30
+}
+
+/// Stream recording, under the `recording` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct RecordingConfig {
+    /// Where recordings are saved; defaults to a `vibecast` folder in the
+    /// user's music directory
+    #[serde(default)]
+    pub directory: Option<PathBuf>,
+    /// Recordings made automatically at set times
+    #[serde(default)]
+    pub schedules: Vec<RecordingSchedule>,
+    /// Most the recordings may take up, in megabytes; the oldest are
+    /// deleted to stay under it
+    #[serde(default)]
+    pub max_disk_usage_mb: Option<u64>,
+}
+
+/// A weekly recording window, e.g. Groove Salad on Thursdays 20:00-22:00
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingSchedule {
+    /// Station id, e.g. "groovesalad"
+    pub station: String,
+    /// Days it starts on ("mon" to "sun"); every day if empty
+    #[serde(default)]
+    pub days: Vec<String>,
+    /// Local start time, "HH:MM"
+    pub start: String,
+    /// Local end time, "HH:MM"; an end before the start runs past midnight
+    pub end: String,
+    /// Also switch playback to the station while it records
+    #[serde(default)]
+    pub play: bool,
+}
+
+/// Last.fm history import, under the `lastfm` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LastFmConfig {
+    /// API key for reading scrobbles; `LASTFM_API_KEY` takes precedence
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Unix time of the newest scrobble imported, so the next import only
+    /// fetches later ones
+    #[serde(default)]
+    pub imported_until: i64,
+}
+
+/// Release details from MusicBrainz, under the `musicbrainz` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct MusicBrainzConfig {
+    /// Look up the album, year and label of each track
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+/// Random station picks and surf mode, under the `surf` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurfConfig {
+    /// Only pick from favorite stations
+    #[serde(default)]
+    pub favorites_only: bool,
+    /// Only pick stations whose genre contains this text
+    #[serde(default)]
+    pub genre: Option<String>,
+    /// Minutes between station changes in surf mode
+    #[serde(default = "default_surf_minutes")]
+    pub interval_minutes: u64,
+    /// Minutes before moving on to the next queued station; 0 waits for `n`
+    #[serde(default)]
+    pub queue_minutes: u64,
+}
+
+impl Default for SurfConfig {
+    fn default() -> Self {
+        Self {
+            favorites_only: false,
+            genre: None,
+            interval_minutes: default_surf_minutes(),
+            queue_minutes: 0,
+        }
+    }
+}
+
+fn default_surf_minutes() -> u64 {
+    10
+}
+
+/// Visualizer sensitivity under the `visualizer` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VisualizerConfig {
+    /// Boost applied to audio levels, in dB
+    #[serde(default)]
+    pub gain_db: f32,
+    /// Level treated as silence, in dB
+    #[serde(default = "default_floor_db")]
+    pub floor_db: f32,
+    /// Smoothing kept when levels rise, 0.0 (instant) to 0.95
+    #[serde(default = "default_attack")]
+    pub attack: f32,
+    /// Smoothing kept when levels fall, 0.0 (instant) to 0.99
+    #[serde(default = "default_decay")]
+    pub decay: f32,
+    /// Frequency bins drawn by the bars and waterfall, 8 to 128
+    #[serde(default = "default_bins")]
+    pub bins: usize,
+}
+
+impl Default for VisualizerConfig {
+    fn default() -> Self {
+        let settings = SpectrumSettings::default();
+        Self {
+            gain_db: settings.gain_db,
+            floor_db: settings.floor_db,
+            attack: settings.attack,
+            decay: settings.decay,
+            bins: settings.bins,
+        }
+    }
+}
+
+fn default_floor_db() -> f32 {
+    SpectrumSettings::default().floor_db
+}
+
+fn default_attack() -> f32 {
+    SpectrumSettings::default().attack
+}
+
+fn default_decay() -> f32 {
+    SpectrumSettings::default().decay
+}
+
+fn default_bins() -> usize {
+    SpectrumSettings::default().bins
+}
+
+/// Right-hand panels that can be arranged via `layout.panels`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelKind {
+    NowPlaying,
+    History,
+    Visualizer,
+    VuMeter,
+    /// Upcoming scheduled recordings
+    Schedule,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanelConfig {
+    pub kind: PanelKind,
+    /// Fixed height in rows; `None` lets the panel fill the remaining space
+    #[serde(default)]
+    pub height: Option<u16>,
+    /// Whether the panel is shown at startup
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Screen layout under the `layout` key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    /// Width of the station list as a percentage of the screen
+    #[serde(default = "default_station_list_percent")]
+    pub station_list_percent: u16,
+    /// Right-hand panels, top to bottom
+    #[serde(default = "default_panels")]
+    pub panels: Vec<PanelConfig>,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            station_list_percent: default_station_list_percent(),
+            panels: default_panels(),
+        }
+    }
+}
+
+impl LayoutConfig {
+    pub fn station_list_percent(&self) -> u16 {
+        self.station_list_percent.clamp(10, 90)
+    }
+
+    pub fn panel(&self, kind: PanelKind) -> Option<&PanelConfig> {
+        self.panels.iter().find(|p| p.kind == kind)
+    }
+
+    /// Grow or shrink the station list by `delta` percentage points
+    pub fn resize_station_list(&mut self, delta: i16) {
+        let percent = self.station_list_percent() as i16 + delta;
+        self.station_list_percent = percent.clamp(10, 90) as u16;
+    }
+
+    /// Grow or shrink a fixed-height panel by `delta` rows. Panels that fill
+    /// the remaining space are left alone.
+    pub fn resize_panel(&mut self, kind: PanelKind, delta: i16) {
+        if let Some(height) = self
+            .panels
+            .iter_mut()
+            .find(|p| p.kind == kind)
+            .and_then(|p| p.height.as_mut())
+        {
+            *height = (*height as i16 + delta).clamp(4, 40) as u16;
+        }
+    }
+
+    /// Whether a panel is configured and enabled at startup
+    pub fn starts_enabled(&self, kind: PanelKind) -> bool {
+        self.panel(kind).is_some_and(|p| p.enabled)
+    }
+
+    /// Show a panel at startup or not, adding it at the bottom if it isn't
+    /// configured, as it's drawn when toggled on
+    pub fn set_enabled(&mut self, kind: PanelKind, enabled: bool) {
+        match self.panels.iter_mut().find(|p| p.kind == kind) {
+            Some(panel) => panel.enabled = enabled,
+            None if enabled => self.panels.push(PanelConfig {
+                kind,
+                height: (kind == PanelKind::VuMeter).then_some(5),
+                enabled,
+            }),
+            None => {}
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_station_list_percent() -> u16 {
+    35
 }
 
+fn default_panels() -> Vec<PanelConfig> {
+    vec![
+        PanelConfig {
+            kind: PanelKind::NowPlaying,
+            height: None,
+            enabled: true,
+        },
+        PanelConfig {
+            kind: PanelKind::History,
+            height: Some(8),
+            enabled: true,
+        },
+        PanelConfig {
+            kind: PanelKind::Visualizer,
+            height: Some(12),
+            enabled: true,
+        },
+        PanelConfig {
+            kind: PanelKind::VuMeter,
+            height: Some(5),
+            enabled: false,
+        },
+    ]
+}
+
+/// Display options under the `ui` key
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct UiConfig {
+    /// Tint accent/highlight colors from the current artwork
+    #[serde(default)]
+    pub dynamic_colors: bool,
+    /// Replace animated visualizations with a still level meter
+    #[serde(default)]
+    pub reduced_motion: bool,
+    /// Plain line-based output for terminal screen readers
+    #[serde(default)]
+    pub screen_reader: bool,
+    /// Replace Unicode glyphs (stars, bars, box drawing) with plain ASCII
+    #[serde(default)]
+    pub ascii: bool,
+    /// Desktop notification on each track change
+    #[serde(default)]
+    pub notifications: bool,
+    /// How notifications are shown
+    #[serde(default)]
+    pub notify_via: NotifyVia,
+    /// Show station artwork; on unless turned off
+    #[serde(default)]
+    pub show_artwork: Option<bool>,
+    /// Force an image protocol instead of detecting one
+    #[serde(default)]
+    pub image_protocol: Option<ImageProtocol>,
+    /// Terminal title while running; empty leaves the title alone
+    #[serde(default)]
+    pub title_template: Option<String>,
+    /// Language for messages and help, e.g. "es"; taken from `LANG` if unset
+    #[serde(default)]
+    pub language: Option<String>,
+    /// New-user tips are done with, either dismissed or shown for enough
+    /// sessions
+    #[serde(default)]
+    pub hints_shown: bool,
+    /// Sessions the tips have been shown in
+    #[serde(default)]
+    pub hint_sessions: u32,
+    /// What the status bar shows, as `|`-separated sections of tokens
+    #[serde(default)]
+    pub status_template: Option<String>,
+    /// Default to a light theme; detected from the terminal if unset
+    #[serde(default)]
+    pub prefer_light: Option<bool>,
+}
+
+/// Terminal title used unless `ui.title_template` is set
+pub const DEFAULT_TITLE_TEMPLATE: &str = "{state} {artist} – {title} · {station} — vibecast";
+
+/// Sessions new-user tips are shown in before they stop by themselves
+const HINT_SESSIONS: u32 = 3;
+
+/// Status bar layout used unless `ui.status_template` is set
+pub const DEFAULT_STATUS_TEMPLATE: &str =
+    "{state} | {volume} | {theme} | {rec} | {behind_live} | {surf} | {queue} | {hints}";
+
 pub struct ConfigStore {
     path: PathBuf,
     pub config: Config,
+    /// Modification time of the file as last read or written
+    modified: Option<SystemTime>,
 }
 
 impl ConfigStore {
@@ -29,51 +590,375 @@ impl ConfigStore {
         } else {
             Config::default()
         };
+        let modified = modified_time(&path);
 
-        Ok(Self { path, config })
+        Ok(Self {
+            path,
+            config,
+            modified,
+        })
     }
 
     fn config_path() -> Result<PathBuf> {
-        let config_dir = directories::ProjectDirs::from("com", "vibecast", "vibecast")
-            .map(|dirs| dirs.config_dir().to_path_buf())
-            .or_else(|| directories::BaseDirs::new().map(|d| d.config_dir().join("vibecast")))
-            .unwrap_or_else(|| PathBuf::from(".").join("vibecast"));
+        let config_dir = super::config_dir();
 
         std::fs::create_dir_all(&config_dir)?;
         Ok(config_dir.join("config.json"))
     }
 
-    pub fn save(&self) -> Result<()> {
+    pub fn save(&mut self) -> Result<()> {
         if let Some(parent) = self.path.parent() {
             std::fs::create_dir_all(parent)?;
         }
         let content = serde_json::to_string_pretty(&self.config)?;
         std::fs::write(&self.path, content)?;
+        self.modified = modified_time(&self.path);
         Ok(())
     }
 
-    pub fn theme_type(&self) -> ThemeType {
-        match self.config.theme.as_str() {
-            "Synthwave" => ThemeType::Synthwave,
-            "Ocean" => ThemeType::Ocean,
-            "Forest" => ThemeType::Forest,
-            "Sunset" => ThemeType::Sunset,
-            "Mono" => ThemeType::Monochrome,
-            "Cyberpunk" => ThemeType::Cyberpunk,
-            _ => ThemeType::default(),
+    /// Re-read the file if something else has changed it since it was last
+    /// read or written. A file that no longer parses is left for later.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return false;
         }
+        self.modified = modified;
+        let Some(config) = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+        else {
+            return false;
+        };
+        self.config = config;
+        true
+    }
+
+    pub fn theme_type(&self) -> ThemeType {
+        ThemeType::from_name(&self.config.theme).unwrap_or_default()
     }
 
     pub fn set_theme(&mut self, theme_type: ThemeType) {
-        self.config.theme = match theme_type {
-            ThemeType::Synthwave => "Synthwave",
-            ThemeType::Ocean => "Ocean",
-            ThemeType::Forest => "Forest",
-            ThemeType::Sunset => "Sunset",
-            ThemeType::Monochrome => "Mono",
-            ThemeType::Cyberpunk => "Cyberpunk",
+        self.config.theme = theme_type.name().to_string();
+    }
+
+    /// Name of the saved theme, which may refer to a custom theme file
+    pub fn theme_name(&self) -> &str {
+        &self.config.theme
+    }
+
+    pub fn set_theme_name(&mut self, name: &str) {
+        self.config.theme = name.to_string();
+    }
+
+    pub fn theme_overrides(&self) -> &HashMap<String, HashMap<String, String>> {
+        &self.config.theme_overrides
+    }
+
+    pub fn ascii_ui(&self) -> bool {
+        self.config.ui.ascii
+    }
+
+    pub fn dynamic_colors(&self) -> bool {
+        self.config.ui.dynamic_colors
+    }
+
+    pub fn reduced_motion(&self) -> bool {
+        self.config.ui.reduced_motion
+    }
+
+    pub fn screen_reader(&self) -> bool {
+        self.config.ui.screen_reader
+    }
+
+    /// Template for the terminal title, or `None` to leave it alone
+    pub fn title_template(&self) -> Option<&str> {
+        match self.config.ui.title_template.as_deref() {
+            Some("") => None,
+            Some(template) => Some(template),
+            None => Some(DEFAULT_TITLE_TEMPLATE),
+        }
+    }
+
+    /// Count a session towards the new-user tips, returning whether this one
+    /// shows them
+    pub fn begin_hint_session(&mut self) -> bool {
+        let ui = &mut self.config.ui;
+        if ui.hints_shown {
+            return false;
+        }
+        ui.hint_sessions += 1;
+        ui.hints_shown = ui.hint_sessions >= HINT_SESSIONS;
+        true
+    }
+
+    pub fn set_hints_shown(&mut self) {
+        self.config.ui.hints_shown = true;
+    }
+
+    pub fn language(&self) -> Option<&str> {
+        self.config.ui.language.as_deref()
+    }
+
+    pub fn status_template(&self) -> &str {
+        self.config
+            .ui
+            .status_template
+            .as_deref()
+            .unwrap_or(DEFAULT_STATUS_TEMPLATE)
+    }
+
+    pub fn notifications(&self) -> bool {
+        self.config.ui.notifications
+    }
+
+    pub fn set_notifications(&mut self, on: bool) {
+        self.config.ui.notifications = on;
+    }
+
+    pub fn notify_via(&self) -> NotifyVia {
+        self.config.ui.notify_via
+    }
+
+    pub fn set_notify_via(&mut self, via: NotifyVia) {
+        self.config.ui.notify_via = via;
+    }
+
+    pub fn show_artwork(&self) -> bool {
+        self.config.ui.show_artwork.unwrap_or(true)
+    }
+
+    /// Whether the terminal has a light background, as configured or else
+    /// as the terminal reports
+    pub fn prefer_light(&self) -> bool {
+        self.config
+            .ui
+            .prefer_light
+            .or_else(terminal_is_light)
+            .unwrap_or(false)
+    }
+
+    pub fn set_show_artwork(&mut self, show: bool) {
+        self.config.ui.show_artwork = Some(show);
+    }
+
+    pub fn audio_quality(&self) -> AudioQuality {
+        self.config.audio.quality
+    }
+
+    pub fn set_audio_quality(&mut self, quality: AudioQuality) {
+        self.config.audio.quality = quality;
+    }
+
+    /// Visualizer sensitivity, clamped to usable ranges
+    pub fn spectrum_settings(&self) -> SpectrumSettings {
+        let v = &self.config.visualizer;
+        SpectrumSettings {
+            gain_db: v.gain_db.clamp(-12.0, 30.0),
+            floor_db: v.floor_db.clamp(-90.0, -24.0),
+            attack: v.attack.clamp(0.0, 0.95),
+            decay: v.decay.clamp(0.0, 0.99),
+            bins: v.bins.clamp(MIN_BINS, MAX_BINS),
+        }
+    }
+
+    pub fn set_spectrum_settings(&mut self, settings: SpectrumSettings) {
+        self.config.visualizer = VisualizerConfig {
+            gain_db: settings.gain_db,
+            floor_db: settings.floor_db,
+            attack: settings.attack,
+            decay: settings.decay,
+            bins: settings.bins,
+        };
+    }
+
+    /// Directory recordings are written to and listed from
+    pub fn recordings_dir(&self) -> PathBuf {
+        if let Some(dir) = &self.config.recording.directory {
+            return dir.clone();
         }
-        .to_string();
+        directories::UserDirs::new()
+            .and_then(|dirs| dirs.audio_dir().map(|dir| dir.join("vibecast")))
+            .or_else(|| {
+                directories::ProjectDirs::from("com", "vibecast", "vibecast")
+                    .map(|dirs| dirs.data_dir().join("recordings"))
+            })
+            .unwrap_or_else(|| PathBuf::from("recordings"))
+    }
+
+    pub fn recording_schedules(&self) -> &[RecordingSchedule] {
+        &self.config.recording.schedules
+    }
+
+    /// Disk quota for recordings in bytes, if one is set
+    pub fn recording_quota(&self) -> Option<u64> {
+        self.config
+            .recording
+            .max_disk_usage_mb
+            .filter(|&mb| mb > 0)
+            .map(|mb| mb * 1_000_000)
+    }
+
+    /// Volume change per keypress, 1-50
+    pub fn volume_step(&self) -> u8 {
+        self.config.audio.volume_step.clamp(1, 50)
+    }
+
+    pub fn volume_curve(&self) -> VolumeCurve {
+        self.config.audio.volume_curve
+    }
+
+    pub fn track_delay(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f32(self.config.audio.track_delay_secs.clamp(0.0, 60.0))
+    }
+
+    /// Stream URL configured in place of SomaFM's for a channel
+    pub fn stream_override(&self, channel_id: &str) -> Option<&str> {
+        self.config
+            .audio
+            .stream_overrides
+            .get(channel_id)
+            .map(String::as_str)
+            .filter(|url| !url.is_empty())
+    }
+
+    /// Playback without a key press before the "still listening?" prompt
+    pub fn idle_check(&self) -> Option<std::time::Duration> {
+        let hours = self.config.audio.idle_check_hours;
+        (hours > 0.0).then(|| std::time::Duration::from_secs_f32(hours.min(48.0) * 3600.0))
+    }
+
+    pub fn icy_metadata(&self) -> bool {
+        self.config.audio.icy_metadata
+    }
+
+    pub fn snapcast_fifo(&self) -> Option<PathBuf> {
+        self.config.audio.snapcast_fifo.clone()
+    }
+
+    pub fn pause_on_device_removal(&self) -> bool {
+        self.config.audio.pause_on_device_removal
+    }
+
+    pub fn focus_loss(&self) -> FocusLoss {
+        self.config.audio.on_focus_loss
+    }
+
+    /// Volume to duck `volume` to while unfocused
+    pub fn ducked_volume(&self, volume: u8) -> u8 {
+        (volume as u16 * self.config.audio.duck_percent.min(100) as u16 / 100) as u8
+    }
+
+    pub fn musicbrainz_enabled(&self) -> bool {
+        self.config.musicbrainz.enabled
+    }
+
+    /// Network settings, with the DNS timeout between 1 and 30 seconds
+    pub fn network(&self) -> NetworkSettings {
+        let network = &self.config.network;
+        NetworkSettings {
+            prefer_ipv4: network.prefer_ipv4,
+            dns_timeout: std::time::Duration::from_secs_f32(
+                network.dns_timeout_secs.clamp(1.0, 30.0),
+            ),
+        }
+    }
+
+    /// Whether anything is on the watch list
+    pub fn watching(&self) -> bool {
+        !self.config.watch.artists.is_empty() || !self.config.watch.tracks.is_empty()
+    }
+
+    /// Whether `track` is by a watched artist or is a watched track
+    pub fn is_watched(&self, track: &Track) -> bool {
+        let watch = &self.config.watch;
+        watch.artists.iter().any(|artist| track.by_artist(artist))
+            || watch.tracks.iter().any(|entry| track.is_entry(entry))
+    }
+
+    /// Watch the artist of `track`, or stop watching them. Returns whether
+    /// they're now watched.
+    pub fn toggle_watched_artist(&mut self, track: &Track) -> bool {
+        let artists = &mut self.config.watch.artists;
+        let before = artists.len();
+        artists.retain(|artist| !track.by_artist(artist));
+        if artists.len() == before {
+            artists.push(track.artist.clone());
+        }
+        artists.len() > before
+    }
+
+    /// Watch `track`, or stop watching it. Returns whether it's now watched.
+    pub fn toggle_watched_track(&mut self, track: &Track) -> bool {
+        let tracks = &mut self.config.watch.tracks;
+        let before = tracks.len();
+        tracks.retain(|entry| !track.is_entry(entry));
+        if tracks.len() == before {
+            tracks.push(track.entry());
+        }
+        tracks.len() > before
+    }
+
+    /// Where favorites are synced to, if anywhere
+    pub fn sync_target(&self) -> Option<SyncTarget> {
+        let sync = &self.config.sync;
+        if let Some(path) = &sync.path {
+            return Some(SyncTarget::Path(path.clone()));
+        }
+        Some(SyncTarget::WebDav {
+            url: sync.webdav_url.clone()?,
+            username: sync.username.clone(),
+            password: std::env::var("VIBECAST_SYNC_PASSWORD")
+                .ok()
+                .or_else(|| sync.password.clone()),
+        })
+    }
+
+    /// Songs API poll interval, 5 seconds to 5 minutes
+    pub fn song_poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.polling.songs_secs.clamp(5, 300))
+    }
+
+    /// Songs API poll interval while idle, never shorter than the usual one
+    /// and at most 10 minutes
+    pub fn idle_song_poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.polling.idle_songs_secs.clamp(5, 600))
+            .max(self.song_poll_interval())
+    }
+
+    /// Audio level poll interval, 20 ms to 1 second
+    pub fn audio_poll_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(self.config.polling.audio_ms.clamp(20, 1000))
+    }
+
+    pub fn metrics_port(&self) -> Option<u16> {
+        self.config.daemon.metrics_port
+    }
+
+    pub fn surf(&self) -> &SurfConfig {
+        &self.config.surf
+    }
+
+    /// Time between station changes in surf mode, at least a minute
+    pub fn surf_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.config.surf.interval_minutes.max(1) * 60)
+    }
+
+    /// Time each queued station plays before the next, if the queue moves
+    /// on by itself
+    pub fn queue_interval(&self) -> Option<std::time::Duration> {
+        match self.config.surf.queue_minutes {
+            0 => None,
+            minutes => Some(std::time::Duration::from_secs(minutes * 60)),
+        }
+    }
+
+    pub fn layout(&self) -> &LayoutConfig {
+        &self.config.layout
+    }
+
+    pub fn layout_mut(&mut self) -> &mut LayoutConfig {
+        &mut self.config.layout
     }
 
     pub fn visualization_mode(&self) -> VisualizationMode {
@@ -86,10 +971,70 @@ impl ConfigStore {
             "Heart" => VisualizationMode::Heart,
             "Spiral" => VisualizationMode::Spiral,
             "Rain" => VisualizationMode::Rain,
+            "Bars" => VisualizationMode::Bars,
+            "Waterfall" => VisualizationMode::Waterfall,
             _ => VisualizationMode::Spiral, // Default to Spiral
         }
     }
 
+    /// Name of the saved visualization, which may refer to a plugin script
+    pub fn visualization_name(&self) -> &str {
+        &self.config.visualization
+    }
+
+    pub fn set_visualization_name(&mut self, name: &str) {
+        self.config.visualization = name.to_string();
+    }
+
+    /// Whether a visualization (by name) is part of the `v` rotation
+    pub fn visualization_enabled(&self, name: &str) -> bool {
+        !self
+            .config
+            .disabled_visualizations
+            .iter()
+            .any(|disabled| disabled.eq_ignore_ascii_case(name))
+    }
+
+    pub fn set_visualization_enabled(&mut self, name: &str, enabled: bool) {
+        let disabled = &mut self.config.disabled_visualizations;
+        disabled.retain(|d| !d.eq_ignore_ascii_case(name));
+        if !enabled {
+            disabled.push(name.to_string());
+        }
+    }
+
+    /// The configured custom stations, as channels to list
+    pub fn custom_stations(&self) -> Vec<Channel> {
+        self.config
+            .custom_stations
+            .iter()
+            .map(|station| Channel {
+                id: station.id.clone(),
+                title: station.title.clone(),
+                description: station.description.clone(),
+                genre: station.genre.clone(),
+                dj: String::new(),
+                djmail: None,
+                listeners: 0,
+                image: String::new(),
+                largeimage: String::new(),
+                xlimage: None,
+                last_playing: String::new(),
+                playlists: Vec::new(),
+                stream_override: Some(station.url.clone()),
+                source: StationSource::Custom,
+            })
+            .collect()
+    }
+
+    pub fn sort_name(&self) -> &str {
+        &self.config.sort
+    }
+
+    pub fn set_sort_name(&mut self, name: &str) {
+        self.config.sort = name.to_string();
+    }
+
     pub fn set_visualization(&mut self, mode: VisualizationMode) {
         self.config.visualization = mode.name().to_string();
     }
@@ -100,6 +1045,12 @@ impl Default for ConfigStore {
         Self::load().unwrap_or_else(|_| Self {
             path: PathBuf::from("config.json"),
             config: Config::default(),
+            modified: None,
         })
     }
 }
+
+/// When a file was last written, `None` if it's missing
+pub(super) fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
@@ -1,12 +1,33 @@
 #![allow(dead_code)]
 
 use anyhow::Result;
-use std::collections::HashSet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
+use std::time::SystemTime;
 
+use super::config::modified_time;
+use super::sync::{Change, SyncDoc};
+
+/// Favorite station ids, in the order the user put them
 pub struct FavoritesStore {
     path: PathBuf,
-    favorites: HashSet<String>,
+    favorites: Vec<String>,
+    /// When each station was last added or removed, for sync
+    changes: BTreeMap<String, Change>,
+    /// When the favorites were last reordered, in unix milliseconds
+    order_updated: i64,
+    /// Modification time of `favorites.json` as last read or written
+    modified: Option<SystemTime>,
+}
+
+/// What sync needs beyond the favorites themselves, kept in
+/// `favorites-sync.json` so `favorites.json` stays a plain list
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct SyncState {
+    order_updated: i64,
+    changes: BTreeMap<String, Change>,
 }
 
 impl FavoritesStore {
@@ -15,57 +36,209 @@ impl FavoritesStore {
 
         let favorites = if path.exists() {
             let content = std::fs::read_to_string(&path)?;
-            serde_json::from_str(&content).unwrap_or_default()
+            Self::parse(&content).unwrap_or_default()
         } else {
-            HashSet::new()
+            Vec::new()
         };
+        let state: SyncState = std::fs::read_to_string(path.with_file_name("favorites-sync.json"))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default();
 
-        Ok(Self { path, favorites })
+        let mut store = Self {
+            modified: modified_time(&path),
+            path,
+            favorites,
+            changes: state.changes,
+            order_updated: state.order_updated,
+        };
+        // Favorites from before sync count as added at the start of time
+        for id in &store.favorites {
+            store.changes.entry(id.clone()).or_insert(Change {
+                favorite: true,
+                at: 0,
+            });
+        }
+        Ok(store)
     }
 
     fn config_path() -> Result<PathBuf> {
-        let config_dir = directories::ProjectDirs::from("com", "vibecast", "vibecast")
-            .map(|dirs| dirs.config_dir().to_path_buf())
-            .or_else(|| directories::BaseDirs::new().map(|d| d.config_dir().join("vibecast")))
-            .unwrap_or_else(|| PathBuf::from(".").join("vibecast"));
+        let config_dir = super::config_dir();
 
         std::fs::create_dir_all(&config_dir)?;
         Ok(config_dir.join("favorites.json"))
     }
 
-    pub fn save(&self) -> Result<()> {
+    /// The ids in `favorites.json`. Older versions saved an unordered set;
+    /// it loads in file order.
+    fn parse(content: &str) -> Option<Vec<String>> {
+        let mut favorites: Vec<String> = serde_json::from_str(content).ok()?;
+        let mut seen = std::collections::HashSet::new();
+        favorites.retain(|id| seen.insert(id.clone()));
+        Some(favorites)
+    }
+
+    /// Re-read `favorites.json` if something else has changed it since it
+    /// was last read or written, returning whether the favorites changed.
+    /// Stations added or removed there count as changed now, for sync.
+    pub fn reload_if_changed(&mut self) -> bool {
+        let modified = modified_time(&self.path);
+        if modified == self.modified {
+            return false;
+        }
+        self.modified = modified;
+        let Some(favorites) = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| Self::parse(&content))
+        else {
+            return false;
+        };
+        if favorites == self.favorites {
+            return false;
+        }
+
+        let at = chrono::Utc::now().timestamp_millis();
+        for id in favorites.iter().filter(|id| !self.favorites.contains(id)) {
+            self.changes
+                .insert(id.clone(), Change { favorite: true, at });
+        }
+        for id in self.favorites.iter().filter(|id| !favorites.contains(id)) {
+            self.changes.insert(
+                id.clone(),
+                Change {
+                    favorite: false,
+                    at,
+                },
+            );
+        }
+        self.order_updated = at;
+        self.favorites = favorites;
+        true
+    }
+
+    pub fn save(&mut self) -> Result<()> {
         if let Some(parent) = self.path.parent() {
             std::fs::create_dir_all(parent)?;
         }
         let content = serde_json::to_string_pretty(&self.favorites)?;
         std::fs::write(&self.path, content)?;
+        let state = SyncState {
+            order_updated: self.order_updated,
+            changes: self.changes.clone(),
+        };
+        std::fs::write(
+            self.path.with_file_name("favorites-sync.json"),
+            serde_json::to_string_pretty(&state)?,
+        )?;
+        self.modified = modified_time(&self.path);
         Ok(())
     }
 
+    /// Add a station at the end of the favorites, or remove it, returning
+    /// whether it's now a favorite
     pub fn toggle(&mut self, station_id: &str) -> bool {
-        if self.favorites.contains(station_id) {
-            self.favorites.remove(station_id);
+        let favorite = if let Some(position) = self.position(station_id) {
+            self.favorites.remove(position);
             false
         } else {
-            self.favorites.insert(station_id.to_string());
+            self.favorites.push(station_id.to_string());
             true
-        }
+        };
+        let at = chrono::Utc::now().timestamp_millis();
+        self.changes
+            .insert(station_id.to_string(), Change { favorite, at });
+        favorite
     }
 
     pub fn is_favorite(&self, station_id: &str) -> bool {
-        self.favorites.contains(station_id)
+        self.position(station_id).is_some()
+    }
+
+    /// Where a favorite comes in the user's order
+    pub fn position(&self, station_id: &str) -> Option<usize> {
+        self.favorites.iter().position(|id| id == station_id)
     }
 
-    pub fn favorites(&self) -> &HashSet<String> {
+    /// Move a favorite one place earlier (`up`) or later, returning its new
+    /// position, or `None` if it's not a favorite or already at that end
+    pub fn shift(&mut self, station_id: &str, up: bool) -> Option<usize> {
+        let position = self.position(station_id)?;
+        let target = if up {
+            position.checked_sub(1)?
+        } else {
+            Some(position + 1).filter(|&target| target < self.favorites.len())?
+        };
+        self.favorites.swap(position, target);
+        self.order_updated = chrono::Utc::now().timestamp_millis();
+        Some(target)
+    }
+
+    pub fn favorites(&self) -> &[String] {
         &self.favorites
     }
+
+    /// The favorites as they're synced
+    pub fn sync_doc(&self) -> SyncDoc {
+        SyncDoc {
+            order: self.favorites.clone(),
+            order_updated: self.order_updated,
+            changes: self.changes.clone(),
+        }
+    }
+
+    /// Take on favorites merged with the shared copy, saving them. Returns
+    /// whether the list changed.
+    pub fn apply(&mut self, doc: SyncDoc) -> Result<bool> {
+        let favorites = doc.favorites();
+        let changed = favorites != self.favorites;
+        if changed || doc.changes != self.changes || doc.order_updated != self.order_updated {
+            self.favorites = favorites;
+            self.changes = doc.changes;
+            self.order_updated = doc.order_updated;
+            self.save()?;
+        }
+        Ok(changed)
+    }
 }
 
 impl Default for FavoritesStore {
     fn default() -> Self {
         Self::load().unwrap_or_else(|_| Self {
             path: PathBuf::from("favorites.json"),
-            favorites: HashSet::new(),
+            favorites: Vec::new(),
+            changes: BTreeMap::new(),
+            order_updated: 0,
+            modified: None,
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shift() {
+        let mut store = FavoritesStore {
+            path: PathBuf::from("favorites.json"),
+            favorites: Vec::new(),
+            changes: BTreeMap::new(),
+            order_updated: 0,
+            modified: None,
+        };
+        for id in ["groovesalad", "dronezone", "lush"] {
+            store.toggle(id);
+        }
+
+        assert_eq!(store.shift("lush", true), Some(1));
+        assert_eq!(store.favorites(), ["groovesalad", "lush", "dronezone"]);
+        assert_eq!(store.shift("groovesalad", true), None);
+        assert_eq!(store.shift("dronezone", false), None);
+        assert_eq!(store.shift("bootliquor", true), None);
+
+        // Removing and adding again puts it last
+        store.toggle("groovesalad");
+        store.toggle("groovesalad");
+        assert_eq!(store.favorites(), ["lush", "dronezone", "groovesalad"]);
+    }
+}
@@ -0,0 +1,116 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use crate::api::Track;
+
+/// A track heard again within this many seconds of its last play is the same
+/// play, e.g. after switching stations and back
+const REPLAY_WINDOW_SECS: i64 = 15 * 60;
+
+/// How often a track has been heard, across stations and sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayCount {
+    pub artist: String,
+    pub title: String,
+    pub count: u32,
+    /// Unix time of the most recent play
+    pub last_played: i64,
+}
+
+/// Play counts per track, saved to `history.json`. Each track is stored once
+/// however often it plays.
+pub struct PlayHistoryStore {
+    path: PathBuf,
+    plays: HashMap<String, PlayCount>,
+}
+
+impl PlayHistoryStore {
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path()?;
+
+        let plays = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            HashMap::new()
+        };
+
+        Ok(Self { path, plays })
+    }
+
+    fn config_path() -> Result<PathBuf> {
+        let config_dir = super::config_dir();
+
+        std::fs::create_dir_all(&config_dir)?;
+        Ok(config_dir.join("history.json"))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&self.plays)?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    /// Count a play of `track` at unix time `now`, returning its total
+    pub fn record(&mut self, track: &Track, now: i64) -> u32 {
+        let entry = self
+            .plays
+            .entry(track.song_key())
+            .or_insert_with(|| PlayCount {
+                artist: track.artist.clone(),
+                title: track.title.clone(),
+                count: 0,
+                last_played: i64::MIN,
+            });
+        // Imported plays can be older than ones already counted
+        if now.abs_diff(entry.last_played) > REPLAY_WINDOW_SECS as u64 {
+            entry.count += 1;
+        }
+        entry.last_played = entry.last_played.max(now);
+        entry.count
+    }
+
+    /// Times `track` has been heard, 0 if never
+    pub fn count(&self, track: &Track) -> u32 {
+        self.plays
+            .get(&track.song_key())
+            .map_or(0, |play| play.count)
+    }
+}
+
+impl Default for PlayHistoryStore {
+    fn default() -> Self {
+        Self::load().unwrap_or_else(|_| Self {
+            path: PathBuf::from("history.json"),
+            plays: HashMap::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_dedupes_replays() {
+        let mut history = PlayHistoryStore {
+            path: PathBuf::from("history.json"),
+            plays: HashMap::new(),
+        };
+        let track = Track::from_stream_title("groovesalad", "Bonobo - Kerala");
+        // Reported by another station with different formatting
+        let same = Track::from_stream_title("dronezone", "BONOBO - Kerala.");
+
+        assert_eq!(history.record(&track, 1_000), 1);
+        assert_eq!(history.record(&same, 1_060), 1);
+        assert_eq!(history.record(&same, 1_060 + REPLAY_WINDOW_SECS + 1), 2);
+        assert_eq!(history.count(&track), 2);
+        // An imported play from long before
+        assert_eq!(history.record(&track, -REPLAY_WINDOW_SECS), 3);
+    }
+}
@@ -0,0 +1,271 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::i18n::{t, tf};
+
+/// One-off jobs this overdue when vibecast starts were missed while it
+/// wasn't running, and are dropped rather than run late
+const MISSED_AFTER_MS: i64 = 60_000;
+
+/// Something the app does when it falls due
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Job {
+    /// Sleep timer: pause playback
+    Sleep,
+    /// Start playing a station, or the highlighted one
+    Alarm { station: Option<String> },
+    /// Switch to a random station, while surf mode is on
+    Surf,
+    /// Move on to the next station in the queue
+    AdvanceQueue,
+    /// Start and finish scheduled recordings
+    RecordingSchedules,
+    /// Keep recordings under the disk quota
+    RecordingQuota,
+    /// Drop old station artwork from the cache
+    EvictArtwork,
+    /// Write the session snapshot for crash recovery
+    SaveSession,
+    /// Merge the favorites with the synced copy
+    SyncFavorites,
+    /// Look for watched artists on the favorite stations
+    ScanWatched,
+}
+
+impl Job {
+    /// Jobs the user set, which are saved so they survive a restart. The
+    /// rest are set up again at launch.
+    pub fn is_saved(&self) -> bool {
+        matches!(self, Self::Sleep | Self::Alarm { .. })
+    }
+
+    /// Name in the jobs view and `vibecast ctl jobs`
+    pub fn label(&self) -> String {
+        match self {
+            Self::Sleep => t("job-sleep").to_string(),
+            Self::Alarm {
+                station: Some(station),
+            } => tf("job-alarm-station", &[("station", station)]),
+            Self::Alarm { station: None } => t("job-alarm").to_string(),
+            Self::Surf => t("job-surf").to_string(),
+            Self::AdvanceQueue => t("job-advance-queue").to_string(),
+            Self::RecordingSchedules => t("job-recording-schedules").to_string(),
+            Self::RecordingQuota => t("job-recording-quota").to_string(),
+            Self::EvictArtwork => t("job-evict-artwork").to_string(),
+            Self::SaveSession => t("job-save-session").to_string(),
+            Self::SyncFavorites => t("job-sync-favorites").to_string(),
+            Self::ScanWatched => t("job-scan-watched").to_string(),
+        }
+    }
+}
+
+/// A job and when it's next due
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduledJob {
+    pub id: u64,
+    pub job: Job,
+    /// Unix time in milliseconds
+    pub due_ms: i64,
+    /// Milliseconds between runs of a repeating job
+    #[serde(default)]
+    pub every_ms: Option<i64>,
+}
+
+impl ScheduledJob {
+    /// Time until it's due, e.g. "14m 30s"
+    pub fn due_in(&self, now_ms: i64) -> String {
+        format_duration((self.due_ms - now_ms).max(0) / 1000)
+    }
+
+    /// "every 10s" for repeating jobs
+    pub fn repeats(&self) -> Option<String> {
+        self.every_ms
+            .map(|every| tf("job-every", &[("time", &format_duration(every / 1000))]))
+    }
+}
+
+/// Seconds as "1h 5m", "14m 30s" or "9s"
+fn format_duration(secs: i64) -> String {
+    let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, secs)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+/// Pending jobs, soonest first. The user's own are saved to `jobs.json`.
+pub struct JobStore {
+    path: PathBuf,
+    jobs: Vec<ScheduledJob>,
+    next_id: u64,
+}
+
+impl JobStore {
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path()?;
+
+        let mut jobs: Vec<ScheduledJob> = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let now = chrono::Utc::now().timestamp_millis();
+        jobs.retain(|job| job.every_ms.is_some() || job.due_ms > now - MISSED_AFTER_MS);
+        jobs.sort_by_key(|job| job.due_ms);
+        let next_id = jobs.iter().map(|job| job.id + 1).max().unwrap_or(1);
+
+        Ok(Self {
+            path,
+            jobs,
+            next_id,
+        })
+    }
+
+    fn config_path() -> Result<PathBuf> {
+        let config_dir = super::config_dir();
+
+        std::fs::create_dir_all(&config_dir)?;
+        Ok(config_dir.join("jobs.json"))
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let saved: Vec<&ScheduledJob> = self.jobs.iter().filter(|job| job.job.is_saved()).collect();
+        let content = serde_json::to_string_pretty(&saved)?;
+        std::fs::write(&self.path, content)?;
+        Ok(())
+    }
+
+    /// Add `job`, due at unix time `due_ms` and then every `every` if set,
+    /// returning its id
+    pub fn schedule(&mut self, job: Job, due_ms: i64, every: Option<Duration>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let saved = job.is_saved();
+        let position = self.jobs.partition_point(|other| other.due_ms <= due_ms);
+        self.jobs.insert(
+            position,
+            ScheduledJob {
+                id,
+                job,
+                due_ms,
+                every_ms: every.map(|every| every.as_millis() as i64),
+            },
+        );
+        if saved {
+            let _ = self.save();
+        }
+        id
+    }
+
+    /// Remove the job with `id`, returning whether there was one
+    pub fn cancel(&mut self, id: u64) -> bool {
+        let Some(position) = self.jobs.iter().position(|job| job.id == id) else {
+            return false;
+        };
+        if self.jobs.remove(position).job.is_saved() {
+            let _ = self.save();
+        }
+        true
+    }
+
+    /// Remove every pending `job`, returning whether there were any
+    pub fn cancel_all(&mut self, job: &Job) -> bool {
+        let before = self.jobs.len();
+        self.jobs.retain(|pending| pending.job != *job);
+        let removed = self.jobs.len() < before;
+        if removed && job.is_saved() {
+            let _ = self.save();
+        }
+        removed
+    }
+
+    /// The next run of `job`, if one is pending
+    pub fn next(&self, job: &Job) -> Option<&ScheduledJob> {
+        self.jobs.iter().find(|pending| pending.job == *job)
+    }
+
+    pub fn pending(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    /// Take the jobs due by unix time `now_ms`, in order. Repeating jobs are
+    /// put back for their next run after `now_ms`; a run missed while the
+    /// app was busy isn't made up.
+    pub fn take_due(&mut self, now_ms: i64) -> Vec<Job> {
+        let due = self.jobs.partition_point(|job| job.due_ms <= now_ms);
+        if due == 0 {
+            return Vec::new();
+        }
+        let taken: Vec<ScheduledJob> = self.jobs.drain(..due).collect();
+        let mut saved = false;
+        let mut jobs = Vec::with_capacity(taken.len());
+        for mut scheduled in taken {
+            jobs.push(scheduled.job.clone());
+            match scheduled.every_ms {
+                Some(every) if every > 0 => {
+                    scheduled.due_ms = now_ms + every;
+                    let position = self
+                        .jobs
+                        .partition_point(|other| other.due_ms <= scheduled.due_ms);
+                    self.jobs.insert(position, scheduled);
+                }
+                _ => saved |= scheduled.job.is_saved(),
+            }
+        }
+        if saved {
+            let _ = self.save();
+        }
+        jobs
+    }
+}
+
+impl Default for JobStore {
+    fn default() -> Self {
+        Self::load().unwrap_or_else(|_| Self {
+            path: PathBuf::from("jobs.json"),
+            jobs: Vec::new(),
+            next_id: 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_take_due() {
+        let mut store = JobStore {
+            path: std::env::temp_dir().join(format!("vibecast-jobs-{}.json", std::process::id())),
+            jobs: Vec::new(),
+            next_id: 1,
+        };
+        store.schedule(Job::RecordingQuota, 1_000, Some(Duration::from_secs(10)));
+        store.schedule(Job::Surf, 5_000, None);
+        store.schedule(Job::RecordingSchedules, 500, Some(Duration::from_secs(1)));
+
+        assert!(store.take_due(400).is_empty());
+        assert_eq!(
+            store.take_due(1_200),
+            [Job::RecordingSchedules, Job::RecordingQuota]
+        );
+        // Repeating jobs come back relative to when they ran
+        let due: Vec<i64> = store.pending().iter().map(|job| job.due_ms).collect();
+        assert_eq!(due, [2_200, 5_000, 11_200]);
+
+        assert_eq!(store.take_due(5_000), [Job::RecordingSchedules, Job::Surf]);
+        assert!(store.next(&Job::Surf).is_none());
+        assert!(store.cancel_all(&Job::RecordingQuota));
+        assert_eq!(store.pending().len(), 1);
+    }
+}
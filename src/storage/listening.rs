@@ -0,0 +1,160 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Days of listening kept day by day; older days only count in the total
+const KEEP_DAYS: i64 = 28;
+
+/// How long a station has been listened to
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StationListening {
+    /// Milliseconds, all time
+    pub total_ms: u64,
+    /// Milliseconds per local date, as `2024-05-31`
+    days: BTreeMap<String, u64>,
+    /// Unix time last listened to, in seconds
+    pub last_listened: Option<i64>,
+}
+
+/// Listening time per station, saved to `listening.json`
+pub struct ListeningStore {
+    path: PathBuf,
+    stations: BTreeMap<String, StationListening>,
+    /// Whether there's listening not yet saved
+    changed: bool,
+}
+
+impl ListeningStore {
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path()?;
+
+        let stations = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).unwrap_or_default()
+        } else {
+            BTreeMap::new()
+        };
+
+        Ok(Self {
+            path,
+            stations,
+            changed: false,
+        })
+    }
+
+    fn config_path() -> Result<PathBuf> {
+        let config_dir = super::config_dir();
+
+        std::fs::create_dir_all(&config_dir)?;
+        Ok(config_dir.join("listening.json"))
+    }
+
+    /// Write the listening time if any was added since the last save
+    pub fn save(&mut self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&self.stations)?;
+        std::fs::write(&self.path, content)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    /// Count `elapsed` of listening to `station_id` on `today`
+    pub fn add(&mut self, station_id: &str, elapsed: Duration, today: NaiveDate) {
+        let ms = elapsed.as_millis() as u64;
+        let station = self.stations.entry(station_id.to_string()).or_default();
+        station.total_ms += ms;
+        *station.days.entry(day_key(today)).or_default() += ms;
+        station.last_listened = Some(chrono::Utc::now().timestamp());
+
+        let oldest = day_key(today - chrono::Duration::days(KEEP_DAYS - 1));
+        station.days.retain(|day, _| *day >= oldest);
+        self.changed = true;
+    }
+
+    /// When `station_id` was last listened to, as unix seconds
+    pub fn last_listened(&self, station_id: &str) -> Option<i64> {
+        self.stations.get(station_id)?.last_listened
+    }
+
+    /// The `n` stations listened to last, most recent first, with when
+    pub fn recent(&self, n: usize) -> Vec<(&str, i64)> {
+        let mut recent: Vec<(&str, i64)> = self
+            .stations
+            .iter()
+            .filter_map(|(id, station)| Some((id.as_str(), station.last_listened?)))
+            .collect();
+        recent.sort_by_key(|&(_, at)| std::cmp::Reverse(at));
+        recent.truncate(n);
+        recent
+    }
+
+    /// Listening to `station_id` over the seven days up to `today`
+    pub fn week(&self, station_id: &str, today: NaiveDate) -> Duration {
+        let first = day_key(today - chrono::Duration::days(6));
+        let ms = self.stations.get(station_id).map_or(0, |station| {
+            station
+                .days
+                .iter()
+                .filter(|(day, _)| **day >= first)
+                .map(|(_, ms)| ms)
+                .sum()
+        });
+        Duration::from_millis(ms)
+    }
+}
+
+/// ISO dates sort in date order as strings
+fn day_key(day: NaiveDate) -> String {
+    day.format("%Y-%m-%d").to_string()
+}
+
+impl Default for ListeningStore {
+    fn default() -> Self {
+        Self::load().unwrap_or_else(|_| Self {
+            path: PathBuf::from("listening.json"),
+            stations: BTreeMap::new(),
+            changed: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week() {
+        let mut store = ListeningStore {
+            path: PathBuf::from("listening.json"),
+            stations: BTreeMap::new(),
+            changed: false,
+        };
+        let day = |d| NaiveDate::from_ymd_opt(2024, 5, d).unwrap();
+        let hour = Duration::from_secs(3600);
+
+        store.add(
+            "dronezone",
+            hour,
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+        );
+        store.add("dronezone", hour, day(20));
+        store.add("dronezone", hour * 2, day(24));
+        store.add("lush", hour, day(24));
+
+        assert_eq!(store.week("dronezone", day(26)), hour * 3);
+        assert_eq!(store.week("dronezone", day(27)), hour * 2);
+        assert_eq!(store.week("groovesalad", day(26)), Duration::ZERO);
+        // Days from four weeks back are dropped, but still in the total
+        assert_eq!(store.stations["dronezone"].days.len(), 2);
+        assert_eq!(store.stations["dronezone"].total_ms, 4 * 3_600_000);
+    }
+}
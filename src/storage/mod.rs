@@ -1,5 +1,53 @@
+use std::path::PathBuf;
+
 pub mod config;
 pub mod favorites;
+pub mod history;
+pub mod jobs;
+pub mod listening;
+pub mod recordings;
+pub mod session;
+pub mod sync;
+pub mod themes;
 
-pub use config::ConfigStore;
+pub use config::{ConfigStore, FocusLoss, PanelConfig, PanelKind, RecordingSchedule};
 pub use favorites::FavoritesStore;
+pub use history::PlayHistoryStore;
+pub use jobs::{Job, JobStore, ScheduledJob};
+pub use listening::ListeningStore;
+pub use recordings::{format_size, Recording, RecordingStore};
+pub use session::{Session, SessionStore};
+pub use themes::ThemeStore;
+
+/// The directory settings files live in. Tests get a scratch directory
+/// instead, so they never touch the user's settings.
+pub fn config_dir() -> PathBuf {
+    if cfg!(test) {
+        return scratch_dir().join("config");
+    }
+    directories::ProjectDirs::from("com", "vibecast", "vibecast")
+        .map(|dirs| dirs.config_dir().to_path_buf())
+        .or_else(|| directories::BaseDirs::new().map(|d| d.config_dir().join("vibecast")))
+        .unwrap_or_else(|| PathBuf::from(".").join("vibecast"))
+}
+
+/// The directory the session is saved in. Only Linux has a state
+/// directory; elsewhere it's local app data.
+pub fn state_dir() -> PathBuf {
+    if cfg!(test) {
+        return scratch_dir().join("state");
+    }
+    directories::ProjectDirs::from("com", "vibecast", "vibecast")
+        .map(|dirs| {
+            dirs.state_dir()
+                .unwrap_or_else(|| dirs.data_local_dir())
+                .to_path_buf()
+        })
+        .or_else(|| directories::BaseDirs::new().map(|d| d.data_local_dir().join("vibecast")))
+        .unwrap_or_else(|| PathBuf::from(".").join("vibecast"))
+}
+
+/// Where tests keep what would otherwise go in the user's directories
+fn scratch_dir() -> PathBuf {
+    std::env::temp_dir().join(format!("vibecast-test-{}", std::process::id()))
+}
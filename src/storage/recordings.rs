@@ -0,0 +1,271 @@
+use anyhow::{anyhow, Result};
+use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Timestamp part of recording file names
+const NAME_TIME_FORMAT: &str = "%Y-%m-%d_%H%M";
+
+/// File in the recordings directory naming every file vibecast wrote there
+const MANIFEST: &str = ".vibecast-recordings.json";
+
+/// A saved stream recording
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recording {
+    pub path: PathBuf,
+    /// File name without the extension
+    pub name: String,
+    /// Station id, for files named the way vibecast names them
+    pub station_id: Option<String>,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+impl Recording {
+    fn from_path(path: PathBuf) -> Option<Self> {
+        let metadata = std::fs::metadata(&path).ok()?;
+        if !metadata.is_file() {
+            return None;
+        }
+        let name = path.file_stem()?.to_string_lossy().into_owned();
+        // "<station>_<date>_<time>", with "-N" after the time for a second
+        // recording in the same minute
+        let mut parts = name.rsplitn(3, '_');
+        let station_id = match (parts.next(), parts.next(), parts.next()) {
+            (Some(time), Some(date), Some(station))
+                if chrono::NaiveDateTime::parse_from_str(
+                    &format!("{}_{}", date, time.split('-').next().unwrap_or_default()),
+                    NAME_TIME_FORMAT,
+                )
+                .is_ok() =>
+            {
+                Some(station.to_string())
+            }
+            _ => None,
+        };
+        Some(Self {
+            path,
+            name,
+            station_id,
+            size: metadata.len(),
+            modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+        })
+    }
+
+    /// Size for display, e.g. "12.4 MB"
+    pub fn format_size(&self) -> String {
+        format_size(self.size)
+    }
+}
+
+/// Byte count for display, e.g. "12.4 MB"
+pub fn format_size(bytes: u64) -> String {
+    let mb = bytes as f64 / 1_000_000.0;
+    if mb >= 1000.0 {
+        format!("{:.1} GB", mb / 1000.0)
+    } else {
+        format!("{:.1} MB", mb)
+    }
+}
+
+/// Recordings in one directory. Only files vibecast wrote count, so other
+/// files in the same directory are never listed, renamed or deleted.
+pub struct RecordingStore {
+    dir: PathBuf,
+}
+
+impl RecordingStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// File names of the recordings vibecast wrote. Before there's a
+    /// manifest, files named the way vibecast names them.
+    fn written(&self) -> BTreeSet<String> {
+        if let Ok(content) = std::fs::read_to_string(self.dir.join(MANIFEST)) {
+            return serde_json::from_str(&content).unwrap_or_default();
+        }
+        std::fs::read_dir(&self.dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| Recording::from_path(entry.ok()?.path()))
+                    .filter(|recording| recording.station_id.is_some())
+                    .filter_map(|recording| file_name(&recording.path))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Change the manifest with `change`
+    fn update_written(&self, change: impl FnOnce(&mut BTreeSet<String>)) -> Result<()> {
+        let mut written = self.written();
+        change(&mut written);
+        std::fs::write(
+            self.dir.join(MANIFEST),
+            serde_json::to_string_pretty(&written)?,
+        )?;
+        Ok(())
+    }
+
+    /// Path for a new recording of `station_id` starting now
+    pub fn new_path(&self, station_id: &str, extension: &str) -> Result<PathBuf> {
+        std::fs::create_dir_all(&self.dir)?;
+        let time = chrono::Local::now().format(NAME_TIME_FORMAT);
+        let mut path = self
+            .dir
+            .join(format!("{}_{}.{}", station_id, time, extension));
+        // A second recording in the same minute
+        let mut n = 2;
+        while path.exists() {
+            path = self
+                .dir
+                .join(format!("{}_{}-{}.{}", station_id, time, n, extension));
+            n += 1;
+        }
+        if let Some(name) = file_name(&path) {
+            self.update_written(|written| {
+                written.insert(name);
+            })?;
+        }
+        Ok(path)
+    }
+
+    /// Every recording, newest first
+    pub fn list(&self) -> Vec<Recording> {
+        let mut recordings: Vec<Recording> = self
+            .written()
+            .into_iter()
+            .filter_map(|name| Recording::from_path(self.dir.join(name)))
+            .collect();
+        recordings.sort_by_key(|r| std::cmp::Reverse(r.modified));
+        recordings
+    }
+
+    /// Give a recording a new name, keeping its extension
+    pub fn rename(&self, recording: &Recording, name: &str) -> Result<PathBuf> {
+        let name = name.trim();
+        if name.is_empty() || name.contains(['/', '\\']) {
+            return Err(anyhow!("Invalid name"));
+        }
+        let mut path = self.dir.join(name);
+        if let Some(extension) = recording.path.extension() {
+            path.set_extension(extension);
+        }
+        if path.exists() {
+            return Err(anyhow!("{} already exists", name));
+        }
+        std::fs::rename(&recording.path, &path)?;
+        self.update_written(|written| {
+            written.extend(file_name(&path));
+            if let Some(old) = file_name(&recording.path) {
+                written.remove(&old);
+            }
+        })?;
+        Ok(path)
+    }
+
+    pub fn delete(&self, recording: &Recording) -> Result<()> {
+        std::fs::remove_file(&recording.path)?;
+        if let Some(name) = file_name(&recording.path) {
+            self.update_written(|written| {
+                written.remove(&name);
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Total size of every recording, in bytes
+    pub fn usage(&self) -> u64 {
+        self.list().iter().map(|r| r.size).sum()
+    }
+
+    /// Delete the oldest recordings until they fit in `max_bytes`, leaving
+    /// ones still being written alone. Returns what was deleted.
+    pub fn rotate(&self, max_bytes: u64, in_progress: &[PathBuf]) -> Result<Vec<Recording>> {
+        let recordings = self.list();
+        let mut usage: u64 = recordings.iter().map(|r| r.size).sum();
+        let mut deleted = Vec::new();
+        for recording in recordings.into_iter().rev() {
+            if usage <= max_bytes {
+                break;
+            }
+            if in_progress.contains(&recording.path) {
+                continue;
+            }
+            self.delete(&recording)?;
+            usage -= recording.size;
+            deleted.push(recording);
+        }
+        Ok(deleted)
+    }
+}
+
+fn file_name(path: &std::path::Path) -> Option<String> {
+    Some(path.file_name()?.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_rename_delete() {
+        let dir = std::env::temp_dir().join(format!("vibecast_recordings_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let store = RecordingStore::new(dir.clone());
+
+        let path = store.new_path("groove_salad", "mp3").unwrap();
+        std::fs::write(&path, b"audio").unwrap();
+        let recordings = store.list();
+        assert_eq!(recordings.len(), 1);
+        assert_eq!(recordings[0].station_id.as_deref(), Some("groove_salad"));
+        assert_eq!(recordings[0].size, 5);
+
+        let renamed = store.rename(&recordings[0], "Late night set").unwrap();
+        assert_eq!(renamed, dir.join("Late night set.mp3"));
+        let recordings = store.list();
+        assert_eq!(recordings[0].station_id, None);
+
+        store.delete(&recordings[0]).unwrap();
+        assert!(store.list().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_rotate_oldest_first() {
+        let dir = std::env::temp_dir().join(format!("vibecast_rotate_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let store = RecordingStore::new(dir.clone());
+
+        let now = SystemTime::now();
+        for (i, name) in ["oldest", "older", "newest"].iter().enumerate() {
+            let path = dir.join(format!("{}_2024-05-0{}_1200.mp3", name, i + 1));
+            let file = std::fs::File::create(&path).unwrap();
+            file.set_len(100).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(3600 * (3 - i as u64)))
+                .unwrap();
+        }
+
+        // Someone else's file, older and bigger than any recording
+        let music = dir.join("album.flac");
+        let file = std::fs::File::create(&music).unwrap();
+        file.set_len(1000).unwrap();
+        file.set_modified(now - std::time::Duration::from_secs(86400))
+            .unwrap();
+
+        // The oldest is being written to, so the next oldest goes
+        let deleted = store
+            .rotate(250, &[dir.join("oldest_2024-05-01_1200.mp3")])
+            .unwrap();
+        let names: Vec<&str> = deleted.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["older_2024-05-02_1200"]);
+        assert_eq!(store.usage(), 200);
+        assert!(store.rotate(250, &[]).unwrap().is_empty());
+        // Files vibecast didn't write are never counted or deleted
+        assert_eq!(store.rotate(0, &[]).unwrap().len(), 2);
+        assert!(music.exists());
+        assert!(store.list().is_empty());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
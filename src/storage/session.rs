@@ -0,0 +1,99 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Enough of the app's state to pick up where it left off: the station,
+/// volume, which panels were showing and where the list was scrolled to
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    /// Station id of the playing station
+    pub station: Option<String>,
+    /// Whether it was playing rather than paused
+    pub playing: bool,
+    /// Station id of the highlighted station
+    pub selected: Option<String>,
+    /// First station row in view
+    pub list_offset: usize,
+    /// The volume, before muting if muted
+    pub volume: u8,
+    pub muted: bool,
+    pub show_visualizer: bool,
+    pub show_vu_meter: bool,
+    pub show_artwork: bool,
+    pub show_history: bool,
+    pub show_grid: bool,
+    pub detailed_list: bool,
+    /// Station ids in the play queue
+    pub queue: Vec<String>,
+    /// Set when vibecast quits normally. A session still without it at the
+    /// next launch was cut short by a crash.
+    pub clean_exit: bool,
+}
+
+/// `session.json` in the state directory, rewritten while vibecast runs
+pub struct SessionStore {
+    path: PathBuf,
+    /// The session found at launch
+    previous: Option<Session>,
+    /// The last one written, to skip writing it again unchanged
+    saved: Option<Session>,
+}
+
+impl SessionStore {
+    pub fn load() -> Result<Self> {
+        let path = Self::state_path()?;
+
+        let previous = if path.exists() {
+            let content = std::fs::read_to_string(&path)?;
+            serde_json::from_str(&content).ok()
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            previous,
+            saved: None,
+        })
+    }
+
+    fn state_path() -> Result<PathBuf> {
+        let state_dir = super::state_dir();
+
+        std::fs::create_dir_all(&state_dir)?;
+        Ok(state_dir.join("session.json"))
+    }
+
+    /// The session saved by the last run, if there was one
+    pub fn previous(&self) -> Option<&Session> {
+        self.previous.as_ref()
+    }
+
+    /// Write `session` unless it's what was last written. It goes to a
+    /// temporary file first, so a crash mid-write keeps the old one.
+    pub fn save(&mut self, session: Session) -> Result<()> {
+        if self.saved.as_ref() == Some(&session) {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(&session)?;
+        let temp = self.path.with_extension("json.tmp");
+        std::fs::write(&temp, content)?;
+        std::fs::rename(&temp, &self.path)?;
+        self.saved = Some(session);
+        Ok(())
+    }
+}
+
+impl Default for SessionStore {
+    fn default() -> Self {
+        Self::load().unwrap_or_else(|_| Self {
+            path: PathBuf::from("session.json"),
+            previous: None,
+            saved: None,
+        })
+    }
+}
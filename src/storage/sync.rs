@@ -0,0 +1,217 @@
+//! Favorites sync: the favorites, with when each was last added or removed,
+//! merged with a shared copy in a synced folder or on a WebDAV server. The
+//! newer change to each station wins, so adding a favorite on one machine
+//! and removing another elsewhere both survive.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::api::http;
+
+/// The last time a station was added to or removed from the favorites
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Change {
+    pub favorite: bool,
+    /// Unix time in milliseconds
+    pub at: i64,
+}
+
+/// Favorites as they're synced: the order, and every station's last change
+/// so removals carry over too
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncDoc {
+    /// Station ids in the user's order
+    pub order: Vec<String>,
+    /// When the order was last changed by hand, in unix milliseconds
+    pub order_updated: i64,
+    pub changes: BTreeMap<String, Change>,
+}
+
+impl SyncDoc {
+    /// The station ids that are favorites, in order
+    pub fn favorites(&self) -> Vec<String> {
+        self.order
+            .iter()
+            .filter(|id| self.changes.get(*id).is_some_and(|change| change.favorite))
+            .cloned()
+            .collect()
+    }
+}
+
+/// Combine two copies of the favorites. Each station takes its newer change
+/// (an add wins a tie), and the order comes from the copy reordered last,
+/// with favorites only the other copy has added at the end.
+pub fn merge(local: &SyncDoc, remote: &SyncDoc) -> SyncDoc {
+    let mut changes = local.changes.clone();
+    for (id, change) in &remote.changes {
+        let newer = match changes.get(id) {
+            Some(ours) => (change.at, change.favorite) > (ours.at, ours.favorite),
+            None => true,
+        };
+        if newer {
+            changes.insert(id.clone(), *change);
+        }
+    }
+
+    let (first, second) = if remote.order_updated > local.order_updated {
+        (remote, local)
+    } else {
+        (local, remote)
+    };
+    let mut order: Vec<String> = Vec::new();
+    for id in first.order.iter().chain(&second.order) {
+        if changes.get(id).is_some_and(|change| change.favorite) && !order.contains(id) {
+            order.push(id.clone());
+        }
+    }
+
+    SyncDoc {
+        order,
+        order_updated: local.order_updated.max(remote.order_updated),
+        changes,
+    }
+}
+
+/// Where the shared copy lives, from the `sync` config
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncTarget {
+    /// A file in a folder another tool keeps in sync
+    Path(PathBuf),
+    WebDav {
+        url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+}
+
+impl SyncTarget {
+    /// The shared copy, `None` if there isn't one yet
+    async fn read(&self) -> Result<Option<SyncDoc>> {
+        let content = match self {
+            Self::Path(path) => match tokio::fs::read_to_string(path).await {
+                Ok(content) => content,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+                Err(err) => return Err(err.into()),
+            },
+            Self::WebDav { url, .. } => {
+                let response = self.authorize(http_client()?.get(url)).send().await?;
+                if response.status() == reqwest::StatusCode::NOT_FOUND {
+                    return Ok(None);
+                }
+                response.error_for_status()?.text().await?
+            }
+        };
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    async fn write(&self, doc: &SyncDoc) -> Result<()> {
+        let content = serde_json::to_string_pretty(doc)?;
+        match self {
+            Self::Path(path) => {
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                // Synced folders can pick up a half-written file otherwise
+                let temp = path.with_extension("json.tmp");
+                tokio::fs::write(&temp, content).await?;
+                tokio::fs::rename(&temp, path).await?;
+            }
+            Self::WebDav { url, .. } => {
+                let request = http_client()?
+                    .put(url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(content);
+                self.authorize(request).send().await?.error_for_status()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn authorize(&self, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+        match self {
+            Self::WebDav {
+                username: Some(username),
+                password,
+                ..
+            } => request.basic_auth(username, password.as_ref()),
+            _ => request,
+        }
+    }
+}
+
+fn http_client() -> Result<reqwest::Client> {
+    Ok(http::client_builder()
+        .timeout(Duration::from_secs(15))
+        .build()?)
+}
+
+/// Merge `local` with the shared copy, writing the result back if it
+/// changed it. Returns the merged favorites.
+pub async fn sync(target: &SyncTarget, local: SyncDoc) -> Result<SyncDoc> {
+    let remote = target.read().await?;
+    let merged = match &remote {
+        Some(remote) => merge(&local, remote),
+        None => local,
+    };
+    if remote.as_ref() != Some(&merged) {
+        target.write(&merged).await?;
+    }
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(order: &[&str], order_updated: i64, changes: &[(&str, bool, i64)]) -> SyncDoc {
+        SyncDoc {
+            order: order.iter().map(|id| id.to_string()).collect(),
+            order_updated,
+            changes: changes
+                .iter()
+                .map(|&(id, favorite, at)| (id.to_string(), Change { favorite, at }))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge() {
+        // Laptop added lush and reordered; desktop removed dronezone since
+        let laptop = doc(
+            &["lush", "groovesalad", "dronezone"],
+            300,
+            &[
+                ("groovesalad", true, 100),
+                ("dronezone", true, 100),
+                ("lush", true, 200),
+            ],
+        );
+        let desktop = doc(
+            &["groovesalad", "bootliquor"],
+            50,
+            &[
+                ("groovesalad", true, 100),
+                ("dronezone", false, 400),
+                ("bootliquor", true, 150),
+            ],
+        );
+
+        let merged = merge(&laptop, &desktop);
+        assert_eq!(merged.order, ["lush", "groovesalad", "bootliquor"]);
+        assert_eq!(merged.favorites(), merged.order);
+        assert_eq!(merged.order_updated, 300);
+        assert_eq!(
+            merged.changes["dronezone"],
+            Change {
+                favorite: false,
+                at: 400
+            }
+        );
+        // Either way round gives the same favorites
+        assert_eq!(merge(&desktop, &laptop).order, merged.order);
+    }
+}
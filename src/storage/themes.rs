@@ -0,0 +1,227 @@
+#![allow(dead_code)]
+
+use anyhow::{anyhow, Result};
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use crate::ui::{Theme, ThemeType};
+
+/// On-disk theme definition. Colors accept `#rrggbb` hex, ANSI color names
+/// (`red`, `lightblue`, ...) or 256-color indexes.
+#[derive(Debug, Deserialize)]
+struct ThemeFile {
+    name: Option<String>,
+    background: String,
+    foreground: String,
+    primary: String,
+    secondary: String,
+    accent: String,
+    muted: String,
+    highlight: String,
+    success: String,
+    warning: String,
+}
+
+impl ThemeFile {
+    fn into_theme(self, fallback_name: &str) -> Result<Theme> {
+        Ok(Theme {
+            name: self.name.unwrap_or_else(|| fallback_name.to_string()),
+            background: parse_color(&self.background)?,
+            foreground: parse_color(&self.foreground)?,
+            primary: parse_color(&self.primary)?,
+            secondary: parse_color(&self.secondary)?,
+            accent: parse_color(&self.accent)?,
+            muted: parse_color(&self.muted)?,
+            highlight: parse_color(&self.highlight)?,
+            success: parse_color(&self.success)?,
+            warning: parse_color(&self.warning)?,
+            widgets: Default::default(),
+        })
+    }
+}
+
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value.trim()).map_err(|_| anyhow!("invalid color: {}", value))
+}
+
+/// Catalog of built-in themes plus user themes loaded from the themes directory
+pub struct ThemeStore {
+    dir: PathBuf,
+    custom: Vec<Theme>,
+    last_modified: Option<SystemTime>,
+    /// Fall back to a light theme rather than a dark one
+    prefer_light: bool,
+}
+
+impl ThemeStore {
+    pub fn load() -> Result<Self> {
+        let dir = Self::themes_dir()?;
+        let mut store = Self {
+            dir,
+            custom: Vec::new(),
+            last_modified: None,
+            prefer_light: false,
+        };
+        store.reload();
+        Ok(store)
+    }
+
+    fn themes_dir() -> Result<PathBuf> {
+        let config_dir = super::config_dir();
+
+        let dir = config_dir.join("themes");
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Latest modification time across the directory and its theme files
+    fn modified_time(&self) -> Option<SystemTime> {
+        let mut latest = std::fs::metadata(&self.dir).and_then(|m| m.modified()).ok();
+        for path in self.theme_files() {
+            if let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) {
+                latest = latest.max(Some(modified));
+            }
+        }
+        latest
+    }
+
+    fn theme_files(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| {
+                matches!(
+                    p.extension().and_then(|e| e.to_str()),
+                    Some("json") | Some("toml")
+                )
+            })
+            .collect();
+        files.sort();
+        files
+    }
+
+    fn load_file(path: &Path) -> Result<Theme> {
+        let content = std::fs::read_to_string(path)?;
+        let file: ThemeFile = match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => toml::from_str(&content)?,
+            _ => serde_json::from_str(&content)?,
+        };
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("Custom");
+        file.into_theme(stem)
+    }
+
+    /// Re-read all theme files. Invalid files are skipped.
+    pub fn reload(&mut self) {
+        self.last_modified = self.modified_time();
+        self.custom = self
+            .theme_files()
+            .iter()
+            .filter_map(|path| Self::load_file(path).ok())
+            .filter(|theme| ThemeType::from_name(&theme.name).is_none())
+            .collect();
+    }
+
+    /// Reload if anything in the themes directory changed since the last load
+    pub fn reload_if_changed(&mut self) -> bool {
+        if self.modified_time() == self.last_modified {
+            return false;
+        }
+        self.reload();
+        true
+    }
+
+    /// Names of all available themes: built-ins first, then custom themes
+    pub fn names(&self) -> Vec<String> {
+        ThemeType::ALL
+            .iter()
+            .map(|t| t.name().to_string())
+            .chain(self.custom.iter().map(|t| t.name.clone()))
+            .collect()
+    }
+
+    pub fn set_prefer_light(&mut self, prefer_light: bool) {
+        self.prefer_light = prefer_light;
+    }
+
+    /// Look up a theme by name, falling back to the preferred theme for this
+    /// terminal. Colors are as defined; `Theme::for_terminal` adapts them.
+    pub fn resolve(&self, name: &str) -> Theme {
+        ThemeType::from_name(name)
+            .map(Theme::from_type)
+            .or_else(|| self.custom.iter().find(|t| t.name == name).cloned())
+            .unwrap_or_else(|| Theme::from_type(ThemeType::preferred(self.prefer_light)))
+    }
+
+    /// Name of the theme after `current` in the cycle
+    pub fn next_name(&self, current: &str) -> String {
+        let names = self.names();
+        let next = names
+            .iter()
+            .position(|n| n == current)
+            .map(|i| (i + 1) % names.len())
+            .unwrap_or(0);
+        names[next].clone()
+    }
+
+    pub fn prev_name(&self, current: &str) -> String {
+        let names = self.names();
+        let prev = names
+            .iter()
+            .position(|n| n == current)
+            .map(|i| (i + names.len() - 1) % names.len())
+            .unwrap_or(0);
+        names[prev].clone()
+    }
+}
+
+impl Default for ThemeStore {
+    fn default() -> Self {
+        Self::load().unwrap_or_else(|_| Self {
+            dir: PathBuf::from("themes"),
+            custom: Vec::new(),
+            last_modified: None,
+            prefer_light: false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_file_parsing() {
+        let content = r##"
+            name = "Paper"
+            background = "#fafafa"
+            foreground = "black"
+            primary = "#0055aa"
+            secondary = "blue"
+            accent = "#aa5500"
+            muted = "gray"
+            highlight = "yellow"
+            success = "green"
+            warning = "208"
+        "##;
+        let file: ThemeFile = toml::from_str(content).unwrap();
+        let theme = file.into_theme("paper").unwrap();
+        assert_eq!(theme.name, "Paper");
+        assert_eq!(theme.background, Color::Rgb(0xfa, 0xfa, 0xfa));
+        assert_eq!(theme.secondary, Color::Blue);
+        assert_eq!(theme.warning, Color::Indexed(208));
+    }
+
+    #[test]
+    fn test_invalid_color_rejected() {
+        assert!(parse_color("#12345").is_err());
+        assert!(parse_color("not-a-color").is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Running `vibecast daemon` as a systemd service: readiness notification
+//! for `Type=notify` units, and a control socket handed over by a `.socket`
+//! unit. Outside systemd both do nothing.
+
+/// Tell the service manager about a state change, e.g. "READY=1"
+pub fn notify(state: &str) {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::linux::net::SocketAddrExt;
+        use std::os::unix::net::{SocketAddr, UnixDatagram};
+
+        let Some(path) = std::env::var_os("NOTIFY_SOCKET") else {
+            return;
+        };
+        let Ok(socket) = UnixDatagram::unbound() else {
+            return;
+        };
+        let path = path.to_string_lossy();
+        // A leading '@' names a socket in the abstract namespace
+        let _ = match path.strip_prefix('@') {
+            Some(name) => SocketAddr::from_abstract_name(name.as_bytes())
+                .and_then(|addr| socket.send_to_addr(state.as_bytes(), &addr)),
+            None => socket.send_to(state.as_bytes(), path.as_ref()),
+        };
+    }
+    #[cfg(not(target_os = "linux"))]
+    let _ = state;
+}
+
+/// The listening socket systemd passed in, if this process was socket
+/// activated
+#[cfg(unix)]
+pub fn inherited_listener() -> Option<std::os::unix::net::UnixListener> {
+    use std::os::fd::FromRawFd;
+
+    // Passed sockets start at fd 3
+    const FIRST_FD: i32 = 3;
+
+    let pid: u32 = std::env::var("LISTEN_PID").ok()?.parse().ok()?;
+    let fds: u32 = std::env::var("LISTEN_FDS").ok()?.parse().ok()?;
+    if pid != std::process::id() || fds == 0 {
+        return None;
+    }
+    // SAFETY: LISTEN_PID names this process, so systemd opened FIRST_FD for
+    // it and nothing else in the process owns it; this is only called once
+    let listener = unsafe { std::os::unix::net::UnixListener::from_raw_fd(FIRST_FD) };
+    listener.set_nonblocking(true).ok()?;
+    Some(listener)
+}
@@ -1,20 +1,58 @@
 #![allow(dead_code)]
 
 use image::DynamicImage;
-use ratatui_image::{picker::Picker, protocol::StatefulProtocol};
+use ratatui_image::{
+    picker::{Picker, ProtocolType},
+    protocol::StatefulProtocol,
+};
+use serde::{Deserialize, Serialize};
 use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
 static PICKER: OnceLock<Option<Picker>> = OnceLock::new();
 
+/// Image protocol forced with `ui.image_protocol`, for when detection gets
+/// it wrong (often over tmux or SSH)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocol {
+    Kitty,
+    Sixel,
+    #[serde(alias = "iterm")]
+    Iterm2,
+    Halfblocks,
+    /// No artwork at all
+    None,
+}
+
 /// Initialize the image picker early, before TUI starts.
 /// Image quality depends on terminal support:
 /// - Best: Kitty, iTerm2, WezTerm (native image protocols)
 /// - Good: Terminals with Sixel support
 /// - Basic: Halfblocks fallback (uses Unicode block characters)
-pub fn init_picker() {
+///
+/// `protocol` overrides the detected protocol; the terminal is still queried
+/// for its font size.
+pub fn init_picker(protocol: Option<ImageProtocol>) {
     PICKER.get_or_init(|| {
+        let protocol_type = match protocol {
+            Some(ImageProtocol::None) => return None,
+            Some(ImageProtocol::Kitty) => Some(ProtocolType::Kitty),
+            Some(ImageProtocol::Sixel) => Some(ProtocolType::Sixel),
+            Some(ImageProtocol::Iterm2) => Some(ProtocolType::Iterm2),
+            Some(ImageProtocol::Halfblocks) => Some(ProtocolType::Halfblocks),
+            None => None,
+        };
         // Try to create a picker, catching any panics
-        std::panic::catch_unwind(|| Picker::from_query_stdio().ok()).unwrap_or(None)
+        let detected = std::panic::catch_unwind(|| Picker::from_query_stdio().ok()).unwrap_or(None);
+        match protocol_type {
+            Some(protocol_type) => {
+                let mut picker = detected.unwrap_or_else(|| Picker::from_fontsize((8, 16)));
+                picker.set_protocol_type(protocol_type);
+                Some(picker)
+            }
+            None => detected,
+        }
     });
 }
 
@@ -29,9 +67,29 @@ pub fn has_quality_protocol() -> bool {
     get_picker().is_some()
 }
 
+/// Cells the Now Playing artwork occupies, at most
+pub const ARTWORK_COLUMNS: u16 = 16;
+pub const ARTWORK_ROWS: u16 = 8;
+
+/// Largest artwork worth keeping, in pixels: twice what the artwork cells
+/// cover, enough for clean scaling. Bigger images are scaled down before
+/// they reach the image protocol, keeping Kitty and Sixel payloads small.
+pub fn max_artwork_size() -> (u32, u32) {
+    let (font_width, font_height) = get_picker().map(|p| p.font_size()).unwrap_or((8, 16));
+    (
+        2 * ARTWORK_COLUMNS as u32 * font_width as u32,
+        2 * ARTWORK_ROWS as u32 * font_height as u32,
+    )
+}
+
+/// How long a missing image counts as still loading; after that the
+/// placeholder stops shimmering
+const LOADING_TIMEOUT: Duration = Duration::from_secs(10);
+
 pub struct ArtworkState {
     pub(crate) protocol: Option<StatefulProtocol>,
     current_url: Option<String>,
+    cleared_at: Instant,
 }
 
 impl ArtworkState {
@@ -39,6 +97,7 @@ impl ArtworkState {
         Self {
             protocol: None,
             current_url: None,
+            cleared_at: Instant::now(),
         }
     }
 
@@ -52,6 +111,12 @@ impl ArtworkState {
     pub fn clear(&mut self) {
         self.protocol = None;
         self.current_url = None;
+        self.cleared_at = Instant::now();
+    }
+
+    /// Whether an image is probably on its way
+    pub fn is_loading(&self) -> bool {
+        self.protocol.is_none() && self.cleared_at.elapsed() < LOADING_TIMEOUT
     }
 
     pub fn current_url(&self) -> Option<&str> {
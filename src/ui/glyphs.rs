@@ -0,0 +1,63 @@
+use ratatui::{buffer::Buffer, layout::Rect};
+
+/// ASCII replacement for a decorative glyph, or `None` if it should be kept.
+/// Every mapping is one cell wide so layouts don't shift.
+pub fn ascii_glyph(c: char) -> Option<char> {
+    let replacement = match c {
+        // Box drawing
+        '─' | '━' | '═' => '-',
+        '│' | '┃' | '║' | '¦' => '|',
+        '┌' | '┐' | '└' | '┘' | '├' | '┤' | '┬' | '┴' | '┼' | '╭' | '╮' | '╰' | '╯' => {
+            '+'
+        }
+        // Status and list markers
+        '★' | '✦' | '✧' | '◆' | '♦' => '*',
+        '▶' | '▷' | '▸' | '→' => '>',
+        '◀' | '◁' | '←' => '<',
+        '⏸' => '=',
+        '■' => '#',
+        '♫' | '♪' => '~',
+        '♥' => '@',
+        // Bars and blocks
+        '█' | '▓' | '▇' | '▆' | '▅' => '#',
+        '▒' | '▄' | '▃' | '▂' | '▁' => '=',
+        '░' | '▀' | '▔' => '-',
+        // Dots and circles
+        '·' | '∙' | '•' => '.',
+        '○' => 'o',
+        '●' => 'O',
+        '◉' => '@',
+        '▲' => '^',
+        '▼' => 'v',
+        '⟳' => '@',
+        '✕' | '×' => 'x',
+        // Text cursor, truncation and "-∞ dB"
+        '▏' => '|',
+        '∞' => '~',
+        '…' => '.',
+        // Braille spinner
+        '\u{2801}'..='\u{28FF}' => '*',
+        _ => return None,
+    };
+    Some(replacement)
+}
+
+/// Replace decorative Unicode glyphs in a rendered frame with ASCII, for
+/// terminals and SSH clients without good Unicode support. This works on
+/// the whole frame, so a mapped glyph inside station text (a ★ in a song
+/// title, say) is replaced too; other characters are left alone.
+pub fn asciify(buf: &mut Buffer, area: Rect) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let Some(cell) = buf.cell_mut((x, y)) else {
+                continue;
+            };
+            let mut chars = cell.symbol().chars();
+            if let (Some(c), None) = (chars.next(), chars.next()) {
+                if let Some(replacement) = ascii_glyph(c) {
+                    cell.set_char(replacement);
+                }
+            }
+        }
+    }
+}
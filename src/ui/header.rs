@@ -6,20 +6,75 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Widget},
 };
 
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
+
 use super::theme::Theme;
+use crate::i18n::{t, tf};
+use crate::player::StreamHealth;
 
 pub struct Header<'a> {
     station_name: Option<&'a str>,
+    clock: &'a str,
+    listening: Duration,
+    api_reachable: bool,
+    stream_health: Option<StreamHealth>,
     theme: &'a Theme,
 }
 
+/// Format a listening duration as "1h 23m" (or "23m" under an hour)
+pub fn format_listening(elapsed: Duration) -> String {
+    let minutes = elapsed.as_secs() / 60;
+    if minutes >= 60 {
+        format!("{}h {:02}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
 impl<'a> Header<'a> {
-    pub fn new(station_name: Option<&'a str>, theme: &'a Theme) -> Self {
+    pub fn new(
+        station_name: Option<&'a str>,
+        clock: &'a str,
+        listening: Duration,
+        theme: &'a Theme,
+    ) -> Self {
         Self {
             station_name,
+            clock,
+            listening,
+            api_reachable: true,
+            stream_health: None,
             theme,
         }
     }
+
+    /// Show whether the station API answers and how the stream is doing
+    pub fn connectivity(mut self, api_reachable: bool, stream: Option<StreamHealth>) -> Self {
+        self.api_reachable = api_reachable;
+        self.stream_health = stream;
+        self
+    }
+
+    /// The most pressing connection problem, or a dot while all is well
+    fn indicator(&self) -> Option<Span<'static>> {
+        let theme = self.theme;
+        Some(match (self.stream_health, self.api_reachable) {
+            (Some(StreamHealth::Lost), _) => Span::styled(
+                format!("⟳ {}", t("header-reconnecting")),
+                theme.paused_style(),
+            ),
+            (Some(StreamHealth::Buffering), _) => {
+                Span::styled(format!("○ {}", t("header-buffering")), theme.paused_style())
+            }
+            (_, false) => Span::styled(
+                format!("✕ {}", t("header-api-offline")),
+                theme.paused_style(),
+            ),
+            (Some(StreamHealth::Healthy), true) => Span::styled("●", theme.playing_style()),
+            (None, true) => return None,
+        })
+    }
 }
 
 impl<'a> Widget for Header<'a> {
@@ -27,10 +82,18 @@ impl<'a> Widget for Header<'a> {
         let theme = self.theme;
         let title = "VIBECAST";
 
-        let right_text = self
+        let station_text = self
             .station_name
-            .map(|name| format!("Now Playing: {}", name))
+            .map(|name| tf("header-now-playing", &[("station", &name)]))
             .unwrap_or_default();
+        let listening_text = if self.listening.as_secs() >= 60 {
+            tf(
+                "header-listening",
+                &[("time", &format_listening(self.listening))],
+            )
+        } else {
+            String::new()
+        };
 
         // Create a gradient-like effect for the title
         let title_spans: Vec<Span> = title
@@ -68,12 +131,50 @@ impl<'a> Widget for Header<'a> {
             buf,
         );
 
-        // Render station name on the right
-        if !right_text.is_empty() && inner.width > 30 {
-            let right_len = right_text.len() as u16;
+        // Right side: station name, listening time and clock, dropping the
+        // least important parts first when space is short
+        let separator = Span::styled(" │ ", theme.muted_style());
+        let clock_span = Span::styled(self.clock, theme.normal_style());
+        let listening_span = Span::styled(listening_text.as_str(), theme.muted_style());
+        let station_span = Span::styled(station_text.as_str(), theme.selected_style());
+
+        let title_width = title.len() as u16 + 3;
+        let available = inner.width.saturating_sub(title_width + 1) as usize;
+        let candidates = [
+            vec![
+                station_span.clone(),
+                listening_span.clone(),
+                clock_span.clone(),
+            ],
+            vec![station_span, clock_span.clone()],
+            vec![listening_span, clock_span.clone()],
+            vec![clock_span],
+        ];
+
+        // Connection problems are never dropped for space
+        let indicator = self.indicator();
+        let right_spans = candidates.into_iter().find_map(|parts| {
+            let parts: Vec<Span> = indicator
+                .iter()
+                .cloned()
+                .chain(parts)
+                .filter(|span| !span.content.is_empty())
+                .collect();
+            let mut spans = Vec::with_capacity(parts.len() * 2);
+            for (i, part) in parts.into_iter().enumerate() {
+                if i > 0 {
+                    spans.push(separator.clone());
+                }
+                spans.push(part);
+            }
+            let width: usize = spans.iter().map(|s| s.content.width()).sum();
+            (width <= available).then_some((spans, width))
+        });
+
+        if let Some((spans, width)) = right_spans {
+            let right_len = width as u16;
             let right_x = inner.x + inner.width.saturating_sub(right_len + 1);
-            let right_line = Line::from(Span::styled(&right_text, theme.selected_style()));
-            let right_para = Paragraph::new(right_line).alignment(Alignment::Right);
+            let right_para = Paragraph::new(Line::from(spans)).alignment(Alignment::Right);
             right_para.render(
                 Rect {
                     x: right_x,
@@ -1,49 +1,109 @@
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Constraint, Layout, Rect},
+    layout::{Constraint, Layout, Rect},
     style::Modifier,
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Widget},
 };
+use unicode_width::UnicodeWidthStr;
 
 use super::theme::Theme;
+use crate::i18n::t;
+use crate::input::BINDINGS;
 
+/// Key list generated from the keymap, sized to fit its content and
+/// scrolled when the terminal is too short for it
 pub struct HelpOverlay<'a> {
+    scroll: usize,
     theme: &'a Theme,
 }
 
 impl<'a> HelpOverlay<'a> {
     pub fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+        Self { scroll: 0, theme }
     }
 
-    fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-        let popup_layout = Layout::vertical([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
+    /// Lines scrolled past at the top
+    pub fn scroll(mut self, scroll: usize) -> Self {
+        self.scroll = scroll;
+        self
+    }
+
+    /// Width of the key column: the longest key list
+    fn key_width() -> usize {
+        BINDINGS
+            .iter()
+            .map(|binding| binding.keys_label().width())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Every line of the key list, as (keys, text); section headings have
+    /// no keys and blank lines are empty
+    fn entries() -> Vec<(String, &'static str)> {
+        let mut entries = Vec::new();
+        let mut section = "";
+        for binding in BINDINGS {
+            if binding.section != section {
+                if !section.is_empty() {
+                    entries.push((String::new(), ""));
+                }
+                section = binding.section;
+                entries.push((String::new(), t(section)));
+                entries.push((String::new(), ""));
+            }
+            entries.push((binding.keys_label(), t(binding.help)));
+        }
+        entries
+    }
 
-        Layout::horizontal([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
+    /// Popup area within `area`, and how many key list lines fit in it
+    fn layout(area: Rect) -> (Rect, usize) {
+        let entries = Self::entries();
+        let key_width = Self::key_width();
+        let content_width = entries
+            .iter()
+            .map(|(_, text)| key_width + 4 + text.width())
+            .chain([t("help-footer").width()])
+            .max()
+            .unwrap_or(0);
+        // Borders, plus a blank line and the footer
+        let width = (content_width as u16 + 4).min(area.width);
+        let height = (entries.len() as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
         ])
-        .split(popup_layout[1])[1]
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+        (popup_area, height.saturating_sub(4) as usize)
+    }
+
+    /// Furthest the list can scroll when drawn in `area`
+    pub fn max_scroll(area: Rect) -> usize {
+        let (_, visible) = Self::layout(area);
+        Self::entries().len().saturating_sub(visible)
     }
 }
 
 impl<'a> Widget for HelpOverlay<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let theme = self.theme;
-        let popup_area = Self::centered_rect(60, 70, area);
+        let (popup_area, visible) = Self::layout(area);
 
-        // Clear the area behind the popup
         Clear.render(popup_area, buf);
 
         let block = Block::default()
-            .title(Span::styled(" Keyboard Shortcuts ", theme.title_style()))
+            .title(Span::styled(
+                format!(" {} ", t("help-title")),
+                theme.title_style(),
+            ))
             .borders(Borders::ALL)
             .border_style(theme.active_border_style())
             .style(ratatui::style::Style::default().bg(theme.background));
@@ -51,86 +111,39 @@ impl<'a> Widget for HelpOverlay<'a> {
         let inner = block.inner(popup_area);
         block.render(popup_area, buf);
 
-        let shortcuts = vec![
-            (
-                "Playback",
-                vec![
-                    ("p / Space", "Play / Pause"),
-                    ("Enter", "Play selected station"),
-                    ("q / Esc", "Quit"),
-                ],
-            ),
-            (
-                "Navigation",
-                vec![
-                    ("j / Down", "Move down"),
-                    ("k / Up", "Move up"),
-                    ("g", "Go to top"),
-                    ("G", "Go to bottom"),
-                ],
-            ),
-            (
-                "Volume",
-                vec![
-                    ("+ / =", "Volume up"),
-                    ("- / _", "Volume down"),
-                    ("m", "Mute / Unmute"),
-                ],
-            ),
-            (
-                "Stations",
-                vec![
-                    ("f", "Toggle favorite"),
-                    ("s", "Cycle sort mode"),
-                    ("R", "Refresh stations"),
-                ],
-            ),
-            (
-                "Display",
-                vec![
-                    ("v", "Cycle visualization style"),
-                    ("V", "Show/hide visualizer"),
-                    ("a", "Toggle artwork"),
-                    ("r", "Toggle recently played"),
-                    ("t", "Cycle color theme"),
-                ],
-            ),
-            (
-                "Audio",
-                vec![
-                    ("< / ,", "Lower audio quality"),
-                    ("> / .", "Higher audio quality"),
-                    ("?", "Toggle this help"),
-                ],
-            ),
-        ];
-
-        let mut lines = vec![];
-
-        for (section, items) in shortcuts {
-            // Section header
-            lines.push(Line::from(Span::styled(
-                section,
-                theme.selected_style().add_modifier(Modifier::BOLD),
-            )));
-            lines.push(Line::from(""));
-
-            for (key, desc) in items {
-                lines.push(Line::from(vec![
-                    Span::styled(format!("  {:12}", key), theme.highlight_style()),
-                    Span::raw("  "),
-                    Span::styled(desc, theme.normal_style()),
-                ]));
-            }
-            lines.push(Line::from(""));
-        }
+        let entries = Self::entries();
+        let key_width = Self::key_width();
+        let scroll = self.scroll.min(entries.len().saturating_sub(visible));
+        let mut lines: Vec<Line> = entries
+            .into_iter()
+            .skip(scroll)
+            .take(visible)
+            .map(|(keys, text)| {
+                if keys.is_empty() {
+                    Line::from(Span::styled(
+                        text,
+                        theme.selected_style().add_modifier(Modifier::BOLD),
+                    ))
+                } else {
+                    Line::from(vec![
+                        Span::styled(
+                            format!("  {:width$}", keys, width = key_width),
+                            theme.highlight_style(),
+                        ),
+                        Span::raw("  "),
+                        Span::styled(text, theme.normal_style()),
+                    ])
+                }
+            })
+            .collect();
 
+        lines.resize(visible.max(lines.len()), Line::from(""));
+        lines.push(Line::from(""));
         lines.push(Line::from(Span::styled(
-            "Press any key to close",
+            t("help-footer"),
             theme.muted_style(),
         )));
 
-        let paragraph = Paragraph::new(lines).alignment(Alignment::Left);
-        paragraph.render(inner, buf);
+        Paragraph::new(lines).render(inner, buf);
     }
 }
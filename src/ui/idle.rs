@@ -0,0 +1,69 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::{t, tf};
+
+/// Prompt asking whether anyone's still listening, before pausing
+pub struct IdlePrompt<'a> {
+    /// Seconds until playback pauses
+    pauses_in: u64,
+    theme: &'a Theme,
+}
+
+impl<'a> IdlePrompt<'a> {
+    pub fn new(pauses_in: u64, theme: &'a Theme) -> Self {
+        Self { pauses_in, theme }
+    }
+}
+
+impl<'a> Widget for IdlePrompt<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let width = 44.min(area.width);
+        let height = 6.min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("idle-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let lines = vec![
+            Line::from(Span::styled(
+                tf("idle-pausing", &[("seconds", &self.pauses_in)]),
+                theme.normal_style(),
+            )),
+            Line::from(""),
+            Line::from(Span::styled(t("idle-keep-playing"), theme.muted_style())),
+        ];
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .render(inner, buf);
+    }
+}
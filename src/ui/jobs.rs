@@ -0,0 +1,110 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::{t, tf};
+use crate::storage::ScheduledJob;
+
+/// Popup listing pending timers and background jobs, soonest first
+pub struct JobsPopup<'a> {
+    jobs: &'a [ScheduledJob],
+    selected: usize,
+    /// Unix time in milliseconds, for the countdowns
+    now_ms: i64,
+    theme: &'a Theme,
+}
+
+impl<'a> JobsPopup<'a> {
+    pub fn new(jobs: &'a [ScheduledJob], selected: usize, now_ms: i64, theme: &'a Theme) -> Self {
+        Self {
+            jobs,
+            selected,
+            now_ms,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for JobsPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // One row per job plus borders and the hint line
+        let width = 60.min(area.width);
+        let height = (self.jobs.len().max(1) as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("jobs-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.jobs.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", t("jobs-empty")),
+                theme.muted_style(),
+            )));
+        }
+        for (i, job) in self.jobs.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else if job.job.is_saved() {
+                theme.normal_style()
+            } else {
+                theme.muted_style()
+            };
+            let mut spans = vec![Span::styled(
+                format!(
+                    " {} {}",
+                    tf(
+                        "jobs-due-in",
+                        &[("time", &format!("{:<8}", job.due_in(self.now_ms)))]
+                    ),
+                    job.job.label()
+                ),
+                style,
+            )];
+            if let Some(repeats) = job.repeats() {
+                spans.push(Span::styled(format!("  {}", repeats), theme.muted_style()));
+            }
+            lines.push(Line::from(spans));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-jobs"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,110 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+use super::theme::Theme;
+use crate::api::Channel;
+use crate::i18n::{t, tf};
+
+/// Plain line-based rendering for screen readers: no borders, no
+/// animation, one fact per line in a fixed order. The selected station is
+/// always the first line so the cursor can stay parked at the top-left.
+pub struct LinearView<'a> {
+    status: &'a str,
+    station: Option<&'a Channel>,
+    track: Option<&'a str>,
+    selected: Option<(usize, usize, &'a Channel, bool)>,
+    announcement: Option<&'a str>,
+    show_keys: bool,
+    theme: &'a Theme,
+}
+
+impl<'a> LinearView<'a> {
+    pub fn new(
+        status: &'a str,
+        station: Option<&'a Channel>,
+        track: Option<&'a str>,
+        selected: Option<(usize, usize, &'a Channel, bool)>,
+        announcement: Option<&'a str>,
+        show_keys: bool,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            status,
+            station,
+            track,
+            selected,
+            announcement,
+            show_keys,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for LinearView<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let station = match self.station {
+            Some(channel) => tf("sr-station", &[("station", &channel.title)]),
+            None => t("sr-station-none").to_string(),
+        };
+        let track = tf(
+            "sr-track",
+            &[("track", &self.track.unwrap_or(t("sr-track-unknown")))],
+        );
+        let selected = match self.selected {
+            Some((index, total, channel, favorite)) => {
+                let id = if favorite {
+                    "sr-selected-favorite"
+                } else {
+                    "sr-selected"
+                };
+                tf(
+                    id,
+                    &[
+                        ("index", &(index + 1)),
+                        ("total", &total),
+                        ("station", &channel.title),
+                        ("genre", &channel.genre),
+                        ("listeners", &channel.listeners),
+                    ],
+                )
+            }
+            None => t("sr-no-stations").to_string(),
+        };
+
+        let mut lines = vec![
+            Line::from(Span::styled(selected, theme.selected_style())),
+            Line::from(Span::styled(
+                tf("sr-status", &[("status", &self.status)]),
+                theme.normal_style(),
+            )),
+            Line::from(Span::styled(station, theme.normal_style())),
+            Line::from(Span::styled(track, theme.normal_style())),
+        ];
+
+        if let Some(announcement) = self.announcement {
+            lines.push(Line::from(Span::styled(
+                announcement.to_string(),
+                theme.normal_style(),
+            )));
+        }
+
+        if self.show_keys {
+            lines.push(Line::from(Span::styled(t("sr-keys"), theme.normal_style())));
+        } else {
+            lines.push(Line::from(Span::styled(
+                t("sr-keys-hint"),
+                theme.muted_style(),
+            )));
+        }
+
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: true })
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,97 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::{t, tf};
+use crate::links::Link;
+
+/// Popup listing the station's links, each with its URL
+pub struct LinksPopup<'a> {
+    station: Option<&'a str>,
+    links: &'a [Link],
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> LinksPopup<'a> {
+    pub fn new(
+        station: Option<&'a str>,
+        links: &'a [Link],
+        selected: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            station,
+            links,
+            selected,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for LinksPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // Two rows per link plus borders and the hint line
+        let width = 64.min(area.width);
+        let height = (self.links.len() as u16 * 2 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let title = match self.station {
+            Some(station) => format!(" {} ", tf("links-title-station", &[("station", &station)])),
+            None => format!(" {} ", t("links-title")),
+        };
+        let block = Block::default()
+            .title(Span::styled(title, theme.title_style()))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = (inner.height.saturating_sub(2) / 2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, link) in self.links.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else {
+                theme.normal_style()
+            };
+            lines.push(Line::from(Span::styled(format!(" {}", link.label), style)));
+            lines.push(Line::from(Span::styled(
+                format!("   {}", link.url),
+                theme.muted_style(),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-links"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
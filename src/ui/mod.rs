@@ -1,19 +1,63 @@
 pub mod artwork;
+pub mod glyphs;
 pub mod header;
 pub mod help;
+pub mod idle;
+pub mod jobs;
+pub mod linear;
+pub mod links;
 pub mod now_playing;
+pub mod outputs;
+pub mod queue;
+pub mod recent;
+pub mod recordings;
+pub mod schedule;
+pub mod settings;
+pub mod similar;
+#[cfg(test)]
+mod snapshot_tests;
 pub mod song_history;
+pub mod station_footer;
+pub mod station_grid;
 pub mod station_list;
 pub mod status_bar;
 pub mod theme;
+pub mod theme_picker;
+pub mod timeline;
+pub mod tip_bar;
 pub mod visualizer;
+pub mod visualizer_picker;
+pub mod volume_popup;
+pub mod vu_meter;
 
-pub use artwork::{init_picker, ArtworkState};
+pub use artwork::{
+    has_quality_protocol, init_picker, max_artwork_size, ArtworkState, ImageProtocol,
+};
+pub use glyphs::asciify;
 pub use header::Header;
 pub use help::HelpOverlay;
+pub use idle::IdlePrompt;
+pub use jobs::JobsPopup;
+pub use linear::LinearView;
+pub use links::LinksPopup;
 pub use now_playing::NowPlaying;
+pub use outputs::OutputPicker;
+pub use queue::QueuePopup;
+pub use recent::RecentPopup;
+pub use recordings::RecordingsBrowser;
+pub use schedule::{ScheduleEntry, SchedulePanel};
+pub use settings::SettingsScreen;
+pub use similar::SimilarPopup;
 pub use song_history::SongHistory;
+pub use station_footer::{StationFooter, STATION_FOOTER_HEIGHT};
+pub use station_grid::{StationGrid, THUMBNAIL_SIZE};
 pub use station_list::StationList;
 pub use status_bar::StatusBar;
-pub use theme::{Theme, ThemeType};
+pub use theme::{terminal_is_light, Theme, ThemeType};
+pub use theme_picker::ThemePicker;
+pub use timeline::TimelinePopup;
+pub use tip_bar::TipBar;
 pub use visualizer::{VisualizationMode, Visualizer};
+pub use visualizer_picker::VisualizerPicker;
+pub use volume_popup::VolumePopup;
+pub use vu_meter::{VuMeter, VuMeterState};
@@ -5,10 +5,13 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, StatefulWidget, Widget},
 };
 use ratatui_image::{FilterType, Resize, StatefulImage};
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
 
-use super::artwork::ArtworkState;
+use super::artwork::{ArtworkState, ARTWORK_COLUMNS, ARTWORK_ROWS};
 use super::theme::Theme;
-use crate::api::{AudioQuality, Channel, Song};
+use crate::api::{AudioQuality, Channel, ReleaseInfo, Song};
+use crate::i18n::{t, tf};
 
 pub struct NowPlaying<'a> {
     channel: Option<&'a Channel>,
@@ -17,6 +20,12 @@ pub struct NowPlaying<'a> {
     is_paused: bool,
     audio_quality: AudioQuality,
     show_artwork: bool,
+    /// Animation frame for the placeholder's shimmer while artwork loads
+    shimmer: Option<u64>,
+    /// MusicBrainz details, which take precedence over the station's album
+    release: Option<&'a ReleaseInfo>,
+    /// Estimated time into the song and its length
+    progress: Option<(Duration, Duration)>,
     theme: &'a Theme,
 }
 
@@ -37,10 +46,136 @@ impl<'a> NowPlaying<'a> {
             is_paused,
             audio_quality,
             show_artwork,
+            shimmer: None,
+            release: None,
+            progress: None,
             theme,
         }
     }
 
+    /// Animate the placeholder shown until the artwork arrives
+    pub fn shimmer(mut self, frame: Option<u64>) -> Self {
+        self.shimmer = frame;
+        self
+    }
+
+    /// Show the album, year and label MusicBrainz has for the song
+    pub fn release(mut self, release: Option<&'a ReleaseInfo>) -> Self {
+        self.release = release;
+        self
+    }
+
+    /// Show an estimated progress bar for the song
+    pub fn progress(mut self, progress: Option<(Duration, Duration)>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Progress through the song, marked as an estimate since the station
+    /// only says when each song started
+    fn progress_line(&self, width: u16) -> Option<Line<'a>> {
+        let (elapsed, length) = self.progress?;
+        let theme = self.theme;
+        let elapsed_label = format!("  ~{} ", format_time(elapsed));
+        let length_label = format!(
+            " {}",
+            tf("now-playing-estimate", &[("time", &format_time(length))])
+        );
+        let bar_width =
+            (width as usize).checked_sub(elapsed_label.width() + length_label.width())?;
+        if bar_width < 5 {
+            return None;
+        }
+        let ratio = elapsed.as_secs_f32() / length.as_secs_f32().max(1.0);
+        let filled = ((ratio * bar_width as f32).round() as usize).min(bar_width);
+        Some(Line::from(vec![
+            Span::styled(elapsed_label, theme.muted_style()),
+            Span::styled(
+                "━".repeat(filled),
+                ratatui::style::Style::default().fg(theme.accent),
+            ),
+            Span::styled("─".repeat(bar_width - filled), theme.muted_style()),
+            Span::styled(length_label, theme.muted_style()),
+        ]))
+    }
+
+    /// Album line, with year and label when MusicBrainz has them
+    fn album_lines(&self, album: Option<&'a str>) -> Vec<Line<'a>> {
+        let theme = self.theme;
+        let mut lines = vec![];
+        let album = self.release.map(|release| release.album.as_str()).or(album);
+        if let Some(album) = album.filter(|album| !album.is_empty()) {
+            let mut spans = vec![
+                Span::styled(format!("  {} ", t("now-playing-from")), theme.muted_style()),
+                Span::styled(album, theme.muted_style()),
+            ];
+            if let Some(year) = self.release.and_then(|release| release.year.as_deref()) {
+                spans.push(Span::styled(format!(" ({})", year), theme.muted_style()));
+            }
+            lines.push(Line::from(spans));
+        }
+        if let Some(label) = self.release.and_then(|release| release.label.as_deref()) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", t("now-playing-on")), theme.muted_style()),
+                Span::styled(label, theme.muted_style()),
+            ]));
+        }
+        lines
+    }
+
+    /// Box with the station's initials, the same size as the artwork so
+    /// nothing moves when the image arrives
+    fn render_placeholder(&self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border_style());
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        // A diagonal band sweeping across while loading
+        if let Some(frame) = self.shimmer {
+            let sweep = (inner.width + inner.height) as u64 + 4;
+            let band = (frame / 2 % sweep) as i32 - 2;
+            for y in inner.top()..inner.bottom() {
+                for x in inner.left()..inner.right() {
+                    let diagonal = (x - inner.x + y - inner.y) as i32;
+                    if (diagonal - band).abs() <= 1 {
+                        if let Some(cell) = buf.cell_mut((x, y)) {
+                            cell.set_char('░').set_style(theme.muted_style());
+                        }
+                    }
+                }
+            }
+        }
+
+        let initials: String = self
+            .channel
+            .map(|channel| {
+                channel
+                    .title
+                    .split_whitespace()
+                    .filter_map(|word| word.chars().next())
+                    .take(2)
+                    .flat_map(char::to_uppercase)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let middle = Rect {
+            y: inner.y + inner.height / 2,
+            height: inner.height.min(1),
+            ..inner
+        };
+        Paragraph::new(Line::from(Span::styled(
+            initials,
+            ratatui::style::Style::default()
+                .fg(theme.accent)
+                .add_modifier(ratatui::style::Modifier::BOLD),
+        )))
+        .alignment(ratatui::layout::Alignment::Center)
+        .render(middle, buf);
+    }
+
     fn render_content(&self, area: Rect, buf: &mut Buffer) {
         if area.height < 3 || area.width < 15 {
             return;
@@ -88,14 +223,14 @@ impl<'a> NowPlaying<'a> {
                     Span::styled(&channel.genre, theme.muted_style()),
                     Span::styled(" • ", theme.muted_style()),
                     Span::styled(
-                        format!("{} listeners", channel.listeners),
+                        tf("now-playing-listeners", &[("count", &channel.listeners)]),
                         theme.muted_style(),
                     ),
                 ]);
                 Paragraph::new(genre_line).render(genre_area, buf);
             }
         } else {
-            let no_station = Line::from(Span::styled("No station selected", theme.muted_style()));
+            let no_station = Line::from(Span::styled(t("now-playing-none"), theme.muted_style()));
             Paragraph::new(no_station).render(chunks[0], buf);
         }
 
@@ -117,28 +252,23 @@ impl<'a> NowPlaying<'a> {
 
             // Artist
             lines.push(Line::from(vec![
-                Span::styled("  by ", theme.muted_style()),
+                Span::styled(format!("  {} ", t("now-playing-by")), theme.muted_style()),
                 Span::styled(
                     &song.artist,
                     ratatui::style::Style::default().fg(theme.secondary),
                 ),
             ]));
 
-            // Album
-            if let Some(album) = &song.album {
-                if !album.is_empty() {
-                    lines.push(Line::from(vec![
-                        Span::styled("  from ", theme.muted_style()),
-                        Span::styled(album, theme.muted_style()),
-                    ]));
-                }
+            lines.extend(self.album_lines(song.album.as_deref()));
+            if let Some(progress) = self.progress_line(song_area.width) {
+                lines.push(progress);
             }
 
             Paragraph::new(lines).render(song_area, buf);
         } else if let Some(title) = self.stream_title {
             // Parse stream title (usually "Artist - Title")
             let lines = if let Some((artist, song_title)) = title.split_once(" - ") {
-                vec![
+                let mut lines = vec![
                     Line::from(vec![
                         Span::styled("♫ ", ratatui::style::Style::default().fg(theme.accent)),
                         Span::styled(
@@ -149,10 +279,12 @@ impl<'a> NowPlaying<'a> {
                         ),
                     ]),
                     Line::from(vec![
-                        Span::styled("  by ", theme.muted_style()),
+                        Span::styled(format!("  {} ", t("now-playing-by")), theme.muted_style()),
                         Span::styled(artist, ratatui::style::Style::default().fg(theme.secondary)),
                     ]),
-                ]
+                ];
+                lines.extend(self.album_lines(None));
+                lines
             } else {
                 vec![Line::from(vec![
                     Span::styled("♫ ", ratatui::style::Style::default().fg(theme.accent)),
@@ -161,12 +293,18 @@ impl<'a> NowPlaying<'a> {
             };
             Paragraph::new(lines).render(song_area, buf);
         } else if self.channel.is_some() {
-            let waiting = Line::from(Span::styled("Loading song info...", theme.muted_style()));
+            let waiting = Line::from(Span::styled(t("now-playing-loading"), theme.muted_style()));
             Paragraph::new(waiting).render(song_area, buf);
         }
     }
 }
 
+/// Format a song time as "3:07"
+fn format_time(time: Duration) -> String {
+    let secs = time.as_secs();
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
 impl<'a> StatefulWidget for NowPlaying<'a> {
     type State = ArtworkState;
 
@@ -176,7 +314,10 @@ impl<'a> StatefulWidget for NowPlaying<'a> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(theme.border_style())
-            .title(Span::styled(" Now Playing ", theme.title_style()));
+            .title(Span::styled(
+                format!(" {} ", t("now-playing-title")),
+                theme.title_style(),
+            ));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -185,14 +326,14 @@ impl<'a> StatefulWidget for NowPlaying<'a> {
             return;
         }
 
-        // Check if we should show artwork
-        let show_art = self.show_artwork && state.protocol.is_some() && inner.width >= 40;
+        // Artwork, or a placeholder of the same size until it arrives
+        let show_art = self.show_artwork && self.channel.is_some() && inner.width >= 40;
 
         if show_art {
             // Fixed artwork size for consistent display across all stations
             // 16 chars wide x 8 chars tall = roughly square in terminal
-            let art_width: u16 = 16;
-            let art_height = inner.height.min(8);
+            let art_width = ARTWORK_COLUMNS;
+            let art_height = inner.height.min(ARTWORK_ROWS);
 
             // Create artwork area and content area side by side
             let art_area = Rect {
@@ -215,6 +356,8 @@ impl<'a> StatefulWidget for NowPlaying<'a> {
                 let image =
                     StatefulImage::default().resize(Resize::Fit(Some(FilterType::Lanczos3)));
                 StatefulWidget::render(image, art_area, buf, protocol);
+            } else {
+                self.render_placeholder(art_area, buf);
             }
 
             // Render content immediately to the right of artwork
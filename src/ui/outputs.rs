@@ -0,0 +1,120 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::airplay::AirPlayDevice;
+use crate::i18n::t;
+
+/// Popup picking where audio plays: this computer first, then the AirPlay
+/// speakers found, or a note while they're still being looked for
+pub struct OutputPicker<'a> {
+    /// `None` while discovery is running
+    devices: Option<&'a [AirPlayDevice]>,
+    /// Speaker in use, `None` when playing here
+    current: Option<&'a AirPlayDevice>,
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> OutputPicker<'a> {
+    pub fn new(
+        devices: Option<&'a [AirPlayDevice]>,
+        current: Option<&'a AirPlayDevice>,
+        selected: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            devices,
+            current,
+            selected,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for OutputPicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        let devices = self.devices.unwrap_or_default();
+
+        // This computer, the speakers or a note about them, borders and hints
+        let width = 44.min(area.width);
+        let height = (devices.len().max(1) as u16 + 5).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("outputs-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let entries = std::iter::once((t("outputs-local"), self.current.is_none())).chain(
+            devices
+                .iter()
+                .map(|device| (device.name.as_str(), self.current == Some(device))),
+        );
+        let mut lines: Vec<Line> = entries
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, (name, current))| {
+                let marker = if current { "▶ " } else { "  " };
+                let style = if i == self.selected {
+                    theme.highlight_style()
+                } else {
+                    theme.normal_style()
+                };
+                Line::from(vec![
+                    Span::styled(marker, theme.playing_style()),
+                    Span::styled(name, style),
+                ])
+            })
+            .collect();
+        let note = match self.devices {
+            None => Some(t("outputs-searching")),
+            Some([]) => Some(t("outputs-none")),
+            Some(_) => None,
+        };
+        if let Some(note) = note {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", note),
+                theme.muted_style(),
+            )));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-outputs"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
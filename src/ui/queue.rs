@@ -0,0 +1,104 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::api::Channel;
+use crate::i18n::{t, tf};
+
+/// Popup listing the stations waiting in the play queue, next first
+pub struct QueuePopup<'a> {
+    stations: &'a [&'a Channel],
+    selected: usize,
+    /// Time until the queue moves on by itself, e.g. "4m 10s"
+    advances_in: Option<String>,
+    theme: &'a Theme,
+}
+
+impl<'a> QueuePopup<'a> {
+    pub fn new(stations: &'a [&'a Channel], selected: usize, theme: &'a Theme) -> Self {
+        Self {
+            stations,
+            selected,
+            advances_in: None,
+            theme,
+        }
+    }
+
+    /// Show when the next station starts on its own
+    pub fn advances_in(mut self, advances_in: Option<String>) -> Self {
+        self.advances_in = advances_in;
+        self
+    }
+}
+
+impl<'a> Widget for QueuePopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // One row per station plus borders and the hint line
+        let width = 60.min(area.width);
+        let height = (self.stations.len().max(1) as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let title = match &self.advances_in {
+            Some(time) => format!(" {} ", tf("queue-title-next", &[("time", time)])),
+            None => format!(" {} ", t("queue-title")),
+        };
+        let block = Block::default()
+            .title(Span::styled(title, theme.title_style()))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.stations.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", t("queue-empty")),
+                theme.muted_style(),
+            )));
+        }
+        for (i, channel) in self.stations.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else {
+                theme.normal_style()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {:>2}. {}", i + 1, channel.title), style),
+                Span::styled(format!("  {}", channel.genre), theme.muted_style()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-queue"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,96 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::api::Channel;
+use crate::i18n::t;
+
+/// Popup of the stations listened to last, most recent first, numbered so
+/// one key plays any of them
+pub struct RecentPopup<'a> {
+    /// Each station with when it was last heard, e.g. "3h ago"
+    entries: &'a [(&'a Channel, String)],
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> RecentPopup<'a> {
+    pub fn new(entries: &'a [(&'a Channel, String)], selected: usize, theme: &'a Theme) -> Self {
+        Self {
+            entries,
+            selected,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for RecentPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // One row per station plus borders and the hint line
+        let width = 56.min(area.width);
+        let height = (self.entries.len().max(1) as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("recent-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        if self.entries.is_empty() {
+            lines.push(Line::from(Span::styled(
+                format!(" {}", t("recent-empty")),
+                theme.muted_style(),
+            )));
+        }
+        for (i, (channel, when)) in self.entries.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else {
+                theme.normal_style()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {}. {}", i + 1, channel.title), style),
+                Span::styled(format!("  {}", when), theme.muted_style()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-recent"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
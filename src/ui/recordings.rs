@@ -0,0 +1,132 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::t;
+use crate::storage::Recording;
+
+/// Popup listing saved recordings, newest first
+pub struct RecordingsBrowser<'a> {
+    recordings: &'a [Recording],
+    selected: usize,
+    /// Name being typed for the selected recording
+    renaming: Option<&'a str>,
+    confirm_delete: bool,
+    theme: &'a Theme,
+}
+
+impl<'a> RecordingsBrowser<'a> {
+    pub fn new(recordings: &'a [Recording], selected: usize, theme: &'a Theme) -> Self {
+        Self {
+            recordings,
+            selected,
+            renaming: None,
+            confirm_delete: false,
+            theme,
+        }
+    }
+
+    pub fn renaming(mut self, name: Option<&'a str>) -> Self {
+        self.renaming = name;
+        self
+    }
+
+    pub fn confirm_delete(mut self, confirm: bool) -> Self {
+        self.confirm_delete = confirm;
+        self
+    }
+}
+
+impl<'a> Widget for RecordingsBrowser<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let width = 64.min(area.width);
+        // One row per recording plus borders and the hint line
+        let height = (self.recordings.len() as u16 + 4)
+            .max(8)
+            .min(area.height * 4 / 5)
+            .min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("recordings-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = if self.recordings.is_empty() {
+            vec![Line::from(Span::styled(
+                t("recordings-empty"),
+                theme.muted_style(),
+            ))]
+        } else {
+            self.recordings
+                .iter()
+                .enumerate()
+                .skip(first)
+                .take(visible)
+                .map(|(i, recording)| {
+                    let style = if i == self.selected {
+                        theme.highlight_style()
+                    } else {
+                        theme.normal_style()
+                    };
+                    let size = recording.format_size();
+                    let name_width = (inner.width as usize).saturating_sub(size.len() + 3);
+                    let name: String = recording.name.chars().take(name_width).collect();
+                    Line::from(vec![
+                        Span::styled(format!(" {:<width$}", name, width = name_width), style),
+                        Span::styled(format!(" {} ", size), theme.muted_style()),
+                    ])
+                })
+                .collect()
+        };
+
+        lines.resize(visible.max(lines.len()), Line::from(""));
+        lines.push(Line::from(""));
+        lines.push(match (self.renaming, self.confirm_delete) {
+            (Some(name), _) => Line::from(vec![
+                Span::styled(
+                    format!("{} ", t("recordings-new-name")),
+                    theme.muted_style(),
+                ),
+                Span::styled(format!("{}▏", name), theme.selected_style()),
+            ]),
+            (None, true) => Line::from(Span::styled(
+                t("recordings-confirm-delete"),
+                theme.paused_style(),
+            )),
+            (None, false) => Line::from(Span::styled(t("keys-recordings"), theme.muted_style())),
+        });
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
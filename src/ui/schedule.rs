@@ -0,0 +1,73 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// One line of the schedule panel
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleEntry {
+    pub station: String,
+    /// "until 22:00" or "Thu 20:00-22:00"
+    pub when: String,
+    /// Recording right now
+    pub active: bool,
+}
+
+/// Scheduled recordings, running ones first
+pub struct SchedulePanel<'a> {
+    entries: &'a [ScheduleEntry],
+    theme: &'a Theme,
+}
+
+impl<'a> SchedulePanel<'a> {
+    pub fn new(entries: &'a [ScheduleEntry], theme: &'a Theme) -> Self {
+        Self { entries, theme }
+    }
+}
+
+impl<'a> Widget for SchedulePanel<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border_style())
+            .title(Span::styled(
+                format!(" {} ", t("schedule-title")),
+                theme.title_style(),
+            ));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let lines: Vec<Line> = if self.entries.is_empty() {
+            vec![Line::from(Span::styled(
+                t("schedule-empty"),
+                theme.muted_style(),
+            ))]
+        } else {
+            self.entries
+                .iter()
+                .map(|entry| {
+                    let (marker, style) = if entry.active {
+                        ("● ", theme.paused_style())
+                    } else {
+                        ("  ", theme.normal_style())
+                    };
+                    Line::from(vec![
+                        Span::styled(marker, style),
+                        Span::styled(entry.station.as_str(), style),
+                        Span::styled(format!("  {}", entry.when), theme.muted_style()),
+                    ])
+                })
+                .collect()
+        };
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,104 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use unicode_width::UnicodeWidthStr;
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// Popup of options that can be changed in place. Each entry is a label and
+/// the current value.
+pub struct SettingsScreen<'a> {
+    entries: &'a [(&'a str, String)],
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> SettingsScreen<'a> {
+    pub fn new(entries: &'a [(&'a str, String)], selected: usize, theme: &'a Theme) -> Self {
+        Self {
+            entries,
+            selected,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for SettingsScreen<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let width = 52.min(area.width);
+        let height = (self.entries.len() as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("settings-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let label_width = self
+            .entries
+            .iter()
+            .map(|(label, _)| label.width())
+            .max()
+            .unwrap_or(0);
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, (label, value))| {
+                let (label_style, value_style) = if i == self.selected {
+                    (theme.highlight_style(), theme.selected_style())
+                } else {
+                    (theme.normal_style(), theme.muted_style())
+                };
+                Line::from(vec![
+                    Span::styled(
+                        format!(" {:<width$} ", label, width = label_width),
+                        label_style,
+                    ),
+                    Span::styled(format!(" ◀ {} ▶", value), value_style),
+                ])
+            })
+            .collect();
+
+        lines.resize(visible.max(lines.len()), Line::from(""));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-settings"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
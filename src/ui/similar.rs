@@ -0,0 +1,96 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::api::Channel;
+use crate::i18n::{t, tf};
+
+/// Popup of stations sharing a genre with another, to switch to one quickly
+pub struct SimilarPopup<'a> {
+    station: &'a str,
+    /// Each station with the genres it shares, e.g. "ambient, space"
+    entries: &'a [(&'a Channel, String)],
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> SimilarPopup<'a> {
+    pub fn new(
+        station: &'a str,
+        entries: &'a [(&'a Channel, String)],
+        selected: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            station,
+            entries,
+            selected,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for SimilarPopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // One row per station plus borders and the hint line
+        let width = 64.min(area.width);
+        let height = (self.entries.len() as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", tf("similar-title", &[("station", &self.station)])),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = Vec::new();
+        for (i, (channel, shared)) in self.entries.iter().enumerate().skip(first).take(visible) {
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else {
+                theme.normal_style()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!(" {}", channel.title), style),
+                Span::styled(format!("  {}", shared), theme.muted_style()),
+            ]));
+        }
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-similar"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
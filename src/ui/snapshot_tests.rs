@@ -0,0 +1,176 @@
+//! Renders each widget into a `TestBackend` with fixture data and compares the
+//! text against the stored snapshots in `snapshots/`. After an intentional
+//! layout change, review and accept the new output with `cargo insta review`.
+
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+use std::time::{Duration, Instant};
+
+use super::*;
+use crate::api::{
+    test_support, AudioQuality, Channel, ListenerTrends, Song, SortMode, StationCatalog,
+    StationSource,
+};
+use crate::visualizer::{SpectrumData, SpectrumSettings};
+
+fn channel(id: &str, title: &str, genre: &str, listeners: u32) -> Channel {
+    Channel {
+        title: title.to_string(),
+        description: format!("{} description", title),
+        genre: genre.to_string(),
+        listeners,
+        last_playing: "Artist - Last Track".to_string(),
+        ..test_support::channel(id)
+    }
+}
+
+fn channels() -> Vec<Channel> {
+    vec![
+        channel("groovesalad", "Groove Salad", "ambient|electronica", 1520),
+        channel("dronezone", "Drone Zone", "ambient|space", 840),
+        channel("secretagent", "Secret Agent", "lounge", 312),
+    ]
+}
+
+fn song(artist: &str, title: &str) -> Song {
+    Song {
+        album: Some("Fixture Album".to_string()),
+        ..test_support::song(artist, title)
+    }
+}
+
+/// A loud, sloping spectrum so every mode has something to draw
+fn spectrum() -> SpectrumData {
+    let count = SpectrumSettings::default().bins;
+    let bins: Vec<f32> = (0..count)
+        .map(|i| 1.0 - i as f32 / count as f32 * 0.8)
+        .collect();
+    SpectrumData {
+        peaks: bins.iter().map(|b| (b + 0.1).min(1.0)).collect(),
+        bins,
+        rms: 0.6,
+        peak: 0.8,
+        channels: [0.7, 0.7],
+        has_audio: true,
+        beat: false,
+        bpm: None,
+        last_update: Instant::now(),
+    }
+}
+
+fn render(width: u16, height: u16, draw: impl FnOnce(&mut ratatui::Frame, Rect)) -> TestBackend {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|f| draw(f, f.area())).unwrap();
+    terminal.backend().clone()
+}
+
+#[test]
+fn header() {
+    let theme = Theme::default();
+    let backend = render(60, 3, |f, area| {
+        let header = Header::new(
+            Some("Groove Salad"),
+            "21:45",
+            Duration::from_secs(95 * 60),
+            &theme,
+        );
+        f.render_widget(header, area);
+    });
+    insta::assert_snapshot!(backend);
+}
+
+#[test]
+fn station_list() {
+    let theme = Theme::default();
+    let mut catalog = StationCatalog::default();
+    catalog.set_source(StationSource::SomaFm, channels());
+    catalog.sort(SortMode::ListenersOnly, &[], |_| None, "");
+    let favorites = vec!["dronezone".to_string()];
+    let trends = ListenerTrends::default();
+    let backend = render(40, 8, |f, area| {
+        let list = StationList::new(
+            &catalog,
+            &favorites,
+            &trends,
+            Some("groovesalad"),
+            true,
+            false,
+            &theme,
+        );
+        let mut state = ratatui::widgets::ListState::default().with_selected(Some(1));
+        f.render_stateful_widget(list, area, &mut state);
+    });
+    insta::assert_snapshot!(backend);
+}
+
+#[test]
+fn now_playing() {
+    let theme = Theme::default();
+    let channels = channels();
+    let song = song("Bonobo", "Kerala");
+    let backend = render(50, 10, |f, area| {
+        let now_playing = NowPlaying::new(
+            Some(&channels[0]),
+            Some(&song),
+            None,
+            false,
+            AudioQuality::Highest,
+            false,
+            &theme,
+        );
+        f.render_stateful_widget(now_playing, area, &mut ArtworkState::new());
+    });
+    insta::assert_snapshot!(backend);
+}
+
+#[test]
+fn status_bar() {
+    let theme = Theme::default();
+    let backend = render(80, 1, |f, area| {
+        f.render_widget(StatusBar::new(true, false, 80, "Cyberpunk", &theme), area);
+    });
+    insta::assert_snapshot!(backend);
+}
+
+#[test]
+fn help_overlay() {
+    let theme = Theme::default();
+    let backend = render(100, 50, |f, area| {
+        f.render_widget(HelpOverlay::new(&theme), area);
+    });
+    insta::assert_snapshot!(backend);
+}
+
+#[test]
+fn visualizer_modes() {
+    let theme = Theme::default();
+    let spectrum = spectrum();
+    for mode in VisualizationMode::BUILT_IN {
+        let backend = render(40, 12, |f, area| {
+            let visualizer = Visualizer::new(&spectrum, true, false, mode, 42, false, &theme);
+            f.render_widget(visualizer, area);
+        });
+        insta::assert_snapshot!(
+            format!("visualizer_{}", mode.name().to_lowercase()),
+            backend
+        );
+    }
+}
+
+#[test]
+fn visualizer_no_data() {
+    let theme = Theme::default();
+    let spectrum = SpectrumData::default();
+    let backend = render(40, 12, |f, area| {
+        let visualizer = Visualizer::new(
+            &spectrum,
+            true,
+            false,
+            VisualizationMode::Bars,
+            42,
+            false,
+            &theme,
+        );
+        f.render_widget(visualizer, area);
+    });
+    insta::assert_snapshot!(backend);
+}
@@ -10,9 +10,11 @@ use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::theme::Theme;
 use crate::api::Song;
+use crate::i18n::t;
 
 pub struct SongHistory<'a> {
     songs: &'a [Song],
+    play_counts: &'a [u32],
     theme: &'a Theme,
 }
 
@@ -51,7 +53,17 @@ fn truncate_to_width(text: &str, max_width: usize) -> String {
 
 impl<'a> SongHistory<'a> {
     pub fn new(songs: &'a [Song], theme: &'a Theme) -> Self {
-        Self { songs, theme }
+        Self {
+            songs,
+            play_counts: &[],
+            theme,
+        }
+    }
+
+    /// Times each song has been heard, in the same order as the songs
+    pub fn play_counts(mut self, play_counts: &'a [u32]) -> Self {
+        self.play_counts = play_counts;
+        self
     }
 }
 
@@ -62,7 +74,10 @@ impl<'a> Widget for SongHistory<'a> {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(theme.border_style())
-            .title(Span::styled(" Previously Played ", theme.title_style()));
+            .title(Span::styled(
+                format!(" {} ", t("history-title")),
+                theme.title_style(),
+            ));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -72,7 +87,7 @@ impl<'a> Widget for SongHistory<'a> {
         }
 
         if self.songs.is_empty() {
-            let empty = Line::from(Span::styled("No history available", theme.muted_style()));
+            let empty = Line::from(Span::styled(t("history-empty"), theme.muted_style()));
             Paragraph::new(empty).render(inner, buf);
             return;
         }
@@ -87,8 +102,13 @@ impl<'a> Widget for SongHistory<'a> {
                 format!("{} - {}", song.artist, song.title)
             };
 
+            let seen = match self.play_counts.get(i) {
+                Some(&count) if count > 1 => format!(" ×{}", count),
+                _ => String::new(),
+            };
+
             // Truncate if too long
-            let max_width = inner.width.saturating_sub(4) as usize;
+            let max_width = (inner.width.saturating_sub(4) as usize).saturating_sub(seen.width());
             let truncated = truncate_to_width(&display, max_width);
 
             // First song slightly highlighted, rest muted
@@ -101,6 +121,7 @@ impl<'a> Widget for SongHistory<'a> {
             lines.push(Line::from(vec![
                 Span::styled("  ", style),
                 Span::styled(truncated, style),
+                Span::styled(seen, theme.muted_style()),
             ]));
         }
 
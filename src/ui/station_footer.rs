@@ -0,0 +1,64 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Widget, Wrap},
+};
+
+use super::theme::Theme;
+use crate::api::Channel;
+
+/// Rows the footer takes below the station list
+pub const STATION_FOOTER_HEIGHT: u16 = 4;
+
+/// Genre and description of the highlighted station, below the list
+pub struct StationFooter<'a> {
+    channel: &'a Channel,
+    /// How long the station's been listened to, e.g. "3h 12m this week"
+    listened: Option<String>,
+    theme: &'a Theme,
+}
+
+impl<'a> StationFooter<'a> {
+    pub fn new(channel: &'a Channel, theme: &'a Theme) -> Self {
+        Self {
+            channel,
+            listened: None,
+            theme,
+        }
+    }
+
+    /// Show the station's listening time on the bottom border
+    pub fn listened(mut self, listened: Option<String>) -> Self {
+        self.listened = listened;
+        self
+    }
+}
+
+impl<'a> Widget for StationFooter<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // SomaFM separates genres with '|'
+        let genre = self.channel.genre.replace('|', ", ");
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border_style())
+            .title(Span::styled(format!(" {} ", genre), theme.muted_style()));
+        if let Some(listened) = &self.listened {
+            block = block.title_bottom(
+                Line::from(Span::styled(format!(" {} ", listened), theme.muted_style()))
+                    .right_aligned(),
+            );
+        }
+
+        let description = Line::from(Span::styled(
+            self.channel.description.as_str(),
+            theme.normal_style(),
+        ));
+        Paragraph::new(description)
+            .wrap(Wrap { trim: true })
+            .block(block)
+            .render(area, buf);
+    }
+}
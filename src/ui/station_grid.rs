@@ -0,0 +1,167 @@
+use image::RgbImage;
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    text::Span,
+    widgets::{Block, Borders, Widget},
+};
+use std::collections::HashMap;
+use unicode_width::UnicodeWidthStr;
+
+use super::theme::Theme;
+use crate::api::{Channel, StationCatalog};
+use crate::i18n::t;
+
+/// Thumbnail size in pixels; rendered with halfblocks as 16 columns x 8 rows
+pub const THUMBNAIL_SIZE: u32 = 16;
+
+const TILE_WIDTH: u16 = THUMBNAIL_SIZE as u16 + 2;
+const TILE_HEIGHT: u16 = THUMBNAIL_SIZE as u16 / 2 + 2;
+
+pub struct StationGrid<'a> {
+    catalog: &'a StationCatalog,
+    thumbnails: &'a HashMap<String, RgbImage>,
+    favorites: &'a [String],
+    current_station: Option<&'a str>,
+    selected: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> StationGrid<'a> {
+    pub fn new(
+        catalog: &'a StationCatalog,
+        thumbnails: &'a HashMap<String, RgbImage>,
+        favorites: &'a [String],
+        current_station: Option<&'a str>,
+        selected: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            catalog,
+            thumbnails,
+            favorites,
+            current_station,
+            selected,
+            theme,
+        }
+    }
+
+    /// Number of tile columns that fit in an area of the given width
+    /// (including the border)
+    pub fn columns_for(width: u16) -> usize {
+        (width.saturating_sub(2) / TILE_WIDTH).max(1) as usize
+    }
+
+    fn render_thumbnail(&self, channel: &Channel, x: u16, y: u16, buf: &mut Buffer) {
+        let Some(thumbnail) = self.thumbnails.get(&channel.id) else {
+            // Placeholder until the thumbnail has loaded
+            for row in 0..THUMBNAIL_SIZE as u16 / 2 {
+                for col in 0..THUMBNAIL_SIZE as u16 {
+                    if let Some(cell) = buf.cell_mut((x + col, y + row)) {
+                        cell.set_char('░').set_style(self.theme.muted_style());
+                    }
+                }
+            }
+            return;
+        };
+
+        // Each cell shows two vertical pixels: the upper half block takes the
+        // top pixel as foreground and the bottom pixel as background
+        for row in 0..thumbnail.height() / 2 {
+            for col in 0..thumbnail.width() {
+                let top = thumbnail.get_pixel(col, row * 2).0;
+                let bottom = thumbnail.get_pixel(col, row * 2 + 1).0;
+                if let Some(cell) = buf.cell_mut((x + col as u16, y + row as u16)) {
+                    cell.set_char('▀').set_style(
+                        Style::default()
+                            .fg(Color::Rgb(top[0], top[1], top[2]))
+                            .bg(Color::Rgb(bottom[0], bottom[1], bottom[2])),
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for ch in text.chars() {
+        if out.width() + 2 > width {
+            break;
+        }
+        out.push(ch);
+    }
+    out.push('…');
+    out
+}
+
+impl<'a> Widget for StationGrid<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .title(Span::styled(
+                format!(" {} ", t("grid-title")),
+                theme.title_style(),
+            ));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width < TILE_WIDTH || inner.height < TILE_HEIGHT {
+            return;
+        }
+
+        let columns = Self::columns_for(area.width);
+        let visible_rows = (inner.height / TILE_HEIGHT) as usize;
+        let selected_row = self.selected / columns;
+        let first_row = selected_row.saturating_sub(visible_rows - 1);
+
+        // Only the tiles in view are looked at
+        let len = self.catalog.sorted().len();
+        let first = (first_row * columns).min(len);
+        let shown = first..(first + visible_rows * columns).min(len);
+        for (i, channel) in shown.clone().zip(self.catalog.listed(shown)) {
+            let row = (i / columns - first_row) as u16;
+            let col = (i % columns) as u16;
+            let x = inner.x + col * TILE_WIDTH + 1;
+            let y = inner.y + row * TILE_HEIGHT;
+
+            self.render_thumbnail(channel, x, y, buf);
+
+            let is_favorite = self.favorites.contains(&channel.id);
+            let is_playing = self.current_station == Some(channel.id.as_str());
+            let prefix = if is_playing {
+                "▶ "
+            } else if is_favorite {
+                "★ "
+            } else {
+                ""
+            };
+            let label = truncate(
+                &format!("{}{}", prefix, channel.title),
+                THUMBNAIL_SIZE as usize,
+            );
+            let style = if i == self.selected {
+                theme.highlight_style()
+            } else if is_playing {
+                theme.playing_style()
+            } else {
+                theme.normal_style()
+            };
+            buf.set_stringn(
+                x,
+                y + THUMBNAIL_SIZE as u16 / 2,
+                label,
+                THUMBNAIL_SIZE as usize,
+                style,
+            );
+        }
+    }
+}
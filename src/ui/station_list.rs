@@ -1,38 +1,124 @@
+use std::collections::HashMap;
+
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, StatefulWidget},
 };
-use std::collections::HashSet;
 
 use super::theme::Theme;
-use crate::api::Channel;
+use crate::api::{ListenerTrends, Song, StationCatalog, Trend};
+use crate::i18n::{t, tf};
 
+/// The catalog's listed stations. Only the rows in view are built, so
+/// the list stays quick however many stations there are.
 pub struct StationList<'a> {
-    channels: &'a [Channel],
-    favorites: &'a HashSet<String>,
+    catalog: &'a StationCatalog,
+    favorites: &'a [String],
+    trends: &'a ListenerTrends,
     current_station: Option<&'a str>,
     is_focused: bool,
+    detailed: bool,
+    /// Songs fetched for the detailed list, by station id, shown in place
+    /// of the last track in the station list
+    now_playing: Option<&'a HashMap<String, Song>>,
+    search: Option<(&'a str, bool)>,
+    /// How the list is sorted, shown in the title
+    sort_label: Option<&'a str>,
+    /// The only source listed, shown in the title
+    source_label: Option<&'a str>,
+    /// Tag each station with where it comes from
+    badges: bool,
+    standby_station: Option<&'a str>,
+    /// Animation frame and last error while the list is still loading
+    loading: Option<(u64, Option<&'a str>)>,
     theme: &'a Theme,
 }
 
+/// Spinner shown while the channel list loads
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
 impl<'a> StationList<'a> {
     pub fn new(
-        channels: &'a [Channel],
-        favorites: &'a HashSet<String>,
+        catalog: &'a StationCatalog,
+        favorites: &'a [String],
+        trends: &'a ListenerTrends,
         current_station: Option<&'a str>,
         is_focused: bool,
+        detailed: bool,
         theme: &'a Theme,
     ) -> Self {
         Self {
-            channels,
+            catalog,
             favorites,
+            trends,
             current_station,
             is_focused,
+            detailed,
+            now_playing: None,
+            search: None,
+            sort_label: None,
+            source_label: None,
+            badges: false,
+            standby_station: None,
+            loading: None,
             theme,
         }
     }
+
+    /// Station list rows that fit in an area `height` rows tall, with the
+    /// border
+    pub fn rows_for(height: u16, detailed: bool) -> usize {
+        let row_height = if detailed { 2 } else { 1 };
+        (height.saturating_sub(2) / row_height) as usize
+    }
+
+    /// Show what each station is playing in the detailed list, where known
+    pub fn now_playing(mut self, songs: &'a HashMap<String, Song>) -> Self {
+        self.now_playing = Some(songs);
+        self
+    }
+
+    /// Name the sort order in the title
+    pub fn sort_label(mut self, label: &'a str) -> Self {
+        self.sort_label = Some(label);
+        self
+    }
+
+    /// Name the one source being listed in the title
+    pub fn source_label(mut self, label: Option<&'a str>) -> Self {
+        self.source_label = label;
+        self
+    }
+
+    /// Tag each station with its source, for lists mixing sources
+    pub fn badges(mut self, badges: bool) -> Self {
+        self.badges = badges;
+        self
+    }
+
+    /// Show the search query in the title; `editing` adds a cursor
+    pub fn search(mut self, query: &'a str, editing: bool) -> Self {
+        if editing || !query.is_empty() {
+            self.search = Some((query, editing));
+        }
+        self
+    }
+
+    /// Show a spinner, and the last error if fetching failed, in place of
+    /// stations that haven't arrived yet
+    pub fn loading(mut self, loading: Option<(u64, Option<&'a str>)>) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Mark the station playing muted in an A/B comparison
+    pub fn standby(mut self, station: Option<&'a str>) -> Self {
+        self.standby_station = station;
+        self
+    }
 }
 
 impl<'a> StatefulWidget for StationList<'a> {
@@ -41,15 +127,33 @@ impl<'a> StatefulWidget for StationList<'a> {
     fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
         let theme = self.theme;
 
-        let items: Vec<ListItem> = self
-            .channels
-            .iter()
-            .map(|channel| {
+        let total = self.catalog.sorted().len();
+        let rows = self.catalog.window(
+            state.offset(),
+            state.selected(),
+            Self::rows_for(area.height, self.detailed),
+        );
+        *state.offset_mut() = rows.start;
+
+        // Numbers right-aligned to the widest, for jumping to with `:`
+        let number_width = total.to_string().len();
+        let first = rows.start;
+        let mut items: Vec<ListItem> = self
+            .catalog
+            .listed(rows.clone())
+            .enumerate()
+            .map(|(i, channel)| {
                 let is_favorite = self.favorites.contains(&channel.id);
                 let is_playing = self.current_station == Some(&channel.id);
 
                 let star = if is_favorite { "★ " } else { "  " };
-                let playing_indicator = if is_playing { "▶ " } else { "" };
+                let playing_indicator = if is_playing {
+                    "▶ "
+                } else if self.standby_station == Some(&channel.id) {
+                    "▷ "
+                } else {
+                    ""
+                };
 
                 let star_style = if is_favorite {
                     theme.favorite_style()
@@ -57,41 +161,125 @@ impl<'a> StatefulWidget for StationList<'a> {
                     theme.muted_style()
                 };
 
+                let trend = self.trends.trend(&channel.id);
+
                 let title_style = if is_playing {
                     theme.playing_style()
+                } else if trend == Trend::Surging {
+                    theme.normal_style().add_modifier(Modifier::BOLD)
                 } else {
                     theme.normal_style()
                 };
 
                 let listeners = format!(" {}", channel.format_listeners());
+                let (arrow, arrow_style) = match trend {
+                    Trend::Steady => ("", theme.muted_style()),
+                    Trend::Rising => (" ▲", Style::default().fg(theme.success)),
+                    Trend::Falling => (" ▼", Style::default().fg(theme.warning)),
+                    Trend::Surging => (
+                        " ▲▲",
+                        Style::default()
+                            .fg(theme.success)
+                            .add_modifier(Modifier::BOLD),
+                    ),
+                };
 
+                let badge = if self.badges {
+                    format!("{} ", channel.source.badge())
+                } else {
+                    String::new()
+                };
                 let line = Line::from(vec![
+                    Span::styled(
+                        format!("{:>width$} ", first + i + 1, width = number_width),
+                        theme.muted_style(),
+                    ),
+                    Span::styled(badge, theme.muted_style()),
                     Span::styled(playing_indicator, theme.playing_style()),
                     Span::styled(star, star_style),
                     Span::styled(&channel.title, title_style),
                     Span::styled(listeners, theme.muted_style()),
+                    Span::styled(arrow, arrow_style),
                 ]);
 
-                ListItem::new(line)
+                if self.detailed {
+                    // Second, dimmed line: genre and what's playing, or
+                    // failing that the last track the station list had
+                    let playing = match self.now_playing.and_then(|songs| songs.get(&channel.id)) {
+                        Some(song) => format!("{} - {}", song.artist, song.title),
+                        None => channel.last_playing.clone(),
+                    };
+                    let details = Line::from(vec![
+                        Span::raw("    "),
+                        Span::styled(&channel.genre, theme.muted_style()),
+                        Span::styled(" · ", theme.muted_style()),
+                        Span::styled(playing, theme.muted_style()),
+                    ]);
+                    ListItem::new(vec![line, details])
+                } else {
+                    ListItem::new(line)
+                }
             })
             .collect();
 
+        if let (Some((frame, error)), true) = (self.loading, items.is_empty()) {
+            let spinner = SPINNER[(frame / 2) as usize % SPINNER.len()];
+            items.push(ListItem::new(Line::from(vec![
+                Span::styled(format!(" {} ", spinner), theme.playing_style()),
+                Span::styled(t("stations-loading"), theme.muted_style()),
+            ])));
+            if let Some(error) = error {
+                items.push(ListItem::new(Line::from(Span::styled(
+                    format!("   {}", tf("stations-retrying", &[("error", &error)])),
+                    theme.muted_style(),
+                ))));
+            }
+        }
+
         let border_style = if self.is_focused {
             theme.active_border_style()
         } else {
             theme.border_style()
         };
 
+        let mut title = vec![Span::styled(
+            format!(" {} ", t("stations-title")),
+            theme.title_style(),
+        )];
+        if let Some(label) = self.sort_label {
+            title.push(Span::styled(format!("· {} ", label), theme.muted_style()));
+        }
+        if let Some(label) = self.source_label {
+            title.push(Span::styled(
+                format!("· {} ", tf("stations-only", &[("source", &label)])),
+                theme.muted_style(),
+            ));
+        }
+        if let Some((query, editing)) = self.search {
+            title.push(Span::styled(
+                format!("/{}{} ", query, if editing { "▏" } else { "" }),
+                theme.selected_style(),
+            ));
+            title.push(Span::styled(format!("({}) ", total), theme.muted_style()));
+        }
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(border_style)
-            .title(Span::styled(" Stations ", theme.title_style()));
+            .title(Line::from(title));
 
         let list = List::new(items)
             .block(block)
             .highlight_style(theme.highlight_style())
             .highlight_symbol("│ ");
 
-        StatefulWidget::render(list, area, buf, state);
+        // The list only holds the rows in view, so its own state counts
+        // from the first of them
+        let mut window_state = ListState::default().with_selected(
+            state
+                .selected()
+                .filter(|selected| rows.contains(selected))
+                .map(|selected| selected - first),
+        );
+        StatefulWidget::render(list, area, buf, &mut window_state);
     }
 }
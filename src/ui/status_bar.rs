@@ -4,14 +4,28 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Paragraph, Widget},
 };
+use std::time::Duration;
+use unicode_width::UnicodeWidthStr;
 
 use super::theme::Theme;
+use crate::i18n::{t, tf};
+use crate::storage::config::DEFAULT_STATUS_TEMPLATE;
 
 pub struct StatusBar<'a> {
     is_playing: bool,
     is_paused: bool,
     volume: u8,
+    /// Volume mpv actually plays at, when a curve makes it differ
+    raw_volume: u8,
     theme_name: &'a str,
+    message: Option<&'a str>,
+    surfing: bool,
+    queued: usize,
+    recording: bool,
+    behind_live: Option<Duration>,
+    quality: &'a str,
+    bitrate: Option<u32>,
+    template: &'a str,
     theme: &'a Theme,
 }
 
@@ -27,62 +41,292 @@ impl<'a> StatusBar<'a> {
             is_playing,
             is_paused,
             volume,
+            raw_volume: volume,
             theme_name,
+            message: None,
+            surfing: false,
+            queued: 0,
+            recording: false,
+            behind_live: None,
+            quality: "",
+            bitrate: None,
+            template: DEFAULT_STATUS_TEMPLATE,
             theme,
         }
     }
 
+    /// Show mpv's own volume as the percentage, with the bar still
+    /// following the volume setting
+    pub fn raw_volume(mut self, raw_volume: u8) -> Self {
+        self.raw_volume = raw_volume;
+        self
+    }
+
+    /// Short-lived feedback shown in place of the key hints
+    pub fn message(mut self, message: Option<&'a str>) -> Self {
+        self.message = message;
+        self
+    }
+
+    /// Mark that surf mode is rotating stations
+    pub fn surfing(mut self, surfing: bool) -> Self {
+        self.surfing = surfing;
+        self
+    }
+
+    /// Number of stations waiting in the play queue
+    pub fn queued(mut self, queued: usize) -> Self {
+        self.queued = queued;
+        self
+    }
+
+    /// Mark that the stream is being saved to a file
+    pub fn recording(mut self, recording: bool) -> Self {
+        self.recording = recording;
+        self
+    }
+
+    /// How far a paused-and-resumed stream trails live
+    pub fn behind_live(mut self, behind: Option<Duration>) -> Self {
+        self.behind_live = behind;
+        self
+    }
+
+    /// Audio quality label, e.g. "HQ"
+    pub fn quality(mut self, quality: &'a str) -> Self {
+        self.quality = quality;
+        self
+    }
+
+    /// Measured stream bitrate in kbps
+    pub fn bitrate(mut self, bitrate: Option<u32>) -> Self {
+        self.bitrate = bitrate;
+        self
+    }
+
+    /// Layout as `|`-separated sections of `{token}`s and text; sections
+    /// whose tokens are all empty are left out
+    pub fn template(mut self, template: &'a str) -> Self {
+        self.template = template;
+        self
+    }
+
     fn volume_bar(&self) -> String {
         let filled = (self.volume as usize * 10) / 100;
         let empty = 10 - filled;
         format!("{}{}", "█".repeat(filled), "░".repeat(empty))
     }
+
+    /// Spans for one token, `None` if the name isn't a token
+    fn token(&self, name: &str) -> Option<Vec<Span<'a>>> {
+        let theme = self.theme;
+        let spans = match name {
+            "state" => {
+                let (icon, text, style) = if !self.is_playing {
+                    ("■", t("state-stopped"), theme.muted_style())
+                } else if self.is_paused {
+                    ("⏸", t("state-paused"), theme.paused_style())
+                } else {
+                    ("▶", t("state-playing"), theme.playing_style())
+                };
+                // Padded to the longest state so the rest of the bar doesn't shift
+                let width = ["state-stopped", "state-paused", "state-playing"]
+                    .iter()
+                    .map(|id| t(id).width())
+                    .max()
+                    .unwrap_or(0);
+                let padding = " ".repeat(width.saturating_sub(text.width()));
+                vec![Span::styled(format!("{} {}{}", icon, text, padding), style)]
+            }
+            "volume" => vec![
+                Span::styled(format!("{} ", t("status-volume")), theme.muted_style()),
+                Span::styled(self.volume_bar(), theme.normal_style()),
+                Span::styled(format!(" {:>3}%", self.raw_volume), theme.muted_style()),
+            ],
+            "volume_bar" => vec![Span::styled(self.volume_bar(), theme.normal_style())],
+            "volume_percent" => vec![Span::styled(
+                format!("{}%", self.raw_volume),
+                theme.normal_style(),
+            )],
+            "quality" => vec![Span::styled(self.quality, theme.selected_style())],
+            "bitrate" => self
+                .bitrate
+                .map(|kbps| Span::styled(format!("{} kbps", kbps), theme.muted_style()))
+                .into_iter()
+                .collect(),
+            "theme" => vec![Span::styled(
+                format!("{:<10}", self.theme_name),
+                theme.selected_style(),
+            )],
+            "rec" if self.recording => vec![Span::styled(
+                format!("● {}", t("status-rec")),
+                theme.paused_style(),
+            )],
+            "behind_live" => self
+                .behind_live
+                .map(|behind| {
+                    let secs = behind.as_secs();
+                    let time = format!("{}:{:02}", secs / 60, secs % 60);
+                    Span::styled(
+                        tf("status-behind-live", &[("time", &time)]),
+                        theme.paused_style(),
+                    )
+                })
+                .into_iter()
+                .collect(),
+            "surf" if self.surfing => vec![Span::styled(
+                format!("⟳ {}", t("status-surf")),
+                theme.playing_style(),
+            )],
+            "queue" if self.queued > 0 => vec![Span::styled(
+                format!("▸ {}", tf("status-queued", &[("count", &self.queued)])),
+                theme.playing_style(),
+            )],
+            "hints" => match self.message {
+                Some(message) => vec![Span::styled(message, theme.playing_style())],
+                None => {
+                    let hints = [
+                        ('p', "hint-play"),
+                        ('f', "hint-fav"),
+                        ('v', "hint-viz"),
+                        ('?', "hint-help"),
+                    ];
+                    let mut spans = Vec::new();
+                    for (i, (key, id)) in hints.into_iter().enumerate() {
+                        // "[p]lay" when the word starts with its key, else "[p] word"
+                        let word = t(id);
+                        let rest = word.strip_prefix(key).unwrap_or(word);
+                        let space = if key.is_alphabetic() && rest.len() == word.len() {
+                            " "
+                        } else {
+                            ""
+                        };
+                        let end = if i + 1 < hints.len() { " " } else { "" };
+                        spans.push(Span::styled(format!("[{}]", key), theme.selected_style()));
+                        spans.push(Span::styled(
+                            format!("{}{}{}", space, rest, end),
+                            theme.muted_style(),
+                        ));
+                    }
+                    spans
+                }
+            },
+            "rec" | "surf" | "queue" => Vec::new(),
+            _ => return None,
+        };
+        Some(spans)
+    }
+
+    /// Spans for one template section, `None` when it has nothing to show.
+    /// Spacing around a token that turned out empty is dropped with it.
+    fn section(&self, section: &'a str) -> Option<Vec<Span<'a>>> {
+        let muted = self.theme.muted_style();
+        let mut spans = Vec::new();
+        let mut has_tokens = false;
+        let mut shows_token = false;
+        // Text pushed last, and whether the token before it was empty
+        let mut last_text = None;
+        let mut after_empty = false;
+        let push_text = |spans: &mut Vec<Span<'a>>, text: &'a str, after_empty: bool| {
+            let text = if after_empty { text.trim_start() } else { text };
+            if !text.is_empty() {
+                spans.push(Span::styled(text, muted));
+            }
+            text
+        };
+
+        let mut rest = section;
+        while let Some(open) = rest.find('{') {
+            let Some(len) = rest[open..].find('}') else {
+                break;
+            };
+            if open > 0 {
+                last_text = Some(push_text(&mut spans, &rest[..open], after_empty));
+            }
+            let name = &rest[open + 1..open + len];
+            match self.token(name) {
+                Some(token) if token.is_empty() => {
+                    has_tokens = true;
+                    if let Some(text) = last_text.take() {
+                        if !text.is_empty() {
+                            spans.pop();
+                        }
+                        push_text(&mut spans, text.trim_end(), false);
+                    }
+                    after_empty = true;
+                }
+                Some(token) => {
+                    has_tokens = true;
+                    shows_token = true;
+                    spans.extend(token);
+                    last_text = None;
+                    after_empty = false;
+                }
+                None => {
+                    last_text = Some(push_text(&mut spans, &rest[open..=open + len], false));
+                    after_empty = false;
+                }
+            }
+            rest = &rest[open + len + 1..];
+        }
+        push_text(&mut spans, rest, after_empty);
+        (!spans.is_empty() && (shows_token || !has_tokens)).then_some(spans)
+    }
 }
 
 impl<'a> Widget for StatusBar<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let theme = self.theme;
+        let mut sections: Vec<Vec<Span>> = self
+            .template
+            .split('|')
+            .filter_map(|section| self.section(section.trim()))
+            .collect();
+        // Feedback has to show somewhere even if the template left out hints
+        if let Some(message) = self.message.filter(|_| !self.template.contains("{hints}")) {
+            sections.push(vec![Span::styled(message, theme.playing_style())]);
+        }
 
-        // Fixed-width status section (11 chars: " ▶ Playing " or " ⏸ Paused  " or " ■ Stopped ")
-        let (status_icon, status_text, status_style) = if !self.is_playing {
-            ("■", "Stopped", theme.muted_style())
-        } else if self.is_paused {
-            ("⏸", "Paused ", theme.paused_style())
-        } else {
-            ("▶", "Playing", theme.playing_style())
-        };
+        let mut spans = vec![Span::raw(" ")];
+        for (i, section) in sections.into_iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::styled(" │ ", theme.muted_style()));
+            }
+            spans.extend(section);
+        }
+
+        Paragraph::new(Line::from(spans)).render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    fn render(bar: StatusBar) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(60, 1)).unwrap();
+        terminal.draw(|f| f.render_widget(bar, f.area())).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect::<String>()
+    }
+
+    #[test]
+    fn test_template() {
+        let theme = Theme::default();
+        let bar = StatusBar::new(true, true, 50, "Nord", &theme)
+            .template("{state} | {quality} {bitrate} | {rec} | Vol {volume_percent}")
+            .quality("HQ");
+        assert_eq!(render(bar).trim_end(), " ⏸ Paused  │ HQ │ Vol 50%");
 
-        let volume_bar = self.volume_bar();
-        // Fixed-width volume section: "Vol: ██████████ 100%"
-        let volume_percent = format!("{:>3}%", self.volume);
-
-        // Fixed-width theme name (pad to 10 chars)
-        let theme_display = format!("{:<10}", self.theme_name);
-
-        let line = Line::from(vec![
-            // Status section (fixed 11 chars)
-            Span::styled(format!(" {} ", status_icon), status_style),
-            Span::styled(status_text, status_style),
-            Span::styled(" │ ", theme.muted_style()),
-            // Volume section (fixed width: "Vol: " + 10 bar chars + " " + 4 percent chars = 20)
-            Span::styled("Vol: ", theme.muted_style()),
-            Span::styled(&volume_bar, theme.normal_style()),
-            Span::styled(format!(" {}", volume_percent), theme.muted_style()),
-            Span::styled(" │ ", theme.muted_style()),
-            // Theme section (fixed 10 chars)
-            Span::styled(&theme_display, theme.selected_style()),
-            Span::styled(" │ ", theme.muted_style()),
-            // Help hints
-            Span::styled("[p]", theme.selected_style()),
-            Span::styled("lay ", theme.muted_style()),
-            Span::styled("[f]", theme.selected_style()),
-            Span::styled("av ", theme.muted_style()),
-            Span::styled("[v]", theme.selected_style()),
-            Span::styled("iz ", theme.muted_style()),
-            Span::styled("[?]", theme.selected_style()),
-            Span::styled("help", theme.muted_style()),
-        ]);
-
-        Paragraph::new(line).render(area, buf);
+        // Unknown tokens stay as typed, and hints give way to messages
+        let bar = StatusBar::new(false, false, 50, "Nord", &theme)
+            .template("{nope} | {hints}")
+            .message(Some("Saved"));
+        assert_eq!(render(bar).trim_end(), " {nope} │ Saved");
     }
 }
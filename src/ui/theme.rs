@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
 use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ThemeType {
@@ -11,9 +13,49 @@ pub enum ThemeType {
     Monochrome,
     #[default]
     Cyberpunk,
+    Adaptive,
+    HighContrast,
+    Daylight,
+    Linen,
 }
 
 impl ThemeType {
+    /// All built-in themes, in cycle order
+    pub const ALL: [ThemeType; 10] = [
+        Self::Synthwave,
+        Self::Ocean,
+        Self::Forest,
+        Self::Sunset,
+        Self::Monochrome,
+        Self::Cyberpunk,
+        Self::Adaptive,
+        Self::HighContrast,
+        Self::Daylight,
+        Self::Linen,
+    ];
+
+    /// Default theme for this terminal: the ANSI palette theme when truecolor
+    /// isn't available, since the RGB themes can't render faithfully there,
+    /// otherwise a light theme on light backgrounds
+    pub fn preferred(light: bool) -> Self {
+        if !supports_truecolor() {
+            Self::Adaptive
+        } else if light {
+            Self::Daylight
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Whether the theme is made for light backgrounds
+    pub fn is_light(self) -> bool {
+        matches!(self, Self::Daylight | Self::Linen)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|t| t.name() == name)
+    }
+
     pub fn next(self) -> Self {
         match self {
             Self::Synthwave => Self::Ocean,
@@ -21,7 +63,11 @@ impl ThemeType {
             Self::Forest => Self::Sunset,
             Self::Sunset => Self::Monochrome,
             Self::Monochrome => Self::Cyberpunk,
-            Self::Cyberpunk => Self::Synthwave,
+            Self::Cyberpunk => Self::Adaptive,
+            Self::Adaptive => Self::HighContrast,
+            Self::HighContrast => Self::Daylight,
+            Self::Daylight => Self::Linen,
+            Self::Linen => Self::Synthwave,
         }
     }
 
@@ -33,13 +79,17 @@ impl ThemeType {
             Self::Sunset => "Sunset",
             Self::Monochrome => "Mono",
             Self::Cyberpunk => "Cyberpunk",
+            Self::Adaptive => "Adaptive",
+            Self::HighContrast => "Contrast",
+            Self::Daylight => "Daylight",
+            Self::Linen => "Linen",
         }
     }
 }
 
 #[derive(Clone)]
 pub struct Theme {
-    pub name: &'static str,
+    pub name: String,
     pub background: Color,
     pub foreground: Color,
     pub primary: Color,
@@ -49,6 +99,8 @@ pub struct Theme {
     pub highlight: Color,
     pub success: Color,
     pub warning: Color,
+    /// This theme with `theme_overrides` applied, by widget name
+    pub widgets: HashMap<String, Theme>,
 }
 
 impl Theme {
@@ -60,12 +112,101 @@ impl Theme {
             ThemeType::Sunset => Self::sunset(),
             ThemeType::Monochrome => Self::monochrome(),
             ThemeType::Cyberpunk => Self::cyberpunk(),
+            ThemeType::Adaptive => Self::adaptive(),
+            ThemeType::HighContrast => Self::high_contrast(),
+            ThemeType::Daylight => Self::daylight(),
+            ThemeType::Linen => Self::linen(),
+        }
+    }
+
+    /// Colors for one part of the UI: this theme, with any overrides for
+    /// `widget` on top
+    pub fn widget(&self, widget: &str) -> &Theme {
+        self.widgets.get(widget).unwrap_or(self)
+    }
+
+    /// Layer per-widget overrides (widget name to color slot to color) over
+    /// this theme. Unknown slots and invalid colors are skipped.
+    pub fn with_overrides(mut self, overrides: &HashMap<String, HashMap<String, String>>) -> Self {
+        self.widgets.clear();
+        let base = self.clone();
+        for (widget, colors) in overrides {
+            let mut theme = base.clone();
+            for (slot, value) in colors {
+                if let (Some(color), Ok(value)) =
+                    (theme.slot_mut(slot), Color::from_str(value.trim()))
+                {
+                    *color = for_terminal(value);
+                }
+            }
+            self.widgets.insert(widget.clone(), theme);
+        }
+        self
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Option<&mut Color> {
+        match slot {
+            "background" => Some(&mut self.background),
+            "foreground" => Some(&mut self.foreground),
+            "primary" => Some(&mut self.primary),
+            "secondary" => Some(&mut self.secondary),
+            "accent" => Some(&mut self.accent),
+            "muted" => Some(&mut self.muted),
+            "highlight" => Some(&mut self.highlight),
+            "success" => Some(&mut self.success),
+            "warning" => Some(&mut self.warning),
+            _ => None,
+        }
+    }
+
+    /// Re-tint accent and highlight towards colors taken from artwork.
+    /// Only RGB colors are blended; palette-based themes are left unchanged.
+    pub fn tinted(self, palette: &[(u8, u8, u8)]) -> Self {
+        let Some(&first) = palette.first() else {
+            return self;
+        };
+        let second = palette.get(1).copied().unwrap_or(first);
+        Self {
+            accent: blend(self.accent, first, 0.6),
+            highlight: blend(self.highlight, second, 0.5),
+            ..self
+        }
+    }
+
+    /// Adapt the theme to the terminal: drop all colors when `NO_COLOR` is
+    /// set, and downsample RGB colors to the 256-color palette when the
+    /// terminal doesn't advertise truecolor support
+    pub fn for_terminal(self) -> Self {
+        if no_color() {
+            Self {
+                name: self.name,
+                ..Self::colorless()
+            }
+        } else if supports_truecolor() {
+            self
+        } else {
+            self.into_ansi256()
+        }
+    }
+
+    pub fn into_ansi256(self) -> Self {
+        Self {
+            background: quantize(self.background),
+            foreground: quantize(self.foreground),
+            primary: quantize(self.primary),
+            secondary: quantize(self.secondary),
+            accent: quantize(self.accent),
+            muted: quantize(self.muted),
+            highlight: quantize(self.highlight),
+            success: quantize(self.success),
+            warning: quantize(self.warning),
+            ..self
         }
     }
 
     fn synthwave() -> Self {
         Self {
-            name: "Synthwave",
+            name: "Synthwave".to_string(),
             background: Color::Rgb(20, 12, 28),
             foreground: Color::Rgb(255, 230, 250),
             primary: Color::Rgb(255, 0, 128),   // Hot pink
@@ -75,12 +216,13 @@ impl Theme {
             highlight: Color::Rgb(255, 220, 0), // Yellow
             success: Color::Rgb(0, 255, 180),
             warning: Color::Rgb(255, 180, 0),
+            widgets: HashMap::new(),
         }
     }
 
     fn ocean() -> Self {
         Self {
-            name: "Ocean",
+            name: "Ocean".to_string(),
             background: Color::Rgb(10, 25, 47),
             foreground: Color::Rgb(200, 220, 240),
             primary: Color::Rgb(100, 180, 255), // Sky blue
@@ -90,12 +232,13 @@ impl Theme {
             highlight: Color::Rgb(255, 200, 100), // Sandy
             success: Color::Rgb(80, 220, 150),
             warning: Color::Rgb(255, 180, 80),
+            widgets: HashMap::new(),
         }
     }
 
     fn forest() -> Self {
         Self {
-            name: "Forest",
+            name: "Forest".to_string(),
             background: Color::Rgb(15, 25, 15),
             foreground: Color::Rgb(220, 235, 210),
             primary: Color::Rgb(120, 200, 80),   // Leaf green
@@ -105,12 +248,13 @@ impl Theme {
             highlight: Color::Rgb(255, 200, 80), // Sunlight
             success: Color::Rgb(100, 220, 100),
             warning: Color::Rgb(220, 180, 60),
+            widgets: HashMap::new(),
         }
     }
 
     fn sunset() -> Self {
         Self {
-            name: "Sunset",
+            name: "Sunset".to_string(),
             background: Color::Rgb(30, 15, 25),
             foreground: Color::Rgb(255, 240, 230),
             primary: Color::Rgb(255, 100, 50),    // Orange
@@ -120,12 +264,13 @@ impl Theme {
             highlight: Color::Rgb(255, 220, 100), // Yellow
             success: Color::Rgb(150, 230, 120),
             warning: Color::Rgb(255, 200, 80),
+            widgets: HashMap::new(),
         }
     }
 
     fn monochrome() -> Self {
         Self {
-            name: "Mono",
+            name: "Mono".to_string(),
             background: Color::Rgb(15, 15, 15),
             foreground: Color::Rgb(220, 220, 220),
             primary: Color::Rgb(255, 255, 255),   // White
@@ -135,12 +280,13 @@ impl Theme {
             highlight: Color::Rgb(255, 255, 255), // White
             success: Color::Rgb(180, 255, 180),
             warning: Color::Rgb(255, 220, 150),
+            widgets: HashMap::new(),
         }
     }
 
     fn cyberpunk() -> Self {
         Self {
-            name: "Cyberpunk",
+            name: "Cyberpunk".to_string(),
             background: Color::Rgb(10, 10, 20),
             foreground: Color::Rgb(0, 255, 65), // Matrix green
             primary: Color::Rgb(0, 255, 65),    // Neon green
@@ -150,6 +296,95 @@ impl Theme {
             highlight: Color::Rgb(255, 255, 0), // Yellow
             success: Color::Rgb(0, 255, 100),
             warning: Color::Rgb(255, 150, 0),
+            widgets: HashMap::new(),
+        }
+    }
+
+    /// Uses the terminal's own ANSI palette, so it follows base16 and other
+    /// terminal color schemes and works without truecolor
+    fn adaptive() -> Self {
+        Self {
+            name: "Adaptive".to_string(),
+            background: Color::Reset,
+            foreground: Color::Reset,
+            primary: Color::Magenta,
+            secondary: Color::Cyan,
+            accent: Color::Blue,
+            muted: Color::DarkGray,
+            highlight: Color::Yellow,
+            success: Color::Green,
+            warning: Color::Red,
+            widgets: HashMap::new(),
+        }
+    }
+
+    /// Pure black background with every foreground color at a WCAG AAA
+    /// (7:1 or better) contrast ratio
+    fn high_contrast() -> Self {
+        Self {
+            name: "Contrast".to_string(),
+            background: Color::Rgb(0, 0, 0),
+            foreground: Color::Rgb(255, 255, 255),
+            primary: Color::Rgb(255, 255, 0),   // Yellow
+            secondary: Color::Rgb(0, 255, 255), // Cyan
+            accent: Color::Rgb(255, 255, 255),  // White
+            muted: Color::Rgb(200, 200, 200),
+            highlight: Color::Rgb(255, 255, 0), // Yellow
+            success: Color::Rgb(0, 255, 0),
+            warning: Color::Rgb(255, 170, 0),
+            widgets: HashMap::new(),
+        }
+    }
+
+    /// Near-white background with dark, saturated colors that stay readable
+    /// on it
+    fn daylight() -> Self {
+        Self {
+            name: "Daylight".to_string(),
+            background: Color::Rgb(250, 250, 247),
+            foreground: Color::Rgb(30, 32, 40),
+            primary: Color::Rgb(0, 90, 180),    // Blue
+            secondary: Color::Rgb(0, 125, 125), // Teal
+            accent: Color::Rgb(170, 50, 140),   // Magenta
+            muted: Color::Rgb(115, 118, 128),
+            highlight: Color::Rgb(180, 100, 0), // Amber
+            success: Color::Rgb(20, 125, 50),
+            warning: Color::Rgb(190, 70, 0),
+            widgets: HashMap::new(),
+        }
+    }
+
+    /// Warm paper background with ink-like colors
+    fn linen() -> Self {
+        Self {
+            name: "Linen".to_string(),
+            background: Color::Rgb(245, 238, 225),
+            foreground: Color::Rgb(55, 42, 32),
+            primary: Color::Rgb(165, 60, 40),   // Rust
+            secondary: Color::Rgb(60, 105, 60), // Sage
+            accent: Color::Rgb(40, 90, 145),    // Ink blue
+            muted: Color::Rgb(140, 124, 108),
+            highlight: Color::Rgb(175, 40, 95), // Raspberry
+            success: Color::Rgb(55, 120, 40),
+            warning: Color::Rgb(180, 90, 0),
+            widgets: HashMap::new(),
+        }
+    }
+
+    /// Terminal default colors only; emphasis comes from modifiers
+    fn colorless() -> Self {
+        Self {
+            name: "NoColor".to_string(),
+            background: Color::Reset,
+            foreground: Color::Reset,
+            primary: Color::Reset,
+            secondary: Color::Reset,
+            accent: Color::Reset,
+            muted: Color::Reset,
+            highlight: Color::Reset,
+            success: Color::Reset,
+            warning: Color::Reset,
+            widgets: HashMap::new(),
         }
     }
 
@@ -168,6 +403,10 @@ impl Theme {
     }
 
     pub fn highlight_style(&self) -> Style {
+        if self.primary == Color::Reset {
+            // No color to fill with, so invert the default colors instead
+            return Style::default().add_modifier(Modifier::REVERSED | Modifier::BOLD);
+        }
         Style::default()
             .fg(self.background)
             .bg(self.primary)
@@ -216,5 +455,118 @@ impl Default for Theme {
     }
 }
 
+/// Whether the user asked for no color output (https://no-color.org)
+pub fn no_color() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty())
+}
+
+/// Whether the terminal advertises 24-bit color via `COLORTERM`
+/// (Windows Terminal always supports it but doesn't set the variable)
+pub fn supports_truecolor() -> bool {
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    matches!(colorterm.as_str(), "truecolor" | "24bit") || std::env::var_os("WT_SESSION").is_some()
+}
+
+/// Whether the terminal reports a light background through `COLORFGBG`;
+/// `None` when it doesn't say
+pub fn terminal_is_light() -> Option<bool> {
+    light_background(&std::env::var("COLORFGBG").ok()?)
+}
+
+/// `COLORFGBG` is `fg;bg` (sometimes `fg;default;bg`) in ANSI color
+/// numbers; light gray and the bright colors are light backgrounds
+fn light_background(colorfgbg: &str) -> Option<bool> {
+    let background: u8 = colorfgbg.rsplit(';').next()?.parse().ok()?;
+    Some(matches!(background, 7 | 9..=15))
+}
+
+/// Mix an RGB color towards `target` by `amount` (0.0 to 1.0)
+fn blend(color: Color, target: (u8, u8, u8), amount: f32) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount) as u8;
+            Color::Rgb(mix(r, target.0), mix(g, target.1), mix(b, target.2))
+        }
+        other => other,
+    }
+}
+
+/// Adapt a single color the way `Theme::for_terminal` adapts a theme
+fn for_terminal(color: Color) -> Color {
+    if no_color() {
+        Color::Reset
+    } else if supports_truecolor() {
+        color
+    } else {
+        quantize(color)
+    }
+}
+
+/// Map a color to the nearest entry of the xterm 256-color palette
+fn quantize(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Indexed(rgb_to_ansi256(r, g, b)),
+        other => other,
+    }
+}
+
+fn rgb_to_ansi256(r: u8, g: u8, b: u8) -> u8 {
+    // Grays map onto the 24-step grayscale ramp (232-255, 8 + 10 * i)
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            _ => 232 + ((r - 8 + 5) / 10).min(23),
+        };
+    }
+
+    // Everything else onto the 6x6x6 color cube (16-231)
+    let level = |c: u8| ((c as u16 * 5 + 127) / 255) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
 // Legacy static theme for components that haven't been updated yet
 pub static THEME: std::sync::LazyLock<Theme> = std::sync::LazyLock::new(Theme::default);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rgb_to_ansi256() {
+        assert_eq!(rgb_to_ansi256(0, 0, 0), 16);
+        assert_eq!(rgb_to_ansi256(255, 255, 255), 231);
+        assert_eq!(rgb_to_ansi256(255, 0, 0), 196);
+        assert_eq!(rgb_to_ansi256(0, 255, 65), 47);
+        assert_eq!(rgb_to_ansi256(128, 128, 128), 244);
+    }
+
+    #[test]
+    fn test_light_background() {
+        assert_eq!(light_background("0;15"), Some(true));
+        assert_eq!(light_background("15;0"), Some(false));
+        assert_eq!(light_background("12;default;7"), Some(true));
+        assert_eq!(light_background("15;8"), Some(false));
+        assert_eq!(light_background("default;default"), None);
+    }
+
+    #[test]
+    fn test_widget_overrides() {
+        let overrides = HashMap::from([(
+            "visualizer".to_string(),
+            HashMap::from([
+                ("primary".to_string(), "white".to_string()),
+                ("accent".to_string(), "not a color".to_string()),
+                ("sparkle".to_string(), "red".to_string()),
+            ]),
+        )]);
+        let theme = Theme::from_type(ThemeType::Synthwave).with_overrides(&overrides);
+        let base = Theme::from_type(ThemeType::Synthwave);
+
+        let visualizer = theme.widget("visualizer");
+        assert_ne!(visualizer.primary, base.primary);
+        assert_eq!(visualizer.accent, base.accent);
+        assert_eq!(visualizer.secondary, base.secondary);
+        assert_eq!(theme.widget("header").primary, base.primary);
+    }
+}
@@ -0,0 +1,123 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// Popup listing every theme by name, each with a swatch of its colors.
+/// The popup itself is drawn in the theme being previewed.
+pub struct ThemePicker<'a> {
+    themes: &'a [Theme],
+    selected: usize,
+    /// The theme in use when the picker opened
+    current: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> ThemePicker<'a> {
+    pub fn new(themes: &'a [Theme], selected: usize, current: usize, theme: &'a Theme) -> Self {
+        Self {
+            themes,
+            selected,
+            current,
+            theme,
+        }
+    }
+}
+
+/// A row of blocks in a theme's main colors, on its background
+fn swatch(theme: &Theme) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::styled(" ", Style::default().bg(theme.background))];
+    for color in [
+        theme.foreground,
+        theme.primary,
+        theme.secondary,
+        theme.accent,
+        theme.highlight,
+        theme.success,
+        theme.warning,
+        theme.muted,
+    ] {
+        spans.push(Span::styled(
+            "██",
+            Style::default().fg(color).bg(theme.background),
+        ));
+    }
+    spans.push(Span::styled(" ", Style::default().bg(theme.background)));
+    spans
+}
+
+impl<'a> Widget for ThemePicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // Sized to the list: one row per theme plus borders and the hint line
+        let width = 44.min(area.width);
+        let height = (self.themes.len() as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("themes-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = self
+            .themes
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, entry)| {
+                let marker = if i == self.current { "▶ " } else { "  " };
+                let style = if i == self.selected {
+                    theme.highlight_style()
+                } else {
+                    theme.normal_style()
+                };
+                let mut spans = vec![
+                    Span::styled(marker, theme.playing_style()),
+                    Span::styled(format!("{:<16}", entry.name), style),
+                ];
+                spans.extend(swatch(entry));
+                Line::from(spans)
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-themes"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
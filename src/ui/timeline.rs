@@ -0,0 +1,138 @@
+use chrono::{Local, TimeZone};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::api::TimelineEntry;
+use crate::i18n::t;
+
+/// Popup listing everything a station played, newest first
+pub struct TimelinePopup<'a> {
+    station: &'a str,
+    entries: &'a [&'a TimelineEntry],
+    scroll: usize,
+    /// "since 14:02" or "today"
+    scope: &'a str,
+    search: Option<(&'a str, bool)>,
+    theme: &'a Theme,
+}
+
+impl<'a> TimelinePopup<'a> {
+    pub fn new(
+        station: &'a str,
+        entries: &'a [&'a TimelineEntry],
+        scroll: usize,
+        scope: &'a str,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            station,
+            entries,
+            scroll,
+            scope,
+            search: None,
+            theme,
+        }
+    }
+
+    /// Show the filter in the title; `editing` adds a cursor
+    pub fn search(mut self, query: &'a str, editing: bool) -> Self {
+        if editing || !query.is_empty() {
+            self.search = Some((query, editing));
+        }
+        self
+    }
+}
+
+impl<'a> Widget for TimelinePopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let width = 72.min(area.width);
+        let height = (area.height * 4 / 5).max(8).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let mut title = vec![Span::styled(
+            format!(" {} · {} ", self.station, self.scope),
+            theme.title_style(),
+        )];
+        if let Some((query, editing)) = self.search {
+            title.push(Span::styled(
+                format!("/{}{} ", query, if editing { "▏" } else { "" }),
+                theme.selected_style(),
+            ));
+        }
+        title.push(Span::styled(
+            format!("({}) ", self.entries.len()),
+            theme.muted_style(),
+        ));
+
+        let block = Block::default()
+            .title(Line::from(title))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.scroll.min(self.entries.len().saturating_sub(visible));
+
+        let mut lines: Vec<Line> = if self.entries.is_empty() {
+            vec![Line::from(Span::styled(
+                t("timeline-empty"),
+                theme.muted_style(),
+            ))]
+        } else {
+            self.entries
+                .iter()
+                .skip(first)
+                .take(visible)
+                .map(|entry| {
+                    let time = Local
+                        .timestamp_opt(entry.played_at, 0)
+                        .single()
+                        .map(|t| t.format("%H:%M").to_string())
+                        .unwrap_or_default();
+                    let mut spans = vec![
+                        Span::styled(format!("{:>5}  ", time), theme.muted_style()),
+                        Span::styled(entry.title.as_str(), theme.normal_style()),
+                    ];
+                    if !entry.artist.is_empty() {
+                        spans.push(Span::styled(" · ", theme.muted_style()));
+                        spans.push(Span::styled(entry.artist.as_str(), theme.muted_style()));
+                    }
+                    Line::from(spans)
+                })
+                .collect()
+        };
+
+        // Pin the hint to the bottom row
+        lines.resize(visible.max(lines.len()), Line::from(""));
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-timeline"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,33 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// One-line new-user tip shown above the status bar
+pub struct TipBar<'a> {
+    tip: &'a str,
+    theme: &'a Theme,
+}
+
+impl<'a> TipBar<'a> {
+    pub fn new(tip: &'a str, theme: &'a Theme) -> Self {
+        Self { tip, theme }
+    }
+}
+
+impl<'a> Widget for TipBar<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+        let line = Line::from(vec![
+            Span::styled(format!(" {} ", t("tip-label")), theme.selected_style()),
+            Span::styled(self.tip, theme.normal_style()),
+            Span::styled(format!("  ({})", t("tip-dismiss")), theme.muted_style()),
+        ]);
+        Paragraph::new(line).render(area, buf);
+    }
+}
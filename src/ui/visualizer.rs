@@ -1,13 +1,17 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Style,
+    style::{Color, Style},
     text::Span,
     widgets::{Block, Borders, Widget},
 };
 
 use super::theme::Theme;
-use crate::visualizer::SpectrumData;
+use std::str::FromStr;
+use unicode_width::UnicodeWidthStr;
+
+use crate::i18n::{t, tf};
+use crate::visualizer::{PluginFrame, PluginRegistry, SpectrumData};
 
 /// Different visualization modes
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -21,21 +25,26 @@ pub enum VisualizationMode {
     Heart,
     Spiral,
     Rain,
+    Bars,
+    Waterfall,
+    /// A script from the visualizer plugin registry
+    Plugin(usize),
 }
 
 impl VisualizationMode {
-    pub fn next(self) -> Self {
-        match self {
-            Self::Spirograph => Self::Pulse,
-            Self::Pulse => Self::Wave,
-            Self::Wave => Self::Bounce,
-            Self::Bounce => Self::Starfield,
-            Self::Starfield => Self::Heart,
-            Self::Heart => Self::Spiral,
-            Self::Spiral => Self::Rain,
-            Self::Rain => Self::Spirograph,
-        }
-    }
+    /// Built-in modes in rotation order; plugin modes follow these
+    pub const BUILT_IN: [VisualizationMode; 10] = [
+        Self::Spirograph,
+        Self::Pulse,
+        Self::Wave,
+        Self::Bounce,
+        Self::Starfield,
+        Self::Heart,
+        Self::Spiral,
+        Self::Rain,
+        Self::Bars,
+        Self::Waterfall,
+    ];
 
     pub fn name(self) -> &'static str {
         match self {
@@ -47,19 +56,31 @@ impl VisualizationMode {
             Self::Heart => "Heart",
             Self::Spiral => "Spiral",
             Self::Rain => "Rain",
+            Self::Bars => "Bars",
+            Self::Waterfall => "Waterfall",
+            Self::Plugin(_) => "Plugin",
         }
     }
 }
 
+// Eighth-height blocks for sub-cell bar tops
+const BAR_CHARS: &[char] = &[' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+// Intensity ramp for the waterfall
+const WATERFALL_CHARS: &[char] = &[' ', '░', '▒', '▓', '█'];
+
 // Characters for spirograph drawing
 const SPIRO_CHARS: &[char] = &['·', '•', '○', '●', '◉', '★', '✦', '✧'];
 
 pub struct Visualizer<'a> {
     spectrum: &'a SpectrumData,
+    history: &'a [Vec<f32>],
+    plugins: Option<&'a PluginRegistry>,
     is_playing: bool,
     is_paused: bool,
     mode: VisualizationMode,
     frame: u64,
+    reduced_motion: bool,
     theme: &'a Theme,
 }
 
@@ -70,22 +91,254 @@ impl<'a> Visualizer<'a> {
         is_paused: bool,
         mode: VisualizationMode,
         frame: u64,
+        reduced_motion: bool,
         theme: &'a Theme,
     ) -> Self {
         Self {
             spectrum,
+            history: &[],
+            plugins: None,
             is_playing,
             is_paused,
             mode,
             frame,
+            reduced_motion,
             theme,
         }
     }
 
+    /// Recent spectrum frames (oldest first) for the waterfall mode
+    pub fn history(mut self, history: &'a [Vec<f32>]) -> Self {
+        self.history = history;
+        self
+    }
+
+    /// Script registry used to draw `VisualizationMode::Plugin` modes
+    pub fn plugins(mut self, plugins: &'a PluginRegistry) -> Self {
+        self.plugins = Some(plugins);
+        self
+    }
+
     fn energy(&self) -> f32 {
         (self.spectrum.rms * 0.5 + self.spectrum.peak * 0.5).clamp(0.0, 1.0)
     }
 
+    /// Energy with a kick on detected beats, for modes that pulse
+    fn beat_energy(&self) -> f32 {
+        if self.spectrum.beat {
+            (self.energy() + 0.35).min(1.0)
+        } else {
+            self.energy()
+        }
+    }
+
+    /// Shown while playing without level data from mpv: a dim dot drifting
+    /// along a line, labeled so it isn't taken for a reacting visualization
+    fn render_no_data(&self, area: Rect, buf: &mut Buffer) {
+        let muted = Style::default().fg(self.theme.muted);
+        let label = t("viz-no-data");
+        let y = area.y + area.height / 2;
+        if area.width as usize > label.width() {
+            let x = area.x + (area.width - label.width() as u16) / 2;
+            buf.set_string(x, y, label, muted);
+        }
+
+        let line_y = y + 1;
+        if line_y >= area.y + area.height {
+            return;
+        }
+        let dot = (!self.reduced_motion).then(|| (self.frame / 6 % area.width as u64) as u16);
+        for i in 0..area.width {
+            let ch = if Some(i) == dot { '•' } else { '·' };
+            if let Some(cell) = buf.cell_mut((area.x + i, line_y)) {
+                cell.set_char(ch).set_style(muted);
+            }
+        }
+    }
+
+    /// Right channel's level less the left's, -1.0 (all left) to 1.0 (all
+    /// right)
+    fn balance(&self) -> f32 {
+        let [left, right] = self.spectrum.channels;
+        (right - left).clamp(-1.0, 1.0)
+    }
+
+    /// Still level meter used in reduced-motion mode: bars that only change
+    /// length with the (smoothed) audio level, one per channel when there's
+    /// room
+    fn render_level(&self, area: Rect, buf: &mut Buffer) {
+        let width = area.width.saturating_sub(4);
+        let x = area.x + 2;
+        let rows: Vec<(u16, f32)> = if area.height >= 3 {
+            let y = area.y + area.height / 2;
+            let [left, right] = self.spectrum.channels;
+            vec![(y - 1, left), (y + 1, right)]
+        } else {
+            vec![(area.y + area.height / 2, self.energy())]
+        };
+
+        for (y, level) in rows {
+            let filled = (level.clamp(0.0, 1.0) * width as f32).round() as u16;
+            for i in 0..width {
+                let (ch, color) = if i < filled {
+                    ('█', self.theme.accent)
+                } else {
+                    ('░', self.theme.muted)
+                };
+                if let Some(cell) = buf.cell_mut((x + i, y)) {
+                    cell.set_char(ch).set_style(Style::default().fg(color));
+                }
+            }
+        }
+    }
+
+    fn render_bars(&self, area: Rect, buf: &mut Buffer) {
+        // One bar per bin, or fewer (skipping bins) when the area is narrow
+        let bins = self.spectrum.bins.len();
+        if bins == 0 {
+            return;
+        }
+        let bars = bins.min(area.width as usize);
+        let slot = area.width as usize / bars;
+        // Leave a gap between bars when there is room for one
+        let bar_width = if slot >= 2 { slot - 1 } else { 1 } as u16;
+        let left = area.x + ((area.width as usize - slot * bars) / 2) as u16;
+        let height = area.height as f32;
+
+        for col in 0..bars {
+            let bin = col * bins / bars;
+            let value = self.spectrum.bins[bin].clamp(0.0, 1.0);
+            let peak = self.spectrum.peaks[bin].clamp(0.0, 1.0);
+
+            // Each bar is drawn in eighths of a cell, bottom up
+            let eighths = (value * height * 8.0).round() as u16;
+            let x = left + (col * slot) as u16;
+
+            for row in 0..area.height {
+                let filled = eighths.saturating_sub(row * 8).min(8);
+                if filled == 0 {
+                    break;
+                }
+                let y = area.y + area.height - 1 - row;
+                // Gradient from primary at the bottom to highlight at the top
+                let level = row as f32 / height;
+                let color = if level > 0.75 {
+                    self.theme.highlight
+                } else if level > 0.4 {
+                    self.theme.accent
+                } else {
+                    self.theme.primary
+                };
+                for dx in 0..bar_width {
+                    if let Some(cell) = buf.cell_mut((x + dx, y)) {
+                        cell.set_char(BAR_CHARS[filled as usize])
+                            .set_style(Style::default().fg(color));
+                    }
+                }
+            }
+
+            // Peak cap floats above the bar and falls slowly
+            let peak_row = (peak * height) as u16;
+            if peak_row > eighths / 8 && peak_row < area.height {
+                let y = area.y + area.height - 1 - peak_row;
+                for dx in 0..bar_width {
+                    if let Some(cell) = buf.cell_mut((x + dx, y)) {
+                        cell.set_char('▔')
+                            .set_style(Style::default().fg(self.theme.highlight));
+                    }
+                }
+            }
+        }
+    }
+
+    fn render_waterfall(&self, area: Rect, buf: &mut Buffer) {
+        // Newest frame in the rightmost column, scrolling left over time
+        let columns = (area.width as usize).min(self.history.len());
+        let frames = &self.history[self.history.len() - columns..];
+        let left = area.x + area.width - columns as u16;
+
+        for (col, frame) in frames.iter().enumerate() {
+            let x = left + col as u16;
+            for row in 0..area.height {
+                // Low frequencies at the bottom
+                let bin = row as usize * frame.len() / area.height as usize;
+                let value = frame[bin].clamp(0.0, 1.0);
+                let level = (value * (WATERFALL_CHARS.len() - 1) as f32).round() as usize;
+                if level == 0 {
+                    continue;
+                }
+                let color = match level {
+                    1 => self.theme.muted,
+                    2 => self.theme.primary,
+                    3 => self.theme.accent,
+                    _ => self.theme.highlight,
+                };
+                let y = area.y + area.height - 1 - row;
+                if let Some(cell) = buf.cell_mut((x, y)) {
+                    cell.set_char(WATERFALL_CHARS[level])
+                        .set_style(Style::default().fg(color));
+                }
+            }
+        }
+    }
+
+    /// Resolve a plugin color: a theme slot name, or any ratatui color
+    fn plugin_color(&self, name: &str) -> Color {
+        match name {
+            "foreground" => self.theme.foreground,
+            "primary" => self.theme.primary,
+            "secondary" => self.theme.secondary,
+            "accent" => self.theme.accent,
+            "muted" => self.theme.muted,
+            "highlight" => self.theme.highlight,
+            "success" => self.theme.success,
+            "warning" => self.theme.warning,
+            other => Color::from_str(other).unwrap_or(self.theme.accent),
+        }
+    }
+
+    fn render_plugin(&self, index: usize, area: Rect, buf: &mut Buffer) {
+        let Some(plugins) = self.plugins else {
+            return;
+        };
+        let frame = PluginFrame {
+            spectrum: self.spectrum,
+            frame: self.frame,
+            width: area.width,
+            height: area.height,
+        };
+
+        match plugins.render(index, &frame) {
+            Ok(cells) => {
+                for cell in cells {
+                    if cell.x < 0
+                        || cell.y < 0
+                        || cell.x >= area.width as i64
+                        || cell.y >= area.height as i64
+                    {
+                        continue;
+                    }
+                    let color = self.plugin_color(&cell.color);
+                    if let Some(c) = buf.cell_mut((area.x + cell.x as u16, area.y + cell.y as u16))
+                    {
+                        c.set_char(cell.ch).set_style(Style::default().fg(color));
+                    }
+                }
+            }
+            Err(err) => {
+                // Show script errors in place of the visualization
+                let msg = err.to_string();
+                buf.set_stringn(
+                    area.x,
+                    area.y,
+                    msg,
+                    area.width as usize,
+                    self.theme.muted_style(),
+                );
+            }
+        }
+    }
+
     fn render_spirograph(&self, area: Rect, buf: &mut Buffer) {
         let energy = self.energy();
         let time = self.frame as f32 * 0.03;
@@ -177,9 +430,11 @@ impl<'a> Visualizer<'a> {
     }
 
     fn render_pulse(&self, area: Rect, buf: &mut Buffer) {
-        let cx = area.x + area.width / 2;
+        // The rings drift toward the louder channel
+        let drift = self.balance() * area.width as f32 / 4.0;
+        let cx = (area.x as f32 + area.width as f32 / 2.0 + drift) as u16;
         let cy = area.y + area.height / 2;
-        let energy = self.energy();
+        let energy = self.beat_energy();
 
         // Speed varies dramatically with energy - slow when quiet, fast when loud
         let speed = 0.05 + energy * 0.35;
@@ -289,7 +544,7 @@ impl<'a> Visualizer<'a> {
     }
 
     fn render_bounce(&self, area: Rect, buf: &mut Buffer) {
-        let energy = self.energy();
+        let energy = self.beat_energy();
         let time = self.frame as f32 * 0.1;
 
         // Many bouncing items with varied characters, colors, speeds, and positions
@@ -565,10 +820,20 @@ impl<'a> Widget for Visualizer<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let theme = self.theme;
 
+        let title = match self.spectrum.bpm {
+            Some(bpm) if self.is_playing && !self.is_paused => {
+                format!(
+                    " {} ",
+                    tf("viz-title-bpm", &[("bpm", &format!("{:.0}", bpm))])
+                )
+            }
+            _ => format!(" {} ", t("viz-title")),
+        };
+
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(theme.border_style())
-            .title(Span::styled(" Visualizer ", theme.title_style()));
+            .title(Span::styled(title, theme.title_style()));
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -578,7 +843,11 @@ impl<'a> Widget for Visualizer<'a> {
         }
 
         // Render the selected visualization
-        if self.is_playing && !self.is_paused {
+        if self.is_playing && !self.is_paused && !self.spectrum.has_audio {
+            self.render_no_data(inner, buf);
+        } else if self.is_playing && !self.is_paused && self.reduced_motion {
+            self.render_level(inner, buf);
+        } else if self.is_playing && !self.is_paused {
             match self.mode {
                 VisualizationMode::Spirograph => self.render_spirograph(inner, buf),
                 VisualizationMode::Pulse => self.render_pulse(inner, buf),
@@ -588,13 +857,16 @@ impl<'a> Widget for Visualizer<'a> {
                 VisualizationMode::Heart => self.render_heart(inner, buf),
                 VisualizationMode::Spiral => self.render_spiral(inner, buf),
                 VisualizationMode::Rain => self.render_rain(inner, buf),
+                VisualizationMode::Bars => self.render_bars(inner, buf),
+                VisualizationMode::Waterfall => self.render_waterfall(inner, buf),
+                VisualizationMode::Plugin(index) => self.render_plugin(index, inner, buf),
             }
         }
 
         // If paused, show message
         if self.is_paused && inner.width > 10 {
-            let msg = "PAUSED";
-            let x = inner.x + (inner.width - msg.len() as u16) / 2;
+            let msg = t("viz-paused");
+            let x = inner.x + (inner.width.saturating_sub(msg.width() as u16)) / 2;
             let y = inner.y + inner.height / 2;
             if y < inner.y + inner.height {
                 for (i, c) in msg.chars().enumerate() {
@@ -607,8 +879,8 @@ impl<'a> Widget for Visualizer<'a> {
 
         // If not playing, show message
         if !self.is_playing && inner.width > 20 {
-            let msg = "Select a station to play";
-            let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
+            let msg = t("viz-idle");
+            let x = inner.x + (inner.width.saturating_sub(msg.width() as u16)) / 2;
             let y = inner.y + inner.height / 2;
             if y < inner.y + inner.height {
                 for (i, c) in msg.chars().enumerate() {
@@ -0,0 +1,111 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Constraint, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// Popup listing visualization modes by name. Each entry is the mode's name
+/// and whether it's part of the `v` rotation.
+pub struct VisualizerPicker<'a> {
+    entries: &'a [(String, bool)],
+    selected: usize,
+    current: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> VisualizerPicker<'a> {
+    pub fn new(
+        entries: &'a [(String, bool)],
+        selected: usize,
+        current: usize,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            entries,
+            selected,
+            current,
+            theme,
+        }
+    }
+}
+
+impl<'a> Widget for VisualizerPicker<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        // Sized to the list: one row per mode plus borders and the hint line
+        let width = 36.min(area.width);
+        let height = (self.entries.len() as u16 + 4).min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("viz-picker-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(theme.active_border_style())
+            .style(ratatui::style::Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        // Keep the selection in view on short terminals
+        let visible = inner.height.saturating_sub(2) as usize;
+        let first = self.selected.saturating_sub(visible.saturating_sub(1));
+
+        let mut lines: Vec<Line> = self
+            .entries
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .map(|(i, (name, enabled))| {
+                let marker = if i == self.current { "▶ " } else { "  " };
+                let style = if i == self.selected {
+                    theme.highlight_style()
+                } else if *enabled {
+                    theme.normal_style()
+                } else {
+                    theme.muted_style()
+                };
+                let mut spans = vec![
+                    Span::styled(marker, theme.playing_style()),
+                    Span::styled(name.as_str(), style),
+                ];
+                if !enabled {
+                    spans.push(Span::styled(
+                        format!(" {}", t("viz-picker-off")),
+                        theme.muted_style(),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t("keys-viz-picker"),
+            theme.muted_style(),
+        )));
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
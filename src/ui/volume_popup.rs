@@ -0,0 +1,166 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Constraint, Layout, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph, Widget},
+};
+
+use super::theme::Theme;
+use crate::i18n::{t, tf};
+use crate::player::VolumeCurve;
+
+/// Settings marked on the scale under the slider
+const SCALE_MARKS: [u8; 5] = [0, 25, 50, 75, 100];
+
+/// Brief popup shown on volume keys: a large slider with the volume and
+/// roughly how loud that is in dB
+pub struct VolumePopup<'a> {
+    volume: u8,
+    muted: bool,
+    curve: VolumeCurve,
+    /// About to close, so drawn dimmed
+    fading: bool,
+    theme: &'a Theme,
+}
+
+impl<'a> VolumePopup<'a> {
+    pub fn new(volume: u8, theme: &'a Theme) -> Self {
+        Self {
+            volume,
+            muted: false,
+            curve: VolumeCurve::default(),
+            fading: false,
+            theme,
+        }
+    }
+
+    pub fn muted(mut self, muted: bool) -> Self {
+        self.muted = muted;
+        self
+    }
+
+    /// The volume curve, which the dB figures follow
+    pub fn curve(mut self, curve: VolumeCurve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    pub fn fading(mut self, fading: bool) -> Self {
+        self.fading = fading;
+        self
+    }
+
+    /// dB labels spaced out under a slider `width` cells wide
+    fn scale(&self, width: usize) -> String {
+        let mut scale = vec![' '; width];
+        for mark in SCALE_MARKS {
+            let label: Vec<char> = decibels(self.curve.apply(mark), 0).chars().collect();
+            let at = mark as usize * width.saturating_sub(1) / 100;
+            let start = at
+                .saturating_sub(label.len() / 2)
+                .min(width.saturating_sub(label.len()));
+            for (cell, c) in scale.iter_mut().skip(start).zip(label) {
+                *cell = c;
+            }
+        }
+        scale.into_iter().collect()
+    }
+}
+
+/// Gain at mpv volume `raw`, whose volume control is cubic, e.g. "-18 dB"
+fn decibels(raw: u8, precision: usize) -> String {
+    if raw == 0 {
+        return "-∞ dB".to_string();
+    }
+    let db = 60.0 * (raw as f32 / 100.0).log10();
+    format!("{:.*} dB", precision, db)
+}
+
+impl<'a> Widget for VolumePopup<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let theme = self.theme;
+
+        let width = 50.min(area.width);
+        let height = 8.min(area.height);
+        let [_, popup_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+        let [_, popup_area, _] = Layout::horizontal([
+            Constraint::Fill(1),
+            Constraint::Length(width),
+            Constraint::Fill(1),
+        ])
+        .areas(popup_area);
+
+        Clear.render(popup_area, buf);
+
+        let (border, bar, text) = if self.fading {
+            (
+                theme.border_style(),
+                theme.muted_style(),
+                theme.muted_style(),
+            )
+        } else {
+            (
+                theme.active_border_style(),
+                theme.playing_style(),
+                theme.normal_style(),
+            )
+        };
+        let block = Block::default()
+            .title(Span::styled(
+                format!(" {} ", t("volume-title")),
+                theme.title_style(),
+            ))
+            .borders(Borders::ALL)
+            .border_style(border)
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(popup_area);
+        block.render(popup_area, buf);
+
+        let volume = if self.muted { 0 } else { self.volume };
+        let reading = if self.muted {
+            tf("volume-muted", &[("volume", &self.volume)])
+        } else {
+            format!("{}%  ·  {}", volume, decibels(self.curve.apply(volume), 1))
+        };
+
+        let slider_width = inner.width.saturating_sub(2) as usize;
+        let filled = volume as usize * slider_width / 100;
+        let slider = Line::from(vec![
+            Span::styled("█".repeat(filled), bar),
+            Span::styled("░".repeat(slider_width - filled), theme.muted_style()),
+        ]);
+
+        let lines = vec![
+            Line::from(Span::styled(reading, text)),
+            Line::from(""),
+            slider.clone(),
+            slider,
+            Line::from(Span::styled(self.scale(slider_width), theme.muted_style())),
+            Line::from(Span::styled(t("keys-volume"), theme.muted_style())),
+        ];
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decibels() {
+        assert_eq!(decibels(100, 0), "0 dB");
+        assert_eq!(decibels(50, 1), "-18.1 dB");
+        assert_eq!(decibels(0, 0), "-∞ dB");
+        // Cubic settings fall off much faster
+        assert_eq!(decibels(VolumeCurve::Cubic.apply(50), 0), "-53 dB");
+    }
+}
@@ -0,0 +1,202 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::Span,
+    widgets::{Block, Borders, StatefulWidget, Widget},
+};
+use std::time::{Duration, Instant};
+
+use super::theme::Theme;
+use crate::i18n::t;
+
+/// Bottom of the meter scale
+const FLOOR_DB: f32 = -60.0;
+
+/// How long a peak marker stays put before it starts to fall
+const PEAK_HOLD: Duration = Duration::from_millis(1500);
+
+/// Fall rate of released peak markers and of the meter when the signal stops
+const FALL_DB_PER_SEC: f32 = 20.0;
+
+/// Scale labels, in dB
+const SCALE: [i16; 7] = [-60, -40, -20, -10, -6, -3, 0];
+
+/// Meter ballistics for left and right, updated once per tick
+pub struct VuMeterState {
+    /// Smoothed RMS level per channel, in dB
+    rms: [f32; 2],
+    /// Held peak per channel, in dB
+    hold: [f32; 2],
+    held_at: [Instant; 2],
+    last_update: Instant,
+}
+
+impl Default for VuMeterState {
+    fn default() -> Self {
+        let now = Instant::now();
+        Self {
+            rms: [FLOOR_DB; 2],
+            hold: [FLOOR_DB; 2],
+            held_at: [now; 2],
+            last_update: now,
+        }
+    }
+}
+
+impl VuMeterState {
+    /// Feed the latest left/right `(rms_db, peak_db)` levels, or `None` when
+    /// there is no signal so the meters fall back to the floor
+    pub fn update(&mut self, levels: Option<[(f32, f32); 2]>) {
+        let now = Instant::now();
+        let fall = FALL_DB_PER_SEC * now.duration_since(self.last_update).as_secs_f32();
+        self.last_update = now;
+
+        for ch in 0..2 {
+            let (rms, peak) = match levels {
+                Some(levels) => (levels[ch].0.max(FLOOR_DB), levels[ch].1.max(FLOOR_DB)),
+                None => (FLOOR_DB, FLOOR_DB),
+            };
+
+            // Quick rise, slower release, like a needle
+            let smoothing = if rms > self.rms[ch] { 0.5 } else { 0.15 };
+            self.rms[ch] += (rms - self.rms[ch]) * smoothing;
+
+            if peak >= self.hold[ch] {
+                self.hold[ch] = peak;
+                self.held_at[ch] = now;
+            } else if now.duration_since(self.held_at[ch]) > PEAK_HOLD {
+                self.hold[ch] = (self.hold[ch] - fall).max(peak);
+            }
+        }
+    }
+
+    /// Whether both meters have fallen back to the floor, so redraws can stop
+    pub fn is_settled(&self) -> bool {
+        self.rms
+            .iter()
+            .chain(self.hold.iter())
+            .all(|&db| db <= FLOOR_DB + 0.05)
+    }
+}
+
+/// Compact stereo level meter: RMS bars with peak hold on a dB scale
+pub struct VuMeter<'a> {
+    theme: &'a Theme,
+}
+
+impl<'a> VuMeter<'a> {
+    pub fn new(theme: &'a Theme) -> Self {
+        Self { theme }
+    }
+
+    /// Position of a dB value along a meter `width` cells wide
+    fn position(db: f32, width: u16) -> u16 {
+        let fraction = ((db - FLOOR_DB) / -FLOOR_DB).clamp(0.0, 1.0);
+        (fraction * width as f32).round() as u16
+    }
+
+    fn render_channel(
+        &self,
+        label: &str,
+        rms: f32,
+        hold: f32,
+        area: Rect,
+        y: u16,
+        buf: &mut Buffer,
+    ) {
+        let theme = self.theme;
+        let meter_width = area.width.saturating_sub(11);
+        let x = area.x + 2;
+
+        buf.set_string(area.x, y, label, theme.title_style());
+
+        let filled = Self::position(rms, meter_width);
+        let hold_at = Self::position(hold, meter_width).min(meter_width.saturating_sub(1));
+        for i in 0..meter_width {
+            // Color by where the cell sits on the scale, not by the level
+            let cell_db = FLOOR_DB + (i as f32 + 0.5) / meter_width as f32 * -FLOOR_DB;
+            let color = if cell_db > -6.0 {
+                theme.highlight
+            } else if cell_db > -18.0 {
+                theme.warning
+            } else {
+                theme.success
+            };
+            let (ch, style) = if i < filled {
+                ('█', Style::default().fg(color))
+            } else if i == hold_at && hold > FLOOR_DB {
+                ('┃', Style::default().fg(color))
+            } else {
+                ('░', theme.muted_style())
+            };
+            if let Some(cell) = buf.cell_mut((x + i, y)) {
+                cell.set_char(ch).set_style(style);
+            }
+        }
+
+        let value = if rms > FLOOR_DB {
+            format!("{:>5.1} dB", rms)
+        } else {
+            "  --- dB".to_string()
+        };
+        buf.set_string(
+            x + meter_width,
+            y,
+            format!(" {}", value),
+            theme.muted_style(),
+        );
+    }
+
+    fn render_scale(&self, area: Rect, y: u16, buf: &mut Buffer) {
+        let meter_width = area.width.saturating_sub(11);
+        let x = area.x + 2;
+        let mut next_free = x;
+
+        for db in SCALE {
+            let label = db.to_string();
+            let center = x + Self::position(db as f32, meter_width);
+            let start = center
+                .saturating_sub(label.len() as u16 / 2)
+                .max(x)
+                .min((x + meter_width).saturating_sub(label.len() as u16));
+            // Skip labels that would overlap the previous one
+            if start < next_free {
+                continue;
+            }
+            buf.set_string(start, y, &label, self.theme.muted_style());
+            next_free = start + label.len() as u16 + 1;
+        }
+    }
+}
+
+impl<'a> StatefulWidget for VuMeter<'a> {
+    type State = VuMeterState;
+
+    fn render(self, area: Rect, buf: &mut Buffer, state: &mut Self::State) {
+        let theme = self.theme;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(theme.border_style())
+            .title(Span::styled(
+                format!(" {} ", t("levels-title")),
+                theme.title_style(),
+            ));
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.width < 16 || inner.height < 1 {
+            return;
+        }
+
+        self.render_channel("L", state.rms[0], state.hold[0], inner, inner.y, buf);
+        if inner.height >= 2 {
+            self.render_channel("R", state.rms[1], state.hold[1], inner, inner.y + 1, buf);
+        }
+        if inner.height >= 3 {
+            self.render_scale(inner, inner.y + 2, buf);
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Number of recent energy samples the onset threshold is averaged over
+const WINDOW: usize = 40;
+
+/// Energy must exceed the recent average by this factor to count as a beat
+const THRESHOLD: f32 = 1.35;
+
+/// Ignore onsets quieter than this, so silence doesn't produce beats
+const MIN_ENERGY: f32 = 0.05;
+
+/// Shortest gap between beats (200 BPM)
+const MIN_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Longest gap still counted towards the tempo (30 BPM)
+const MAX_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long `beat` stays set after an onset, so the accent is visible
+const BEAT_HOLD: Duration = Duration::from_millis(120);
+
+/// Number of beat intervals the BPM estimate is taken over
+const INTERVALS: usize = 8;
+
+/// Simple energy-onset beat detector over the RMS history
+#[derive(Debug, Default)]
+pub struct BeatDetector {
+    energies: VecDeque<f32>,
+    last_beat: Option<Instant>,
+    intervals: VecDeque<Duration>,
+}
+
+impl BeatDetector {
+    /// Feed one energy sample; returns true if it's a new beat
+    pub fn process(&mut self, energy: f32, now: Instant) -> bool {
+        let average = if self.energies.is_empty() {
+            0.0
+        } else {
+            self.energies.iter().sum::<f32>() / self.energies.len() as f32
+        };
+
+        let rested = match self.last_beat {
+            Some(last) => now.duration_since(last) >= MIN_INTERVAL,
+            None => true,
+        };
+        let is_beat = rested
+            && self.energies.len() >= WINDOW / 2
+            && energy > MIN_ENERGY
+            && energy > average * THRESHOLD;
+
+        if self.energies.len() == WINDOW {
+            self.energies.pop_front();
+        }
+        self.energies.push_back(energy);
+
+        if is_beat {
+            if let Some(last) = self.last_beat {
+                let interval = now.duration_since(last);
+                if interval <= MAX_INTERVAL {
+                    if self.intervals.len() == INTERVALS {
+                        self.intervals.pop_front();
+                    }
+                    self.intervals.push_back(interval);
+                } else {
+                    // Lost the rhythm; start the estimate over
+                    self.intervals.clear();
+                }
+            }
+            self.last_beat = Some(now);
+        }
+
+        is_beat
+    }
+
+    /// Whether a beat happened recently enough to still be shown
+    pub fn on_beat(&self, now: Instant) -> bool {
+        self.last_beat
+            .is_some_and(|last| now.duration_since(last) < BEAT_HOLD)
+    }
+
+    /// Estimated tempo from the median beat interval, once there are enough
+    /// recent beats to be meaningful
+    pub fn bpm(&self, now: Instant) -> Option<f32> {
+        let last = self.last_beat?;
+        if self.intervals.len() < 4 || now.duration_since(last) > MAX_INTERVAL {
+            return None;
+        }
+        let mut intervals: Vec<Duration> = self.intervals.iter().copied().collect();
+        intervals.sort();
+        let median = intervals[intervals.len() / 2];
+        Some(60.0 / median.as_secs_f32())
+    }
+
+    /// Forget everything, e.g. when playback stops
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_steady_tempo() {
+        let mut detector = BeatDetector::default();
+        let start = Instant::now();
+        let mut beats = 0;
+
+        // 50ms samples with a loud onset every 500ms (120 BPM)
+        for i in 0..120u64 {
+            let energy = if i % 10 == 0 { 0.8 } else { 0.2 };
+            if detector.process(energy, start + Duration::from_millis(i * 50)) {
+                beats += 1;
+            }
+        }
+
+        assert!(beats >= 8);
+        let bpm = detector
+            .bpm(start + Duration::from_millis(119 * 50))
+            .unwrap();
+        assert!((bpm - 120.0).abs() < 1.0);
+    }
+}
@@ -1,3 +1,9 @@
+mod beat;
+mod plugins;
 mod spectrum;
 
-pub use spectrum::{SpectrumAnalyzer, SpectrumData};
+pub use plugins::{PluginFrame, PluginRegistry};
+pub use spectrum::{
+    SpectrumAnalyzer, SpectrumData, SpectrumSettings, HISTORY_LEN, MAX_BINS, MIN_BINS,
+    SMOOTHING_PRESETS,
+};
@@ -0,0 +1,203 @@
+use anyhow::{anyhow, Result};
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+use std::path::{Path, PathBuf};
+
+use super::spectrum::SpectrumData;
+
+/// Upper bound on script work per frame, so a runaway loop can't hang the UI
+const MAX_OPERATIONS: u64 = 500_000;
+
+/// A cell drawn by a plugin, relative to the top-left of the visualizer area.
+/// `color` is a theme slot (`accent`, `primary`, ...) or any color name/hex.
+pub struct PluginCell {
+    pub x: i64,
+    pub y: i64,
+    pub ch: char,
+    pub color: String,
+}
+
+/// Everything a plugin gets to draw one frame
+pub struct PluginFrame<'a> {
+    pub spectrum: &'a SpectrumData,
+    pub frame: u64,
+    pub width: u16,
+    pub height: u16,
+}
+
+struct Plugin {
+    name: String,
+    ast: AST,
+}
+
+/// Visualizations loaded from Rhai scripts in the `visualizers` config
+/// directory. Each script defines `fn render(ctx)` returning an array of
+/// `#{ x, y, ch, color }` maps.
+pub struct PluginRegistry {
+    engine: Engine,
+    plugins: Vec<Plugin>,
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // Scripts must not write to the terminal behind the TUI's back
+        engine.on_print(|_| {});
+        engine.on_debug(|_, _, _| {});
+        Self {
+            engine,
+            plugins: Vec::new(),
+        }
+    }
+}
+
+impl PluginRegistry {
+    pub fn load() -> Result<Self> {
+        let mut registry = Self::default();
+        registry.load_dir(&Self::plugins_dir()?);
+        Ok(registry)
+    }
+
+    fn plugins_dir() -> Result<PathBuf> {
+        let config_dir = crate::storage::config_dir();
+
+        let dir = config_dir.join("visualizers");
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Compile every `.rhai` file in `dir`. Scripts that fail to compile are
+    /// skipped.
+    pub fn load_dir(&mut self, dir: &Path) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("rhai"))
+            .collect();
+        files.sort();
+
+        for path in files {
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            if let Ok(ast) = self.engine.compile_file(path.clone()) {
+                self.plugins.push(Plugin {
+                    name: name.to_string(),
+                    ast,
+                });
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.plugins.len()
+    }
+
+    pub fn name(&self, index: usize) -> Option<&str> {
+        self.plugins.get(index).map(|p| p.name.as_str())
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.plugins.iter().position(|p| p.name == name)
+    }
+
+    /// Run a plugin's `render` function for one frame
+    pub fn render(&self, index: usize, frame: &PluginFrame) -> Result<Vec<PluginCell>> {
+        let plugin = self
+            .plugins
+            .get(index)
+            .ok_or_else(|| anyhow!("no visualizer plugin {}", index))?;
+
+        let spectrum = frame.spectrum;
+        let floats =
+            |values: &[f32]| -> Array { values.iter().map(|&v| Dynamic::from(v as f64)).collect() };
+        let mut ctx = Map::new();
+        ctx.insert("bins".into(), floats(&spectrum.bins).into());
+        ctx.insert("peaks".into(), floats(&spectrum.peaks).into());
+        ctx.insert("rms".into(), (spectrum.rms as f64).into());
+        ctx.insert("peak".into(), (spectrum.peak as f64).into());
+        ctx.insert("left".into(), (spectrum.channels[0] as f64).into());
+        ctx.insert("right".into(), (spectrum.channels[1] as f64).into());
+        ctx.insert("beat".into(), spectrum.beat.into());
+        ctx.insert(
+            "bpm".into(),
+            spectrum
+                .bpm
+                .map(|bpm| Dynamic::from(bpm as f64))
+                .unwrap_or(Dynamic::UNIT),
+        );
+        ctx.insert("frame".into(), (frame.frame as i64).into());
+        ctx.insert("width".into(), (frame.width as i64).into());
+        ctx.insert("height".into(), (frame.height as i64).into());
+
+        let cells: Array = self
+            .engine
+            .call_fn(&mut Scope::new(), &plugin.ast, "render", (ctx,))
+            .map_err(|e| anyhow!("{}: {}", plugin.name, e))?;
+
+        Ok(cells.into_iter().filter_map(parse_cell).collect())
+    }
+}
+
+fn parse_cell(value: Dynamic) -> Option<PluginCell> {
+    let map = value.try_cast::<Map>()?;
+    let int = |key: &str| map.get(key).and_then(|v| v.as_int().ok());
+    let text = |key: &str| map.get(key).and_then(|v| v.clone().into_string().ok());
+
+    Some(PluginCell {
+        x: int("x")?,
+        y: int("y")?,
+        ch: text("ch").and_then(|s| s.chars().next()).unwrap_or('•'),
+        color: text("color").unwrap_or_else(|| "accent".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_script() {
+        let dir = std::env::temp_dir().join(format!("vibecast-plugins-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("diagonal.rhai"),
+            r#"
+            fn render(ctx) {
+                let cells = [];
+                for i in 0..ctx.height {
+                    cells.push(#{ x: i, y: i, ch: "*", color: "highlight" });
+                }
+                cells
+            }
+            "#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.rhai"), "fn render(ctx) {").unwrap();
+
+        let mut registry = PluginRegistry::default();
+        registry.load_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(registry.len(), 1);
+        assert_eq!(registry.index_of("diagonal"), Some(0));
+
+        let spectrum = SpectrumData::default();
+        let cells = registry
+            .render(
+                0,
+                &PluginFrame {
+                    spectrum: &spectrum,
+                    frame: 0,
+                    width: 10,
+                    height: 3,
+                },
+            )
+            .unwrap();
+        assert_eq!(cells.len(), 3);
+        assert_eq!((cells[2].x, cells[2].y, cells[2].ch), (2, 2, '*'));
+        assert_eq!(cells[2].color, "highlight");
+    }
+}
@@ -1,44 +1,114 @@
 #![allow(dead_code)]
 
+use std::collections::VecDeque;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 
-/// Number of frequency bins for visualization
-pub const NUM_BINS: usize = 32;
+use super::beat::BeatDetector;
+use crate::player::AudioLevels;
+
+/// Number of frequency bins for visualization, unless configured otherwise
+pub const DEFAULT_BINS: usize = 32;
+
+/// Range allowed for `visualizer.bins`
+pub const MIN_BINS: usize = 8;
+pub const MAX_BINS: usize = 128;
+
+/// Number of past spectrum frames kept for the waterfall
+pub const HISTORY_LEN: usize = 512;
+
+/// Levels missing for this long mean there's no level data, rather than a
+/// poll that came back empty
+const NO_DATA_AFTER: Duration = Duration::from_secs(1);
+
+/// Minimum time between recorded history frames
+const HISTORY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How audio levels are mapped onto the 0.0-1.0 spectrum
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpectrumSettings {
+    /// Boost (or cut) applied to incoming levels, in dB
+    pub gain_db: f32,
+    /// Level shown as silence; raise it for quiet channels
+    pub floor_db: f32,
+    /// Share of the previous value kept when the level rises (0 = instant)
+    pub attack: f32,
+    /// Share of the previous value kept when the level falls (0 = instant)
+    pub decay: f32,
+    /// Number of frequency bins, `MIN_BINS` to `MAX_BINS`
+    pub bins: usize,
+}
+
+impl Default for SpectrumSettings {
+    fn default() -> Self {
+        Self {
+            gain_db: 0.0,
+            floor_db: -60.0,
+            attack: 0.3,
+            decay: 0.85,
+            bins: DEFAULT_BINS,
+        }
+    }
+}
+
+/// Attack/decay pairs cycled from the keyboard, each with the text id of
+/// its name
+pub const SMOOTHING_PRESETS: [(&str, f32, f32); 3] = [
+    ("smoothing-snappy", 0.1, 0.6),
+    ("smoothing-normal", 0.3, 0.85),
+    ("smoothing-smooth", 0.6, 0.95),
+];
 
 /// Represents audio spectrum data for visualization
 #[derive(Clone)]
 pub struct SpectrumData {
     /// Normalized frequency bin values (0.0 to 1.0)
-    pub bins: [f32; NUM_BINS],
-    /// Peak values with decay
-    pub peaks: [f32; NUM_BINS],
+    pub bins: Vec<f32>,
+    /// Peak values with decay, one per bin
+    pub peaks: Vec<f32>,
     /// RMS level (0.0 to 1.0)
     pub rms: f32,
     /// Peak level (0.0 to 1.0)
     pub peak: f32,
-    /// Whether we have real audio data
+    /// Left and right RMS levels (0.0 to 1.0); both the overall level for
+    /// mono streams
+    pub channels: [f32; 2],
+    /// Whether levels are coming in from mpv. A visualization without
+    /// them would only be pretending to react.
     pub has_audio: bool,
-    /// Timestamp of last update
+    /// Set briefly when a beat (energy onset) is detected
+    pub beat: bool,
+    /// Estimated tempo, once enough regular beats have been seen
+    pub bpm: Option<f32>,
+    /// When levels last came in
     pub last_update: Instant,
 }
 
 impl Default for SpectrumData {
     fn default() -> Self {
         Self {
-            bins: [0.0; NUM_BINS],
-            peaks: [0.0; NUM_BINS],
+            bins: vec![0.0; DEFAULT_BINS],
+            peaks: vec![0.0; DEFAULT_BINS],
             rms: 0.0,
             peak: 0.0,
+            channels: [0.0; 2],
             has_audio: false,
+            beat: false,
+            bpm: None,
             last_update: Instant::now(),
         }
     }
 }
 
 impl SpectrumData {
+    /// Change the number of bins, starting the new ones from silence
+    pub fn resize(&mut self, bins: usize) {
+        self.bins = vec![0.0; bins];
+        self.peaks = vec![0.0; bins];
+    }
+
     /// Apply decay to values for smooth animation
     pub fn decay(&mut self, factor: f32) {
         for bin in &mut self.bins {
@@ -49,26 +119,41 @@ impl SpectrumData {
         }
         self.rms *= factor;
         self.peak *= factor;
+        for channel in &mut self.channels {
+            *channel *= factor;
+        }
     }
 
     /// Generate spectrum based on RMS/peak audio levels
-    /// Note: We only have overall levels from mpv, not per-frequency FFT data,
-    /// so all bars respond uniformly to the audio energy
-    pub fn simulate_from_levels(&mut self, rms: f32, peak: f32) {
+    /// Note: We only have overall and per-channel levels from mpv, not
+    /// per-frequency FFT data, so all bars respond uniformly to the audio energy
+    pub fn simulate_from_levels(
+        &mut self,
+        rms: f32,
+        peak: f32,
+        channels: [f32; 2],
+        attack: f32,
+        decay: f32,
+    ) {
         self.rms = rms;
         self.peak = peak;
         self.has_audio = true;
         self.last_update = Instant::now();
 
+        for (level, value) in self.channels.iter_mut().zip(channels) {
+            let smoothing = if value > *level { attack } else { decay };
+            *level = *level * smoothing + value * (1.0 - smoothing);
+        }
+
         // Mix RMS and peak for the energy level
         let energy = (rms * 0.5 + peak * 0.5).clamp(0.0, 1.0);
 
-        for i in 0..NUM_BINS {
+        for i in 0..self.bins.len() {
             // All bins respond directly to audio level - no artificial variation
             let value = energy;
 
             // Smooth transitions - fast attack, slow visible decay
-            let smoothing = if value > self.bins[i] { 0.3 } else { 0.85 };
+            let smoothing = if value > self.bins[i] { attack } else { decay };
             self.bins[i] = self.bins[i] * smoothing + value * (1.0 - smoothing);
 
             // Update peaks with slow decay
@@ -82,6 +167,7 @@ impl SpectrumData {
 
     /// Decay spectrum when no audio data is available (or paused/stopped)
     pub fn animate(&mut self, playing: bool, paused: bool) {
+        self.has_audio = self.has_audio && self.last_update.elapsed() < NO_DATA_AFTER;
         // When not playing or paused, just decay existing values
         // No artificial animation - only real audio should drive the spectrum
         if !playing || paused {
@@ -91,45 +177,130 @@ impl SpectrumData {
             // This handles the case where mpv hasn't provided data yet
             self.decay(0.92);
         }
-        self.last_update = Instant::now();
     }
 }
 
+/// Ring buffer of recent spectrum frames, oldest first
+struct SpectrumHistory {
+    frames: VecDeque<Vec<f32>>,
+    last_push: Instant,
+}
+
 /// Analyzer that processes audio data and produces spectrum information
 pub struct SpectrumAnalyzer {
     data: Arc<RwLock<SpectrumData>>,
+    history: Arc<RwLock<SpectrumHistory>>,
+    beats: Arc<RwLock<BeatDetector>>,
     active: Arc<AtomicBool>,
+    settings: SpectrumSettings,
 }
 
 impl SpectrumAnalyzer {
     pub fn new() -> Self {
         Self {
             data: Arc::new(RwLock::new(SpectrumData::default())),
+            history: Arc::new(RwLock::new(SpectrumHistory {
+                frames: VecDeque::with_capacity(HISTORY_LEN),
+                last_push: Instant::now(),
+            })),
+            beats: Arc::new(RwLock::new(BeatDetector::default())),
             active: Arc::new(AtomicBool::new(false)),
+            settings: SpectrumSettings::default(),
         }
     }
 
+    pub fn settings(&self) -> SpectrumSettings {
+        self.settings
+    }
+
+    pub fn set_settings(&mut self, settings: SpectrumSettings) {
+        self.settings = settings;
+    }
+
     /// Get a clone of the current spectrum data
     pub async fn get_data(&self) -> SpectrumData {
         self.data.read().await.clone()
     }
 
+    /// The most recent `n` spectrum frames, oldest first
+    pub async fn history(&self, n: usize) -> Vec<Vec<f32>> {
+        let history = self.history.read().await;
+        let skip = history.frames.len().saturating_sub(n);
+        history.frames.iter().skip(skip).cloned().collect()
+    }
+
+    /// Record the current bins, at most once per `HISTORY_INTERVAL`
+    async fn record_history(&self, bins: Vec<f32>) {
+        let mut history = self.history.write().await;
+        // Frames from before a change in bin count no longer line up
+        if history
+            .frames
+            .back()
+            .is_some_and(|frame| frame.len() != bins.len())
+        {
+            history.frames.clear();
+        }
+        if history.last_push.elapsed() < HISTORY_INTERVAL {
+            return;
+        }
+        history.last_push = Instant::now();
+        if history.frames.len() == HISTORY_LEN {
+            history.frames.pop_front();
+        }
+        history.frames.push_back(bins);
+    }
+
     /// Update spectrum with audio levels (from mpv metadata)
-    pub async fn update_from_levels(&self, rms_db: f32, peak_db: f32) {
-        // Convert dB to linear (0-1 range)
-        // Typical audio range: -60dB (silent) to 0dB (max)
-        let rms = db_to_linear(rms_db.clamp(-60.0, 0.0));
-        let peak = db_to_linear(peak_db.clamp(-60.0, 0.0));
-
-        let mut data = self.data.write().await;
-        data.simulate_from_levels(rms, peak);
+    pub async fn update_from_levels(&self, levels: &AudioLevels) {
+        // Convert dB to linear (0-1 range) between the floor and 0dB, after gain
+        let settings = self.settings;
+        let unit = |db: f32| level_to_unit(db + settings.gain_db, settings.floor_db);
+        let rms = unit(levels.rms_db);
+        let peak = unit(levels.peak_db);
+        let channels = levels
+            .stereo
+            .map_or([rms; 2], |[left, right]| [unit(left.0), unit(right.0)]);
+
+        let now = Instant::now();
+        let (beat, bpm) = {
+            let mut beats = self.beats.write().await;
+            beats.process(rms, now);
+            (beats.on_beat(now), beats.bpm(now))
+        };
+
+        let bins = {
+            let mut data = self.data.write().await;
+            if data.bins.len() != settings.bins {
+                data.resize(settings.bins);
+            }
+            data.simulate_from_levels(rms, peak, channels, settings.attack, settings.decay);
+            data.beat = beat;
+            data.bpm = bpm;
+            data.bins.clone()
+        };
         self.active.store(true, Ordering::Relaxed);
+        self.record_history(bins).await;
     }
 
     /// Animate the spectrum when no real audio data
     pub async fn animate(&self, playing: bool, paused: bool) {
-        let mut data = self.data.write().await;
-        data.animate(playing, paused);
+        if !playing || paused {
+            self.beats.write().await.reset();
+        }
+
+        let bins = {
+            let mut data = self.data.write().await;
+            if data.bins.len() != self.settings.bins {
+                data.resize(self.settings.bins);
+            }
+            data.animate(playing, paused);
+            data.beat = false;
+            if !playing || paused {
+                data.bpm = None;
+            }
+            data.bins.clone()
+        };
+        self.record_history(bins).await;
     }
 
     /// Check if we're receiving real audio data
@@ -156,6 +327,13 @@ fn db_to_linear(db: f32) -> f32 {
     linear.clamp(0.0, 1.0)
 }
 
+/// Map a dB level onto 0.0-1.0, with `floor_db` and below as silence
+fn level_to_unit(db: f32, floor_db: f32) -> f32 {
+    let floor = db_to_linear(floor_db);
+    let linear = db_to_linear(db.clamp(floor_db, 0.0));
+    ((linear - floor) / (1.0 - floor)).clamp(0.0, 1.0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -166,4 +344,14 @@ mod tests {
         assert!((db_to_linear(-6.0) - 0.5).abs() < 0.1);
         assert!(db_to_linear(-60.0) < 0.01);
     }
+
+    #[test]
+    fn test_level_to_unit() {
+        assert_eq!(level_to_unit(-70.0, -60.0), 0.0);
+        assert_eq!(level_to_unit(-30.0, -30.0), 0.0);
+        assert!((level_to_unit(0.0, -30.0) - 1.0).abs() < 0.001);
+        // A raised floor hides background noise that would otherwise show
+        assert!(level_to_unit(-40.0, -60.0) > 0.0);
+        assert_eq!(level_to_unit(-40.0, -30.0), 0.0);
+    }
 }